@@ -0,0 +1,404 @@
+//! Interning of class expressions
+//!
+//! A `ClassExpressionArena` stores each distinct class expression
+//! once, with its sub-expressions replaced by `CeId` handles to the
+//! expressions which are already stored. Structurally identical
+//! expressions, however often they are interned, are given the same
+//! handle, so the handles are cheap to copy and compare, and
+//! expressions which reuse the same fillers many times need only one
+//! copy of each in the arena.
+//!
+//! The ontologies and their indexes do not use an arena: interning
+//! the expressions of an ontology copies them into the arena, for
+//! code which wants to compare or share them by handle, and leaves
+//! the ontology as it is.
+//!
+//! Handles are only meaningful for the arena which returned them.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! use horned_owl::arena::ClassExpressionArena;
+//!
+//! let b = Build::new();
+//! let some = ClassExpression::ObjectSomeValuesFrom {
+//!     ope: b.object_property("http://www.example.com/r").into(),
+//!     bce: Box::new(b.class("http://www.example.com/A").into()),
+//! };
+//!
+//! let mut arena = ClassExpressionArena::new();
+//! let a = arena.intern(&some);
+//! let b = arena.intern(&ClassExpression::ObjectUnionOf(vec![some.clone(), some.clone()]));
+//!
+//! assert_eq!(a, arena.intern(&some));
+//! assert_eq!(arena.len(), 3);
+//! assert_eq!(arena.expression(a), some);
+//! ```
+use crate::model::*;
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// A handle to a class expression in a `ClassExpressionArena`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CeId(u32);
+
+/// A class expression, with its sub-expressions replaced by handles.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Node {
+    Class(Class),
+    ObjectIntersectionOf(Vec<CeId>),
+    ObjectUnionOf(Vec<CeId>),
+    ObjectComplementOf(CeId),
+    ObjectOneOf(Vec<NamedIndividual>),
+    ObjectSomeValuesFrom {
+        ope: ObjectPropertyExpression,
+        bce: CeId,
+    },
+    ObjectAllValuesFrom {
+        ope: ObjectPropertyExpression,
+        bce: CeId,
+    },
+    ObjectHasValue {
+        ope: ObjectPropertyExpression,
+        i: NamedIndividual,
+    },
+    ObjectHasSelf(ObjectPropertyExpression),
+    ObjectMinCardinality {
+        n: u32,
+        ope: ObjectPropertyExpression,
+        bce: CeId,
+    },
+    ObjectMaxCardinality {
+        n: u32,
+        ope: ObjectPropertyExpression,
+        bce: CeId,
+    },
+    ObjectExactCardinality {
+        n: u32,
+        ope: ObjectPropertyExpression,
+        bce: CeId,
+    },
+    DataSomeValuesFrom {
+        dp: DataProperty,
+        dr: DataRange,
+    },
+    DataAllValuesFrom {
+        dp: DataProperty,
+        dr: DataRange,
+    },
+    DataHasValue {
+        dp: DataProperty,
+        l: Literal,
+    },
+    DataMinCardinality {
+        n: u32,
+        dp: DataProperty,
+        dr: DataRange,
+    },
+    DataMaxCardinality {
+        n: u32,
+        dp: DataProperty,
+        dr: DataRange,
+    },
+    DataExactCardinality {
+        n: u32,
+        dp: DataProperty,
+        dr: DataRange,
+    },
+}
+
+/// An arena of interned class expressions.
+#[derive(Clone, Debug, Default)]
+pub struct ClassExpressionArena {
+    nodes: Vec<Node>,
+    /// The handles of the nodes with each hash
+    ids: HashMap<u64, Vec<CeId>>,
+    state: RandomState,
+}
+
+impl ClassExpressionArena {
+    pub fn new() -> ClassExpressionArena {
+        ClassExpressionArena::default()
+    }
+
+    /// The number of distinct expressions in the arena.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn find(&self, hash: u64, node: &Node) -> Option<CeId> {
+        self.ids
+            .get(&hash)?
+            .iter()
+            .find(|id| self.node(**id) == node)
+            .copied()
+    }
+
+    fn insert(&mut self, node: Node) -> CeId {
+        let hash = self.state.hash_one(&node);
+        if let Some(id) = self.find(hash, &node) {
+            return id;
+        }
+        let id = CeId(self.nodes.len() as u32);
+        self.nodes.push(node);
+        self.ids.entry(hash).or_default().push(id);
+        id
+    }
+
+    fn intern_all(&mut self, ces: &[ClassExpression]) -> Vec<CeId> {
+        ces.iter().map(|ce| self.intern(ce)).collect()
+    }
+
+    /// Store `ce` and its sub-expressions, returning the handle for
+    /// `ce`.
+    pub fn intern(&mut self, ce: &ClassExpression) -> CeId {
+        use ClassExpression as CE;
+        let node = match ce {
+            CE::Class(c) => Node::Class(c.clone()),
+            CE::ObjectIntersectionOf(v) => Node::ObjectIntersectionOf(self.intern_all(v)),
+            CE::ObjectUnionOf(v) => Node::ObjectUnionOf(self.intern_all(v)),
+            CE::ObjectComplementOf(bce) => Node::ObjectComplementOf(self.intern(bce)),
+            CE::ObjectOneOf(v) => Node::ObjectOneOf(v.clone()),
+            CE::ObjectSomeValuesFrom { ope, bce } => Node::ObjectSomeValuesFrom {
+                ope: ope.clone(),
+                bce: self.intern(bce),
+            },
+            CE::ObjectAllValuesFrom { ope, bce } => Node::ObjectAllValuesFrom {
+                ope: ope.clone(),
+                bce: self.intern(bce),
+            },
+            CE::ObjectHasValue { ope, i } => Node::ObjectHasValue {
+                ope: ope.clone(),
+                i: i.clone(),
+            },
+            CE::ObjectHasSelf(ope) => Node::ObjectHasSelf(ope.clone()),
+            CE::ObjectMinCardinality { n, ope, bce } => Node::ObjectMinCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.intern(bce),
+            },
+            CE::ObjectMaxCardinality { n, ope, bce } => Node::ObjectMaxCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.intern(bce),
+            },
+            CE::ObjectExactCardinality { n, ope, bce } => Node::ObjectExactCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.intern(bce),
+            },
+            CE::DataSomeValuesFrom { dp, dr } => Node::DataSomeValuesFrom {
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            CE::DataAllValuesFrom { dp, dr } => Node::DataAllValuesFrom {
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            CE::DataHasValue { dp, l } => Node::DataHasValue {
+                dp: dp.clone(),
+                l: l.clone(),
+            },
+            CE::DataMinCardinality { n, dp, dr } => Node::DataMinCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            CE::DataMaxCardinality { n, dp, dr } => Node::DataMaxCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            CE::DataExactCardinality { n, dp, dr } => Node::DataExactCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+        };
+        self.insert(node)
+    }
+
+    /// Return the node for `id`.
+    ///
+    /// # Panics
+    ///
+    /// If `id` was not returned by this arena.
+    pub fn node(&self, id: CeId) -> &Node {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// Return the handle for `ce`, if it has been interned.
+    pub fn get(&self, ce: &ClassExpression) -> Option<CeId> {
+        use ClassExpression as CE;
+        let all = |v: &[ClassExpression]| -> Option<Vec<CeId>> {
+            v.iter().map(|ce| self.get(ce)).collect()
+        };
+        let node = match ce {
+            CE::ObjectIntersectionOf(v) => Node::ObjectIntersectionOf(all(v)?),
+            CE::ObjectUnionOf(v) => Node::ObjectUnionOf(all(v)?),
+            CE::ObjectComplementOf(bce) => Node::ObjectComplementOf(self.get(bce)?),
+            CE::ObjectSomeValuesFrom { ope, bce } => Node::ObjectSomeValuesFrom {
+                ope: ope.clone(),
+                bce: self.get(bce)?,
+            },
+            CE::ObjectAllValuesFrom { ope, bce } => Node::ObjectAllValuesFrom {
+                ope: ope.clone(),
+                bce: self.get(bce)?,
+            },
+            CE::ObjectMinCardinality { n, ope, bce } => Node::ObjectMinCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.get(bce)?,
+            },
+            CE::ObjectMaxCardinality { n, ope, bce } => Node::ObjectMaxCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.get(bce)?,
+            },
+            CE::ObjectExactCardinality { n, ope, bce } => Node::ObjectExactCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: self.get(bce)?,
+            },
+            // Expressions without sub-expressions intern to
+            // themselves, so a scratch arena gives their node
+            _ => {
+                let mut scratch = ClassExpressionArena::new();
+                let id = scratch.intern(ce);
+                scratch.nodes.swap_remove(id.0 as usize)
+            }
+        };
+        self.find(self.state.hash_one(&node), &node)
+    }
+
+    /// Rebuild the class expression for `id`.
+    ///
+    /// # Panics
+    ///
+    /// If `id` was not returned by this arena.
+    pub fn expression(&self, id: CeId) -> ClassExpression {
+        use ClassExpression as CE;
+        let all = |v: &[CeId]| v.iter().map(|id| self.expression(*id)).collect();
+        let bx = |id: &CeId| Box::new(self.expression(*id));
+        match self.node(id) {
+            Node::Class(c) => CE::Class(c.clone()),
+            Node::ObjectIntersectionOf(v) => CE::ObjectIntersectionOf(all(v)),
+            Node::ObjectUnionOf(v) => CE::ObjectUnionOf(all(v)),
+            Node::ObjectComplementOf(bce) => CE::ObjectComplementOf(bx(bce)),
+            Node::ObjectOneOf(v) => CE::ObjectOneOf(v.clone()),
+            Node::ObjectSomeValuesFrom { ope, bce } => CE::ObjectSomeValuesFrom {
+                ope: ope.clone(),
+                bce: bx(bce),
+            },
+            Node::ObjectAllValuesFrom { ope, bce } => CE::ObjectAllValuesFrom {
+                ope: ope.clone(),
+                bce: bx(bce),
+            },
+            Node::ObjectHasValue { ope, i } => CE::ObjectHasValue {
+                ope: ope.clone(),
+                i: i.clone(),
+            },
+            Node::ObjectHasSelf(ope) => CE::ObjectHasSelf(ope.clone()),
+            Node::ObjectMinCardinality { n, ope, bce } => CE::ObjectMinCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: bx(bce),
+            },
+            Node::ObjectMaxCardinality { n, ope, bce } => CE::ObjectMaxCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: bx(bce),
+            },
+            Node::ObjectExactCardinality { n, ope, bce } => CE::ObjectExactCardinality {
+                n: *n,
+                ope: ope.clone(),
+                bce: bx(bce),
+            },
+            Node::DataSomeValuesFrom { dp, dr } => CE::DataSomeValuesFrom {
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            Node::DataAllValuesFrom { dp, dr } => CE::DataAllValuesFrom {
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            Node::DataHasValue { dp, l } => CE::DataHasValue {
+                dp: dp.clone(),
+                l: l.clone(),
+            },
+            Node::DataMinCardinality { n, dp, dr } => CE::DataMinCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            Node::DataMaxCardinality { n, dp, dr } => CE::DataMaxCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+            Node::DataExactCardinality { n, dp, dr } => CE::DataExactCardinality {
+                n: *n,
+                dp: dp.clone(),
+                dr: dr.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::read;
+    use crate::metrics::expressions;
+    use crate::ontology::axiom_mapped::AxiomMappedOntology;
+
+    #[test]
+    fn shared() {
+        let b = Build::new();
+        let a: ClassExpression = b.class("http://ex.com/a").into();
+        let not_a = ClassExpression::ObjectComplementOf(Box::new(a.clone()));
+        let mut arena = ClassExpressionArena::new();
+
+        assert_eq!(arena.get(&not_a), None);
+        let and = arena.intern(&ClassExpression::ObjectIntersectionOf(vec![
+            not_a.clone(),
+            a.clone(),
+            not_a.clone(),
+        ]));
+        assert_eq!(arena.len(), 3);
+
+        let id = arena.get(&not_a).unwrap();
+        assert_eq!(
+            arena.node(and),
+            &Node::ObjectIntersectionOf(vec![id, arena.get(&a).unwrap(), id])
+        );
+        assert_eq!(
+            arena.node(id),
+            &Node::ObjectComplementOf(arena.get(&a).unwrap())
+        );
+    }
+
+    #[test]
+    fn family() {
+        let (so, _) = read(&mut include_str!("ont/owl-xml/family.owx").as_bytes()).unwrap();
+        let o: AxiomMappedOntology = so.into();
+
+        let mut arena = ClassExpressionArena::new();
+        let mut n = 0;
+        for ax in o.i().axioms() {
+            for ce in expressions(ax).0 {
+                n += 1;
+                let id = arena.intern(ce);
+                assert_eq!(&arena.expression(id), ce);
+                assert_eq!(arena.get(ce), Some(id));
+            }
+        }
+        assert!(arena.len() < n);
+    }
+}
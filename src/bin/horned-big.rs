@@ -2,8 +2,7 @@ use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
-use failure::Error;
-
+use horned_owl::error::{CommandError, HornedError};
 use horned_owl::io::owx::writer::write;
 use horned_owl::model::Build;
 use horned_owl::model::MutableOntology;
@@ -11,7 +10,7 @@ use horned_owl::ontology::set::SetOntology;
 
 use std::io::stdout;
 
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), HornedError> {
     let matches = App::new("horned-big")
         .version("0.1")
         .about("Generate a big OWL file for testing")
@@ -27,8 +26,13 @@ fn main() -> Result<(), Error> {
     matcher(matches)
 }
 
-fn matcher(matches: ArgMatches) -> Result<(), Error> {
-    let size: isize = matches.value_of("SIZE").unwrap().parse()?;
+fn matcher(matches: ArgMatches) -> Result<(), HornedError> {
+    let size = matches
+        .value_of("SIZE")
+        .ok_or(CommandError::MissingArgument)?;
+    let size: isize = size
+        .parse()
+        .map_err(|_| CommandError::InvalidArgument(format!("SIZE: {}", size)))?;
 
     let b = Build::new();
     let mut o = SetOntology::new();
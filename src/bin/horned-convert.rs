@@ -0,0 +1,290 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use curie::PrefixMapping;
+
+use horned_owl::command::{
+    format_type, is_gz_path, parse_path_as, path_type, write_as, write_gz_as,
+};
+use horned_owl::error::{CommandError, HornedError, ValidityError};
+use horned_owl::io::ResourceType;
+use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+use std::path::Path;
+
+const FORMATS: &[&str] = &["owx", "owl", "ofn", "omn", "nt", "hob"];
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-convert")
+        .version("0.1")
+        .about("Convert an OWL Ontology between syntaxes")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("OUTPUT")
+                .help("Sets the output file to use, or stdout if absent")
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("input-format")
+                .long("input-format")
+                .short("i")
+                .takes_value(true)
+                .possible_values(FORMATS)
+                .help("The format of the input, if not given by its extension"),
+        )
+        .arg(
+            Arg::with_name("output-format")
+                .long("output-format")
+                .short("o")
+                .takes_value(true)
+                .possible_values(FORMATS)
+                .help("The format of the output, if not given by its extension"),
+        )
+        .arg(
+            Arg::with_name("prefix")
+                .long("prefix")
+                .short("p")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Adds a prefix to the output, as NAME=IRI"),
+        )
+        .arg(
+            Arg::with_name("gzip")
+                .long("gzip")
+                .short("z")
+                .help("Compress the output; implied by a .gz output file"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+    let input = Path::new(input);
+    let output = matches.value_of("OUTPUT").map(Path::new);
+
+    let input_type = matches
+        .value_of("input-format")
+        .and_then(format_type)
+        .or_else(|| path_type(input))
+        .ok_or_else(|| unknown_format("input"))?;
+
+    let output_type = matches
+        .value_of("output-format")
+        .and_then(format_type)
+        .or_else(|| output.and_then(path_type))
+        .ok_or_else(|| unknown_format("output"))?;
+
+    let (ont, mapping, _) = parse_path_as(input, input_type)?.decompose();
+    let ont: AxiomMappedOntology = ont.into();
+
+    let mut mapping = mapping.unwrap_or_default();
+    if let Some(prefixes) = matches.values_of("prefix") {
+        for p in prefixes {
+            let mut split = p.splitn(2, '=');
+            match (split.next(), split.next()) {
+                (Some(name), Some(iri)) => mapping
+                    .add_prefix(name, iri)
+                    .map_err(|e| ValidityError::Prefix(format!("{:?}", e)))?,
+                _ => {
+                    return Err(CommandError::InvalidArgument(format!(
+                        "Prefix should be NAME=IRI: {}",
+                        p
+                    ))
+                    .into())
+                }
+            }
+        }
+    }
+
+    let gzip = matches.is_present("gzip") || matches!(output, Some(p) if is_gz_path(p));
+    match output {
+        Some(path) => {
+            let mut w = BufWriter::new(File::create(path)?);
+            write(&mut w, &ont, &mapping, output_type, gzip)?;
+            Ok(w.flush()?)
+        }
+        None => write(&mut stdout(), &ont, &mapping, output_type, gzip),
+    }
+}
+
+fn unknown_format(which: &str) -> CommandError {
+    CommandError::InvalidArgument(format!(
+        "The {} format is not given by an extension, so must be one of: {}",
+        which,
+        FORMATS.join(", ")
+    ))
+}
+
+fn write(
+    w: &mut dyn Write,
+    ont: &AxiomMappedOntology,
+    mapping: &PrefixMapping,
+    output_type: ResourceType,
+    gzip: bool,
+) -> Result<(), HornedError> {
+    if gzip {
+        write_gz_as(w, ont, Some(mapping), output_type)
+    } else {
+        write_as(w, ont, Some(mapping), output_type)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn owx_to_ofn() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx").arg("-o").arg("ofn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Declaration(Class(o:C))"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn owx_to_omn() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx").arg("-o").arg("omn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Prefix: o: <http://www.example.com/iri#>",
+            ))
+            .stdout(predicate::str::contains("Class: o:C"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_format() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("owx, owl, ofn, omn, nt, hob"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rdf_to_nt() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-rdf/class.owl").arg("-o").arg("nt");
+        cmd.assert().success().stdout(predicate::str::contains(
+            "<http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Class>",
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn prefix() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-rdf/class.owl")
+            .arg("-o")
+            .arg("ofn")
+            .arg("-p")
+            .arg("ex=http://www.example.com/iri#");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Prefix(ex:=<http://www.example.com/iri#>)",
+            ))
+            .stdout(predicate::str::contains("Declaration(Class(ex:C))"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_file() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = mktemp::Temp::new_dir()?;
+        let out = temp.as_ref().join("class.ofn");
+
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+        cmd.arg("src/ont/owl-xml/class.owx").arg(&out);
+        cmd.assert().success();
+
+        let ofn = std::fs::read_to_string(&out)?;
+        assert!(ofn.contains("Declaration(Class(o:C))"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = mktemp::Temp::new_dir()?;
+        let gz = temp.as_ref().join("class.owx.gz");
+
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+        cmd.arg("src/ont/owl-xml/class.owx").arg(&gz);
+        cmd.assert().success();
+
+        let bytes = std::fs::read(&gz)?;
+        assert_eq!(&bytes[..2], &[0x1f, 0x8b]);
+
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+        cmd.arg(&gz).arg("-o").arg("ofn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Declaration(Class(o:C))"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn binary_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = mktemp::Temp::new_dir()?;
+        let hob = temp.as_ref().join("class.hob");
+
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+        cmd.arg("src/ont/owl-xml/class.owx").arg(&hob);
+        cmd.assert().success();
+
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+        cmd.arg(&hob).arg("-o").arg("ofn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Declaration(Class(o:C))"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn owx_to_rdf() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-convert")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx").arg("-o").arg("owl");
+        cmd.assert().success().stdout(predicate::str::contains(
+            r#"<rdf:type rdf:resource="http://www.w3.org/2002/07/owl#Class"/>"#,
+        ));
+
+        Ok(())
+    }
+}
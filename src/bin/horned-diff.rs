@@ -0,0 +1,142 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use curie::PrefixMapping;
+
+use horned_owl::command::parse_path;
+use horned_owl::diff::diff;
+use horned_owl::error::{CommandError, HornedError, ValidityError};
+use horned_owl::io::ofn::writer::Functional;
+use horned_owl::io::omn::writer::Manchester;
+
+use std::path::Path;
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-diff")
+        .version("0.1")
+        .about("Show the axioms added and removed between two OWL Ontologies")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("OLD")
+                .help("Sets the original file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("NEW")
+                .help("Sets the changed file to use")
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .takes_value(true)
+                .possible_values(&["ofn", "omn"])
+                .default_value("ofn")
+                .help("The syntax used to print axioms"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let old = matches
+        .value_of("OLD")
+        .ok_or(CommandError::MissingArgument)?;
+    let new = matches
+        .value_of("NEW")
+        .ok_or(CommandError::MissingArgument)?;
+
+    let (old, old_mapping, _) = parse_path(Path::new(old))?.decompose();
+    let (new, new_mapping, _) = parse_path(Path::new(new))?.decompose();
+
+    // Use the prefixes from both, preferring the new ones
+    let mut mapping = PrefixMapping::default();
+    for (prefix, ns) in old_mapping
+        .iter()
+        .chain(new_mapping.iter())
+        .flat_map(|m| m.mappings())
+    {
+        mapping
+            .add_prefix(prefix, ns)
+            .map_err(|e| ValidityError::Prefix(format!("{:?}", e)))?;
+    }
+
+    let manchester = matches.value_of("format") == Some("omn");
+    let render = |ax| {
+        if manchester {
+            Manchester(ax, &mapping).to_string()
+        } else {
+            Functional(ax, &mapping).to_string()
+        }
+    };
+
+    let d = diff(&old, &new);
+    for ax in d.removed {
+        println!("- {}", render(ax));
+    }
+    for ax in d.added {
+        println!("+ {}", render(ax));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn same() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-diff")?;
+
+        cmd.arg("src/ont/owl-xml/and.owx")
+            .arg("src/ont/owl-xml/and.owx");
+        cmd.assert().success().stdout(predicate::str::is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn changed() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-diff")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx")
+            .arg("src/ont/owl-xml/one-subclass.owx");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "- Declaration(Class(<http://www.example.com/iri#C>))",
+            ))
+            .stdout(predicate::str::contains("+ SubClassOf(o:B o:A)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn manchester() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-diff")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx")
+            .arg("src/ont/owl-xml/one-subclass.owx")
+            .arg("-f")
+            .arg("omn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "- Class: <http://www.example.com/iri#C>",
+            ))
+            .stdout(predicate::str::contains("+ Class: o:B SubClassOf: o:A"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use horned_owl::command::{parse_path, path_type, write_as};
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::hierarchy::{disjoint_sub_classes, ClassHierarchy};
+use horned_owl::io::ofn::writer::Functional;
+use horned_owl::model::*;
+use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-disjoint")
+        .version("0.1")
+        .about("Make the direct sub classes of a class disjoint")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("OUTPUT")
+                .help("Writes the ontology with the new axioms, rather than printing them")
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("parent")
+                .long("parent")
+                .short("c")
+                .takes_value(true)
+                .required(true)
+                .help("The IRI or CURIE of the parent class"),
+        )
+        .arg(
+            Arg::with_name("pairwise")
+                .long("pairwise")
+                .help("Make one axiom for each pair of sub classes"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+    let parent = matches
+        .value_of("parent")
+        .ok_or(CommandError::MissingArgument)?;
+
+    let (ont, mapping, _) = parse_path(Path::new(input))?.decompose();
+    let mut ont: AxiomMappedOntology = ont.into();
+    let mapping = mapping.unwrap_or_default();
+
+    let parent = Build::new().class(
+        mapping
+            .expand_curie_string(parent)
+            .unwrap_or_else(|_| parent.to_string()),
+    );
+    let h = ClassHierarchy::asserted(&ont);
+    let axioms = disjoint_sub_classes(&h, &parent, matches.is_present("pairwise"));
+
+    match matches.value_of("OUTPUT").map(Path::new) {
+        Some(path) => {
+            let rt = path_type(path).ok_or(CommandError::MissingArgument)?;
+            for ax in axioms {
+                ont.insert(ax);
+            }
+            let mut w = BufWriter::new(File::create(path)?);
+            write_as(&mut w, &ont, Some(&mapping), rt)?;
+            Ok(w.flush()?)
+        }
+        None => {
+            for ax in axioms {
+                println!("{}", Functional(&Axiom::from(ax), &mapping));
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn nary() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-disjoint")?;
+
+        cmd.arg("src/ont/owl-xml/siblings.owx").arg("-c").arg("o:A");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("DisjointClasses(o:B o:C o:D)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn pairwise() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-disjoint")?;
+
+        cmd.arg("src/ont/owl-xml/siblings.owx")
+            .arg("--parent")
+            .arg("http://www.example.com/iri#A")
+            .arg("--pairwise");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("DisjointClasses(o:B o:C)"))
+            .stdout(predicate::str::contains("DisjointClasses(o:C o:D)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_file() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = mktemp::Temp::new_dir()?;
+        let out = temp.as_ref().join("siblings.ofn");
+
+        let mut cmd = Command::cargo_bin("horned-disjoint")?;
+        cmd.arg("src/ont/owl-xml/siblings.owx")
+            .arg(&out)
+            .arg("-c")
+            .arg("o:A");
+        cmd.assert().success();
+
+        let ofn = std::fs::read_to_string(&out)?;
+        assert!(ofn.contains("SubClassOf(o:B o:A)"));
+        assert!(ofn.contains("DisjointClasses(o:B o:C o:D)"));
+
+        Ok(())
+    }
+}
@@ -1,18 +1,19 @@
 extern crate clap;
-extern crate failure;
 extern crate horned_owl;
 
 use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
-use failure::Error;
-
-use horned_owl::{command::parse_path, error::CommandError, ontology::set::SetOntology};
+use horned_owl::{
+    command::parse_path,
+    error::{CommandError, HornedError},
+    ontology::set::SetOntology,
+};
 
 use std::{collections::HashMap, path::Path};
 
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), HornedError> {
     let matches = App::new("horned-parse")
         .version("0.1")
         .about("Parse an OWL File and dump the data structures")
@@ -33,7 +34,7 @@ fn main() -> Result<(), Error> {
     matcher(matches)
 }
 
-fn matcher(matches: ArgMatches) -> Result<(), Error> {
+fn matcher(matches: ArgMatches) -> Result<(), HornedError> {
     let input = matches
         .value_of("INPUT")
         .ok_or(CommandError::MissingArgument)?;
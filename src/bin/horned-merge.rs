@@ -0,0 +1,148 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use horned_owl::collection::OntologyManager;
+use horned_owl::command::{format_type, parse_path_as, path_type, write_as};
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::resolve::{find_catalog, CatalogResolver, HTTPResolver};
+
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+use std::path::Path;
+
+const FORMATS: &[&str] = &["owx", "owl", "ofn", "nt", "hob"];
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-merge")
+        .version("0.1")
+        .about("Merge an OWL Ontology and its imports closure into one ontology")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("OUTPUT")
+                .help("Sets the output file to use, or stdout if absent")
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("input-format")
+                .long("input-format")
+                .short("i")
+                .takes_value(true)
+                .possible_values(FORMATS)
+                .help("The format of the input, if not given by its extension"),
+        )
+        .arg(
+            Arg::with_name("output-format")
+                .long("output-format")
+                .short("o")
+                .takes_value(true)
+                .possible_values(FORMATS)
+                .help("The format of the output, if not given by its extension"),
+        )
+        .arg(
+            Arg::with_name("catalog")
+                .long("catalog")
+                .short("c")
+                .takes_value(true)
+                .help("An XML catalog of local copies of the imports; defaults to the catalog-v001.xml beside the input"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+    let input = Path::new(input);
+    let output = matches.value_of("OUTPUT").map(Path::new);
+
+    let input_type = matches
+        .value_of("input-format")
+        .and_then(format_type)
+        .or_else(|| path_type(input))
+        .ok_or(CommandError::MissingArgument)?;
+
+    let output_type = matches
+        .value_of("output-format")
+        .and_then(format_type)
+        .or_else(|| output.and_then(path_type))
+        .ok_or(CommandError::MissingArgument)?;
+
+    let catalog = matches
+        .value_of("catalog")
+        .map(|c| Path::new(c).to_path_buf())
+        .or_else(|| input.parent().and_then(find_catalog));
+
+    let mut manager = match catalog {
+        Some(c) => {
+            OntologyManager::with_resolver(CatalogResolver::new(&c, HTTPResolver::default())?)
+        }
+        None => OntologyManager::with_resolver(HTTPResolver::default()),
+    };
+
+    let (ont, mapping, _) = parse_path_as(input, input_type)?.decompose();
+    let iri = manager.insert(ont)?;
+    let flat = manager.flatten_imports(&iri)?;
+
+    let mapping = mapping.unwrap_or_default();
+    match output {
+        Some(path) => {
+            let mut w = BufWriter::new(File::create(path)?);
+            write_as(&mut w, &flat, Some(&mapping), output_type)?;
+            Ok(w.flush()?)
+        }
+        None => write_as(&mut stdout(), &flat, Some(&mapping), output_type),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-merge")?;
+
+        cmd.arg("src/ont/owl-xml/import-property.owx")
+            .arg("-c")
+            .arg("src/ont/catalog/catalog-v001.xml")
+            .arg("-o")
+            .arg("ofn");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Declaration(ObjectProperty(other:other-o))",
+            ))
+            .stdout(predicate::str::contains("Declaration(Class(o:A))"))
+            .stdout(predicate::str::contains("Import(").not());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unresolvable() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-merge")?;
+
+        cmd.arg("src/ont/owl-xml/import-property.owx")
+            .arg("-c")
+            .arg("src/ont/catalog/missing.xml")
+            .arg("-o")
+            .arg("ofn");
+        cmd.assert().failure();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use horned_owl::collection::OntologyManager;
+use horned_owl::command::{format_type, parse_path};
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::reasoner::materialize::MaterializeOptions;
+use horned_owl::release::{release, today, write_release, ReleaseOptions};
+use horned_owl::resolve::{find_catalog, CatalogResolver, HTTPResolver};
+
+use std::path::Path;
+
+const FORMATS: &[&str] = &["owx", "owl", "ofn", "nt", "hob"];
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-release")
+        .version("0.1")
+        .about("Release an OWL Ontology with a dated version, in one or more formats")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("OUTPUT")
+                .help("The path of the released files, without an extension")
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .possible_values(FORMATS)
+                .help("A format to write the release in; defaults to owl"),
+        )
+        .arg(
+            Arg::with_name("date")
+                .long("date")
+                .short("d")
+                .takes_value(true)
+                .help("The release date; defaults to today"),
+        )
+        .arg(
+            Arg::with_name("version-iri")
+                .long("version-iri")
+                .takes_value(true)
+                .help("The version IRI, in which {date} is replaced by the release date"),
+        )
+        .arg(
+            Arg::with_name("version-info")
+                .long("version-info")
+                .takes_value(true)
+                .help("The owl:versionInfo, in which {date} is replaced by the release date"),
+        )
+        .arg(
+            Arg::with_name("merge")
+                .long("merge")
+                .short("m")
+                .help("Merge the imports closure into the release"),
+        )
+        .arg(
+            Arg::with_name("catalog")
+                .long("catalog")
+                .short("c")
+                .takes_value(true)
+                .help("An XML catalog of local copies of the imports; defaults to the catalog-v001.xml beside the input"),
+        )
+        .arg(
+            Arg::with_name("materialize")
+                .long("materialize")
+                .help("Assert the inferred class hierarchy"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+    let input = Path::new(input);
+    let output = matches
+        .value_of("OUTPUT")
+        .ok_or(CommandError::MissingArgument)?;
+
+    let formats = match matches.values_of("format") {
+        Some(fs) => fs.filter_map(format_type).collect(),
+        None => vec![format_type("owl").unwrap()],
+    };
+    let date = matches
+        .value_of("date")
+        .map(str::to_string)
+        .unwrap_or_else(today);
+
+    let catalog = matches
+        .value_of("catalog")
+        .map(|c| Path::new(c).to_path_buf())
+        .or_else(|| input.parent().and_then(find_catalog));
+    let mut manager = match catalog {
+        Some(c) => {
+            OntologyManager::with_resolver(CatalogResolver::new(&c, HTTPResolver::default())?)
+        }
+        None => OntologyManager::with_resolver(HTTPResolver::default()),
+    };
+
+    let (ont, mapping, _) = parse_path(input)?.decompose();
+    let iri = manager.insert(ont)?;
+
+    let options = ReleaseOptions {
+        version_iri: matches.value_of("version-iri").map(str::to_string),
+        version_info: matches.value_of("version-info").map(str::to_string),
+        merge_imports: matches.is_present("merge"),
+        materialize: if matches.is_present("materialize") {
+            Some(MaterializeOptions::default())
+        } else {
+            None
+        },
+    };
+    let released = release(&mut manager, &iri, &date, &options)?;
+
+    let mapping = mapping.unwrap_or_default();
+    for path in write_release(&released, Some(&mapping), Path::new(output), &formats)? {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn release() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = mktemp::Temp::new_dir()?;
+        let stem = dir.as_ref().join("o");
+        let mut cmd = Command::cargo_bin("horned-release")?;
+
+        cmd.arg("src/ont/owl-xml/import-property.owx")
+            .arg(&stem)
+            .arg("-c")
+            .arg("src/ont/catalog/catalog-v001.xml")
+            .arg("--merge")
+            .arg("-d")
+            .arg("2020-01-01")
+            .arg("--version-iri")
+            .arg("http://www.example.com/iri/{date}")
+            .arg("-f")
+            .arg("ofn")
+            .arg("-f")
+            .arg("owx");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::ends_with("o.owx\n"));
+
+        let ofn = std::fs::read_to_string(stem.with_extension("ofn"))?;
+        assert!(ofn.contains("<http://www.example.com/iri/2020-01-01>"));
+        assert!(ofn.contains("Declaration(ObjectProperty(other:other-o))"));
+        assert!(!ofn.contains("Import("));
+
+        Ok(())
+    }
+}
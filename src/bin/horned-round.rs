@@ -1,21 +1,21 @@
 extern crate clap;
-extern crate failure;
 extern crate horned_owl;
 
 use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
-use failure::Error;
-
-use horned_owl::io::owx::reader::read;
-use horned_owl::io::owx::writer::write;
+use horned_owl::error::HornedError;
+use horned_owl::io::owx::reader::read_with_layout;
+use horned_owl::io::owx::writer::write_with_layout;
+use horned_owl::io::owx::Layout;
+use horned_owl::model::Build;
 
 use std::fs::File;
 use std::io::stdout;
 use std::io::BufReader;
 
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), HornedError> {
     let matches = App::new("horned-round")
         .version("0.1")
         .about("Parse and Render an OWL Ontology")
@@ -26,17 +26,53 @@ fn main() -> Result<(), Error> {
                 .required(true)
                 .index(1),
         )
+        .arg(
+            Arg::with_name("layout")
+                .long("layout")
+                .short("l")
+                .help("Keep the order of prefixes and ontology annotations, and the comments, of the input"),
+        )
         .get_matches();
 
     matcher(matches)
 }
 
-fn matcher(matches: ArgMatches) -> Result<(), Error> {
+fn matcher(matches: ArgMatches) -> Result<(), HornedError> {
     let input = matches.value_of("INPUT").unwrap();
 
     let file = File::open(input).unwrap();
     let mut bufreader = BufReader::new(file);
-    let (o, p) = read(&mut bufreader)?;
+    let (o, p, layout) = read_with_layout(&mut bufreader, &Build::new())?;
+    let layout = if matches.is_present("layout") {
+        layout
+    } else {
+        Layout::default()
+    };
+
+    write_with_layout(&mut stdout(), &o.into(), Some(&p), &layout)
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn layout() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-round")?;
+
+        cmd.arg("src/ont/owl-xml/comment.owx").arg("--layout");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("<!-- The class A -->"));
+
+        let mut cmd = Command::cargo_bin("horned-round")?;
+        cmd.arg("src/ont/owl-xml/comment.owx");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("<!--").not());
 
-    write(&mut stdout(), &o.into(), Some(&p))
+        Ok(())
+    }
 }
@@ -1,20 +1,18 @@
 extern crate clap;
-extern crate failure;
 extern crate horned_owl;
 
 use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
-use failure::Error;
-
-use horned_owl::{command::naming::name};
-use horned_owl::command::{parse_path, summary::summarize};
-use horned_owl::error::CommandError;
+use horned_owl::command::{parse_path, summary::Summary};
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::expressivity::Expressivity;
+use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
 
 use std::path::Path;
 
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), HornedError> {
     let matches = App::new("horned-summary")
         .version("0.1")
         .about("Summary Statistics for an OWL file.")
@@ -30,24 +28,18 @@ fn main() -> Result<(), Error> {
     matcher(&matches)
 }
 
-fn matcher(matches: &ArgMatches) -> Result<(), Error> {
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
     let input = matches
         .value_of("INPUT")
         .ok_or(CommandError::MissingArgument)?;
 
     let (ont, p, i) = parse_path(Path::new(input))?.decompose();
 
-    let summary = summarize(ont);
-    println!("Ontology has:");
-    println!("\tLogical Axioms: {}", summary.logical_axiom);
-    println!("\tAnnotation Axioms: {}", summary.annotation_axiom);
-    println!();
-    println!("Detailed");
-
-    for (axk, size) in summary.with_axiom_types() {
-        println!("\t{0:<40} | {1:<5}", name(axk), size);
-    }
+    let ont: AxiomMappedOntology = ont.into();
+    let expressivity = Expressivity::new(&ont);
 
+    print!("{}", ont.summary());
+    println!("\nExpressivity: {}", expressivity);
     if let Some(p) = p {
         println!("\nPrefixes");
         for i in p.mappings() {
@@ -0,0 +1,127 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use horned_owl::command::parse_path;
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::hierarchy::{render_tree, ClassHierarchy, TreeOptions};
+use horned_owl::model::*;
+use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+
+use std::io::stdout;
+use std::path::Path;
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-tree")
+        .version("0.1")
+        .about("Print the asserted class hierarchy of an OWL file as a tree")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("root")
+                .long("root")
+                .short("r")
+                .takes_value(true)
+                .help("The IRI or CURIE of the class to start from"),
+        )
+        .arg(
+            Arg::with_name("depth")
+                .long("depth")
+                .short("d")
+                .takes_value(true)
+                .help("Print at most this many levels below the root"),
+        )
+        .arg(
+            Arg::with_name("labels")
+                .long("labels")
+                .short("l")
+                .help("Print the labels of classes rather than their IRIs"),
+        )
+        .get_matches();
+
+    matcher(&matches)
+}
+
+fn matcher(matches: &ArgMatches) -> Result<(), HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+
+    let (ont, mapping, _) = parse_path(Path::new(input))?.decompose();
+    let ont: AxiomMappedOntology = ont.into();
+    let mapping = mapping.unwrap_or_default();
+
+    let root = matches.value_of("root").map(|root| {
+        Build::new().class(
+            mapping
+                .expand_curie_string(root)
+                .unwrap_or_else(|_| root.to_string()),
+        )
+    });
+    let depth = match matches.value_of("depth") {
+        Some(d) => Some(
+            d.parse()
+                .map_err(|_| CommandError::InvalidArgument(format!("depth: {}", d)))?,
+        ),
+        None => None,
+    };
+
+    let h = ClassHierarchy::asserted(&ont);
+    let labels = if matches.is_present("labels") {
+        Some(&ont)
+    } else {
+        None
+    };
+    render_tree(&mut stdout(), &h, &TreeOptions { root, depth }, labels)
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn tree() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-tree")?;
+
+        cmd.arg("src/ont/owl-xml/siblings.owx").arg("-r").arg("o:A");
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "http://www.example.com/iri#A\n  http://www.example.com/iri#B\n",
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn depth() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-tree")?;
+
+        cmd.arg("src/ont/owl-xml/siblings.owx").arg("-d").arg("0");
+        cmd.assert()
+            .success()
+            .stdout(predicate::eq("http://www.example.com/iri#A\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_depth() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-tree")?;
+
+        cmd.arg("src/ont/owl-xml/siblings.owx")
+            .arg("-d")
+            .arg("deep");
+        cmd.assert().failure();
+
+        Ok(())
+    }
+}
@@ -1,14 +1,11 @@
 extern crate clap;
-extern crate failure;
 extern crate horned_owl;
 
 use clap::App;
 use clap::Arg;
 use clap::ArgMatches;
 
-use failure::Error;
-
-use horned_owl::error::CommandError;
+use horned_owl::error::{CommandError, HornedError};
 
 use std::fs::File;
 use std::io::BufReader;
@@ -29,7 +26,7 @@ fn p_tup(triple: &[SpTerm; 3]) {
     );
 }
 
-fn main() -> Result<(), Error> {
+fn main() -> Result<(), HornedError> {
     let matches = App::new("horned-parse")
         .version("0.1")
         .about("Parse an OWL File and dump the data structures")
@@ -45,7 +42,7 @@ fn main() -> Result<(), Error> {
     matcher(matches)
 }
 
-fn matcher(matches: ArgMatches) -> Result<(), Error> {
+fn matcher(matches: ArgMatches) -> Result<(), HornedError> {
     let input = matches
         .value_of("INPUT")
         .ok_or(CommandError::MissingArgument)?;
@@ -0,0 +1,161 @@
+extern crate clap;
+extern crate horned_owl;
+
+use clap::App;
+use clap::Arg;
+use clap::ArgMatches;
+
+use horned_owl::command::parse_path;
+use horned_owl::error::{CommandError, HornedError};
+use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+use horned_owl::validate::{validate, validate_profile, Problem, Profile, Report};
+
+use std::path::Path;
+
+fn main() -> Result<(), HornedError> {
+    let matches = App::new("horned-validate")
+        .version("0.1")
+        .about("Validate an OWL Ontology")
+        .author("Phillip Lord")
+        .arg(
+            Arg::with_name("INPUT")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .takes_value(true)
+                .possible_values(&["json", "text"])
+                .default_value("json")
+                .help("The format of the report"),
+        )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .short("p")
+                .takes_value(true)
+                .possible_values(&["el", "ql", "rl"])
+                .help("Also check that the ontology is in this OWL 2 profile"),
+        )
+        .get_matches();
+
+    if !matcher(&matches)? {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Print the report, returning true if the ontology is valid.
+///
+/// An ontology which cannot be parsed is reported as invalid, with a
+/// single `ParseError` problem.
+fn matcher(matches: &ArgMatches) -> Result<bool, HornedError> {
+    let input = matches
+        .value_of("INPUT")
+        .ok_or(CommandError::MissingArgument)?;
+
+    let report = match parse_path(Path::new(input)) {
+        Ok(output) => {
+            let ont: AxiomMappedOntology = output.into();
+            match matches.value_of("profile") {
+                Some("el") => validate_profile(&ont, Profile::EL),
+                Some("ql") => validate_profile(&ont, Profile::QL),
+                Some("rl") => validate_profile(&ont, Profile::RL),
+                _ => validate(&ont),
+            }
+        }
+        Err(e) => Report {
+            problems: vec![Problem::ParseError(e.to_string())],
+        },
+    };
+
+    match matches.value_of("format") {
+        Some("text") => {
+            for p in &report.problems {
+                println!("{}", p);
+            }
+        }
+        _ => println!("{}", report.to_json()),
+    }
+
+    Ok(report.is_valid())
+}
+
+#[cfg(test)]
+mod test {
+    use assert_cmd::prelude::*; // Add methods on commands
+    use predicates::prelude::*; // Used for writing assertions
+    use std::process::Command; // Run programs
+
+    #[test]
+    fn valid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+
+        cmd.arg("src/ont/owl-xml/class.owx");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(r#"{"valid":true,"problems":[]}"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+
+        cmd.arg("src/ont/owl-xml/family.owx");
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains(r#""valid":false"#))
+            .stdout(predicate::str::contains("MalformedIRI"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn text() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+
+        cmd.arg("src/ont/owl-xml/family.owx").arg("-f").arg("text");
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains("Malformed IRI"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = mktemp::Temp::new_dir()?;
+        let path = temp.as_ref().join("parse-error.owx");
+        std::fs::write(&path, "<Ontology>")?;
+
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+        cmd.arg(&path);
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains(r#""valid":false"#))
+            .stdout(predicate::str::contains(r#""problem":"ParseError","iri":null"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+        cmd.arg("src/ont/owl-xml/some.owx").arg("-p").arg("el");
+        cmd.assert().success();
+
+        let mut cmd = Command::cargo_bin("horned-validate")?;
+        cmd.arg("src/ont/owl-xml/some.owx").arg("-p").arg("rl");
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains(r#""profile":"RL""#));
+
+        Ok(())
+    }
+}
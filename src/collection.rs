@@ -1,7 +1,635 @@
-use crate::model::{IRI, Ontology};
+//! Collections of ontologies
+use crate::error::ManagerError;
+use crate::io::{read_with_build, ResourceType};
+use crate::model::{AnnotatedAxiom, Axiom, AxiomKind, Build, MutableOntology, Ontology, IRI};
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::resolve::{resolve_ontology_with_build, IRIResolver};
 
+use crate::error::HornedError;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::BufRead;
 
+#[allow(dead_code)]
+pub struct OntologyCollection(HashMap<IRI, Box<dyn Ontology>>);
 
-pub struct OntologyCollection (HashMap<IRI, Box<dyn Ontology>>);
+/// Whether an ontology is loaded in an `OntologyManager`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadState {
+    /// The ontology is loaded.
+    Loaded,
+    /// The ontology is imported by a loaded ontology, but has not
+    /// been loaded itself.
+    Unloaded,
+    /// The ontology is neither loaded nor imported.
+    Unknown,
+}
+
+/// Own a set of ontologies, which share a single `Build`, and answer
+/// queries over their imports closures.
+///
+/// Ontologies are keyed by their ontology IRI, or by the IRI they
+/// were imported with if they were fetched. Imports are resolved
+/// against the loaded ontologies first, and then using the
+/// `IRIResolver`, if there is one.
+#[derive(Default)]
+pub struct OntologyManager {
+    build: Build,
+    ontologies: BTreeMap<IRI, AxiomMappedOntology>,
+    resolver: Option<Box<dyn IRIResolver>>,
+}
+
+impl OntologyManager {
+    pub fn new() -> OntologyManager {
+        OntologyManager::default()
+    }
+
+    /// Create a manager which fetches missing imports with `resolver`.
+    pub fn with_resolver<R: IRIResolver + 'static>(resolver: R) -> OntologyManager {
+        OntologyManager {
+            resolver: Some(Box::new(resolver)),
+            ..Default::default()
+        }
+    }
+
+    /// The `Build` shared by all ontologies in this manager.
+    pub fn build(&self) -> &Build {
+        &self.build
+    }
+
+    /// Add `ont`, replacing any ontology with the same IRI, and
+    /// return its IRI.
+    pub fn insert<O: Into<AxiomMappedOntology>>(&mut self, ont: O) -> Result<IRI, HornedError> {
+        let ont: AxiomMappedOntology = ont.into();
+        let iri = ont
+            .id()
+            .iri
+            .clone()
+            .ok_or(ManagerError::AnonymousOntology)?;
+        self.ontologies.insert(iri.clone(), ont);
+        Ok(iri)
+    }
+
+    /// Parse an ontology from `bufread` and add it.
+    pub fn read<R: BufRead>(
+        &mut self,
+        bufread: &mut R,
+        rt: ResourceType,
+    ) -> Result<IRI, HornedError> {
+        let ont = read_with_build(bufread, rt, &self.build)?;
+        self.insert(ont)
+    }
+
+    pub fn get(&self, iri: &IRI) -> Option<&AxiomMappedOntology> {
+        self.ontologies.get(iri)
+    }
+
+    pub fn remove(&mut self, iri: &IRI) -> Option<AxiomMappedOntology> {
+        self.ontologies.remove(iri)
+    }
+
+    pub fn contains(&self, iri: &IRI) -> bool {
+        self.ontologies.contains_key(iri)
+    }
+
+    /// Iterate over the IRIs of the loaded ontologies.
+    pub fn iris(&self) -> impl Iterator<Item = &IRI> {
+        self.ontologies.keys()
+    }
+
+    /// Return whether `iri` is loaded, or is imported by a loaded
+    /// ontology but has not been loaded itself.
+    pub fn load_state(&self, iri: &IRI) -> LoadState {
+        if self.contains(iri) {
+            LoadState::Loaded
+        } else if self
+            .ontologies
+            .values()
+            .any(|o| o.i().import().any(|i| &i.0 == iri))
+        {
+            LoadState::Unloaded
+        } else {
+            LoadState::Unknown
+        }
+    }
+
+    /// Return the ontology for `iri`, fetching it with the resolver
+    /// if it is not already loaded.
+    pub fn load(&mut self, iri: &IRI) -> Result<&AxiomMappedOntology, HornedError> {
+        if !self.contains(iri) {
+            let resolver = self
+                .resolver
+                .as_mut()
+                .ok_or_else(|| ManagerError::UnknownOntology(iri.to_string()))?;
+            let ont = resolve_ontology_with_build(iri, resolver.as_mut(), &self.build)?;
+
+            // Store under the IRI that was imported, which may differ
+            // from the ontology's own, for example a version IRI
+            self.ontologies.insert(iri.clone(), ont.into());
+        }
+        Ok(&self.ontologies[iri])
+    }
+
+    /// Fetch and add every ontology in the imports closure of `iri`
+    /// which is not already loaded.
+    pub fn load_imports(&mut self, iri: &IRI) -> Result<(), HornedError> {
+        let mut todo = self.direct_imports(iri)?;
+
+        while let Some(import) = todo.pop() {
+            if self.contains(&import) {
+                continue;
+            }
+
+            let ont = self.load(&import)?;
+            todo.extend(ont.i().import().map(|i| i.0.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Return the imports in the closure of `iri` which are not yet
+    /// loaded. Only the imports of loaded ontologies are known, so
+    /// loading these may reveal more.
+    pub fn unloaded_imports(&self, iri: &IRI) -> Result<Vec<IRI>, HornedError> {
+        let loaded = self.imports_closure(iri)?;
+        let mut unloaded: Vec<IRI> = vec![];
+        for ont in loaded {
+            for import in ont.i().import() {
+                if !self.contains(&import.0) && !unloaded.contains(&import.0) {
+                    unloaded.push(import.0.clone());
+                }
+            }
+        }
+        Ok(unloaded)
+    }
+
+    /// Call `f` on each ontology in the imports closure of `iri`,
+    /// starting with the ontology itself, until it returns a value.
+    ///
+    /// Ontologies are loaded only when `f` is about to be called on
+    /// them, so a query which is answered by the first ontologies
+    /// does not fetch the rest. Unlike `imports_closure`, this fails
+    /// if an import cannot be loaded.
+    pub fn find_in_closure<T, F>(&mut self, iri: &IRI, mut f: F) -> Result<Option<T>, HornedError>
+    where
+        F: FnMut(&AxiomMappedOntology) -> Option<T>,
+    {
+        let mut done = vec![];
+        let mut todo = vec![iri.clone()];
+
+        while let Some(next) = todo.pop() {
+            if done.contains(&next) {
+                continue;
+            }
+
+            let ont = self.load(&next)?;
+            if let Some(t) = f(ont) {
+                return Ok(Some(t));
+            }
+            todo.extend(ont.i().import().map(|i| i.0.clone()));
+            done.push(next);
+        }
+
+        Ok(None)
+    }
+
+    /// Return a single ontology with every axiom in the imports
+    /// closure of `iri`, fetching any imports which are not loaded.
+    ///
+    /// The result has the ID and the ontology annotations of `iri`,
+    /// but no imports. Axioms which are in more than one ontology of
+    /// the closure appear once.
+    pub fn flatten_imports(&mut self, iri: &IRI) -> Result<AxiomMappedOntology, HornedError> {
+        self.load_imports(iri)?;
+
+        let closure = self.imports_closure(iri)?;
+        let mut flat = AxiomMappedOntology::default();
+        *flat.mut_id() = closure[0].id().clone();
+
+        for (n, ont) in closure.into_iter().enumerate() {
+            for ax in ont.i().iter() {
+                match ax.axiom {
+                    Axiom::Import(_) => {}
+                    Axiom::OntologyAnnotation(_) if n > 0 => {}
+                    _ => {
+                        flat.insert(ax.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(flat)
+    }
+
+    fn direct_imports(&self, iri: &IRI) -> Result<Vec<IRI>, HornedError> {
+        let ont = self
+            .get(iri)
+            .ok_or_else(|| ManagerError::UnknownOntology(iri.to_string()))?;
+        Ok(ont.i().import().map(|i| i.0.clone()).collect())
+    }
+
+    /// Return the imports closure of `iri`, starting with the
+    /// ontology itself. Imports which are not loaded are ignored.
+    pub fn imports_closure(&self, iri: &IRI) -> Result<Vec<&AxiomMappedOntology>, HornedError> {
+        let mut closure: Vec<&AxiomMappedOntology> = vec![];
+        let mut done = vec![];
+        let mut todo = vec![iri.clone()];
+
+        while let Some(next) = todo.pop() {
+            if done.contains(&next) {
+                continue;
+            }
+            if let Some(ont) = self.get(&next) {
+                todo.extend(ont.i().import().map(|i| i.0.clone()));
+                closure.push(ont);
+            } else if closure.is_empty() {
+                return Err(ManagerError::UnknownOntology(next.to_string()).into());
+            }
+            done.push(next);
+        }
+
+        Ok(closure)
+    }
+
+    /// Return the graph of the imports between the loaded
+    /// ontologies.
+    pub fn import_graph(&self) -> ImportGraph {
+        ImportGraph {
+            imports: self
+                .ontologies
+                .iter()
+                .map(|(iri, o)| (iri.clone(), o.i().import().map(|i| i.0.clone()).collect()))
+                .collect(),
+        }
+    }
+
+    /// Iterate over all annotated axioms in the imports closure of `iri`.
+    pub fn closure_axioms(
+        &self,
+        iri: &IRI,
+    ) -> Result<impl Iterator<Item = &AnnotatedAxiom>, HornedError> {
+        Ok(self
+            .imports_closure(iri)?
+            .into_iter()
+            .flat_map(|o| o.i().iter()))
+    }
+
+    /// Iterate over all axioms of kind `axk` in the imports closure
+    /// of `iri`.
+    pub fn closure_axiom(
+        &self,
+        iri: &IRI,
+        axk: AxiomKind,
+    ) -> Result<impl Iterator<Item = &Axiom>, HornedError> {
+        Ok(self
+            .imports_closure(iri)?
+            .into_iter()
+            .flat_map(move |o| o.i().axiom(axk)))
+    }
+}
+
+/// The imports between the ontologies of an `OntologyManager`, as
+/// returned by `OntologyManager::import_graph`.
+///
+/// Each loaded ontology is a node, with an edge to each ontology it
+/// imports, whether or not that is loaded.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// use horned_owl::collection::OntologyManager;
+///
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.mut_id().iri = Some(b.iri("http://www.example.com/a"));
+/// o.insert(Import(b.iri("http://www.example.com/b")));
+///
+/// let mut m = OntologyManager::new();
+/// let a = m.insert(o).unwrap();
+/// let g = m.import_graph();
+///
+/// assert_eq!(g.missing_imports(), vec![&b.iri("http://www.example.com/b")]);
+/// assert!(g.importers(&b.iri("http://www.example.com/b")).any(|i| i == &a));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ImportGraph {
+    imports: BTreeMap<IRI, BTreeSet<IRI>>,
+}
+
+impl ImportGraph {
+    /// Iterate over the IRIs of the ontologies in the graph.
+    pub fn ontologies(&self) -> impl Iterator<Item = &IRI> {
+        self.imports.keys()
+    }
+
+    /// Iterate over the ontologies which `iri` imports directly.
+    pub fn direct_imports<'a>(&'a self, iri: &IRI) -> impl Iterator<Item = &'a IRI> {
+        self.imports.get(iri).into_iter().flatten()
+    }
+
+    /// Return the ontologies which `iri` imports, directly or
+    /// indirectly. These include `iri` only if it is in a cycle.
+    pub fn transitive_imports(&self, iri: &IRI) -> BTreeSet<&IRI> {
+        let mut done = BTreeSet::new();
+        let mut todo: Vec<&IRI> = self.direct_imports(iri).collect();
+
+        while let Some(next) = todo.pop() {
+            if done.insert(next) {
+                todo.extend(self.direct_imports(next));
+            }
+        }
+        done
+    }
+
+    /// Iterate over the loaded ontologies which import `iri`
+    /// directly.
+    pub fn importers<'a>(&'a self, iri: &'a IRI) -> impl Iterator<Item = &'a IRI> {
+        self.imports
+            .iter()
+            .filter(move |(_, imports)| imports.contains(iri))
+            .map(|(importer, _)| importer)
+    }
+
+    /// Return the loaded ontologies which import `iri`, directly or
+    /// indirectly.
+    pub fn transitive_importers<'a>(&'a self, iri: &'a IRI) -> BTreeSet<&'a IRI> {
+        let mut done = BTreeSet::new();
+        let mut todo: Vec<&IRI> = self.importers(iri).collect();
+
+        while let Some(next) = todo.pop() {
+            if done.insert(next) {
+                todo.extend(self.importers(next));
+            }
+        }
+        done
+    }
+
+    /// Return the ontologies which are imported but not loaded.
+    pub fn missing_imports(&self) -> Vec<&IRI> {
+        let missing: BTreeSet<&IRI> = self
+            .imports
+            .values()
+            .flatten()
+            .filter(|i| !self.imports.contains_key(*i))
+            .collect();
+        missing.into_iter().collect()
+    }
+
+    /// Return the cycles in the graph, as the sets of ontologies
+    /// which all import each other, directly or indirectly.
+    pub fn cycles(&self) -> Vec<BTreeSet<&IRI>> {
+        let mut cycles: Vec<BTreeSet<&IRI>> = vec![];
+        for iri in self.ontologies() {
+            if cycles.iter().any(|c| c.contains(iri)) {
+                continue;
+            }
+
+            let imports = self.transitive_imports(iri);
+            if imports.contains(iri) {
+                cycles.push(
+                    imports
+                        .into_iter()
+                        .filter(|i| self.transitive_imports(i).contains(iri))
+                        .collect(),
+                );
+            }
+        }
+        cycles
+    }
+
+    /// Return true if any ontology imports itself, directly or
+    /// indirectly.
+    pub fn has_cycle(&self) -> bool {
+        self.ontologies()
+            .any(|iri| self.transitive_imports(iri).contains(iri))
+    }
+}
+
+/// Fetch the ontology for `iri` and its imports closure with
+/// `resolver`, detecting the syntax of each document.
+///
+/// Each ontology is stored under the IRI it was fetched with, as for
+/// `OntologyManager::load`, so the returned manager answers
+/// `imports_closure(iri)` without further fetching.
+///
+/// # Examples
+/// ```no_run
+/// # use horned_owl::model::*;
+/// use horned_owl::collection::load_ontology;
+/// use horned_owl::resolve::{CatalogResolver, HTTPResolver};
+/// # use std::path::Path;
+///
+/// let mut resolver = CatalogResolver::new(
+///     Path::new("catalog-v001.xml"),
+///     HTTPResolver::default(),
+/// ).unwrap();
+/// let iri = Build::new().iri("http://purl.obolibrary.org/obo/bfo.owl");
+///
+/// let m = load_ontology(&iri, &mut resolver).unwrap();
+/// let closure = m.imports_closure(&iri).unwrap();
+/// ```
+pub fn load_ontology(
+    iri: &IRI,
+    resolver: &mut dyn IRIResolver,
+) -> Result<OntologyManager, HornedError> {
+    let mut m = OntologyManager::new();
+    let mut todo = vec![m.build.iri(iri.as_str())];
+
+    while let Some(iri) = todo.pop() {
+        if m.contains(&iri) {
+            continue;
+        }
+
+        let ont: AxiomMappedOntology =
+            resolve_ontology_with_build(&iri, resolver, &m.build)?.into();
+        todo.extend(ont.i().import().map(|i| i.0.clone()));
+        m.ontologies.insert(iri, ont);
+    }
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ResolveError;
+    use crate::model::Import;
+
+    struct MapResolver(HashMap<String, &'static str>);
+
+    impl IRIResolver for MapResolver {
+        fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+            self.0
+                .get(iri.as_str())
+                .map(|s| s.as_bytes().to_vec())
+                .ok_or_else(|| ResolveError::Unresolvable(iri.to_string()).into())
+        }
+    }
+
+    fn manager() -> (OntologyManager, IRI) {
+        let mut docs = HashMap::new();
+        docs.insert(
+            "http://www.example.com/other-property".to_string(),
+            include_str!("ont/owl-xml/other-property.owx"),
+        );
+
+        let mut m = OntologyManager::with_resolver(MapResolver(docs));
+        let iri = m
+            .read(
+                &mut include_str!("ont/owl-xml/import-property.owx").as_bytes(),
+                ResourceType::OWX,
+            )
+            .unwrap();
+        (m, iri)
+    }
+
+    #[test]
+    fn load_imports() {
+        let (mut m, iri) = manager();
+        assert_eq!(m.imports_closure(&iri).unwrap().len(), 1);
+
+        m.load_imports(&iri).unwrap();
+        assert_eq!(m.iris().count(), 2);
+        assert_eq!(m.imports_closure(&iri).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn closure_query() {
+        let (mut m, iri) = manager();
+        m.load_imports(&iri).unwrap();
+
+        let other = m
+            .build()
+            .iri("http://www.example.com/other-property#other-o");
+        assert!(m
+            .closure_axiom(&iri, AxiomKind::DeclareObjectProperty)
+            .unwrap()
+            .any(|ax| match ax {
+                Axiom::DeclareObjectProperty(d) => d.0 .0 == other,
+                _ => false,
+            }));
+    }
+
+    #[test]
+    fn flatten_imports() {
+        let (mut m, iri) = manager();
+        let flat = m.flatten_imports(&iri).unwrap();
+
+        assert_eq!(flat.id(), m.get(&iri).unwrap().id());
+        assert_eq!(flat.i().import().count(), 0);
+        assert_eq!(flat.i().declare_object_property().count(), 1);
+        assert_eq!(flat.i().declare_class().count(), 2);
+        assert_eq!(flat.i().iter().count(), 4);
+
+        // Without a resolver, the imports must already be loaded
+        let mut m = OntologyManager::new();
+        let iri = m
+            .read(
+                &mut include_str!("ont/owl-xml/import-property.owx").as_bytes(),
+                ResourceType::OWX,
+            )
+            .unwrap();
+        assert!(m.flatten_imports(&iri).is_err());
+    }
+
+    #[test]
+    fn shared_build() {
+        let (mut m, iri) = manager();
+        m.load_imports(&iri).unwrap();
+
+        let imported = m.build().iri("http://www.example.com/other-property");
+        let a = m.get(&imported).unwrap().id().iri.clone().unwrap();
+        let b = m.build().iri("http://www.example.com/other-property");
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+    }
+
+    #[test]
+    fn lazy() {
+        let (mut m, iri) = manager();
+        let import = m.build().iri("http://www.example.com/other-property");
+        assert_eq!(m.load_state(&import), LoadState::Unloaded);
+        assert_eq!(m.unloaded_imports(&iri).unwrap(), vec![import.clone()]);
+
+        // Answered by the root ontology alone
+        let imports = m
+            .find_in_closure(&iri, |o| o.i().import().next().map(|i| i.0.clone()))
+            .unwrap();
+        assert_eq!(imports, Some(import.clone()));
+        assert_eq!(m.load_state(&import), LoadState::Unloaded);
+
+        // Needs the import
+        let other = m
+            .build()
+            .iri("http://www.example.com/other-property#other-o");
+        let found = m
+            .find_in_closure(&iri, |o| {
+                o.i().declare_object_property().find(|d| d.0 .0 == other).cloned()
+            })
+            .unwrap();
+        assert!(found.is_some());
+        assert_eq!(m.load_state(&import), LoadState::Loaded);
+        assert!(m.unloaded_imports(&iri).unwrap().is_empty());
+        assert_eq!(m.load_state(&other), LoadState::Unknown);
+    }
+
+    #[test]
+    fn load() {
+        let mut docs = HashMap::new();
+        docs.insert(
+            "http://www.example.com/other-property".to_string(),
+            include_str!("ont/owl-xml/other-property.owx"),
+        );
+        docs.insert(
+            "http://www.example.com/iri".to_string(),
+            include_str!("ont/owl-xml/import-property.owx"),
+        );
+
+        let b = Build::new();
+        let iri = b.iri("http://www.example.com/iri");
+        let m = load_ontology(&iri, &mut MapResolver(docs)).unwrap();
+
+        assert_eq!(m.iris().count(), 2);
+        assert_eq!(m.imports_closure(&iri).unwrap().len(), 2);
+        let missing = b.iri("http://www.example.com/missing");
+        assert!(load_ontology(&missing, &mut MapResolver(HashMap::new())).is_err());
+    }
+
+    #[test]
+    fn import_graph() {
+        let (mut m, iri) = manager();
+        let other = m.build().iri("http://www.example.com/other-property");
+        let g = m.import_graph();
+        assert_eq!(g.missing_imports(), vec![&other]);
+        assert_eq!(g.direct_imports(&iri).collect::<Vec<_>>(), vec![&other]);
+        assert!(!g.has_cycle());
+
+        // Close a cycle through a third ontology
+        let third = m.build().iri("http://www.example.com/third");
+        let mut o = AxiomMappedOntology::default();
+        o.mut_id().iri = Some(third.clone());
+        o.insert(Import(iri.clone()));
+        m.insert(o).unwrap();
+        m.load_imports(&iri).unwrap();
+        let mut o = m.remove(&other).unwrap();
+        o.insert(Import(third.clone()));
+        m.insert(o).unwrap();
+
+        let g = m.import_graph();
+        assert!(g.missing_imports().is_empty());
+        assert_eq!(g.transitive_imports(&iri).len(), 3);
+        assert_eq!(
+            g.transitive_importers(&other),
+            vec![&iri, &other, &third].into_iter().collect()
+        );
+        assert_eq!(g.cycles().len(), 1);
+        assert_eq!(g.cycles()[0].len(), 3);
+    }
+
+    #[test]
+    fn unknown() {
+        let m = OntologyManager::new();
+        let b = Build::new();
+        assert!(m.imports_closure(&b.iri("http://www.example.com")).is_err());
+    }
+}
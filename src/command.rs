@@ -1,77 +1,160 @@
 //! Support for Horned command line programmes
 
-use crate::{io::{ParserOutput, ResourceType}, model::{Build, IRI}, ontology::{axiom_mapped::AxiomMappedOntology}, resolve::{localize_iri, strict_resolve_iri}};
+use crate::{io::{ParserOutput, ResourceType}, ontology::{axiom_mapped::AxiomMappedOntology}};
+#[cfg(any(feature = "rdf", feature = "remote"))]
+use crate::model::Build;
+use crate::error::{CommandError, ResolveError};
+#[cfg(feature = "remote")]
+use crate::{model::IRI, resolve::{find_catalog, localize_iri, CatalogResolver, FileResolver, HTTPResolver, IRIResolver}};
 
-use failure::Error;
+use curie::PrefixMapping;
 
-use std::{fs::File, io::{BufReader, Write}, path::Path};
+use crate::error::HornedError;
 
+use flate2::{write::GzEncoder, Compression};
+
+use std::{ffi::OsStr, fs::File, io::{BufRead, BufReader, Write}, path::Path};
+
+/// Return the `ResourceType` for a format name, which is also the
+/// file extension used for that format.
+pub fn format_type(name: &str) -> Option<ResourceType> {
+    match name {
+        "owx" => Some(ResourceType::OWX),
+        "owl" => Some(ResourceType::RDF),
+        "ofn" => Some(ResourceType::OFN),
+        "omn" => Some(ResourceType::OMN),
+        "nt" => Some(ResourceType::NT),
+        "hob" => Some(ResourceType::HOB),
+        _ => None,
+    }
+}
+
+/// Return the format name of `rt`, the inverse of `format_type`.
+pub fn format_name(rt: ResourceType) -> &'static str {
+    match rt {
+        ResourceType::OWX => "owx",
+        ResourceType::RDF => "owl",
+        ResourceType::OFN => "ofn",
+        ResourceType::OMN => "omn",
+        ResourceType::NT => "nt",
+        ResourceType::HOB => "hob",
+    }
+}
+
+/// Return the `ResourceType` for `path` from its extension, ignoring
+/// any trailing `.gz`.
 pub fn path_type(path: &Path) -> Option<ResourceType> {
-   match path.extension().map(|s| s.to_str()).flatten() {
-       Some("owx") => Some(ResourceType::OWX),
-       Some("owl") => Some(ResourceType::RDF),
-       _ => None,
-   }
+    let path = if is_gz_path(path) {
+        Path::new(path.file_stem()?)
+    } else {
+        path
+    };
+    path.extension().and_then(|s| s.to_str()).and_then(format_type)
+}
+
+/// Return true if `path` has a `.gz` extension.
+pub fn is_gz_path(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("gz"))
 }
 
-pub fn parse_path(path: &Path) -> Result<ParserOutput, Error>
+pub fn parse_path(path: &Path) -> Result<ParserOutput, HornedError>
 {
-    let file = File::open(&path)?;
-    let mut bufreader = BufReader::new(file);
-
-    Ok(match path_type(path) {
-        Some(ResourceType::OWX) => super::io::owx::reader::read(&mut bufreader)?.into(),
-        Some(ResourceType::RDF) => super::io::rdf::reader::read(&mut bufreader)?.into(),
-        _ => {
-            eprintln!("Do not know how to parse file with path: {:?}", path);
-            todo!()
-        },
-    })
+    let file = File::open(path)?;
+
+    match path_type(path) {
+        Some(rt) => parse_file(file, rt),
+        None => Err(ResolveError::UnknownFormat(path.to_string_lossy().into_owned()).into()),
+    }
+}
+
+/// Parse `path` as `rt`, regardless of its extension.
+///
+/// Gzipped files are decompressed transparently.
+pub fn parse_path_as(path: &Path, rt: ResourceType) -> Result<ParserOutput, HornedError> {
+    parse_file(File::open(path)?, rt)
+}
+
+fn parse_file(file: File, rt: ResourceType) -> Result<ParserOutput, HornedError> {
+    parse_bufread(&mut BufReader::new(file), rt)
+}
+
+/// Parse `bufread` as `rt`; see `io::read`.
+pub fn parse_bufread<R: BufRead>(
+    bufread: &mut R,
+    rt: ResourceType,
+) -> Result<ParserOutput, HornedError> {
+    crate::io::read(bufread, rt)
+}
+
+/// Write `ont` to `write` as `rt`; see `io::write`.
+pub fn write_as(mut write: &mut dyn Write, ont: &AxiomMappedOntology,
+                mapping: Option<&PrefixMapping>, rt: ResourceType) -> Result<(), HornedError> {
+    crate::io::write(&mut write, ont, mapping, rt)
+}
+
+/// Write `ont` to `write` as `rt`, gzip compressed.
+pub fn write_gz_as(write: &mut dyn Write, ont: &AxiomMappedOntology,
+                   mapping: Option<&PrefixMapping>, rt: ResourceType) -> Result<(), HornedError> {
+    let mut gz = GzEncoder::new(write, Compression::default());
+    crate::io::write(&mut gz, ont, mapping, rt)?;
+    gz.finish()?;
+    Ok(())
 }
 
 /// Parse but only as far as the imports, if that makes sense.
-pub fn parse_imports(path: &Path) -> Result<ParserOutput, Error> {
-    let file = File::open(&path)?;
-    let mut bufreader = BufReader::new(file);
-    Ok(match path_type(path) {
-        Some(ResourceType::OWX) => super::io::owx::reader::read(&mut bufreader)?.into(),
+pub fn parse_imports(path: &Path) -> Result<ParserOutput, HornedError> {
+    #[cfg(any(feature = "owx", feature = "rdf"))]
+    let open = || -> Result<_, HornedError> { Ok(BufReader::new(File::open(path)?)) };
+    match path_type(path) {
+        #[cfg(feature = "owx")]
+        Some(ResourceType::OWX) => Ok(super::io::owx::reader::read(&mut open()?)?.into()),
+        #[cfg(feature = "rdf")]
         Some(ResourceType::RDF) => {
+            let mut bufreader = open()?;
             let b = Build::new();
             let mut p = crate::io::rdf::reader::parser_with_build(&mut bufreader,
-                                                              &b);
+                                                              &b)?;
             p.parse_imports()?;
-            p.as_ontology_and_incomplete()?.into()
-        }
-        _ => {
-            eprintln!("Do not know how to parse file with path: {:?}", path);
-            todo!()
+            Ok(p.as_ontology_and_incomplete()?.into())
         }
-    })
+        _ => Err(CommandError::UnsupportedFormat("parsing only the imports").into()),
+    }
 }
 
-pub fn materialize(input: &str) -> Result<Vec<IRI>,Error> {
+#[cfg(feature = "remote")]
+pub fn materialize(input: &str) -> Result<Vec<IRI>,HornedError> {
     let mut v = vec![];
     materialize_1(input, &mut v, true)?;
     Ok(v)
 }
 
+#[cfg(feature = "remote")]
 pub fn materialize_1<'a>(input: &str, done: &'a mut Vec<IRI>, recurse: bool)
-                         -> Result<&'a mut Vec<IRI>,Error> {
+                         -> Result<&'a mut Vec<IRI>,HornedError> {
     println!("Parsing: {}", input);
     let amont:AxiomMappedOntology = parse_imports(Path::new(input))?.into();
     let import = amont.i().import();
 
     let b = Build::new();
 
+    // Use the locations in the catalog, if there is one
+    let catalog = Path::new(input).parent()
+        .and_then(find_catalog)
+        .map(|c| CatalogResolver::new(&c, FileResolver))
+        .transpose()?;
+
     // Get all the imports
     for i in import {
         if !done.contains(&i.0) {
 
-            let local:String = localize_iri(&i.0, &b.iri(input)).into();
+            let local:String = match catalog.as_ref().and_then(|c| c.local_path(&i.0)) {
+                Some(p) => p.to_string_lossy().into_owned(),
+                None => localize_iri(&i.0, &b.iri(input))?.into(),
+            };
             let local_path = Path::new(&local);
             if !local_path.exists() {
                 println!("Retrieving Ontology: {}", &i.0);
-                let imported_data = strict_resolve_iri(&i.0);
+                let imported_data = HTTPResolver::default().resolve(&i.0)?;
                 done.push(i.0.clone());
                 println!("Saving to {}", local);
                 let mut file = File::create(&local)?;
@@ -95,6 +178,18 @@ pub fn materialize_1<'a>(input: &str, done: &'a mut Vec<IRI>, recurse: bool)
 pub mod naming {
     use crate::model::AxiomKind;
     use crate::model::AxiomKind::*;
+    use crate::model::NamedEntityKind;
+
+    pub fn entity_name(nek: &NamedEntityKind) -> &'static str {
+        match nek {
+            NamedEntityKind::Class => "Class",
+            NamedEntityKind::Datatype => "Datatype",
+            NamedEntityKind::ObjectProperty => "Object Property",
+            NamedEntityKind::DataProperty => "Data Property",
+            NamedEntityKind::AnnotationProperty => "Annotation Property",
+            NamedEntityKind::NamedIndividual => "Named Individual",
+        }
+    }
 
     pub fn name(axk: &AxiomKind) -> &'static str {
         match axk {
@@ -148,40 +243,95 @@ pub mod naming {
 
 pub mod summary {
 
-    use crate::{model::AxiomKind, ontology::axiom_mapped::AxiomMappedOntology};
+    use super::naming::{entity_name, name};
+    use crate::{
+        model::{AxiomKind, NamedEntityKind},
+        ontology::axiom_mapped::AxiomMappedOntology,
+        signature::Signature,
+    };
     use indexmap::map::IndexMap;
 
+    use std::fmt;
+
     #[derive(Debug)]
     pub struct SummaryStatistics {
         pub logical_axiom: usize,
         pub annotation_axiom: usize,
         pub axiom_type: IndexMap<AxiomKind, usize>,
+        pub entity_type: IndexMap<NamedEntityKind, usize>,
     }
 
     impl SummaryStatistics {
         pub fn with_axiom_types(&self) -> impl Iterator<Item = (&AxiomKind, &usize)> + '_ {
             self.axiom_type.iter().filter(|&(_, v)| v > &0)
         }
+
+        pub fn with_entity_types(
+            &self,
+        ) -> impl Iterator<Item = (&NamedEntityKind, &usize)> + '_ {
+            self.entity_type.iter().filter(|&(_, v)| v > &0)
+        }
     }
 
-    pub fn summarize<O: Into<AxiomMappedOntology>>(ont: O) -> SummaryStatistics
-    where
-        O: ,
-    {
-        let ont: AxiomMappedOntology = ont.into();
-        SummaryStatistics {
-            logical_axiom: ont.i().iter().count(),
-            annotation_axiom: ont
-                .i()
-                .iter()
-                .map(|aa| aa.ann.iter().count())
-                .sum::<usize>(),
-            axiom_type: axiom_types(ont),
+    /// Prints the counts as a table, omitting kinds which do not
+    /// occur.
+    impl fmt::Display for SummaryStatistics {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "Ontology has:")?;
+            writeln!(f, "\tLogical Axioms: {}", self.logical_axiom)?;
+            writeln!(f, "\tAnnotation Axioms: {}", self.annotation_axiom)?;
+            writeln!(f)?;
+            writeln!(f, "Detailed")?;
+            for (axk, size) in self.with_axiom_types() {
+                writeln!(f, "\t{0:<40} | {1:<5}", name(axk), size)?;
+            }
+            writeln!(f)?;
+            writeln!(f, "Entities")?;
+            for (nek, size) in self.with_entity_types() {
+                writeln!(f, "\t{0:<40} | {1:<5}", entity_name(nek), size)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Access to the summary statistics of an ontology.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// use horned_owl::command::summary::Summary;
+    ///
+    /// let b = Build::new();
+    /// let mut o = AxiomMappedOntology::default();
+    /// o.declare(b.class("http://www.example.com/A"));
+    ///
+    /// let s = o.summary();
+    /// assert_eq!(s.axiom_type[&AxiomKind::DeclareClass], 1);
+    /// assert_eq!(s.entity_type[&NamedEntityKind::Class], 1);
+    /// println!("{}", s);
+    /// ```
+    pub trait Summary {
+        fn summary(&self) -> SummaryStatistics;
+    }
+
+    impl Summary for AxiomMappedOntology {
+        fn summary(&self) -> SummaryStatistics {
+            SummaryStatistics {
+                logical_axiom: self.i().iter().count(),
+                annotation_axiom: self.i().iter().map(|aa| aa.ann.len()).sum::<usize>(),
+                axiom_type: axiom_types(self),
+                entity_type: entity_types(self),
+            }
         }
     }
 
-    fn axiom_types<O: Into<AxiomMappedOntology>>(ont: O) -> IndexMap<AxiomKind, usize> {
+    pub fn summarize<O: Into<AxiomMappedOntology>>(ont: O) -> SummaryStatistics {
         let ont: AxiomMappedOntology = ont.into();
+        ont.summary()
+    }
+
+    fn axiom_types(ont: &AxiomMappedOntology) -> IndexMap<AxiomKind, usize> {
         let mut im = IndexMap::new();
         for ax in AxiomKind::all_kinds() {
             im.insert(ax, ont.i().axiom(ax).count());
@@ -189,4 +339,47 @@ pub mod summary {
 
         im
     }
+
+    fn entity_types(ont: &AxiomMappedOntology) -> IndexMap<NamedEntityKind, usize> {
+        let mut im = IndexMap::new();
+        for ne in ont.signature() {
+            *im.entry(ne.kind()).or_insert(0) += 1;
+        }
+
+        im
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::model::*;
+
+        #[test]
+        fn table() {
+            let b = Build::new();
+            let mut o = AxiomMappedOntology::default();
+            o.declare(b.class("http://www.example.com/A"));
+            o.insert(SubClassOf {
+                sup: b.class("http://www.example.com/B").into(),
+                sub: b.class("http://www.example.com/A").into(),
+            });
+            o.declare(b.object_property("http://www.example.com/r"));
+
+            let s = o.summary();
+            assert_eq!(s.logical_axiom, 3);
+            assert_eq!(s.with_axiom_types().count(), 3);
+            assert_eq!(
+                s.with_entity_types().collect::<Vec<_>>(),
+                vec![
+                    (&NamedEntityKind::Class, &2),
+                    (&NamedEntityKind::ObjectProperty, &1)
+                ]
+            );
+
+            let table = s.to_string();
+            assert!(table.contains("\tSub-Class Of"));
+            assert!(table.contains(&format!("\t{:<40} | 2", "Class")));
+            assert!(!table.contains("Datatype"));
+        }
+    }
 }
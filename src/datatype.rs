@@ -0,0 +1,765 @@
+//! The OWL 2 datatype map
+//!
+//! Each datatype in the
+//! [map](https://www.w3.org/TR/owl2-syntax/#Datatype_Maps) has a
+//! lexical space, the strings which are valid literals of that
+//! datatype. This module checks literals against the lexical space
+//! of their declared datatype, including range checks for the
+//! bounded numeric types, and maps valid literals to their values.
+use crate::model::Literal;
+use crate::vocab::{OWL2Datatype, WithIRI};
+
+/// Return the datatype of `l`, if it is in the OWL 2 datatype map.
+pub fn literal_datatype(l: &Literal) -> Option<OWL2Datatype> {
+    match l {
+        Literal::Simple { .. } => Some(OWL2Datatype::XSDString),
+        Literal::Language { .. } => Some(OWL2Datatype::RDFLangString),
+        Literal::Datatype { datatype_iri, .. } => OWL2Datatype::var_s(datatype_iri),
+    }
+}
+
+/// Returns true if `l` is a valid literal of its datatype.
+///
+/// Literals with a datatype outside the OWL 2 datatype map are
+/// always valid.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::datatype::is_valid;
+/// # use horned_owl::model::*;
+/// let b = Build::new();
+/// let l = Literal::Datatype {
+///     literal: "300".to_string(),
+///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#byte"),
+/// };
+/// assert!(!is_valid(&l));
+/// ```
+pub fn is_valid(l: &Literal) -> bool {
+    match l {
+        Literal::Language { literal, lang } => {
+            is_valid_lexical(OWL2Datatype::RDFLangString, literal) && is_language_tag(lang)
+        }
+        _ => match literal_datatype(l) {
+            Some(dt) => is_valid_lexical(dt, l.literal()),
+            None => true,
+        },
+    }
+}
+
+/// Returns true if `s` is in the lexical space of `dt`.
+pub fn is_valid_lexical(dt: OWL2Datatype, s: &str) -> bool {
+    use OWL2Datatype::*;
+    match dt {
+        RDFSLiteral | RDFXMLLiteral | RDFLangString | XSDString | XSDAnyURI => true,
+        // owl:real has no lexical forms of its own
+        OWLReal => false,
+        OWLRational => is_rational(s),
+        RDFPlainLiteral => match s.rfind('@') {
+            Some(i) => i + 1 == s.len() || is_language_tag(&s[i + 1..]),
+            None => false,
+        },
+        XSDDecimal => is_decimal(s),
+        XSDInteger => integer(s).is_some(),
+        XSDNonNegativeInteger => in_range(s, 0, None),
+        XSDNonPositiveInteger => matches!(integer(s), Some((true, _)) | Some((_, ""))),
+        XSDPositiveInteger => in_range(s, 1, None),
+        XSDNegativeInteger => matches!(integer(s), Some((true, d)) if !d.is_empty()),
+        XSDLong => in_range(s, i64::MIN.into(), Some(i64::MAX.into())),
+        XSDInt => in_range(s, i32::MIN.into(), Some(i32::MAX.into())),
+        XSDShort => in_range(s, i16::MIN.into(), Some(i16::MAX.into())),
+        XSDByte => in_range(s, i8::MIN.into(), Some(i8::MAX.into())),
+        XSDUnsignedLong => in_range(s, 0, Some(u64::MAX.into())),
+        XSDUnsignedInt => in_range(s, 0, Some(u32::MAX.into())),
+        XSDUnsignedShort => in_range(s, 0, Some(u16::MAX.into())),
+        XSDUnsignedByte => in_range(s, 0, Some(u8::MAX.into())),
+        XSDDouble | XSDFloat => is_floating(s),
+        XSDNormalizedString => !s.contains(&['\r', '\n', '\t'][..]),
+        XSDToken => {
+            is_valid_lexical(XSDNormalizedString, s)
+                && !s.starts_with(' ')
+                && !s.ends_with(' ')
+                && !s.contains("  ")
+        }
+        XSDLanguage => is_language_tag(s),
+        XSDName => is_name(s, true),
+        XSDNCName => is_name(s, false),
+        XSDNMTOKEN => !s.is_empty() && s.chars().all(|c| is_name_char(c, true)),
+        XSDBoolean => matches!(s, "true" | "false" | "1" | "0"),
+        XSDHexBinary => s.len().is_multiple_of(2) && s.chars().all(|c| c.is_ascii_hexdigit()),
+        XSDBase64Binary => is_base64(s),
+        XSDDateTime => DateTime::parse(s).is_some(),
+        XSDDateTimeStamp => matches!(
+            DateTime::parse(s),
+            Some(DateTime {
+                offset: Some(_),
+                ..
+            })
+        ),
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn strip_sign(s: &str) -> (bool, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    }
+}
+
+/// Split an integer into its sign and digits without leading zeros,
+/// so that zero has no digits.
+fn integer(s: &str) -> Option<(bool, &str)> {
+    let (neg, digits) = strip_sign(s);
+    if !is_digits(digits) {
+        return None;
+    }
+    Some((neg, digits.trim_start_matches('0')))
+}
+
+fn in_range(s: &str, min: i128, max: Option<i128>) -> bool {
+    match integer(s) {
+        // Zero, whatever its sign
+        Some((_, "")) => min <= 0 && max.is_none_or(|m| m >= 0),
+        Some((neg, d)) => {
+            let v: i128 = match d.parse() {
+                Ok(v) => v,
+                // Too large for any of the bounded ranges
+                Err(_) => return !neg && max.is_none(),
+            };
+            let v = if neg { -v } else { v };
+            v >= min && max.is_none_or(|m| v <= m)
+        }
+        None => false,
+    }
+}
+
+fn is_decimal(s: &str) -> bool {
+    let (_, s) = strip_sign(s);
+    match s.find('.') {
+        Some(i) => {
+            let (int, frac) = (&s[..i], &s[i + 1..]);
+            (is_digits(int) || int.is_empty())
+                && (is_digits(frac) || frac.is_empty())
+                && !(int.is_empty() && frac.is_empty())
+        }
+        None => is_digits(s),
+    }
+}
+
+fn is_floating(s: &str) -> bool {
+    if matches!(s, "INF" | "+INF" | "-INF" | "NaN") {
+        return true;
+    }
+    match s.find(&['e', 'E'][..]) {
+        Some(i) => is_decimal(&s[..i]) && integer(&s[i + 1..]).is_some(),
+        None => is_decimal(s),
+    }
+}
+
+fn is_rational(s: &str) -> bool {
+    match s.find('/') {
+        Some(i) => {
+            integer(&s[..i]).is_some()
+                && is_digits(&s[i + 1..])
+                && integer(&s[i + 1..]) != Some((false, ""))
+        }
+        None => integer(s).is_some(),
+    }
+}
+
+fn is_language_tag(s: &str) -> bool {
+    let mut parts = s.split('-');
+    let primary = parts.next().unwrap_or("");
+    (1..=8).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|p| (1..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+fn is_name_start_char(c: char, colon: bool) -> bool {
+    c.is_alphabetic() || c == '_' || (colon && c == ':')
+}
+
+fn is_name_char(c: char, colon: bool) -> bool {
+    is_name_start_char(c, colon) || c.is_alphanumeric() || c == '-' || c == '.' || c == '\u{B7}'
+}
+
+fn is_name(s: &str, colon: bool) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => is_name_start_char(c, colon) && chars.all(|c| is_name_char(c, colon)),
+        None => false,
+    }
+}
+
+fn is_base64(s: &str) -> bool {
+    let s: Vec<u8> = s.bytes().filter(|b| *b != b' ').collect();
+    if !s.len().is_multiple_of(4) {
+        return false;
+    }
+
+    let data = s.iter().rposition(|b| *b != b'=').map_or(0, |i| i + 1);
+    s.len() - data <= 2
+        && s[..data]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'+' || *b == b'/')
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parse exactly `n` digits from the start of `s`.
+fn fixed(s: &str, n: usize) -> Option<u32> {
+    match s.get(..n) {
+        Some(d) if is_digits(d) => d.parse().ok(),
+        _ => None,
+    }
+}
+
+/// The value of an `xsd:dateTime`.
+///
+/// Fractions of a second beyond nanoseconds are truncated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+    /// The timezone offset from UTC in minutes, if there is one.
+    pub offset: Option<i32>,
+}
+
+impl DateTime {
+    /// Parse the lexical form of an `xsd:dateTime`.
+    pub fn parse(s: &str) -> Option<DateTime> {
+        let (neg, rest) = match s.strip_prefix('-') {
+            Some(r) => (true, r),
+            None => (false, s),
+        };
+
+        // Year: at least four digits, with no leading zero if more
+        let dash = rest.find('-')?;
+        let year = &rest[..dash];
+        if !is_digits(year) || year.len() < 4 || (year.len() > 4 && year.starts_with('0')) {
+            return None;
+        }
+        let year: i64 = year.parse().ok()?;
+        let year = if neg { -year } else { year };
+
+        // MM-DDThh:mm:ss
+        let rest = &rest[dash + 1..];
+        let b = rest.as_bytes();
+        if b.len() < 14 || b[2] != b'-' || b[5] != b'T' || b[8] != b':' || b[11] != b':' {
+            return None;
+        }
+        let month = fixed(rest, 2)?;
+        let day = fixed(&rest[3..], 2)?;
+        let hour = fixed(&rest[6..], 2)?;
+        let minute = fixed(&rest[9..], 2)?;
+        let second = fixed(&rest[12..], 2)?;
+        if !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year, month)
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+
+        let mut rest = &rest[14..];
+        let mut nanosecond = 0;
+        let mut fraction = false;
+        if let Some(r) = rest.strip_prefix('.') {
+            let n = r.bytes().take_while(|b| b.is_ascii_digit()).count();
+            if n == 0 {
+                return None;
+            }
+            fraction = r[..n].bytes().any(|b| b != b'0');
+            nanosecond = format!("{:0<9}", &r[..n.min(9)]).parse().ok()?;
+            rest = &r[n..];
+        }
+
+        // 24:00:00 is the end of the day
+        if hour > 24 || (hour == 24 && (minute != 0 || second != 0 || fraction)) {
+            return None;
+        }
+
+        let offset = match rest {
+            "" => None,
+            "Z" => Some(0),
+            tz => {
+                let b = tz.as_bytes();
+                if b.len() != 6 || (b[0] != b'+' && b[0] != b'-') || b[3] != b':' {
+                    return None;
+                }
+                let (h, m) = (fixed(&tz[1..], 2)?, fixed(&tz[4..], 2)?);
+                if m > 59 || h > 14 || (h == 14 && m != 0) {
+                    return None;
+                }
+                let o = (h * 60 + m) as i32;
+                Some(if b[0] == b'-' { -o } else { o })
+            }
+        };
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        })
+    }
+
+    /// Return the same instant in UTC, with 24:00:00 replaced by the
+    /// start of the next day. A `DateTime` without a timezone keeps
+    /// its local time.
+    pub fn normalize(&self) -> DateTime {
+        let minutes = days_from_civil(self.year, self.month, self.day) * 1440
+            + i64::from(self.hour * 60 + self.minute)
+            - i64::from(self.offset.unwrap_or(0));
+
+        let (year, month, day) = civil_from_days(minutes.div_euclid(1440));
+        let minutes = minutes.rem_euclid(1440) as u32;
+        DateTime {
+            year,
+            month,
+            day,
+            hour: minutes / 60,
+            minute: minutes % 60,
+            offset: self.offset.map(|_| 0),
+            ..*self
+        }
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.year < 0 {
+            write!(f, "-")?;
+        }
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year.abs(),
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second
+        )?;
+        if self.nanosecond > 0 {
+            let frac = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", frac.trim_end_matches('0'))?;
+        }
+        match self.offset {
+            None => Ok(()),
+            Some(0) => write!(f, "Z"),
+            Some(o) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if o < 0 { '-' } else { '+' },
+                o.abs() / 60,
+                o.abs() % 60
+            ),
+        }
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar; see
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn is_integer_type(dt: OWL2Datatype) -> bool {
+    use OWL2Datatype::*;
+    matches!(
+        dt,
+        XSDInteger
+            | XSDNonNegativeInteger
+            | XSDNonPositiveInteger
+            | XSDPositiveInteger
+            | XSDNegativeInteger
+            | XSDLong
+            | XSDInt
+            | XSDShort
+            | XSDByte
+            | XSDUnsignedLong
+            | XSDUnsignedInt
+            | XSDUnsignedShort
+            | XSDUnsignedByte
+    )
+}
+
+fn canonical_decimal(s: &str) -> String {
+    let (neg, s) = strip_sign(s);
+    let (int, frac) = match s.find('.') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    };
+    let int = int.trim_start_matches('0');
+    let frac = frac.trim_end_matches('0');
+
+    let mut c = String::new();
+    if neg && !(int.is_empty() && frac.is_empty()) {
+        c.push('-');
+    }
+    c.push_str(if int.is_empty() { "0" } else { int });
+    if !frac.is_empty() {
+        c.push('.');
+        c.push_str(frac);
+    }
+    c
+}
+
+fn canonical_floating<F: std::str::FromStr + std::fmt::UpperExp>(s: &str) -> Option<String> {
+    if s.ends_with("INF") || s == "NaN" {
+        return Some(s.trim_start_matches('+').to_string());
+    }
+    let e = format!("{:E}", s.parse::<F>().ok()?);
+    Some(match e.find('E') {
+        Some(i) if !e[..i].contains('.') => format!("{}.0{}", &e[..i], &e[i..]),
+        _ => e,
+    })
+}
+
+impl Literal {
+    fn valid_datatype(&self) -> Option<OWL2Datatype> {
+        literal_datatype(self).filter(|_| is_valid(self))
+    }
+
+    /// Return the value of an integer literal, if it fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        let dt = self.valid_datatype()?;
+        if is_integer_type(dt) {
+            self.literal().trim_start_matches('+').parse().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Return the value of a numeric literal as an `f64`, which may
+    /// lose precision.
+    pub fn as_f64(&self) -> Option<f64> {
+        let dt = self.valid_datatype()?;
+        let s = self.literal();
+        match dt {
+            OWL2Datatype::XSDDouble | OWL2Datatype::XSDFloat => match &s[..] {
+                "INF" | "+INF" => Some(f64::INFINITY),
+                "-INF" => Some(f64::NEG_INFINITY),
+                "NaN" => Some(f64::NAN),
+                _ => s.parse().ok(),
+            },
+            OWL2Datatype::XSDDecimal => s.parse().ok(),
+            dt if is_integer_type(dt) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.valid_datatype()? {
+            OWL2Datatype::XSDBoolean => Some(self.literal() == "true" || self.literal() == "1"),
+            _ => None,
+        }
+    }
+
+    /// Return the value of an `xsd:dateTime` or `xsd:dateTimeStamp`
+    /// literal.
+    pub fn as_datetime(&self) -> Option<DateTime> {
+        match self.valid_datatype()? {
+            OWL2Datatype::XSDDateTime | OWL2Datatype::XSDDateTimeStamp => {
+                DateTime::parse(self.literal())
+            }
+            _ => None,
+        }
+    }
+
+    /// Return this literal with its lexical form replaced by the
+    /// canonical form of its value, so that literals with equal
+    /// values are equal.
+    ///
+    /// Invalid literals, and those of datatypes with no canonical
+    /// form, are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new();
+    /// let int = |s: &str| Literal::Datatype {
+    ///     literal: s.to_string(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    /// };
+    /// assert_eq!(int("+01").normalize(), int("1"));
+    /// ```
+    pub fn normalize(&self) -> Literal {
+        let s = self.literal();
+        let canonical = match self.valid_datatype() {
+            Some(OWL2Datatype::XSDDecimal) => canonical_decimal(s),
+            Some(OWL2Datatype::XSDDouble) => {
+                canonical_floating::<f64>(s).unwrap_or_else(|| s.clone())
+            }
+            Some(OWL2Datatype::XSDFloat) => {
+                canonical_floating::<f32>(s).unwrap_or_else(|| s.clone())
+            }
+            Some(OWL2Datatype::XSDBoolean) => self.as_bool().unwrap().to_string(),
+            Some(OWL2Datatype::XSDDateTime) | Some(OWL2Datatype::XSDDateTimeStamp) => {
+                self.as_datetime().unwrap().normalize().to_string()
+            }
+            Some(dt) if is_integer_type(dt) => {
+                let (neg, digits) = integer(s).unwrap();
+                match digits {
+                    "" => "0".to_string(),
+                    d if neg => format!("-{}", d),
+                    d => d.to_string(),
+                }
+            }
+            _ => return self.clone(),
+        };
+
+        match self {
+            Literal::Datatype { datatype_iri, .. } => Literal::Datatype {
+                literal: canonical,
+                datatype_iri: datatype_iri.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::Build;
+
+    use OWL2Datatype::*;
+
+    fn typed(literal: &str, dt: OWL2Datatype) -> Literal {
+        Literal::Datatype {
+            literal: literal.to_string(),
+            datatype_iri: Build::new().iri(dt.iri_str()),
+        }
+    }
+
+    #[test]
+    fn datatype_of_literal() {
+        let l = Literal::Simple {
+            literal: "a".to_string(),
+        };
+        assert_eq!(literal_datatype(&l), Some(XSDString));
+        assert_eq!(literal_datatype(&typed("1", XSDInt)), Some(XSDInt));
+
+        let l = Literal::Datatype {
+            literal: "1".to_string(),
+            datatype_iri: Build::new().iri("http://www.example.com/dt"),
+        };
+        assert_eq!(literal_datatype(&l), None);
+        assert!(is_valid(&l));
+    }
+
+    #[test]
+    fn integers() {
+        assert!(is_valid_lexical(XSDInteger, "-0012"));
+        assert!(is_valid_lexical(XSDInteger, "+12"));
+        assert!(!is_valid_lexical(XSDInteger, "1.0"));
+        assert!(!is_valid_lexical(XSDInteger, ""));
+        assert!(!is_valid_lexical(XSDInteger, "-"));
+
+        assert!(is_valid_lexical(XSDByte, "-128"));
+        assert!(!is_valid_lexical(XSDByte, "128"));
+        assert!(is_valid_lexical(XSDUnsignedLong, "18446744073709551615"));
+        assert!(!is_valid_lexical(XSDUnsignedLong, "18446744073709551616"));
+        assert!(!is_valid_lexical(XSDUnsignedInt, "-1"));
+        assert!(is_valid_lexical(XSDUnsignedInt, "-0"));
+
+        assert!(is_valid_lexical(XSDNonPositiveInteger, "+0"));
+        assert!(!is_valid_lexical(XSDNegativeInteger, "-0"));
+        assert!(!is_valid_lexical(XSDPositiveInteger, "000"));
+        assert!(is_valid_lexical(
+            XSDPositiveInteger,
+            "123456789012345678901234567890123456789012345678901234567890"
+        ));
+        assert!(!is_valid_lexical(
+            XSDLong,
+            "123456789012345678901234567890123456789012345678901234567890"
+        ));
+    }
+
+    #[test]
+    fn numbers() {
+        assert!(is_valid_lexical(XSDDecimal, "-1.5"));
+        assert!(is_valid_lexical(XSDDecimal, ".5"));
+        assert!(is_valid_lexical(XSDDecimal, "5."));
+        assert!(!is_valid_lexical(XSDDecimal, "."));
+        assert!(!is_valid_lexical(XSDDecimal, "1e3"));
+
+        assert!(is_valid_lexical(XSDDouble, "1e3"));
+        assert!(is_valid_lexical(XSDDouble, "-1.5E-10"));
+        assert!(is_valid_lexical(XSDFloat, "INF"));
+        assert!(is_valid_lexical(XSDFloat, "NaN"));
+        assert!(!is_valid_lexical(XSDFloat, "inf"));
+        assert!(!is_valid_lexical(XSDDouble, "1e"));
+
+        assert!(is_valid_lexical(OWLRational, "-1/3"));
+        assert!(!is_valid_lexical(OWLRational, "1/0"));
+        assert!(!is_valid_lexical(OWLRational, "1/-3"));
+        assert!(!is_valid_lexical(OWLReal, "1"));
+    }
+
+    #[test]
+    fn strings() {
+        assert!(is_valid_lexical(XSDString, "\tanything\n"));
+        assert!(!is_valid_lexical(XSDNormalizedString, "a\tb"));
+        assert!(is_valid_lexical(XSDToken, "a b"));
+        assert!(!is_valid_lexical(XSDToken, "a  b"));
+        assert!(!is_valid_lexical(XSDToken, " a"));
+
+        assert!(is_valid_lexical(XSDLanguage, "en-GB"));
+        assert!(!is_valid_lexical(XSDLanguage, "en_GB"));
+        assert!(is_valid_lexical(XSDName, "xsd:int"));
+        assert!(!is_valid_lexical(XSDNCName, "xsd:int"));
+        assert!(!is_valid_lexical(XSDNCName, "1a"));
+        assert!(is_valid_lexical(XSDNMTOKEN, "1a"));
+
+        assert!(is_valid_lexical(RDFPlainLiteral, "hello@en"));
+        assert!(is_valid_lexical(RDFPlainLiteral, "hello@"));
+        assert!(!is_valid_lexical(RDFPlainLiteral, "hello"));
+
+        let l = Literal::Language {
+            literal: "hello".to_string(),
+            lang: "not a tag".to_string(),
+        };
+        assert!(!is_valid(&l));
+    }
+
+    #[test]
+    fn binary() {
+        assert!(is_valid_lexical(XSDBoolean, "1"));
+        assert!(!is_valid_lexical(XSDBoolean, "True"));
+
+        assert!(is_valid_lexical(XSDHexBinary, "0FB7"));
+        assert!(!is_valid_lexical(XSDHexBinary, "0FB"));
+
+        assert!(is_valid_lexical(XSDBase64Binary, "aGVsbG8="));
+        assert!(is_valid_lexical(XSDBase64Binary, ""));
+        assert!(!is_valid_lexical(XSDBase64Binary, "aGVsbG8"));
+        assert!(!is_valid_lexical(XSDBase64Binary, "a==="));
+    }
+
+    #[test]
+    fn date_times() {
+        assert!(is_valid_lexical(XSDDateTime, "2002-10-10T12:00:00"));
+        assert!(is_valid_lexical(XSDDateTime, "2002-10-10T12:00:00.5-05:00"));
+        assert!(is_valid_lexical(XSDDateTime, "-0044-03-15T00:00:00Z"));
+        assert!(is_valid_lexical(XSDDateTime, "2000-02-29T24:00:00"));
+        assert!(!is_valid_lexical(XSDDateTime, "2001-02-29T00:00:00"));
+        assert!(!is_valid_lexical(XSDDateTime, "2002-10-10T24:00:01"));
+        assert!(!is_valid_lexical(XSDDateTime, "2002-10-10"));
+        assert!(!is_valid_lexical(XSDDateTime, "02002-10-10T12:00:00"));
+        assert!(!is_valid_lexical(XSDDateTime, "2002-10-10T12:00:00+15:00"));
+
+        assert!(is_valid_lexical(XSDDateTimeStamp, "2002-10-10T12:00:00Z"));
+        assert!(!is_valid_lexical(XSDDateTimeStamp, "2002-10-10T12:00:00"));
+        assert!(!is_valid(&typed("2002-13-10T12:00:00", XSDDateTime)));
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(typed("+0012", XSDInteger).as_i64(), Some(12));
+        assert_eq!(typed("-5", XSDByte).as_i64(), Some(-5));
+        assert_eq!(typed("99999999999999999999", XSDInteger).as_i64(), None);
+        assert_eq!(typed("1.5", XSDDecimal).as_i64(), None);
+        assert_eq!(typed("300", XSDByte).as_i64(), None);
+
+        assert_eq!(typed("1.5", XSDDecimal).as_f64(), Some(1.5));
+        assert_eq!(typed("-INF", XSDDouble).as_f64(), Some(f64::NEG_INFINITY));
+        assert_eq!(typed("12", XSDInt).as_f64(), Some(12.0));
+        assert_eq!(typed("a", XSDString).as_f64(), None);
+
+        assert_eq!(typed("1", XSDBoolean).as_bool(), Some(true));
+        assert_eq!(typed("false", XSDBoolean).as_bool(), Some(false));
+    }
+
+    #[test]
+    fn date_time_value() {
+        let dt = typed("2002-10-10T12:00:00.25-05:00", XSDDateTime)
+            .as_datetime()
+            .unwrap();
+        assert_eq!(dt.year, 2002);
+        assert_eq!(dt.nanosecond, 250_000_000);
+        assert_eq!(dt.offset, Some(-300));
+        assert_eq!(dt.to_string(), "2002-10-10T12:00:00.25-05:00");
+        assert_eq!(dt.normalize().to_string(), "2002-10-10T17:00:00.25Z");
+
+        let dt = DateTime::parse("1999-12-31T24:00:00").unwrap();
+        assert_eq!(dt.normalize().to_string(), "2000-01-01T00:00:00");
+
+        let dt = DateTime::parse("-0001-03-01T01:00:00+02:00").unwrap();
+        assert_eq!(dt.normalize().to_string(), "-0001-02-28T23:00:00Z");
+
+        assert!(typed("2002-10-10", XSDDateTime).as_datetime().is_none());
+    }
+
+    #[test]
+    fn normalize() {
+        let n = |s: &str, dt| typed(s, dt).normalize().literal().clone();
+
+        assert_eq!(n("01", XSDInteger), "1");
+        assert_eq!(n("-000", XSDInteger), "0");
+        assert_eq!(n("+10", XSDLong), "10");
+        assert_eq!(n("+01.50", XSDDecimal), "1.5");
+        assert_eq!(n("-0.0", XSDDecimal), "0");
+        assert_eq!(n("3.", XSDDecimal), "3");
+        assert_eq!(n("1e3", XSDDouble), "1.0E3");
+        assert_eq!(n("0.00125", XSDDouble), "1.25E-3");
+        assert_eq!(n("+INF", XSDFloat), "INF");
+        assert_eq!(n("0.1", XSDFloat), "1.0E-1");
+        assert_eq!(n("1", XSDBoolean), "true");
+        assert_eq!(
+            n("2002-10-10T00:00:00+01:00", XSDDateTime),
+            "2002-10-09T23:00:00Z"
+        );
+
+        // Unchanged
+        assert_eq!(n("x", XSDInteger), "x");
+        assert_eq!(n(" a ", XSDString), " a ");
+        assert_eq!(typed("01", XSDInteger).normalize(), typed("1", XSDInteger));
+    }
+}
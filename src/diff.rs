@@ -0,0 +1,116 @@
+//! Differences between two ontologies.
+//!
+//! The difference is structural: an axiom is unchanged only if an
+//! identical axiom, including its annotations, is present in both
+//! ontologies.
+use crate::model::*;
+
+use std::collections::HashSet;
+
+/// The axioms added and removed between two ontologies.
+///
+/// Both lists are sorted, so that the difference is deterministic.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Diff<'a> {
+    pub added: Vec<&'a AnnotatedAxiom>,
+    pub removed: Vec<&'a AnnotatedAxiom>,
+}
+
+impl<'a> Diff<'a> {
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Return the difference between the axioms of `old` and `new`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::set::SetOntology;
+/// # use horned_owl::diff::diff;
+/// let b = Build::new();
+/// let mut old = SetOntology::new();
+/// old.declare(b.class("http://www.example.com/A"));
+///
+/// let mut new = SetOntology::new();
+/// new.declare(b.class("http://www.example.com/B"));
+///
+/// let d = diff(&old, &new);
+/// assert_eq!(d.added.len(), 1);
+/// assert_eq!(d.removed.len(), 1);
+/// ```
+pub fn diff<'a, O, N>(old: O, new: N) -> Diff<'a>
+where
+    O: IntoIterator<Item = &'a AnnotatedAxiom>,
+    N: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let old: HashSet<&AnnotatedAxiom> = old.into_iter().collect();
+    let new: HashSet<&AnnotatedAxiom> = new.into_iter().collect();
+
+    let mut added: Vec<_> = new.difference(&old).cloned().collect();
+    let mut removed: Vec<_> = old.difference(&new).cloned().collect();
+    added.sort();
+    removed.sort();
+
+    Diff { added, removed }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::io::owx::reader::test::read_ok;
+    use crate::ontology::set::SetOntology;
+
+    #[test]
+    fn identical() {
+        let (ont, _) = read_ok(&mut include_str!("ont/owl-xml/family.owx").as_bytes());
+        let (ont2, _) = read_ok(&mut include_str!("ont/owl-xml/family.owx").as_bytes());
+        let ont: SetOntology = ont.into();
+        let ont2: SetOntology = ont2.into();
+
+        assert!(diff(&ont, &ont2).is_empty());
+    }
+
+    #[test]
+    fn annotation_change() {
+        let b = Build::new();
+        let sc: AnnotatedAxiom = SubClassOf {
+            sup: b.class("http://www.example.com/B").into(),
+            sub: b.class("http://www.example.com/A").into(),
+        }
+        .into();
+
+        let mut annotated = sc.clone();
+        annotated.ann.insert(Annotation {
+            ap: b.annotation_property("http://www.example.com/comment"),
+            av: b.iri("http://www.example.com/C").into(),
+        });
+
+        let old: SetOntology = vec![sc].into_iter().collect();
+        let new: SetOntology = vec![annotated].into_iter().collect();
+
+        let d = diff(&old, &new);
+        assert_eq!(d.added.len(), 1);
+        assert_eq!(d.removed.len(), 1);
+        assert_eq!(d.added[0].axiom, d.removed[0].axiom);
+        assert!(!d.added[0].ann.is_empty());
+    }
+
+    #[test]
+    fn sorted() {
+        let b = Build::new();
+        let mut new = SetOntology::new();
+        for i in 0..10 {
+            new.declare(b.class(format!("http://www.example.com/C{}", i)));
+        }
+
+        let old = SetOntology::new();
+        let d = diff(&old, &new);
+        assert_eq!(d.added.len(), 10);
+        assert!(d.added.windows(2).all(|w| w[0] < w[1]));
+        assert!(d.removed.is_empty());
+    }
+}
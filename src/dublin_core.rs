@@ -0,0 +1,108 @@
+//! Dublin Core metadata of an ontology
+//!
+//! The release metadata of an ontology, such as its authors and
+//! license, is often given by its header annotations with
+//! [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dcmi-terms/)
+//! properties. These functions read and replace these ontology
+//! annotations, as `version` does for the OWL version annotations.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! use horned_owl::dublin_core::*;
+//!
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! let cc_by = b.iri("http://creativecommons.org/licenses/by/4.0/");
+//! set_creators(&mut o, vec![Literal::Simple { literal: "Jane Doe".to_string() }.into()]);
+//! set_license(&mut o, Some(cc_by.clone().into()));
+//!
+//! assert_eq!(creators(&o)[0].as_str(), Some("Jane Doe"));
+//! assert_eq!(license(&o).and_then(AnnotationValue::as_iri), Some(&cc_by));
+//! ```
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::version::{replace, values_of};
+use crate::vocab::{DCTerms, WithIRI, DC};
+
+/// Return the creators of the ontology, given by either `dc:creator`
+/// or `dcterms:creator`.
+pub fn creators(ont: &AxiomMappedOntology) -> Vec<&AnnotationValue> {
+    values_of(ont, DC::Creator.iri_str())
+        .chain(values_of(ont, DCTerms::Creator.iri_str()))
+        .collect()
+}
+
+/// Replace the creators of the ontology with `dc:creator` annotations
+/// with the values `creators`.
+///
+/// Any `dcterms:creator` annotations are removed.
+pub fn set_creators<I>(ont: &mut AxiomMappedOntology, creators: I)
+where
+    I: IntoIterator<Item = AnnotationValue>,
+{
+    replace(ont, DCTerms::Creator.iri_str(), vec![]);
+    replace(ont, DC::Creator.iri_str(), creators);
+}
+
+/// Return the `dcterms:license` of the ontology.
+pub fn license(ont: &AxiomMappedOntology) -> Option<&AnnotationValue> {
+    values_of(ont, DCTerms::License.iri_str()).next()
+}
+
+/// Set or, with `None`, remove the `dcterms:license` of the ontology.
+pub fn set_license(ont: &mut AxiomMappedOntology, license: Option<AnnotationValue>) {
+    replace(ont, DCTerms::License.iri_str(), license);
+}
+
+/// Return the `dcterms:created` date of the ontology.
+pub fn created(ont: &AxiomMappedOntology) -> Option<&Literal> {
+    values_of(ont, DCTerms::Created.iri_str()).find_map(AnnotationValue::as_literal)
+}
+
+/// Set or, with `None`, remove the `dcterms:created` date of the
+/// ontology.
+pub fn set_created(ont: &mut AxiomMappedOntology, created: Option<Literal>) {
+    replace(ont, DCTerms::Created.iri_str(), created.map(Into::into));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::read;
+
+    #[test]
+    fn from_header() {
+        let ont = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+          ontologyIRI="http://www.example.com/o">
+  <Annotation>
+    <AnnotationProperty IRI="http://purl.org/dc/elements/1.1/creator"/>
+    <Literal>Jane Doe</Literal>
+  </Annotation>
+  <Annotation>
+    <AnnotationProperty IRI="http://purl.org/dc/terms/creator"/>
+    <IRI>https://orcid.org/0000-0000-0000-0000</IRI>
+  </Annotation>
+  <Annotation>
+    <AnnotationProperty IRI="http://purl.org/dc/terms/created"/>
+    <Literal datatypeIRI="http://www.w3.org/2001/XMLSchema#date">2020-01-01</Literal>
+  </Annotation>
+</Ontology>"#;
+        let (so, _) = read(&mut ont.as_bytes()).unwrap();
+        let mut o: AxiomMappedOntology = so.into();
+
+        assert_eq!(creators(&o).len(), 2);
+        assert_eq!(
+            created(&o).map(|l| l.literal().as_str()),
+            Some("2020-01-01")
+        );
+        assert_eq!(license(&o), None);
+
+        set_creators(&mut o, vec![]);
+        set_created(&mut o, None);
+        assert!(creators(&o).is_empty());
+        assert_eq!(o.i().ontology_annotation().count(), 0);
+    }
+}
@@ -1,6 +1,213 @@
 //! Errors
-#[derive(Debug, Fail)]
+//!
+//! All of the fallible functions of this library return a
+//! `HornedError`; its variants group the errors by their cause, so
+//! that callers can match on the kind of failure, while the more
+//! specific enums below give the detail.
+use crate::reasoner::EntailmentReport;
+use crate::validate::Report;
+
+use thiserror::Error;
+
+/// The error type of this library.
+#[derive(Debug, Error)]
+pub enum HornedError {
+    /// A file or stream could not be read or written.
+    #[error("IO Error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    /// A document could not be parsed.
+    #[error("Parsing Error: {0}")]
+    ParserError(#[from] ParserError),
+
+    /// An entity, axiom or ontology is not valid OWL.
+    #[error("Validity Error: {0}")]
+    ValidityError(#[from] ValidityError),
+
+    /// An ontology could not be resolved from its IRI.
+    #[error("Resolve Error: {0}")]
+    ResolveError(#[from] ResolveError),
+
+    /// An operation on an `OntologyManager` failed.
+    #[error("Manager Error: {0}")]
+    ManagerError(#[from] ManagerError),
+
+    /// A reasoner could not answer.
+    #[error("Reasoner Error: {0}")]
+    ReasonerError(#[from] ReasonerError),
+
+    /// A command line tool was used incorrectly.
+    #[error("Command Error: {0}")]
+    CommandError(#[from] CommandError),
+
+    /// A fresh IRI could not be generated.
+    #[error("Fresh Error: {0}")]
+    FreshError(#[from] FreshError),
+
+    /// An OBO identifier could not be translated.
+    #[error("OBO Error: {0}")]
+    OBOError(#[from] OBOError),
+}
+
+#[derive(Debug, Error)]
 pub enum CommandError {
-    #[fail(display = "An argument that was expected is missing")]
+    #[error("An argument that was expected is missing")]
     MissingArgument,
+    #[error("This format is not supported: {0}")]
+    UnsupportedFormat(&'static str),
+    #[error("Invalid value for an argument: {0}")]
+    InvalidArgument(String),
+}
+
+#[derive(Debug, Error)]
+pub enum ParserError {
+    #[error("IRI is punned, which is not allowed: {0}")]
+    Punning(String),
+    #[error("Invalid binary ontology: {0}")]
+    InvalidBinary(String),
+    #[error("Invalid Manchester syntax: {0}")]
+    Manchester(String),
+    #[error("Entity is used but not declared: {0}")]
+    Undeclared(String),
+    #[error("Some of the ontology could not be parsed: {0}")]
+    Incomplete(String),
+    #[error("Invalid XML: {0}")]
+    XML(String),
+    #[error("Invalid RDF: {0}")]
+    RDF(String),
+    #[error("Invalid text: {0}")]
+    Encoding(String),
+    #[error("Invalid number: {0}")]
+    Number(String),
+    #[error("The parser cannot do this now: {0}")]
+    WrongState(&'static str),
+    #[error("End Tag Arrived Unexpectedly: {tag} at {pos}")]
+    UnexpectedEndTag { tag: String, pos: usize },
+    #[error("Missing End Tag: expected {tag} after {pos}")]
+    MissingEndTag { tag: String, pos: usize },
+    #[error("Missing element: Expected {tag} at {pos}")]
+    MissingElement { tag: String, pos: usize },
+    #[error("Missing attribute: Expected {attribute} at {pos}")]
+    MissingAttribute { attribute: String, pos: usize },
+    #[error("Unknown Entity: Expected Kind of {kind}, found {found} at {pos}")]
+    UnknownEntity {
+        kind: String,
+        found: String,
+        pos: usize,
+    },
+    #[error("Unexpected Tag: found {tag} at {pos}")]
+    UnexpectedTag { tag: String, pos: usize },
+    #[error("Unexpected End of File: {pos}")]
+    UnexpectedEof { pos: usize },
+}
+
+#[derive(Debug, Error)]
+pub enum ValidityError {
+    #[error("{} problems found{}", .0.problems.len(), first_problem(.0))]
+    Problems(Report),
+    #[error("IRI is not for a type of entity: {0}")]
+    NotAnEntityType(String),
+    #[error("Literal with language tag and incorrect datatype: {0}")]
+    LanguageDatatype(String),
+    #[error("Invalid prefix mapping: {0}")]
+    Prefix(String),
+    #[error("Cannot be written as RDF: {0}")]
+    RDF(String),
+    #[error("Cannot be written in Manchester syntax: {0}")]
+    Manchester(String),
+    #[error("An anonymous ontology cannot have a version IRI: {0}")]
+    AnonymousVersionIRI(String),
+}
+
+fn first_problem(report: &Report) -> String {
+    report
+        .problems
+        .first()
+        .map(|p| format!(", the first: {}", p))
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Error)]
+pub enum ResolveError {
+    #[error("Cannot resolve IRI: {0}")]
+    Unresolvable(String),
+    #[error("The format of the document could not be determined: {0}")]
+    UnknownFormat(String),
+}
+
+#[derive(Debug, Error)]
+pub enum ManagerError {
+    #[error("No ontology with this IRI has been loaded: {0}")]
+    UnknownOntology(String),
+    #[error("The ontology has no IRI, so cannot be managed")]
+    AnonymousOntology,
+}
+
+#[derive(Debug, Error)]
+pub enum ReasonerError {
+    #[error(
+        "{} expected axioms are not entailed, and {} forbidden axioms are",
+        .0.missing.len(),
+        .0.unexpected.len()
+    )]
+    Entailments(EntailmentReport),
+}
+
+#[derive(Debug, Error)]
+pub enum FreshError {
+    #[error("There are no more fresh IRIs with the base: {0}")]
+    Exhausted(String),
+    #[error("Could not generate a random IRI: {0}")]
+    Random(String),
+}
+
+#[derive(Debug, Error)]
+pub enum OBOError {
+    #[error("Not a valid OBO identifier: {0}")]
+    InvalidID(String),
+}
+
+impl From<quick_xml::Error> for HornedError {
+    fn from(e: quick_xml::Error) -> HornedError {
+        ParserError::XML(e.to_string()).into()
+    }
+}
+
+impl From<std::str::Utf8Error> for HornedError {
+    fn from(e: std::str::Utf8Error) -> HornedError {
+        ParserError::Encoding(e.to_string()).into()
+    }
+}
+
+impl From<std::string::FromUtf8Error> for HornedError {
+    fn from(e: std::string::FromUtf8Error) -> HornedError {
+        ParserError::Encoding(e.to_string()).into()
+    }
+}
+
+impl From<std::num::ParseIntError> for HornedError {
+    fn from(e: std::num::ParseIntError) -> HornedError {
+        ParserError::Number(e.to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn match_kind() {
+        let e: HornedError = ManagerError::AnonymousOntology.into();
+        assert!(matches!(
+            e,
+            HornedError::ManagerError(ManagerError::AnonymousOntology)
+        ));
+
+        let e: HornedError = std::io::Error::new(std::io::ErrorKind::NotFound, "gone").into();
+        assert!(matches!(e, HornedError::IOError(_)));
+        assert_eq!(e.to_string(), "IO Error: gone");
+
+        let e: HornedError = ValidityError::Problems(Report::default()).into();
+        assert_eq!(e.to_string(), "Validity Error: 0 problems found");
+    }
 }
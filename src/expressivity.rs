@@ -0,0 +1,513 @@
+//! The description logic expressivity of an ontology
+//!
+//! `Expressivity` records the description logic constructs used by
+//! an ontology, and names the smallest logic, such as ALC, SHIQ,
+//! SROIQ(D) or EL++, which includes them all, in the same way as the
+//! OWL API.
+use crate::metrics::expressions;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::{WithIRI, OWL};
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// A description logic construct, named by its letter.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Construct {
+    /// Universal restriction and atomic negation
+    AL,
+    /// Union
+    U,
+    /// Complex negation
+    C,
+    /// Full existential restriction
+    E,
+    /// Unqualified number restrictions
+    N,
+    /// Qualified number restrictions
+    Q,
+    /// Role hierarchies
+    H,
+    /// Inverse roles
+    I,
+    /// Nominals
+    O,
+    /// Functional roles
+    F,
+    /// Complex role inclusions and role characteristics
+    R,
+    /// Transitive roles
+    S,
+    /// Datatypes
+    D,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Expressivity {
+    pub constructs: BTreeSet<Construct>,
+    /// False if any construct is used which is outside EL++.
+    pub el: bool,
+}
+
+impl Default for Expressivity {
+    fn default() -> Expressivity {
+        Expressivity {
+            constructs: BTreeSet::new(),
+            el: true,
+        }
+    }
+}
+
+fn is_thing(ce: &ClassExpression) -> bool {
+    matches!(ce, ClassExpression::Class(c) if c.0.as_str() == OWL::Thing.iri_str())
+}
+
+fn is_literal(dr: &DataRange) -> bool {
+    matches!(dr, DataRange::Datatype(dt)
+             if dt.0.as_str() == "http://www.w3.org/2000/01/rdf-schema#Literal")
+}
+
+impl Expressivity {
+    /// Find the expressivity of `ont`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::expressivity::Expressivity;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let b = Build::new();
+    /// let mut o = AxiomMappedOntology::default();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: ClassExpression::ObjectComplementOf(Box::new(
+    ///         ClassExpression::ObjectSomeValuesFrom {
+    ///             ope: b.object_property("http://www.example.com/r").into(),
+    ///             bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///         },
+    ///     )),
+    /// });
+    ///
+    /// assert_eq!(Expressivity::new(&o).to_string(), "ALC");
+    /// ```
+    pub fn new(ont: &AxiomMappedOntology) -> Expressivity {
+        let mut e = Expressivity::default();
+        for ax in ont.i().iter() {
+            e.add_axiom(&ax.axiom);
+        }
+        e
+    }
+
+    fn add(&mut self, c: Construct) {
+        use Construct::*;
+        if !matches!(c, E | H | O | R | S | D) {
+            self.el = false;
+        }
+        self.constructs.insert(c);
+    }
+
+    fn add_property(&mut self, ope: &ObjectPropertyExpression) {
+        if let ObjectPropertyExpression::InverseObjectProperty(_) = ope {
+            self.add(Construct::I);
+        }
+    }
+
+    fn add_cardinality(&mut self, qualified: bool) {
+        self.add(if qualified {
+            Construct::Q
+        } else {
+            Construct::N
+        });
+    }
+
+    fn add_class_expression(&mut self, ce: &ClassExpression) {
+        use ClassExpression::*;
+        use Construct::*;
+        match ce {
+            Class(_) | ObjectIntersectionOf(_) => {}
+            ObjectUnionOf(_) => self.add(U),
+            ObjectComplementOf(bce) => match **bce {
+                Class(_) => self.add(AL),
+                _ => self.add(C),
+            },
+            ObjectOneOf(v) => {
+                self.add(O);
+                if v.len() > 1 {
+                    self.el = false;
+                }
+            }
+            ObjectSomeValuesFrom { ope, bce } => {
+                self.add_property(ope);
+                if !is_thing(bce) {
+                    self.add(E);
+                }
+            }
+            ObjectAllValuesFrom { ope, .. } => {
+                self.add_property(ope);
+                self.add(AL);
+            }
+            ObjectHasValue { ope, .. } => {
+                self.add_property(ope);
+                self.add(O);
+            }
+            ObjectHasSelf(ope) => {
+                self.add_property(ope);
+                self.add(R);
+            }
+            ObjectMinCardinality { ope, bce, .. }
+            | ObjectMaxCardinality { ope, bce, .. }
+            | ObjectExactCardinality { ope, bce, .. } => {
+                self.add_property(ope);
+                self.add_cardinality(!is_thing(bce));
+            }
+            DataSomeValuesFrom { .. } | DataHasValue { .. } => self.add(D),
+            DataAllValuesFrom { .. } => {
+                self.add(D);
+                self.el = false;
+            }
+            DataMinCardinality { dr, .. }
+            | DataMaxCardinality { dr, .. }
+            | DataExactCardinality { dr, .. } => {
+                self.add(D);
+                self.add_cardinality(!is_literal(dr));
+            }
+        }
+    }
+
+    fn add_data_range(&mut self, dr: &DataRange) {
+        self.add(Construct::D);
+        if matches!(
+            dr,
+            DataRange::DataUnionOf(_) | DataRange::DataComplementOf(_)
+        ) {
+            self.el = false;
+        }
+    }
+
+    fn add_axiom(&mut self, ax: &Axiom) {
+        use Construct::*;
+
+        let (ces, drs) = expressions(ax);
+        for ce in ces {
+            self.add_class_expression(ce);
+        }
+        for dr in drs {
+            self.add_data_range(dr);
+        }
+
+        match ax {
+            Axiom::DisjointClasses(ax) => {
+                if ax
+                    .0
+                    .iter()
+                    .all(|ce| matches!(ce, ClassExpression::Class(_)))
+                {
+                    // Disjointness of named classes is also in EL++
+                    self.constructs.insert(AL);
+                } else {
+                    self.add(C)
+                }
+            }
+            Axiom::DisjointUnion(_) => {
+                self.add(U);
+                self.add(AL);
+            }
+            Axiom::SubObjectPropertyOf(ax) => {
+                match &ax.sub {
+                    SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                        for ope in v {
+                            self.add_property(ope);
+                        }
+                        self.add(R);
+                    }
+                    SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                        self.add_property(ope);
+                        self.add(H);
+                    }
+                }
+                self.add_property(&ax.sup);
+            }
+            Axiom::EquivalentObjectProperties(ax) => {
+                for ope in &ax.0 {
+                    self.add_property(ope);
+                }
+                self.add(H);
+            }
+            Axiom::DisjointObjectProperties(ax) => {
+                for ope in &ax.0 {
+                    self.add_property(ope);
+                }
+                self.add(R);
+                self.el = false;
+            }
+            Axiom::InverseObjectProperties(_) | Axiom::SymmetricObjectProperty(_) => self.add(I),
+            Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, .. })
+            | Axiom::ObjectPropertyRange(ObjectPropertyRange { ope, .. })
+            | Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, .. }) => {
+                self.add_property(ope)
+            }
+            Axiom::NegativeObjectPropertyAssertion(ax) => {
+                self.add_property(&ax.ope);
+                self.add(C);
+            }
+            Axiom::FunctionalObjectProperty(ax) => {
+                self.add_property(&ax.0);
+                self.add(F);
+            }
+            Axiom::InverseFunctionalObjectProperty(ax) => {
+                self.add_property(&ax.0);
+                self.add(I);
+                self.add(F);
+            }
+            Axiom::ReflexiveObjectProperty(ax) => {
+                self.add_property(&ax.0);
+                self.add(R);
+            }
+            Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => {
+                self.add_property(ope);
+                self.add(R);
+                self.el = false;
+            }
+            Axiom::TransitiveObjectProperty(ax) => {
+                self.add_property(&ax.0);
+                self.add(S);
+            }
+            Axiom::SubDataPropertyOf(_) | Axiom::EquivalentDataProperties(_) => {
+                self.add(H);
+                self.add(D);
+            }
+            Axiom::DisjointDataProperties(_) => {
+                self.add(D);
+                self.el = false;
+            }
+            Axiom::FunctionalDataProperty(_) => {
+                self.add(F);
+                self.add(D);
+            }
+            Axiom::DataPropertyDomain(_)
+            | Axiom::DataPropertyRange(_)
+            | Axiom::DatatypeDefinition(_)
+            | Axiom::DataPropertyAssertion(_) => self.add(D),
+            Axiom::NegativeDataPropertyAssertion(_) => {
+                self.add(D);
+                self.add(C);
+            }
+            _ => {}
+        }
+    }
+
+    /// The name of the description logic.
+    pub fn dl_name(&self) -> String {
+        use Construct::*;
+        let has = |c| self.constructs.contains(&c);
+
+        if self.el {
+            let el: BTreeSet<Construct> = [E].iter().copied().collect();
+            return if self.constructs.is_subset(&el) {
+                "EL".to_string()
+            } else {
+                "EL++".to_string()
+            };
+        }
+
+        let mut name = String::new();
+        if has(C) || (has(U) && has(E)) {
+            name.push_str(if has(S) { "S" } else { "ALC" });
+        } else {
+            name.push_str("AL");
+            if has(U) {
+                name.push('U');
+            }
+            if has(E) {
+                name.push('E');
+            }
+            if has(S) {
+                name.push('+');
+            }
+        }
+
+        if has(R) {
+            name.push('R');
+        } else if has(H) {
+            name.push('H');
+        }
+        if has(O) {
+            name.push('O');
+        }
+        if has(I) {
+            name.push('I');
+        }
+        if has(Q) {
+            name.push('Q');
+        } else if has(N) {
+            name.push('N');
+        } else if has(F) {
+            name.push('F');
+        }
+        if has(D) {
+            name.push_str("(D)");
+        }
+        name
+    }
+}
+
+impl Display for Expressivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dl_name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(c).into()
+    }
+
+    fn some(b: &Build, ope: ObjectPropertyExpression, c: &str) -> ClassExpression {
+        ClassExpression::ObjectSomeValuesFrom {
+            ope,
+            bce: Box::new(class(b, c)),
+        }
+    }
+
+    fn name(axioms: Vec<Axiom>) -> String {
+        let mut o = AxiomMappedOntology::default();
+        for ax in axioms {
+            o.insert(ax);
+        }
+        Expressivity::new(&o).to_string()
+    }
+
+    #[test]
+    fn el() {
+        let b = Build::new();
+        let r: ObjectPropertyExpression = b.object_property("http://ex.com/r").into();
+        let sc: Axiom = SubClassOf {
+            sub: class(&b, "http://ex.com/a"),
+            sup: some(&b, r.clone(), "http://ex.com/b"),
+        }
+        .into();
+
+        assert_eq!(name(vec![]), "EL");
+        assert_eq!(name(vec![sc.clone()]), "EL");
+        assert_eq!(name(vec![sc, TransitiveObjectProperty(r).into()]), "EL++");
+    }
+
+    #[test]
+    fn alc() {
+        let b = Build::new();
+        let r: ObjectPropertyExpression = b.object_property("http://ex.com/r").into();
+
+        assert_eq!(
+            name(vec![SubClassOf {
+                sub: class(&b, "http://ex.com/a"),
+                sup: ClassExpression::ObjectUnionOf(vec![
+                    class(&b, "http://ex.com/b"),
+                    some(&b, r.clone(), "http://ex.com/c"),
+                ]),
+            }
+            .into()]),
+            "ALC"
+        );
+        assert_eq!(
+            name(vec![SubClassOf {
+                sub: class(&b, "http://ex.com/a"),
+                sup: ClassExpression::ObjectAllValuesFrom {
+                    ope: r,
+                    bce: Box::new(class(&b, "http://ex.com/b")),
+                },
+            }
+            .into()]),
+            "AL"
+        );
+    }
+
+    #[test]
+    fn shiq() {
+        let b = Build::new();
+        let r: ObjectPropertyExpression = b.object_property("http://ex.com/r").into();
+        let s: ObjectPropertyExpression = b.object_property("http://ex.com/s").into();
+
+        assert_eq!(
+            name(vec![
+                SubClassOf {
+                    sub: class(&b, "http://ex.com/a"),
+                    sup: ClassExpression::ObjectComplementOf(Box::new(some(
+                        &b,
+                        ObjectPropertyExpression::InverseObjectProperty(
+                            b.object_property("http://ex.com/r")
+                        ),
+                        "http://ex.com/b"
+                    ))),
+                }
+                .into(),
+                SubClassOf {
+                    sub: class(&b, "http://ex.com/a"),
+                    sup: ClassExpression::ObjectMaxCardinality {
+                        n: 1,
+                        ope: r.clone(),
+                        bce: Box::new(class(&b, "http://ex.com/b")),
+                    },
+                }
+                .into(),
+                SubObjectPropertyOf {
+                    sub: r.clone().into(),
+                    sup: s,
+                }
+                .into(),
+                TransitiveObjectProperty(r).into(),
+            ]),
+            "SHIQ"
+        );
+    }
+
+    #[test]
+    fn sroiq_d() {
+        let b = Build::new();
+        let r: ObjectPropertyExpression = b.object_property("http://ex.com/r").into();
+
+        assert_eq!(
+            name(vec![
+                SubClassOf {
+                    sub: class(&b, "http://ex.com/a"),
+                    sup: ClassExpression::ObjectUnionOf(vec![
+                        ClassExpression::ObjectOneOf(vec![
+                            b.named_individual("http://ex.com/i"),
+                            b.named_individual("http://ex.com/j"),
+                        ]),
+                        ClassExpression::DataSomeValuesFrom {
+                            dp: b.data_property("http://ex.com/d"),
+                            dr: b.datatype("http://ex.com/dt").into(),
+                        },
+                    ]),
+                }
+                .into(),
+                SubObjectPropertyOf {
+                    sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                        r.clone(),
+                        r.clone()
+                    ]),
+                    sup: r.clone(),
+                }
+                .into(),
+                InverseFunctionalObjectProperty(r.clone()).into(),
+                TransitiveObjectProperty(r.clone()).into(),
+                SubClassOf {
+                    sub: class(&b, "http://ex.com/a"),
+                    sup: ClassExpression::ObjectComplementOf(Box::new(
+                        ClassExpression::ObjectMinCardinality {
+                            n: 2,
+                            ope: r,
+                            bce: Box::new(class(&b, "http://ex.com/b")),
+                        }
+                    )),
+                }
+                .into(),
+            ]),
+            "SROIQ(D)"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Generation of IRIs for new entities
+//!
+//! A `FreshIRI` forms new IRIs from a base namespace, by appending
+//! either a counter, zero padded to a fixed width as in
+//! `http://purl.obolibrary.org/obo/GO_0000001`, or a random UUID. It
+//! never returns an IRI which it has returned before, nor one used
+//! by the ontology that it is avoiding.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! use horned_owl::fresh::{FreshIRI, Policy};
+//!
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! o.declare(b.class("http://www.example.com/C_0001"));
+//!
+//! let mut fresh =
+//!     FreshIRI::new(&b, "http://www.example.com/C_", Policy::Numeric { width: 4 }).avoiding(&o);
+//! let c = fresh.class(&mut o).unwrap();
+//!
+//! assert_eq!(c, b.class("http://www.example.com/C_0002"));
+//! assert_eq!(o.i().declare_class().count(), 2);
+//! ```
+use crate::error::FreshError;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::signature::Signature;
+
+use crate::error::HornedError;
+
+use std::collections::BTreeSet;
+
+/// How the IRIs of a `FreshIRI` are formed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Policy {
+    /// A counter from 1, zero padded to `width` digits. With a width
+    /// of 0 the counter is not padded.
+    Numeric { width: usize },
+    /// A random (version 4) UUID.
+    UUID,
+}
+
+/// A generator of fresh IRIs.
+#[derive(Debug)]
+pub struct FreshIRI<'a> {
+    b: &'a Build,
+    base: String,
+    policy: Policy,
+    next: u64,
+    used: BTreeSet<IRI>,
+}
+
+fn uuid() -> Result<String, HornedError> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| FreshError::Random(e.to_string()))?;
+
+    // The version and variant bits
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+impl<'a> FreshIRI<'a> {
+    /// Create a generator of IRIs starting with `base`, built with
+    /// `b`.
+    pub fn new<S: Into<String>>(b: &'a Build, base: S, policy: Policy) -> FreshIRI<'a> {
+        FreshIRI {
+            b,
+            base: base.into(),
+            policy,
+            next: 0,
+            used: BTreeSet::new(),
+        }
+    }
+
+    /// Never return an IRI which is in the signature of `ont`.
+    pub fn avoiding(mut self, ont: &AxiomMappedOntology) -> FreshIRI<'a> {
+        let base = &self.base;
+        self.used.extend(
+            ont.signature()
+                .into_iter()
+                .map(|e| e.iri().clone())
+                .filter(|iri| iri.as_str().starts_with(base.as_str())),
+        );
+        self
+    }
+
+    /// Return a fresh IRI.
+    ///
+    /// This fails if the counter no longer fits in its width, or if
+    /// no random number is available for a UUID.
+    pub fn iri(&mut self) -> Result<IRI, HornedError> {
+        loop {
+            let local = match self.policy {
+                Policy::Numeric { width } => {
+                    self.next += 1;
+                    if width > 0
+                        && 10u64
+                            .checked_pow(width as u32)
+                            .is_some_and(|max| self.next >= max)
+                    {
+                        return Err(FreshError::Exhausted(self.base.clone()).into());
+                    }
+                    format!("{:0width$}", self.next, width = width)
+                }
+                Policy::UUID => uuid()?,
+            };
+
+            let iri = self.b.iri(format!("{}{}", self.base, local));
+            if self.used.insert(iri.clone()) {
+                return Ok(iri);
+            }
+        }
+    }
+
+    /// Return a fresh entity, declared in `ont`.
+    pub fn entity<N, O>(&mut self, ont: &mut O) -> Result<N, HornedError>
+    where
+        N: From<IRI> + Into<NamedEntity> + Clone,
+        O: MutableOntology,
+    {
+        let n = N::from(self.iri()?);
+        ont.declare(n.clone());
+        Ok(n)
+    }
+
+    /// Return a fresh class, declared in `ont`.
+    pub fn class<O: MutableOntology>(&mut self, ont: &mut O) -> Result<Class, HornedError> {
+        self.entity(ont)
+    }
+
+    /// Return a fresh object property, declared in `ont`.
+    pub fn object_property<O: MutableOntology>(
+        &mut self,
+        ont: &mut O,
+    ) -> Result<ObjectProperty, HornedError> {
+        self.entity(ont)
+    }
+
+    /// Return a fresh named individual, declared in `ont`.
+    pub fn named_individual<O: MutableOntology>(
+        &mut self,
+        ont: &mut O,
+    ) -> Result<NamedIndividual, HornedError> {
+        self.entity(ont)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numeric() {
+        let b = Build::new();
+        let mut fresh = FreshIRI::new(&b, "http://ex.com/C_", Policy::Numeric { width: 1 });
+        for n in 1..10 {
+            assert_eq!(
+                fresh.iri().unwrap(),
+                b.iri(format!("http://ex.com/C_{}", n))
+            );
+        }
+        assert!(fresh.iri().is_err());
+
+        let mut fresh = FreshIRI::new(&b, "http://ex.com/C_", Policy::Numeric { width: 0 });
+        fresh.next = 99;
+        assert_eq!(fresh.iri().unwrap(), b.iri("http://ex.com/C_100"));
+    }
+
+    #[test]
+    fn uuid() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let mut fresh = FreshIRI::new(&b, "urn:uuid:", Policy::UUID);
+
+        let i = fresh.named_individual(&mut o).unwrap();
+        let local = i.0.as_str().strip_prefix("urn:uuid:").unwrap();
+        assert_eq!(local.len(), 36);
+        assert_eq!(local.as_bytes()[14], b'4');
+        assert!(local
+            .chars()
+            .all(|c| c == '-' || c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+
+        assert_ne!(fresh.iri().unwrap(), i.0);
+        assert_eq!(o.i().declare_named_individual().count(), 1);
+    }
+}
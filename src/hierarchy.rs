@@ -0,0 +1,1048 @@
+//! The subclass hierarchy of named classes
+//!
+//! A `ClassHierarchy` records the direct super and sub classes of
+//! each named class, either as asserted in an ontology, or as
+//! inferred by a `Reasoner`.
+//!
+//! `sub_class_cycles` finds the cycles of `SubClassOf` axioms in an
+//! ontology, which are usually modelling errors.
+//!
+//! With the `petgraph` feature, a hierarchy, or the told property
+//! hierarchy of an ontology, can be converted to a `petgraph::Graph`
+//! for use with its graph algorithms.
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::reasoner::Reasoner;
+use crate::signature::Signature;
+
+use crate::error::HornedError;
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::Write as StdWrite;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ClassHierarchy {
+    supers: BTreeMap<Class, BTreeSet<Class>>,
+    subs: BTreeMap<Class, BTreeSet<Class>>,
+}
+
+impl ClassHierarchy {
+    pub fn new() -> ClassHierarchy {
+        ClassHierarchy::default()
+    }
+
+    /// Add `c` to the hierarchy, without any super classes.
+    pub fn add_class(&mut self, c: Class) {
+        self.supers.entry(c.clone()).or_default();
+        self.subs.entry(c).or_default();
+    }
+
+    /// Add `sup` as a direct super class of `sub`.
+    pub fn add_edge(&mut self, sub: Class, sup: Class) {
+        self.add_class(sub.clone());
+        self.add_class(sup.clone());
+        self.supers.get_mut(&sub).unwrap().insert(sup.clone());
+        self.subs.get_mut(&sup).unwrap().insert(sub);
+    }
+
+    /// The hierarchy given by the `SubClassOf` and
+    /// `EquivalentClasses` axioms between named classes of `ont`.
+    ///
+    /// Equivalent classes are each a super class of the other.
+    pub fn asserted(ont: &AxiomMappedOntology) -> ClassHierarchy {
+        let mut h = ClassHierarchy::new();
+        for e in ont.signature() {
+            if let NamedEntity::Class(c) = e {
+                h.add_class(c);
+            }
+        }
+
+        for sc in ont.i().sub_class_of() {
+            if let (ClassExpression::Class(sub), ClassExpression::Class(sup)) = (&sc.sub, &sc.sup) {
+                h.add_edge(sub.clone(), sup.clone());
+            }
+        }
+        for ec in ont.i().equivalent_class() {
+            let named: Vec<&Class> =
+                ec.0.iter()
+                    .filter_map(|ce| match ce {
+                        ClassExpression::Class(c) => Some(c),
+                        _ => None,
+                    })
+                    .collect();
+            for a in &named {
+                for b in &named {
+                    if a != b {
+                        h.add_edge((*a).clone(), (*b).clone());
+                    }
+                }
+            }
+        }
+        h
+    }
+
+    /// The hierarchy of the classes of `reasoner`, with only the
+    /// direct subsumptions between them.
+    pub fn inferred<R: Reasoner>(reasoner: &R) -> ClassHierarchy {
+        let classes = reasoner.classes();
+        let supers: BTreeMap<&Class, BTreeSet<&Class>> = classes
+            .iter()
+            .map(|a| {
+                let sub = ClassExpression::Class(a.clone());
+                let sups = classes
+                    .iter()
+                    .filter(|b| a != *b && reasoner.is_subclass(&sub, &(*b).clone().into()))
+                    .collect();
+                (a, sups)
+            })
+            .collect();
+
+        let mut h = ClassHierarchy::new();
+        for (a, sups) in &supers {
+            h.add_class((*a).clone());
+
+            // `b` is direct unless some other super class of `a`,
+            // not equivalent to `a` or `b`, lies between them
+            for b in sups {
+                let direct = !sups.iter().any(|c| {
+                    c != b
+                        && supers[c].contains(b)
+                        && !supers[b].contains(c)
+                        && !supers[c].contains(a)
+                });
+                if direct {
+                    h.add_edge((*a).clone(), (*b).clone());
+                }
+            }
+        }
+        h
+    }
+
+    /// The hierarchy given by the `SubClassOf` axioms between named
+    /// classes of `ont` alone.
+    pub fn asserted_sub_class_of(ont: &AxiomMappedOntology) -> ClassHierarchy {
+        let mut h = ClassHierarchy::new();
+        for sc in ont.i().sub_class_of() {
+            if let (ClassExpression::Class(sub), ClassExpression::Class(sup)) = (&sc.sub, &sc.sup) {
+                h.add_edge(sub.clone(), sup.clone());
+            }
+        }
+        h
+    }
+
+    /// Return the cycles in the hierarchy, as the sets of two or more
+    /// classes which are all super classes of each other.
+    ///
+    /// A class which is only a direct super class of itself is not
+    /// reported.
+    pub fn cycles(&self) -> Vec<BTreeSet<&Class>> {
+        // Kosaraju's algorithm, with an explicit stack so that deep
+        // hierarchies do not overflow
+        let mut finished: Vec<&Class> = vec![];
+        let mut seen: BTreeSet<&Class> = BTreeSet::new();
+        for c in self.classes() {
+            if !seen.insert(c) {
+                continue;
+            }
+            let mut stack = vec![(c, self.direct_super_classes(c))];
+            while let Some((c, sups)) = stack.last_mut() {
+                let c = *c;
+                match sups.next() {
+                    Some(sup) => {
+                        if seen.insert(sup) {
+                            stack.push((sup, self.direct_super_classes(sup)));
+                        }
+                    }
+                    None => {
+                        finished.push(c);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let mut cycles = vec![];
+        let mut assigned: BTreeSet<&Class> = BTreeSet::new();
+        for c in finished.into_iter().rev() {
+            if !assigned.insert(c) {
+                continue;
+            }
+            let mut component = BTreeSet::new();
+            let mut todo = vec![c];
+            while let Some(next) = todo.pop() {
+                component.insert(next);
+                for sub in self.direct_sub_classes(next) {
+                    if assigned.insert(sub) {
+                        todo.push(sub);
+                    }
+                }
+            }
+            if component.len() > 1 {
+                cycles.push(component);
+            }
+        }
+        cycles.sort();
+        cycles
+    }
+
+    /// Iterate over all classes in the hierarchy, in sorted order.
+    pub fn classes(&self) -> impl Iterator<Item = &Class> {
+        self.supers.keys()
+    }
+
+    pub fn contains(&self, c: &Class) -> bool {
+        self.supers.contains_key(c)
+    }
+
+    /// Iterate over the direct super classes of `c`.
+    pub fn direct_super_classes(&self, c: &Class) -> impl Iterator<Item = &Class> {
+        self.supers.get(c).into_iter().flat_map(|s| s.iter())
+    }
+
+    /// Iterate over the direct sub classes of `c`.
+    pub fn direct_sub_classes(&self, c: &Class) -> impl Iterator<Item = &Class> {
+        self.subs.get(c).into_iter().flat_map(|s| s.iter())
+    }
+
+    /// Return `c` and all of its super classes, if it is in the
+    /// hierarchy.
+    pub fn ancestors(&self, c: &Class) -> BTreeSet<&Class> {
+        let mut ancestors = BTreeSet::new();
+        let mut todo: Vec<&Class> = self
+            .supers
+            .get_key_value(c)
+            .map(|(k, _)| k)
+            .into_iter()
+            .collect();
+        while let Some(next) = todo.pop() {
+            if ancestors.insert(next) {
+                todo.extend(self.direct_super_classes(next));
+            }
+        }
+        ancestors
+    }
+
+    /// Return `c` and all of its sub classes, if it is in the
+    /// hierarchy.
+    pub fn descendants(&self, c: &Class) -> BTreeSet<&Class> {
+        let mut descendants = BTreeSet::new();
+        let mut todo: Vec<&Class> = self
+            .subs
+            .get_key_value(c)
+            .map(|(k, _)| k)
+            .into_iter()
+            .collect();
+        while let Some(next) = todo.pop() {
+            if descendants.insert(next) {
+                todo.extend(self.direct_sub_classes(next));
+            }
+        }
+        descendants
+    }
+
+    /// Return the classes which are super classes of both `a` and
+    /// `b`, counting each class as a super class of itself.
+    pub fn common_ancestors(&self, a: &Class, b: &Class) -> BTreeSet<&Class> {
+        let b_ancestors = self.ancestors(b);
+        self.ancestors(a)
+            .into_iter()
+            .filter(|c| b_ancestors.contains(c))
+            .collect()
+    }
+
+    /// Return the most specific of the `common_ancestors` of `a` and
+    /// `b`, that is those with no other common ancestor strictly
+    /// below them.
+    ///
+    /// There may be more than one where the hierarchy has multiple
+    /// inheritance, and equivalent classes are all returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// use horned_owl::hierarchy::ClassHierarchy;
+    ///
+    /// let b = Build::new();
+    /// let animal = b.class("http://www.example.com/animal");
+    /// let cat = b.class("http://www.example.com/cat");
+    /// let dog = b.class("http://www.example.com/dog");
+    /// let mut h = ClassHierarchy::new();
+    /// h.add_edge(cat.clone(), animal.clone());
+    /// h.add_edge(dog.clone(), animal.clone());
+    ///
+    /// assert_eq!(
+    ///     h.lowest_common_ancestors(&cat, &dog).into_iter().collect::<Vec<_>>(),
+    ///     vec![&animal]
+    /// );
+    /// ```
+    pub fn lowest_common_ancestors(&self, a: &Class, b: &Class) -> BTreeSet<&Class> {
+        let common = self.common_ancestors(a, b);
+        let ancestors: BTreeMap<&Class, BTreeSet<&Class>> =
+            common.iter().map(|c| (*c, self.ancestors(c))).collect();
+
+        common
+            .iter()
+            .filter(|c| {
+                !common
+                    .iter()
+                    .any(|d| d != *c && ancestors[d].contains(*c) && !ancestors[*c].contains(d))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Remove `c` from the hierarchy, making its direct super classes
+    /// direct super classes of each of its direct sub classes.
+    ///
+    /// Return false if `c` was not in the hierarchy.
+    pub fn remove_class(&mut self, c: &Class) -> bool {
+        let (sups, subs) = match (self.supers.remove(c), self.subs.remove(c)) {
+            (Some(sups), Some(subs)) => (sups, subs),
+            _ => return false,
+        };
+
+        for sup in &sups {
+            if let Some(s) = self.subs.get_mut(sup) {
+                s.remove(c);
+            }
+        }
+        for sub in &subs {
+            if let Some(s) = self.supers.get_mut(sub) {
+                s.remove(c);
+            }
+        }
+        for sub in &subs {
+            for sup in &sups {
+                if sub != sup {
+                    self.add_edge(sub.clone(), sup.clone());
+                }
+            }
+        }
+        true
+    }
+
+    /// Remove the classes which are `owl:deprecated` in `ont`.
+    pub fn remove_deprecated(&mut self, ont: &AxiomMappedOntology) {
+        let deprecated: Vec<Class> = self
+            .classes()
+            .filter(|c| ont.i().is_deprecated(&c.0))
+            .cloned()
+            .collect();
+        for c in deprecated {
+            self.remove_class(&c);
+        }
+    }
+}
+
+/// Return the cycles of `SubClassOf` axioms between the named
+/// classes of `ont`, as the sets of classes taking part in each.
+///
+/// Equivalent classes are not reported as a cycle, unless they are
+/// also asserted as sub classes of each other.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// use horned_owl::hierarchy::sub_class_cycles;
+///
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// let a = b.class("http://www.example.com/a");
+/// let c = b.class("http://www.example.com/c");
+/// o.insert(SubClassOf { sub: a.clone().into(), sup: c.clone().into() });
+/// o.insert(SubClassOf { sub: c.clone().into(), sup: a.clone().into() });
+///
+/// assert_eq!(sub_class_cycles(&o), vec![vec![a, c]]);
+/// ```
+pub fn sub_class_cycles(ont: &AxiomMappedOntology) -> Vec<Vec<Class>> {
+    ClassHierarchy::asserted_sub_class_of(ont)
+        .cycles()
+        .into_iter()
+        .map(|c| c.into_iter().cloned().collect())
+        .collect()
+}
+
+/// Return `DisjointClasses` axioms between the direct sub classes of
+/// `parent` in `h`, either one for each pair of them, or a single
+/// axiom for them all.
+///
+/// Sub classes which are equivalent to `parent`, or to one another,
+/// are left out.
+pub fn disjoint_sub_classes(
+    h: &ClassHierarchy,
+    parent: &Class,
+    pairwise: bool,
+) -> Vec<DisjointClasses> {
+    let is_sub = |a: &Class, b: &Class| h.direct_super_classes(a).any(|s| s == b);
+
+    let mut subs: Vec<&Class> = vec![];
+    for c in h.direct_sub_classes(parent) {
+        if !is_sub(parent, c) && !subs.iter().any(|s| is_sub(c, s) && is_sub(s, c)) {
+            subs.push(c);
+        }
+    }
+    if subs.len() < 2 {
+        return vec![];
+    }
+
+    let ce = |c: &&Class| ClassExpression::Class((*c).clone());
+    if pairwise {
+        let mut v = vec![];
+        for (i, a) in subs.iter().enumerate() {
+            for b in &subs[i + 1..] {
+                v.push(DisjointClasses(vec![ce(a), ce(b)]));
+            }
+        }
+        v
+    } else {
+        vec![DisjointClasses(subs.iter().map(ce).collect())]
+    }
+}
+
+#[cfg(feature = "petgraph")]
+fn graph<N: Clone + Ord>(
+    nodes: impl IntoIterator<Item = N>,
+    edges: impl IntoIterator<Item = (N, N)>,
+) -> petgraph::Graph<N, ()> {
+    let mut g = petgraph::Graph::new();
+    let mut index = BTreeMap::new();
+    for n in nodes {
+        index.entry(n.clone()).or_insert_with(|| g.add_node(n));
+    }
+    for (sub, sup) in edges {
+        let sub = *index.entry(sub.clone()).or_insert_with(|| g.add_node(sub));
+        let sup = *index.entry(sup.clone()).or_insert_with(|| g.add_node(sup));
+        g.update_edge(sub, sup, ());
+    }
+    g
+}
+
+#[cfg(feature = "petgraph")]
+impl ClassHierarchy {
+    /// Convert to a `petgraph::Graph`, with an edge from each class
+    /// to its direct super classes.
+    pub fn to_graph(&self) -> petgraph::Graph<Class, ()> {
+        graph(
+            self.classes().cloned(),
+            self.supers
+                .iter()
+                .flat_map(|(sub, sups)| sups.iter().map(move |sup| (sub.clone(), sup.clone()))),
+        )
+    }
+}
+
+/// The graph of told `SubObjectPropertyOf` and
+/// `EquivalentObjectProperties` axioms in `ont`, with an edge from
+/// each property to its super properties.
+///
+/// Property chains are not included.
+#[cfg(feature = "petgraph")]
+pub fn object_property_graph(
+    ont: &AxiomMappedOntology,
+) -> petgraph::Graph<ObjectPropertyExpression, ()> {
+    let subs = ont
+        .i()
+        .sub_object_property()
+        .filter_map(|sop| match &sop.sub {
+            SubObjectPropertyExpression::ObjectPropertyExpression(sub) => {
+                Some((sub.clone(), sop.sup.clone()))
+            }
+            _ => None,
+        });
+    let equivs = ont
+        .i()
+        .equivalent_object_properties()
+        .flat_map(|eop| pairs(&eop.0));
+
+    let nodes = ont.signature().into_iter().filter_map(|e| match e {
+        NamedEntity::ObjectProperty(op) => Some(op.into()),
+        _ => None,
+    });
+    graph(nodes, subs.chain(equivs))
+}
+
+/// The graph of told `SubDataPropertyOf` and
+/// `EquivalentDataProperties` axioms in `ont`, with an edge from
+/// each property to its super properties.
+#[cfg(feature = "petgraph")]
+pub fn data_property_graph(ont: &AxiomMappedOntology) -> petgraph::Graph<DataProperty, ()> {
+    let subs = ont
+        .i()
+        .sub_data_property_of()
+        .map(|sdp| (sdp.sub.clone(), sdp.sup.clone()));
+    let equivs = ont
+        .i()
+        .equivalent_data_properties()
+        .flat_map(|edp| pairs(&edp.0));
+
+    let nodes = ont.signature().into_iter().filter_map(|e| match e {
+        NamedEntity::DataProperty(dp) => Some(dp),
+        _ => None,
+    });
+    graph(nodes, subs.chain(equivs))
+}
+
+/// Every ordered pair of different members of `v`.
+#[cfg(feature = "petgraph")]
+fn pairs<T: Clone + PartialEq>(v: &[T]) -> Vec<(T, T)> {
+    v.iter()
+        .flat_map(|a| {
+            v.iter()
+                .filter(move |b| a != *b)
+                .map(move |b| (a.clone(), b.clone()))
+        })
+        .collect()
+}
+
+/// Options for `render_dot`.
+#[derive(Clone, Debug, Default)]
+pub struct DotOptions {
+    /// Render only this class and its descendants.
+    pub root: Option<Class>,
+    /// Render only classes within this many edges of the root, or of
+    /// the top level classes if there is no root.
+    pub depth: Option<usize>,
+}
+
+fn quote(c: &Class) -> String {
+    format!(
+        "\"{}\"",
+        c.0.as_str().replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Write `h` to `write` as a GraphViz DOT digraph, with an edge from
+/// each class to its direct super classes.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::hierarchy::{render_dot, ClassHierarchy, DotOptions};
+/// let b = Build::new();
+/// let mut h = ClassHierarchy::new();
+/// h.add_edge(b.class("http://www.example.com/a"), b.class("http://www.example.com/b"));
+///
+/// let mut dot = vec![];
+/// render_dot(&mut dot, &h, &DotOptions::default()).unwrap();
+/// assert!(String::from_utf8(dot).unwrap()
+///     .contains("\"http://www.example.com/a\" -> \"http://www.example.com/b\";"));
+/// ```
+pub fn render_dot(
+    write: &mut dyn StdWrite,
+    h: &ClassHierarchy,
+    options: &DotOptions,
+) -> Result<(), HornedError> {
+    let todo: Vec<&Class> = match &options.root {
+        Some(root) => h.classes().filter(|c| *c == root).collect(),
+        // Every class, including those which are only in cycles
+        None if options.depth.is_none() => h.classes().collect(),
+        None => h
+            .classes()
+            .filter(|c| h.direct_super_classes(c).next().is_none())
+            .collect(),
+    };
+
+    // Breadth first, so that each class is given its least depth
+    let mut todo: VecDeque<(&Class, usize)> = todo.into_iter().map(|c| (c, 0)).collect();
+    let mut depths: BTreeMap<&Class, usize> = BTreeMap::new();
+    while let Some((c, depth)) = todo.pop_front() {
+        if depths.contains_key(c) {
+            continue;
+        }
+        depths.insert(c, depth);
+        if options.depth.is_none_or(|max| depth < max) {
+            todo.extend(h.direct_sub_classes(c).map(|s| (s, depth + 1)));
+        }
+    }
+
+    writeln!(write, "digraph hierarchy {{")?;
+    writeln!(write, "    rankdir=BT;")?;
+    for c in depths.keys() {
+        writeln!(write, "    {};", quote(c))?;
+    }
+    for c in depths.keys() {
+        for sup in h.direct_super_classes(c) {
+            if depths.contains_key(sup) {
+                writeln!(write, "    {} -> {};", quote(c), quote(sup))?;
+            }
+        }
+    }
+    writeln!(write, "}}")?;
+    Ok(())
+}
+
+/// Options for `render_tree`.
+#[derive(Clone, Debug, Default)]
+pub struct TreeOptions {
+    /// Print only this class and its descendants.
+    pub root: Option<Class>,
+    /// Print only classes within this many edges of the root, or of
+    /// the top level classes if there is no root.
+    pub depth: Option<usize>,
+}
+
+fn render_subtree<'a>(
+    write: &mut dyn StdWrite,
+    h: &'a ClassHierarchy,
+    c: &'a Class,
+    depth: usize,
+    options: &TreeOptions,
+    labels: Option<&AxiomMappedOntology>,
+    path: &mut Vec<&'a Class>,
+) -> Result<(), HornedError> {
+    let name = labels
+        .and_then(|o| o.i().label_with_lang(&c.0, &["en"]))
+        .unwrap_or_else(|| c.0.as_str());
+    if path.contains(&c) {
+        // Do not follow a cycle round again
+        writeln!(write, "{:indent$}{} ...", "", name, indent = depth * 2)?;
+        return Ok(());
+    }
+    writeln!(write, "{:indent$}{}", "", name, indent = depth * 2)?;
+
+    if options.depth.is_none_or(|max| depth < max) {
+        path.push(c);
+        for sub in h.direct_sub_classes(c) {
+            render_subtree(write, h, sub, depth + 1, options, labels, path)?;
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Write `h` to `write` as an indented tree, with each class below
+/// its direct super classes.
+///
+/// A class with more than one super class appears under each of
+/// them. With `labels`, classes are printed by their English or
+/// untagged `rdfs:label` in that ontology, where they have one,
+/// rather than their IRI.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::hierarchy::{render_tree, ClassHierarchy, TreeOptions};
+/// let b = Build::new();
+/// let mut h = ClassHierarchy::new();
+/// h.add_edge(b.class("http://www.example.com/a"), b.class("http://www.example.com/b"));
+///
+/// let mut tree = vec![];
+/// render_tree(&mut tree, &h, &TreeOptions::default(), None).unwrap();
+/// assert_eq!(
+///     String::from_utf8(tree).unwrap(),
+///     "http://www.example.com/b\n  http://www.example.com/a\n"
+/// );
+/// ```
+pub fn render_tree(
+    write: &mut dyn StdWrite,
+    h: &ClassHierarchy,
+    options: &TreeOptions,
+    labels: Option<&AxiomMappedOntology>,
+) -> Result<(), HornedError> {
+    let roots: Vec<&Class> = match &options.root {
+        Some(root) => h.classes().filter(|c| *c == root).collect(),
+        None => h
+            .classes()
+            .filter(|c| h.direct_super_classes(c).next().is_none())
+            .collect(),
+    };
+
+    for root in roots {
+        render_subtree(write, h, root, 0, options, labels, &mut vec![])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reasoner::structural::StructuralReasoner;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(c).into()
+    }
+
+    fn ontology(b: &Build) -> AxiomMappedOntology {
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(b, "http://ex.com/b"),
+            sup: class(b, "http://ex.com/a"),
+        });
+        o.insert(SubClassOf {
+            sub: class(b, "http://ex.com/c"),
+            sup: class(b, "http://ex.com/b"),
+        });
+        o.insert(SubClassOf {
+            sub: class(b, "http://ex.com/c"),
+            sup: class(b, "http://ex.com/a"),
+        });
+        o.insert(EquivalentClasses(vec![
+            class(b, "http://ex.com/d"),
+            ClassExpression::ObjectIntersectionOf(vec![
+                class(b, "http://ex.com/a"),
+                class(b, "http://ex.com/x"),
+            ]),
+        ]));
+        o
+    }
+
+    fn dot(h: &ClassHierarchy, options: &DotOptions) -> String {
+        let mut w = vec![];
+        render_dot(&mut w, h, options).unwrap();
+        String::from_utf8(w).unwrap()
+    }
+
+    #[test]
+    fn asserted() {
+        let b = Build::new();
+        let h = ClassHierarchy::asserted(&ontology(&b));
+
+        assert_eq!(h.classes().count(), 5);
+        assert_eq!(
+            h.direct_super_classes(&b.class("http://ex.com/c")).count(),
+            2
+        );
+        assert_eq!(
+            h.direct_super_classes(&b.class("http://ex.com/d")).count(),
+            0
+        );
+        assert_eq!(h.direct_sub_classes(&b.class("http://ex.com/a")).count(), 2);
+    }
+
+    #[test]
+    fn deprecated() {
+        let b = Build::new();
+        let mut o = ontology(&b);
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://ex.com/b"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2002/07/owl#deprecated"),
+                av: Literal::Simple {
+                    literal: "true".to_string(),
+                }
+                .into(),
+            },
+        });
+
+        let mut h = ClassHierarchy::asserted(&o);
+        h.remove_deprecated(&o);
+
+        let b_class = b.class("http://ex.com/b");
+        assert!(!h.contains(&b_class));
+        assert_eq!(h.classes().count(), 4);
+        assert_eq!(
+            h.direct_super_classes(&b.class("http://ex.com/c"))
+                .collect::<Vec<_>>(),
+            vec![&b.class("http://ex.com/a")]
+        );
+        assert!(!h.remove_class(&b_class));
+    }
+
+    #[test]
+    fn inferred() {
+        let b = Build::new();
+        let h = ClassHierarchy::inferred(&StructuralReasoner::new(ontology(&b)));
+
+        let c: Vec<_> = h
+            .direct_super_classes(&b.class("http://ex.com/c"))
+            .collect();
+        assert_eq!(c, vec![&b.class("http://ex.com/b")]);
+
+        let d: BTreeSet<_> = h
+            .direct_super_classes(&b.class("http://ex.com/d"))
+            .collect();
+        assert_eq!(d.len(), 2);
+    }
+
+    #[test]
+    fn inferred_equivalent() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(EquivalentClasses(vec![
+            class(&b, "http://ex.com/a"),
+            class(&b, "http://ex.com/b"),
+        ]));
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/c"),
+            sup: class(&b, "http://ex.com/a"),
+        });
+        let h = ClassHierarchy::inferred(&StructuralReasoner::new(o));
+
+        assert_eq!(
+            h.direct_super_classes(&b.class("http://ex.com/c")).count(),
+            2
+        );
+        assert_eq!(
+            h.direct_super_classes(&b.class("http://ex.com/a")).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn dot_all() {
+        let b = Build::new();
+        let s = dot(
+            &ClassHierarchy::asserted(&ontology(&b)),
+            &DotOptions::default(),
+        );
+
+        assert!(s.starts_with("digraph hierarchy {\n"));
+        assert!(s.contains("    \"http://ex.com/x\";\n"));
+        assert!(s.contains("    \"http://ex.com/c\" -> \"http://ex.com/a\";\n"));
+        assert!(s.ends_with("}\n"));
+    }
+
+    #[test]
+    fn dot_root_depth() {
+        let b = Build::new();
+        let h = ClassHierarchy::asserted(&ontology(&b));
+
+        let s = dot(
+            &h,
+            &DotOptions {
+                root: Some(b.class("http://ex.com/b")),
+                depth: None,
+            },
+        );
+        assert!(s.contains("\"http://ex.com/c\" -> \"http://ex.com/b\";"));
+        assert!(!s.contains("\"http://ex.com/a\""));
+
+        let s = dot(
+            &h,
+            &DotOptions {
+                root: Some(b.class("http://ex.com/a")),
+                depth: Some(1),
+            },
+        );
+        assert!(s.contains("\"http://ex.com/c\" -> \"http://ex.com/a\";"));
+        assert!(!s.contains("\"http://ex.com/x\""));
+
+        let s = dot(
+            &h,
+            &DotOptions {
+                root: Some(b.class("http://ex.com/b")),
+                depth: Some(0),
+            },
+        );
+        assert!(s.contains("\"http://ex.com/b\";"));
+        assert!(!s.contains("->"));
+    }
+
+    #[test]
+    fn dot_cycle() {
+        let b = Build::new();
+        let mut h = ClassHierarchy::new();
+        h.add_edge(b.class("http://ex.com/a"), b.class("http://ex.com/b"));
+        h.add_edge(b.class("http://ex.com/b"), b.class("http://ex.com/a"));
+
+        let s = dot(&h, &DotOptions::default());
+        assert!(s.contains("\"http://ex.com/a\" -> \"http://ex.com/b\";"));
+        assert!(s.contains("\"http://ex.com/b\" -> \"http://ex.com/a\";"));
+    }
+
+    #[test]
+    fn cycles() {
+        let b = Build::new();
+        let mut o = ontology(&b);
+        assert!(sub_class_cycles(&o).is_empty());
+
+        // A self-subsumption is not a cycle, nor an equivalence
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/b"),
+            sup: class(&b, "http://ex.com/b"),
+        });
+        o.insert(EquivalentClasses(vec![
+            class(&b, "http://ex.com/a"),
+            class(&b, "http://ex.com/e"),
+        ]));
+        assert!(sub_class_cycles(&o).is_empty());
+        assert_eq!(ClassHierarchy::asserted(&o).cycles().len(), 1);
+
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/a"),
+            sup: class(&b, "http://ex.com/c"),
+        });
+        assert_eq!(
+            sub_class_cycles(&o),
+            vec![vec![
+                b.class("http://ex.com/a"),
+                b.class("http://ex.com/b"),
+                b.class("http://ex.com/c"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn common_ancestors() {
+        let b = Build::new();
+        let h = ClassHierarchy::asserted(&ontology(&b));
+        let c = |s: &str| b.class(format!("http://ex.com/{}", s));
+
+        assert_eq!(h.ancestors(&c("c")).len(), 3);
+        assert!(h.ancestors(&c("y")).is_empty());
+        assert_eq!(
+            h.common_ancestors(&c("c"), &c("b")),
+            vec![&c("a"), &c("b")].into_iter().collect()
+        );
+        assert_eq!(
+            h.lowest_common_ancestors(&c("c"), &c("b")),
+            vec![&c("b")].into_iter().collect()
+        );
+        assert!(h.lowest_common_ancestors(&c("c"), &c("d")).is_empty());
+
+        // Multiple inheritance, and an inferred hierarchy
+        let mut o = ontology(&b);
+        for (sub, sup) in &[("e", "b"), ("e", "x"), ("f", "b"), ("f", "x")] {
+            o.insert(SubClassOf {
+                sub: class(&b, &format!("http://ex.com/{}", sub)),
+                sup: class(&b, &format!("http://ex.com/{}", sup)),
+            });
+        }
+        let h = ClassHierarchy::inferred(&StructuralReasoner::new(o));
+        // Both are inferred to be sub classes of d, which is below x
+        assert_eq!(
+            h.lowest_common_ancestors(&c("e"), &c("f")),
+            vec![&c("b"), &c("d")].into_iter().collect()
+        );
+    }
+
+    fn tree(
+        h: &ClassHierarchy,
+        options: &TreeOptions,
+        labels: Option<&AxiomMappedOntology>,
+    ) -> String {
+        let mut w = vec![];
+        render_tree(&mut w, h, options, labels).unwrap();
+        String::from_utf8(w).unwrap()
+    }
+
+    #[test]
+    fn tree_all() {
+        let b = Build::new();
+        let h = ClassHierarchy::asserted(&ontology(&b));
+
+        assert_eq!(
+            tree(&h, &TreeOptions::default(), None),
+            "http://ex.com/a\n  http://ex.com/b\n    http://ex.com/c\n  http://ex.com/c\n\
+             http://ex.com/d\nhttp://ex.com/x\n"
+        );
+    }
+
+    #[test]
+    fn tree_root_depth_labels() {
+        let b = Build::new();
+        let mut o = ontology(&b);
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://ex.com/b"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: Literal::Simple {
+                    literal: "bee".to_string(),
+                }
+                .into(),
+            },
+        });
+        let h = ClassHierarchy::asserted(&o);
+
+        let options = TreeOptions {
+            root: Some(b.class("http://ex.com/a")),
+            depth: Some(1),
+        };
+        assert_eq!(
+            tree(&h, &options, Some(&o)),
+            "http://ex.com/a\n  bee\n  http://ex.com/c\n"
+        );
+
+        let options = TreeOptions {
+            root: Some(b.class("http://ex.com/missing")),
+            depth: None,
+        };
+        assert_eq!(tree(&h, &options, None), "");
+    }
+
+    #[test]
+    fn tree_cycle() {
+        let b = Build::new();
+        let mut h = ClassHierarchy::new();
+        h.add_edge(b.class("http://ex.com/a"), b.class("http://ex.com/b"));
+        h.add_edge(b.class("http://ex.com/b"), b.class("http://ex.com/a"));
+
+        let options = TreeOptions {
+            root: Some(b.class("http://ex.com/a")),
+            depth: None,
+        };
+        assert_eq!(
+            tree(&h, &options, None),
+            "http://ex.com/a\n  http://ex.com/b\n    http://ex.com/a ...\n"
+        );
+    }
+
+    #[test]
+    fn disjoint() {
+        let b = Build::new();
+        let mut h = ClassHierarchy::new();
+        for c in &["b", "c", "d"] {
+            h.add_edge(
+                b.class(format!("http://ex.com/{}", c)),
+                b.class("http://ex.com/a"),
+            );
+        }
+
+        let a = b.class("http://ex.com/a");
+        let nary = disjoint_sub_classes(&h, &a, false);
+        assert_eq!(nary.len(), 1);
+        assert_eq!(nary[0].0.len(), 3);
+        assert_eq!(disjoint_sub_classes(&h, &a, true).len(), 3);
+
+        // Equivalent classes cannot be disjoint
+        h.add_edge(b.class("http://ex.com/c"), b.class("http://ex.com/b"));
+        h.add_edge(b.class("http://ex.com/b"), b.class("http://ex.com/c"));
+        assert_eq!(disjoint_sub_classes(&h, &a, true).len(), 1);
+        assert!(disjoint_sub_classes(&h, &b.class("http://ex.com/d"), true).is_empty());
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn petgraph() {
+        use petgraph::algo::{is_cyclic_directed, tarjan_scc, toposort};
+
+        let b = Build::new();
+        let g = ClassHierarchy::asserted(&ontology(&b)).to_graph();
+        assert_eq!(g.node_count(), 5);
+        assert_eq!(g.edge_count(), 3);
+        assert!(toposort(&g, None).is_ok());
+
+        let mut h = ClassHierarchy::new();
+        h.add_edge(b.class("http://ex.com/a"), b.class("http://ex.com/b"));
+        h.add_edge(b.class("http://ex.com/b"), b.class("http://ex.com/a"));
+        let g = h.to_graph();
+        assert!(is_cyclic_directed(&g));
+        assert_eq!(tarjan_scc(&g).len(), 1);
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn property_graph() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let r: ObjectPropertyExpression = b.object_property("http://ex.com/r").into();
+        let s: ObjectPropertyExpression = b.object_property("http://ex.com/s").into();
+        o.insert(SubObjectPropertyOf {
+            sub: r.clone().into(),
+            sup: s.clone(),
+        });
+        o.insert(EquivalentDataProperties(vec![
+            b.data_property("http://ex.com/d"),
+            b.data_property("http://ex.com/e"),
+        ]));
+
+        let g = object_property_graph(&o);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+
+        let g = data_property_graph(&o);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 2);
+    }
+}
@@ -0,0 +1,687 @@
+//! A compact binary format for ontologies
+//!
+//! The Horned-OWL binary format is intended for saving a parsed
+//! ontology so that it can be reloaded much faster than it could be
+//! parsed again; it is not meant for exchange with other tools, and
+//! may change between versions of this library.
+//!
+//! A file starts with `MAGIC`, followed by a table of every IRI in
+//! the ontology. Each IRI is written just once, and referenced by its
+//! index in the table afterwards; on reading, the table is interned
+//! with a `Build`. The prefix mapping, the ontology ID and the
+//! annotated axioms follow. Integers are written as LEB128 varints.
+use crate::error::ParserError;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::set::SetOntology;
+
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, Write as StdWrite};
+
+/// The first bytes of a file in this format, including its version.
+pub const MAGIC: &[u8] = b"HOB\x01";
+
+pub(crate) struct Writer {
+    iris: HashMap<IRI, u32>,
+    pub(crate) table: Vec<IRI>,
+    pub(crate) buf: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn new() -> Writer {
+        Writer {
+            iris: HashMap::new(),
+            table: vec![],
+            buf: vec![],
+        }
+    }
+
+    /// A writer with a complete, fixed table of IRIs.
+    pub(crate) fn with_table(table: Vec<IRI>) -> Writer {
+        Writer {
+            iris: table
+                .iter()
+                .enumerate()
+                .map(|(n, iri)| (iri.clone(), n as u32))
+                .collect(),
+            table,
+            buf: vec![],
+        }
+    }
+
+    fn byte(&mut self, b: u8) {
+        self.buf.push(b);
+    }
+}
+
+pub(crate) fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    write_varint(buf, s.len() as u64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+pub(crate) struct Reader<'a> {
+    pub(crate) data: &'a [u8],
+    iris: &'a dyn Fn(usize) -> Option<IRI>,
+}
+
+pub(crate) fn invalid(msg: &str) -> HornedError {
+    ParserError::InvalidBinary(msg.to_string()).into()
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8], iris: &'a dyn Fn(usize) -> Option<IRI>) -> Reader<'a> {
+        Reader { data, iris }
+    }
+
+    fn byte(&mut self) -> Result<u8, HornedError> {
+        let (b, rest) = self
+            .data
+            .split_first()
+            .ok_or_else(|| invalid("unexpected end of input"))?;
+        self.data = rest;
+        Ok(*b)
+    }
+
+    fn varint(&mut self) -> Result<u64, HornedError> {
+        let mut n = 0;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            n |= u64::from(b & 0x7f) << shift;
+            if b & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(invalid("varint is too long"))
+    }
+
+    fn len(&mut self) -> Result<usize, HornedError> {
+        let n = self.varint()? as usize;
+        // Every item takes at least one byte, so a longer length
+        // cannot be right
+        if n > self.data.len() {
+            return Err(invalid("length is longer than the input"));
+        }
+        Ok(n)
+    }
+
+    fn str(&mut self) -> Result<&'a str, HornedError> {
+        let n = self.len()?;
+        let (s, rest) = self.data.split_at(n);
+        self.data = rest;
+        std::str::from_utf8(s).map_err(|_| invalid("string is not UTF-8"))
+    }
+}
+
+pub(crate) trait Encode {
+    fn encode(&self, w: &mut Writer);
+}
+
+pub(crate) trait Decode: Sized {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError>;
+}
+
+impl Encode for IRI {
+    fn encode(&self, w: &mut Writer) {
+        let next = w.table.len() as u32;
+        let n = *w.iris.entry(self.clone()).or_insert(next);
+        if n == next {
+            w.table.push(self.clone());
+        }
+        write_varint(&mut w.buf, n.into());
+    }
+}
+
+impl Decode for IRI {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        let n = r.varint()? as usize;
+        (r.iris)(n).ok_or_else(|| invalid("IRI is not in the table"))
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, w: &mut Writer) {
+        write_str(&mut w.buf, self);
+    }
+}
+
+impl Decode for String {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        Ok(r.str()?.to_string())
+    }
+}
+
+impl Encode for u32 {
+    fn encode(&self, w: &mut Writer) {
+        write_varint(&mut w.buf, (*self).into());
+    }
+}
+
+impl Decode for u32 {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        let n = r.varint()?;
+        if n > u32::MAX.into() {
+            return Err(invalid("integer is too large"));
+        }
+        Ok(n as u32)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, w: &mut Writer) {
+        match self {
+            None => w.byte(0),
+            Some(t) => {
+                w.byte(1);
+                t.encode(w);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        match r.byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(r)?)),
+            _ => Err(invalid("unknown tag for Option")),
+        }
+    }
+}
+
+impl<T: Encode> Encode for Box<T> {
+    fn encode(&self, w: &mut Writer) {
+        (**self).encode(w);
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        Ok(Box::new(T::decode(r)?))
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, w: &mut Writer) {
+        write_varint(&mut w.buf, self.len() as u64);
+        for t in self {
+            t.encode(w);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        let n = r.len()?;
+        let mut v = Vec::with_capacity(n);
+        for _ in 0..n {
+            v.push(T::decode(r)?);
+        }
+        Ok(v)
+    }
+}
+
+impl<T: Encode> Encode for BTreeSet<T> {
+    fn encode(&self, w: &mut Writer) {
+        write_varint(&mut w.buf, self.len() as u64);
+        for t in self {
+            t.encode(w);
+        }
+    }
+}
+
+impl<T: Decode + Ord> Decode for BTreeSet<T> {
+    fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+        Ok(Vec::decode(r)?.into_iter().collect())
+    }
+}
+
+/// Implement `Encode` and `Decode` for structs, writing each field
+/// in turn. Tuple structs name their fields for the bindings only.
+macro_rules! codec_struct {
+    ($($name:ident $(($($t:ident),*))? $({$($f:ident),*})?),* $(,)?) => {
+        $(
+            impl Encode for $name {
+                fn encode(&self, w: &mut Writer) {
+                    let $name $(($($t),*))? $({$($f),*})? = self;
+                    $($($t.encode(w);)*)?
+                    $($($f.encode(w);)*)?
+                }
+            }
+
+            impl Decode for $name {
+                fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+                    Ok($name $(($({
+                        let $t = Decode::decode(r)?;
+                        $t
+                    }),*))? $({$($f: Decode::decode(r)?),*})?)
+                }
+            }
+        )*
+    };
+}
+
+/// Implement `Encode` and `Decode` for enums, writing a tag byte
+/// followed by the fields of the variant. The tags are part of the
+/// format, so must not be changed.
+macro_rules! codec_enum {
+    ($name:ident {
+        $($tag:literal => $variant:ident $(($($t:ident),*))? $({$($f:ident),*})?),* $(,)?
+    }) => {
+        impl Encode for $name {
+            fn encode(&self, w: &mut Writer) {
+                match self {
+                    $(
+                        $name::$variant $(($($t),*))? $({$($f),*})? => {
+                            w.byte($tag);
+                            $($($t.encode(w);)*)?
+                            $($($f.encode(w);)*)?
+                        }
+                    )*
+                }
+            }
+        }
+
+        impl Decode for $name {
+            fn decode(r: &mut Reader) -> Result<Self, HornedError> {
+                Ok(match r.byte()? {
+                    $(
+                        $tag => $name::$variant $(($({
+                            let $t = Decode::decode(r)?;
+                            $t
+                        }),*))? $({$($f: Decode::decode(r)?),*})?,
+                    )*
+                    _ => return Err(invalid(concat!("unknown tag for ", stringify!($name)))),
+                })
+            }
+        }
+    };
+}
+
+codec_struct! {
+    Class(iri),
+    ObjectProperty(iri),
+    DataProperty(iri),
+    AnnotationProperty(iri),
+    NamedIndividual(iri),
+    Datatype(iri),
+    FacetRestriction { f, l },
+    Annotation { ap, av },
+    OntologyID { iri, viri },
+    AnnotatedAxiom { axiom, ann },
+
+    OntologyAnnotation(ann),
+    Import(iri),
+    DeclareClass(c),
+    DeclareObjectProperty(op),
+    DeclareAnnotationProperty(ap),
+    DeclareDataProperty(dp),
+    DeclareNamedIndividual(i),
+    DeclareDatatype(dt),
+    SubClassOf { sup, sub },
+    EquivalentClasses(v),
+    DisjointClasses(v),
+    DisjointUnion(c, v),
+    SubObjectPropertyOf { sup, sub },
+    EquivalentObjectProperties(v),
+    DisjointObjectProperties(v),
+    InverseObjectProperties(a, b),
+    ObjectPropertyDomain { ope, ce },
+    ObjectPropertyRange { ope, ce },
+    FunctionalObjectProperty(ope),
+    InverseFunctionalObjectProperty(ope),
+    ReflexiveObjectProperty(ope),
+    IrreflexiveObjectProperty(ope),
+    SymmetricObjectProperty(ope),
+    AsymmetricObjectProperty(ope),
+    TransitiveObjectProperty(ope),
+    SubDataPropertyOf { sup, sub },
+    EquivalentDataProperties(v),
+    DisjointDataProperties(v),
+    DataPropertyDomain { dp, ce },
+    DataPropertyRange { dp, dr },
+    FunctionalDataProperty(dp),
+    DatatypeDefinition { kind, range },
+    HasKey { ce, vpe },
+    SameIndividual(v),
+    DifferentIndividuals(v),
+    ClassAssertion { ce, i },
+    ObjectPropertyAssertion { ope, from, to },
+    NegativeObjectPropertyAssertion { ope, from, to },
+    DataPropertyAssertion { dp, from, to },
+    NegativeDataPropertyAssertion { dp, from, to },
+    AnnotationAssertion { subject, ann },
+    SubAnnotationPropertyOf { sup, sub },
+    AnnotationPropertyDomain { ap, iri },
+    AnnotationPropertyRange { ap, iri },
+}
+
+codec_enum! {
+    Literal {
+        0 => Simple { literal },
+        1 => Language { literal, lang },
+        2 => Datatype { literal, datatype_iri },
+    }
+}
+
+codec_enum! {
+    AnnotationValue {
+        0 => Literal(l),
+        1 => IRI(iri),
+        2 => AnonymousIndividual(i),
+    }
+}
+
+codec_enum! {
+    Facet {
+        0 => Length,
+        1 => MinLength,
+        2 => MaxLength,
+        3 => Pattern,
+        4 => MinInclusive,
+        5 => MinExclusive,
+        6 => MaxInclusive,
+        7 => MaxExclusive,
+        8 => TotalDigits,
+        9 => FractionDigits,
+        10 => LangRange,
+    }
+}
+
+codec_enum! {
+    ObjectPropertyExpression {
+        0 => ObjectProperty(op),
+        1 => InverseObjectProperty(op),
+    }
+}
+
+codec_enum! {
+    SubObjectPropertyExpression {
+        0 => ObjectPropertyChain(v),
+        1 => ObjectPropertyExpression(ope),
+    }
+}
+
+codec_enum! {
+    PropertyExpression {
+        0 => ObjectPropertyExpression(ope),
+        1 => DataProperty(dp),
+        2 => AnnotationProperty(ap),
+    }
+}
+
+codec_enum! {
+    DataRange {
+        0 => Datatype(dt),
+        1 => DataIntersectionOf(v),
+        2 => DataUnionOf(v),
+        3 => DataComplementOf(dr),
+        4 => DataOneOf(v),
+        5 => DatatypeRestriction(dt, v),
+    }
+}
+
+codec_enum! {
+    ClassExpression {
+        0 => Class(c),
+        1 => ObjectIntersectionOf(v),
+        2 => ObjectUnionOf(v),
+        3 => ObjectComplementOf(bce),
+        4 => ObjectOneOf(v),
+        5 => ObjectSomeValuesFrom { ope, bce },
+        6 => ObjectAllValuesFrom { ope, bce },
+        7 => ObjectHasValue { ope, i },
+        8 => ObjectHasSelf(ope),
+        9 => ObjectMinCardinality { n, ope, bce },
+        10 => ObjectMaxCardinality { n, ope, bce },
+        11 => ObjectExactCardinality { n, ope, bce },
+        12 => DataSomeValuesFrom { dp, dr },
+        13 => DataAllValuesFrom { dp, dr },
+        14 => DataHasValue { dp, l },
+        15 => DataMinCardinality { n, dp, dr },
+        16 => DataMaxCardinality { n, dp, dr },
+        17 => DataExactCardinality { n, dp, dr },
+    }
+}
+
+codec_enum! {
+    Axiom {
+        0 => OntologyAnnotation(ax),
+        1 => Import(ax),
+        2 => DeclareClass(ax),
+        3 => DeclareObjectProperty(ax),
+        4 => DeclareAnnotationProperty(ax),
+        5 => DeclareDataProperty(ax),
+        6 => DeclareNamedIndividual(ax),
+        7 => DeclareDatatype(ax),
+        8 => SubClassOf(ax),
+        9 => EquivalentClasses(ax),
+        10 => DisjointClasses(ax),
+        11 => DisjointUnion(ax),
+        12 => SubObjectPropertyOf(ax),
+        13 => EquivalentObjectProperties(ax),
+        14 => DisjointObjectProperties(ax),
+        15 => InverseObjectProperties(ax),
+        16 => ObjectPropertyDomain(ax),
+        17 => ObjectPropertyRange(ax),
+        18 => FunctionalObjectProperty(ax),
+        19 => InverseFunctionalObjectProperty(ax),
+        20 => ReflexiveObjectProperty(ax),
+        21 => IrreflexiveObjectProperty(ax),
+        22 => SymmetricObjectProperty(ax),
+        23 => AsymmetricObjectProperty(ax),
+        24 => TransitiveObjectProperty(ax),
+        25 => SubDataPropertyOf(ax),
+        26 => EquivalentDataProperties(ax),
+        27 => DisjointDataProperties(ax),
+        28 => DataPropertyDomain(ax),
+        29 => DataPropertyRange(ax),
+        30 => FunctionalDataProperty(ax),
+        31 => DatatypeDefinition(ax),
+        32 => HasKey(ax),
+        33 => SameIndividual(ax),
+        34 => DifferentIndividuals(ax),
+        35 => ClassAssertion(ax),
+        36 => ObjectPropertyAssertion(ax),
+        37 => NegativeObjectPropertyAssertion(ax),
+        38 => DataPropertyAssertion(ax),
+        39 => NegativeDataPropertyAssertion(ax),
+        40 => AnnotationAssertion(ax),
+        41 => SubAnnotationPropertyOf(ax),
+        42 => AnnotationPropertyDomain(ax),
+        43 => AnnotationPropertyRange(ax),
+    }
+}
+
+/// Write `ont` to `write` in the binary format.
+///
+/// The `mapping` is saved with the ontology, except for its default
+/// prefix.
+pub fn write(
+    write: &mut dyn StdWrite,
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let mut w = Writer::new();
+
+    let mut prefixes: Vec<(&String, &String)> =
+        mapping.map(|m| m.mappings().collect()).unwrap_or_default();
+    prefixes.sort();
+    write_varint(&mut w.buf, prefixes.len() as u64);
+    for (name, iri) in prefixes {
+        write_str(&mut w.buf, name);
+        write_str(&mut w.buf, iri);
+    }
+
+    ont.id().encode(&mut w);
+    let axioms: Vec<&AnnotatedAxiom> = ont.i().iter().collect();
+    write_varint(&mut w.buf, axioms.len() as u64);
+    for ax in axioms {
+        ax.encode(&mut w);
+    }
+
+    let mut head = MAGIC.to_vec();
+    write_varint(&mut head, w.table.len() as u64);
+    for iri in &w.table {
+        write_str(&mut head, iri.as_ref());
+    }
+
+    write.write_all(&head)?;
+    write.write_all(&w.buf)?;
+    Ok(())
+}
+
+/// The `SyntaxParser` for the binary format.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Parser;
+
+impl super::SyntaxParser for Parser {
+    fn parse(
+        &self,
+        mut bufread: &mut dyn BufRead,
+        b: &Build,
+    ) -> Result<super::ParserOutput, HornedError> {
+        Ok(read_with_build(&mut bufread, b)?.into())
+    }
+}
+
+/// Read an ontology in the binary format from `bufread`.
+pub fn read<R: BufRead>(bufread: &mut R) -> Result<(SetOntology, PrefixMapping), HornedError> {
+    read_with_build(bufread, &Build::new())
+}
+
+/// Read an ontology as `read`, creating IRIs with `b`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::io::hob;
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::ontology::set::SetOntology;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.declare(b.class("http://www.example.com/a"));
+///
+/// let mut buf = vec![];
+/// hob::write(&mut buf, &o, None).unwrap();
+/// let (round, _) = hob::read_with_build(&mut buf.as_slice(), &b).unwrap();
+/// assert_eq!(SetOntology::from(o), round);
+/// ```
+pub fn read_with_build<R: BufRead>(
+    bufread: &mut R,
+    b: &Build,
+) -> Result<(SetOntology, PrefixMapping), HornedError> {
+    let mut data = vec![];
+    bufread.read_to_end(&mut data)?;
+    if !data.starts_with(MAGIC) {
+        return Err(invalid(
+            "not a Horned-OWL binary file, or the wrong version",
+        ));
+    }
+
+    let none = |_| None;
+    let mut r = Reader::new(&data[MAGIC.len()..], &none);
+
+    let n = r.len()?;
+    let mut iris = Vec::with_capacity(n);
+    for _ in 0..n {
+        iris.push(b.iri(r.str()?));
+    }
+    let table = |n: usize| iris.get(n).cloned();
+    let mut r = Reader::new(r.data, &table);
+
+    let mut mapping = PrefixMapping::default();
+    for _ in 0..r.len()? {
+        let name = r.str()?;
+        let iri = r.str()?;
+        mapping
+            .add_prefix(name, iri)
+            .map_err(|_| invalid("invalid prefix"))?;
+    }
+
+    let mut o = SetOntology::new();
+    *o.mut_id() = OntologyID::decode(&mut r)?;
+    for _ in 0..r.len()? {
+        o.insert(AnnotatedAxiom::decode(&mut r)?);
+    }
+
+    if !r.data.is_empty() {
+        return Err(invalid("trailing data"));
+    }
+    Ok((o, mapping))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::test::read_ok;
+
+    fn roundtrip(owx: &str) {
+        let (ont, mapping) = read_ok(&mut owx.as_bytes());
+
+        let mut buf = vec![];
+        write(&mut buf, &ont, Some(&mapping)).unwrap();
+        let (round, round_mapping) = read(&mut buf.as_slice()).unwrap();
+
+        assert_eq!(SetOntology::from(ont), round);
+
+        let mut orig: Vec<_> = mapping.mappings().collect();
+        let mut round: Vec<_> = round_mapping.mappings().collect();
+        orig.sort();
+        round.sort();
+        assert_eq!(orig, round);
+    }
+
+    macro_rules! roundtrip {
+        ($($name:ident: $file:expr),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    roundtrip(include_str!(concat!("../ont/owl-xml/", $file, ".owx")));
+                }
+            )*
+        }
+    }
+
+    roundtrip! {
+        class: "class",
+        ont: "ont",
+        annotation_on_subclass: "annotation-on-subclass",
+        data_exact_cardinality: "data-exact-cardinality",
+        datatype_union_of: "datatype-union-of",
+        has_key: "data-has-key",
+        literal_escaped: "literal-escaped",
+        facet_restriction: "facet-restriction-complex",
+        negative_data_property_assertion: "negative-data-property-assertion",
+        subproperty_chain: "subproperty-chain-with-inverse",
+        family: "family"
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(read(&mut &b"HOB"[..]).is_err());
+        assert!(read(&mut &b"<Ontology/>"[..]).is_err());
+
+        let mut buf = vec![];
+        write(&mut buf, &AxiomMappedOntology::default(), None).unwrap();
+        buf.push(0);
+        assert!(read(&mut buf.as_slice()).is_err());
+
+        let mut buf = vec![];
+        let (ont, _) = read_ok(&mut include_str!("../ont/owl-xml/class.owx").as_bytes());
+        write(&mut buf, &ont, None).unwrap();
+        buf.truncate(buf.len() - 1);
+        assert!(read(&mut buf.as_slice()).is_err());
+    }
+}
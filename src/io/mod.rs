@@ -1,27 +1,339 @@
 //! Parsers and Renders for OWL Ontologies
+//!
+//! Each syntax is compiled only with its cargo feature: `owx` for
+//! OWL/XML, `rdf` for RDF/XML and N-Triples, `ofn` for the functional
+//! syntax and `omn` for Manchester syntax. All of these are enabled
+//! by default. The binary `hob` format is always available.
+//!
+//! Ontologies in the functional and Manchester syntaxes can be written
+//! but not read: there is no `SyntaxParser` for them, and `parser`
+//! returns a `CommandError::UnsupportedFormat` saying so. The
+//! `omn::reader` parses single class expressions, not documents.
+//! Reading or writing a syntax whose feature is disabled returns the
+//! same error.
+//!
+//! There is no support for the OBO flat file format, in either
+//! direction; `obo` maps OBO identifiers to IRIs, but does not parse
+//! documents.
 
+pub mod hob;
+#[cfg(feature = "ofn")]
+pub mod ofn;
+#[cfg(feature = "omn")]
+pub mod omn;
+#[cfg(feature = "owx")]
 pub mod owx;
+#[cfg(feature = "rdf")]
 pub mod rdf;
 
 
 use curie::PrefixMapping;
 
+use crate::error::HornedError;
+
+use flate2::bufread::GzDecoder;
+
+#[cfg(feature = "rdf")]
+use sophia::serializer::{nt::NtSerializer, TripleSerializer};
+
+use crate::error::{CommandError, ParserError};
+use crate::model::{Build, IRIRewrite, NamedEntity};
 use crate::ontology
     ::{axiom_mapped::AxiomMappedOntology,
        set::SetOntology};
 
-use self::rdf::reader::{IncompleteParse, RDFOntology};
+use crate::signature::{declared, is_legal_punning, punned, Signature};
+use crate::vocab::is_reserved;
+
+#[cfg(feature = "rdf")]
+pub use self::rdf::reader::IncompleteParse;
+#[cfg(feature = "rdf")]
+use self::rdf::reader::RDFOntology;
+
+use std::collections::BTreeSet;
+use std::io::{BufRead, BufReader, Write};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResourceType{OWX, RDF, OFN, OMN, NT, HOB}
+
+/// The triples which an RDF parse could not use; without the `rdf`
+/// feature there are none.
+#[cfg(not(feature = "rdf"))]
+#[derive(Debug)]
+pub enum IncompleteParse {}
+
+#[cfg(not(feature = "rdf"))]
+impl IncompleteParse {
+    pub fn unparsed(&self) -> usize {
+        match *self {}
+    }
+}
+
+/// A parser for one syntax of ontology documents.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// use horned_owl::io::{parser, ResourceType};
+///
+/// let owx = r#"<Ontology xmlns="http://www.w3.org/2002/07/owl#"></Ontology>"#;
+/// let output = parser(ResourceType::OWX).unwrap()
+///     .parse(&mut owx.as_bytes(), &Build::new())
+///     .unwrap();
+/// assert_eq!(output.signature().len(), 0);
+/// ```
+pub trait SyntaxParser {
+    /// Read an ontology from `bufread`, creating IRIs with `b`.
+    fn parse(&self, bufread: &mut dyn BufRead, b: &Build) -> Result<ParserOutput, HornedError>;
+}
+
+/// Return the parser for `rt`, if its feature is enabled.
+///
+/// The functional syntax is write-only, and only class expressions
+/// can be read from Manchester syntax, so `ResourceType::OFN` and
+/// `ResourceType::OMN` are always an error.
+pub fn parser(rt: ResourceType) -> Result<Box<dyn SyntaxParser>, HornedError> {
+    Ok(match rt {
+        #[cfg(feature = "owx")]
+        ResourceType::OWX => Box::new(owx::Parser),
+        #[cfg(feature = "rdf")]
+        ResourceType::RDF => Box::new(rdf::XMLParser),
+        #[cfg(feature = "rdf")]
+        ResourceType::NT => Box::new(rdf::NTParser),
+        ResourceType::HOB => Box::new(hob::Parser),
+        ResourceType::OFN => {
+            return Err(CommandError::UnsupportedFormat("reading functional syntax, which is write-only").into())
+        }
+        ResourceType::OMN => {
+            return Err(CommandError::UnsupportedFormat("reading Manchester syntax documents, as only class expressions can be read").into())
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Err(CommandError::UnsupportedFormat("reading a disabled syntax").into()),
+    })
+}
+
+/// How a parser treats an IRI used as more than one kind of entity.
+///
+/// This is checked in either `Mode`, so a strict parse accepts any
+/// punning which is allowed here.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Punning {
+    /// Accept any punning, logging a warning for punning which OWL 2
+    /// DL does not allow.
+    #[default]
+    Allow,
+    /// Accept the punning allowed by OWL 2 DL, such as a class which
+    /// is also an individual, and fail on any other.
+    RejectIllegal,
+    /// Fail on any punning.
+    Reject,
+}
+
+/// How a parser treats an ontology which parses, but is not quite
+/// correct.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Return what was parsed, logging a warning for each undeclared
+    /// entity and for any of the document which could not be parsed.
+    /// These checks are skipped if warnings are not logged.
+    #[default]
+    Lenient,
+    /// Fail on undeclared entities and on constructs which could not
+    /// be parsed.
+    Strict,
+}
+
+/// Options which control the parsers.
+#[derive(Clone, Debug, Default)]
+pub struct ParserConfiguration {
+    pub punning: Punning,
+    pub mode: Mode,
+    /// Substitutions applied to every IRI as it is read.
+    pub rewrite: IRIRewrite,
+}
 
+impl ParserConfiguration {
+    /// A configuration which fails on any problem.
+    pub fn strict() -> ParserConfiguration {
+        ParserConfiguration {
+            punning: Punning::RejectIllegal,
+            mode: Mode::Strict,
+            ..Default::default()
+        }
+    }
 
-pub enum ResourceType{OWX, RDF}
+    /// A configuration which recovers from any problem.
+    pub fn lenient() -> ParserConfiguration {
+        ParserConfiguration::default()
+    }
+}
+
+/// Return true if `bufread` starts with the gzip magic number. No
+/// input is consumed.
+pub fn is_gzip<R: BufRead>(bufread: &mut R) -> Result<bool, HornedError> {
+    Ok(bufread.fill_buf()?.starts_with(&[0x1f, 0x8b]))
+}
 
+/// Read an ontology in the syntax `rt` from `bufread`.
+///
+/// Gzipped input is decompressed transparently.
+pub fn read<R: BufRead>(bufread: &mut R, rt: ResourceType) -> Result<ParserOutput, HornedError> {
+    read_with_build(bufread, rt, &Build::new())
+}
+
+/// Read an ontology as `read`, creating IRIs with `b`.
+pub fn read_with_build<R: BufRead>(bufread: &mut R, rt: ResourceType, b: &Build)
+                                   -> Result<ParserOutput, HornedError> {
+    read_with_config(bufread, rt, b, &ParserConfiguration::default())
+}
+
+/// Read an ontology as `read_with_build`, rewriting IRIs and
+/// checking the result as given by `config`.
+pub fn read_with_config<R: BufRead>(bufread: &mut R, rt: ResourceType, b: &Build,
+                                    config: &ParserConfiguration)
+                                    -> Result<ParserOutput, HornedError> {
+    let rb;
+    let b = if config.rewrite.is_empty() {
+        b
+    } else {
+        rb = b.with_rewrite(config.rewrite.clone());
+        &rb
+    };
+
+    let output = if is_gzip(bufread)? {
+        read_plain(&mut BufReader::new(GzDecoder::new(bufread)), rt, b)?
+    } else {
+        read_plain(bufread, rt, b)?
+    };
+
+    check_incomplete(&output, config.mode)?;
+
+    // The signature checks only fail in strict mode or when punning
+    // is rejected, so are skipped when they could neither fail nor
+    // log a warning
+    if config.mode == Mode::Strict
+        || config.punning != Punning::Allow
+        || log::log_enabled!(log::Level::Warn)
+    {
+        for w in check_signature(&output, config)? {
+            log::warn!("{}", w);
+        }
+    }
+    Ok(output)
+}
+
+#[allow(unused_variables)]
+fn check_incomplete(output: &ParserOutput, mode: Mode) -> Result<(), HornedError> {
+    #[cfg(feature = "rdf")]
+    if let ParserOutput::RDFParser(_, incomplete) = output {
+        let unparsed = incomplete.unparsed();
+        if unparsed > 0 {
+            let e = ParserError::Incomplete(format!("{} triples", unparsed));
+            match mode {
+                Mode::Strict => return Err(e.into()),
+                Mode::Lenient => log::warn!("{}", e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check the undeclared entities and the punning of `output`,
+/// failing on the first problem which `config` rejects, and returning
+/// the rest as warnings.
+fn check_signature(output: &ParserOutput, config: &ParserConfiguration)
+                   -> Result<Vec<ParserError>, HornedError> {
+    let sig = output.signature();
+    let mut warnings = check_punning(&sig, config.punning)?;
+
+    let declared = output.declared();
+    for e in sig.difference(&declared).filter(|e| !is_reserved(e.iri())) {
+        let e = ParserError::Undeclared(format!("{:?} {}", e.kind(), e.iri()));
+        match config.mode {
+            Mode::Strict => return Err(e.into()),
+            Mode::Lenient => warnings.push(e),
+        }
+    }
+    Ok(warnings)
+}
+
+fn check_punning(sig: &BTreeSet<NamedEntity>, punning: Punning)
+                 -> Result<Vec<ParserError>, HornedError> {
+    let mut warnings = vec![];
+    for (iri, kinds) in punned(sig) {
+        let legal = is_legal_punning(&kinds);
+        let e = ParserError::Punning(iri.to_string());
+        match punning {
+            Punning::Reject => return Err(e.into()),
+            Punning::RejectIllegal if !legal => return Err(e.into()),
+            Punning::Allow if !legal => warnings.push(e),
+            _ => {}
+        }
+    }
+    Ok(warnings)
+}
+
+fn read_plain<R: BufRead>(bufread: &mut R, rt: ResourceType, b: &Build)
+                          -> Result<ParserOutput, HornedError> {
+    parser(rt)?.parse(bufread, b)
+}
+
+/// Write `ont` to `write` in the syntax `rt`.
+///
+/// The `mapping` is used to abbreviate IRIs, where the syntax allows.
+pub fn write<W: Write>(write: &mut W, ont: &AxiomMappedOntology,
+                       mapping: Option<&PrefixMapping>, rt: ResourceType) -> Result<(), HornedError> {
+    match rt {
+        #[cfg(feature = "owx")]
+        ResourceType::OWX => owx::writer::write(write, ont, mapping),
+        #[cfg(feature = "ofn")]
+        ResourceType::OFN => ofn::writer::write(write, ont, mapping),
+        #[cfg(feature = "omn")]
+        ResourceType::OMN => omn::writer::write(write, ont, mapping),
+        ResourceType::HOB => hob::write(write, ont, mapping),
+        #[cfg(feature = "rdf")]
+        ResourceType::NT => {
+            // sophia errors are not `Send`, so cannot be converted
+            // directly
+            NtSerializer::new(write)
+                .serialize_triples(rdf::writer::triples(ont))
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            Ok(())
+        }
+        #[cfg(feature = "rdf")]
+        ResourceType::RDF => rdf::writer::write(write, ont, mapping),
+        #[allow(unreachable_patterns)]
+        _ => Err(CommandError::UnsupportedFormat("writing a disabled syntax").into()),
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 pub enum ParserOutput
 {
     OWXParser(SetOntology, PrefixMapping),
+    #[cfg(feature = "rdf")]
     RDFParser(RDFOntology, IncompleteParse),
 }
 
 impl ParserOutput {
+    /// Return the signature of the parsed ontology.
+    pub fn signature(&self) -> BTreeSet<NamedEntity> {
+        match self {
+            ParserOutput::OWXParser(o, _) => o.signature(),
+            #[cfg(feature = "rdf")]
+            ParserOutput::RDFParser(o, _) => o.i().signature(),
+        }
+    }
+
+    /// Return the entities declared in the parsed ontology.
+    pub fn declared(&self) -> BTreeSet<NamedEntity> {
+        match self {
+            ParserOutput::OWXParser(o, _) => declared(o.iter()),
+            #[cfg(feature = "rdf")]
+            ParserOutput::RDFParser(o, _) => declared(o.i()),
+        }
+    }
+
     pub fn decompose(self) -> (SetOntology,
                                Option<PrefixMapping>,
                                Option<IncompleteParse>)
@@ -30,6 +342,7 @@ impl ParserOutput {
             ParserOutput::OWXParser(o, m) => {
                 (o, Some(m), None)
             }
+            #[cfg(feature = "rdf")]
             ParserOutput::RDFParser(o, i) => {
                 (o.into(), None, Some(i))
             }
@@ -43,6 +356,7 @@ impl From<(SetOntology, PrefixMapping)> for ParserOutput{
     }
 }
 
+#[cfg(feature = "rdf")]
 impl From<(RDFOntology, IncompleteParse)> for ParserOutput{
     fn from(rop: (RDFOntology, IncompleteParse)) -> ParserOutput {
         ParserOutput::RDFParser(rop.0, rop.1)
@@ -53,6 +367,7 @@ impl From<ParserOutput> for SetOntology {
     fn from(p: ParserOutput) -> SetOntology {
         match p {
             ParserOutput::OWXParser(so, _) => so,
+            #[cfg(feature = "rdf")]
             ParserOutput::RDFParser(rdfo, _) => rdfo.into()
         }
     }
@@ -62,7 +377,199 @@ impl From<ParserOutput> for AxiomMappedOntology {
     fn from(p: ParserOutput) -> AxiomMappedOntology {
         match p {
             ParserOutput::OWXParser(so, _) => so.into(),
+            #[cfg(feature = "rdf")]
             ParserOutput::RDFParser(rdfo,_) => rdfo.into()
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{MutableOntology, Ontology};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    #[test]
+    fn read_memory() {
+        let owx = read(
+            &mut include_str!("../ont/owl-xml/class.owx").as_bytes(),
+            ResourceType::OWX,
+        )
+        .unwrap();
+        let rdf = read(
+            &mut include_str!("../ont/owl-rdf/class.owl").as_bytes(),
+            ResourceType::RDF,
+        )
+        .unwrap();
+
+        let owx: SetOntology = owx.into();
+        let rdf: SetOntology = rdf.into();
+        assert_eq!(owx.iter().count(), rdf.iter().count());
+    }
+
+    #[test]
+    fn write_read_memory() {
+        let ont: AxiomMappedOntology = read(
+            &mut include_str!("../ont/owl-xml/one-subclass.owx").as_bytes(),
+            ResourceType::OWX,
+        )
+        .unwrap()
+        .into();
+
+        let mut nt = Vec::new();
+        write(&mut nt, &ont, None, ResourceType::NT).unwrap();
+        let again: SetOntology = read(&mut nt.as_slice(), ResourceType::NT).unwrap().into();
+
+        assert_eq!(again.iter().count(), ont.i().iter().count());
+    }
+
+    #[test]
+    fn read_gzip() {
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(include_bytes!("../ont/owl-xml/class.owx"))
+            .unwrap();
+        let gz = gz.finish().unwrap();
+
+        let ont: SetOntology = read(&mut gz.as_slice(), ResourceType::OWX).unwrap().into();
+        assert_eq!(ont.iter().count(), 1);
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(read(&mut "".as_bytes(), ResourceType::OFN).is_err());
+        assert!(parser(ResourceType::OFN).is_err());
+        assert!(parser(ResourceType::OMN).is_err());
+        assert!(parser(ResourceType::OFN)
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("write-only"));
+    }
+
+    #[test]
+    fn parsers() {
+        let b = Build::new();
+        let ont: AxiomMappedOntology = read(
+            &mut include_str!("../ont/owl-xml/one-subclass.owx").as_bytes(),
+            ResourceType::OWX,
+        )
+        .unwrap()
+        .into();
+
+        for rt in [ResourceType::HOB, ResourceType::NT, ResourceType::OWX, ResourceType::RDF] {
+            let mut buf = Vec::new();
+            let p = parser(rt).unwrap();
+            write(&mut buf, &ont, None, rt).unwrap();
+            let again: SetOntology = p.parse(&mut buf.as_slice(), &b).unwrap().into();
+            assert_eq!(again.iter().count(), ont.i().iter().count());
+        }
+    }
+
+    #[test]
+    fn punning() {
+        let b = Build::new();
+        let mut ont = AxiomMappedOntology::default();
+        ont.declare(b.class("http://www.example.com/a"));
+        ont.declare(b.named_individual("http://www.example.com/a"));
+
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        let read_punning = |punning, owx: &[u8]| {
+            read_with_config(&mut &owx[..], ResourceType::OWX, &Build::new(),
+                             &ParserConfiguration { punning, ..Default::default() })
+        };
+
+        assert!(read_punning(Punning::Allow, &owx).is_ok());
+        assert!(read_punning(Punning::RejectIllegal, &owx).is_ok());
+        assert!(read_punning(Punning::Reject, &owx).is_err());
+
+        ont.declare(b.datatype("http://www.example.com/a"));
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        assert!(read_punning(Punning::Allow, &owx).is_ok());
+        assert!(read_punning(Punning::RejectIllegal, &owx).is_err());
+    }
+
+    #[test]
+    fn strict() {
+        let b = Build::new();
+        let mut ont = AxiomMappedOntology::default();
+        ont.declare(b.class("http://www.example.com/a"));
+        ont.insert(crate::model::SubClassOf::new(b.class("http://www.example.com/a"),
+                                                b.class("http://www.example.com/b")));
+
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        let read_mode = |config: ParserConfiguration, owx: &[u8], rt| {
+            read_with_config(&mut &owx[..], rt, &Build::new(), &config)
+        };
+
+        assert!(read_mode(ParserConfiguration::lenient(), &owx, ResourceType::OWX).is_ok());
+        assert!(read_mode(ParserConfiguration::strict(), &owx, ResourceType::OWX).is_err());
+
+        ont.declare(b.class("http://www.example.com/b"));
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        assert!(read_mode(ParserConfiguration::strict(), &owx, ResourceType::OWX).is_ok());
+
+        // A triple which is not part of the OWL mapping
+        let nt = b"<http://www.example.com/a> <http://www.example.com/p> <http://www.example.com/b> .\n";
+        assert!(read_mode(ParserConfiguration::lenient(), nt, ResourceType::NT).is_ok());
+        assert!(read_mode(ParserConfiguration::strict(), nt, ResourceType::NT).is_err());
+    }
+
+    #[test]
+    fn strict_allow_punning() {
+        let b = Build::new();
+        let mut ont = AxiomMappedOntology::default();
+        ont.declare(b.class("http://www.example.com/a"));
+        ont.declare(b.datatype("http://www.example.com/a"));
+
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        let config = ParserConfiguration { punning: Punning::Allow, ..ParserConfiguration::strict() };
+        assert!(read_with_config(&mut &owx[..], ResourceType::OWX, &b, &config).is_ok());
+        assert!(read_with_config(&mut &owx[..], ResourceType::OWX, &b,
+                                 &ParserConfiguration::strict()).is_err());
+    }
+
+    #[test]
+    fn lenient_warnings() {
+        let b = Build::new();
+        let mut ont = AxiomMappedOntology::default();
+        ont.declare(b.class("http://www.example.com/a"));
+        ont.declare(b.datatype("http://www.example.com/a"));
+        ont.insert(crate::model::SubClassOf::new(b.class("http://www.example.com/a"),
+                                                b.class("http://www.example.com/b")));
+
+        let mut owx = Vec::new();
+        write(&mut owx, &ont, None, ResourceType::OWX).unwrap();
+        let output = read(&mut &owx[..], ResourceType::OWX).unwrap();
+
+        let warnings = check_signature(&output, &ParserConfiguration::lenient()).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(&warnings[0], ParserError::Punning(iri) if iri == "http://www.example.com/a"));
+        assert!(matches!(&warnings[1], ParserError::Undeclared(e) if e.ends_with("http://www.example.com/b")));
+    }
+
+    #[test]
+    fn rewrite() {
+        let config = ParserConfiguration {
+            rewrite: IRIRewrite::new().prefix("http://www.example.com", "http://www.example.org"),
+            ..Default::default()
+        };
+        let ont: AxiomMappedOntology = read_with_config(
+            &mut include_str!("../ont/owl-xml/one-subclass.owx").as_bytes(),
+            ResourceType::OWX, &Build::new(), &config,
+        )
+        .unwrap()
+        .into();
+
+        let b = Build::new();
+        assert_eq!(ont.id().iri, Some(b.iri("http://www.example.org")));
+        assert!(ont.i().declare_class().any(|dc| dc.0 == b.class("http://www.example.org#A")));
+        assert!(ont.i().iter().all(|ax| !format!("{:?}", ax).contains("example.com")));
+    }
+}
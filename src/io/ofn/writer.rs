@@ -0,0 +1,683 @@
+//! Rendering of OWL Ontologies in Functional Syntax
+//!
+//! The syntax is that used by the [OWL 2 Structural
+//! Specification](https://www.w3.org/TR/owl2-syntax/).
+use curie::PrefixMapping;
+
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::WithIRI;
+
+use crate::error::HornedError;
+
+use std::fmt::{self, Display, Formatter};
+use std::io::Write as StdWrite;
+
+/// Write an Ontology to `write`, using the given PrefixMapping
+///
+/// The ontology is written in OWL
+/// [Functional](https://www.w3.org/TR/owl2-syntax/) syntax.
+pub fn write(
+    write: &mut dyn StdWrite,
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let default_mapper = PrefixMapping::default();
+    let mapping = match mapping {
+        Some(m) => m,
+        None => &default_mapper,
+    };
+
+    let mut prefixes: Vec<_> = mapping.mappings().collect();
+    prefixes.sort();
+    for (prefix, ns) in prefixes {
+        writeln!(write, "Prefix({}:=<{}>)", prefix, ns)?;
+    }
+    writeln!(write)?;
+
+    write!(write, "Ontology(")?;
+    let id = ont.id();
+    if let Some(iri) = &id.iri {
+        write!(write, "{}", Functional(iri, mapping))?;
+        if let Some(viri) = &id.viri {
+            write!(write, " {}", Functional(viri, mapping))?;
+        }
+    }
+    writeln!(write)?;
+
+    // Imports and ontology annotations must come before any other
+    // axiom.
+    let kinds = vec![AxiomKind::Import, AxiomKind::OntologyAnnotation]
+        .into_iter()
+        .chain(
+            AxiomKind::all_kinds()
+                .into_iter()
+                .filter(|k| !matches!(k, AxiomKind::Import | AxiomKind::OntologyAnnotation)),
+        );
+
+    for kind in kinds {
+        for ax in ont.i().annotated_axiom(kind) {
+            writeln!(write, "{}", Functional(ax, mapping))?;
+        }
+    }
+
+    writeln!(write, ")")?;
+
+    Ok(())
+}
+
+/// Displays an entity in functional syntax, abbreviating IRIs with
+/// the PrefixMapping.
+///
+/// # Examples
+/// ```
+/// # use curie::PrefixMapping;
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::ofn::writer::Functional;
+/// let b = Build::new();
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("ex", "http://www.example.com/").unwrap();
+///
+/// let sc = SubClassOf {
+///     sup: b.class("http://www.example.com/B").into(),
+///     sub: b.class("http://www.example.com/A").into(),
+/// };
+///
+/// let ax: Axiom = sc.into();
+/// assert_eq!(format!("{}", Functional(&ax, &mapping)), "SubClassOf(ex:A ex:B)");
+/// ```
+pub struct Functional<'a, T: ?Sized>(pub &'a T, pub &'a PrefixMapping);
+
+impl<'a, T: Render + ?Sized> Display for Functional<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.render(f, self.1)
+    }
+}
+
+/// A trait for rendering an entity in functional syntax.
+///
+/// Axioms render their own containing tag, while the structs inside
+/// `Axiom` render only their contents.
+pub trait Render {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result;
+}
+
+/// Render `parts`, separated by spaces
+fn seq(f: &mut Formatter<'_>, m: &PrefixMapping, parts: &[&dyn Render]) -> fmt::Result {
+    for (i, p) in parts.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        p.render(f, m)?;
+    }
+
+    Ok(())
+}
+
+/// Render `parts` within a `name` tag
+fn tag(f: &mut Formatter<'_>, m: &PrefixMapping, name: &str, parts: &[&dyn Render]) -> fmt::Result {
+    write!(f, "{}(", name)?;
+    seq(f, m, parts)?;
+    write!(f, ")")
+}
+
+/// Is `local` usable as the local part of an abbreviated IRI.
+///
+/// This is a subset of `PN_LOCAL`, which cannot start with `-` or
+/// `.`, or end with `.`.
+fn is_local(local: &str) -> bool {
+    !local.starts_with(['-', '.'])
+        && !local.ends_with('.')
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// Render an IRI, abbreviated with the longest matching prefix if
+/// there is one.
+fn iri_or_curie(f: &mut Formatter<'_>, m: &PrefixMapping, iri: &str) -> fmt::Result {
+    let curie = m
+        .mappings()
+        .filter(|(_, ns)| iri.starts_with(ns.as_str()) && is_local(&iri[ns.len()..]))
+        .max_by_key(|(prefix, ns)| (ns.len(), std::cmp::Reverse(prefix.as_str())));
+
+    match curie {
+        Some((prefix, ns)) => write!(f, "{}:{}", prefix, &iri[ns.len()..]),
+        None => write!(f, "<{}>", iri),
+    }
+}
+
+fn quoted(f: &mut Formatter<'_>, literal: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in literal.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            _ => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Render for IRI {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self.node_id() {
+            Some(id) => write!(f, "_:{}", id),
+            None => iri_or_curie(f, m, self),
+        }
+    }
+}
+
+impl Render for u32 {
+    fn render(&self, f: &mut Formatter<'_>, _: &PrefixMapping) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        let parts: Vec<&dyn Render> = self.iter().map(|t| t as &dyn Render).collect();
+        seq(f, m, &parts)
+    }
+}
+
+impl<T: Render> Render for Box<T> {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        (**self).render(f, m)
+    }
+}
+
+macro_rules! render_named {
+    ($($name:ident),*) => {
+        $(
+            impl Render for $name {
+                fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+                    self.0.render(f, m)
+                }
+            }
+        )*
+    }
+}
+
+render_named! {
+    Class, Datatype, ObjectProperty, DataProperty, AnnotationProperty, NamedIndividual
+}
+
+impl Render for Literal {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            Literal::Simple { literal } => quoted(f, literal),
+            Literal::Language { literal, lang } => {
+                quoted(f, literal)?;
+                write!(f, "@{}", lang)
+            }
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => {
+                quoted(f, literal)?;
+                write!(f, "^^")?;
+                datatype_iri.render(f, m)
+            }
+        }
+    }
+}
+
+impl Render for AnnotationValue {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            AnnotationValue::Literal(l) => l.render(f, m),
+            AnnotationValue::IRI(iri) => iri.render(f, m),
+            AnnotationValue::AnonymousIndividual(i) => i.render(f, m),
+        }
+    }
+}
+
+impl Render for Annotation {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        tag(f, m, "Annotation", &[&self.ap, &self.av])
+    }
+}
+
+impl Render for ObjectPropertyExpression {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op) => op.render(f, m),
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                tag(f, m, "ObjectInverseOf", &[op])
+            }
+        }
+    }
+}
+
+impl Render for SubObjectPropertyExpression {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                tag(f, m, "ObjectPropertyChain", &[v])
+            }
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => ope.render(f, m),
+        }
+    }
+}
+
+impl Render for FacetRestriction {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        iri_or_curie(f, m, self.f.iri_str())?;
+        write!(f, " ")?;
+        self.l.render(f, m)
+    }
+}
+
+impl Render for DataRange {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            DataRange::Datatype(dt) => dt.render(f, m),
+            DataRange::DataIntersectionOf(v) => tag(f, m, "DataIntersectionOf", &[v]),
+            DataRange::DataUnionOf(v) => tag(f, m, "DataUnionOf", &[v]),
+            DataRange::DataComplementOf(dr) => tag(f, m, "DataComplementOf", &[dr]),
+            DataRange::DataOneOf(v) => tag(f, m, "DataOneOf", &[v]),
+            DataRange::DatatypeRestriction(dt, v) => tag(f, m, "DatatypeRestriction", &[dt, v]),
+        }
+    }
+}
+
+impl Render for ClassExpression {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        match self {
+            ClassExpression::Class(c) => c.render(f, m),
+            ClassExpression::ObjectIntersectionOf(v) => tag(f, m, "ObjectIntersectionOf", &[v]),
+            ClassExpression::ObjectUnionOf(v) => tag(f, m, "ObjectUnionOf", &[v]),
+            ClassExpression::ObjectComplementOf(bce) => tag(f, m, "ObjectComplementOf", &[bce]),
+            ClassExpression::ObjectOneOf(v) => tag(f, m, "ObjectOneOf", &[v]),
+            ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+                tag(f, m, "ObjectSomeValuesFrom", &[ope, bce])
+            }
+            ClassExpression::ObjectAllValuesFrom { ope, bce } => {
+                tag(f, m, "ObjectAllValuesFrom", &[ope, bce])
+            }
+            ClassExpression::ObjectHasValue { ope, i } => tag(f, m, "ObjectHasValue", &[ope, i]),
+            ClassExpression::ObjectHasSelf(ope) => tag(f, m, "ObjectHasSelf", &[ope]),
+            ClassExpression::ObjectMinCardinality { n, ope, bce } => {
+                tag(f, m, "ObjectMinCardinality", &[n, ope, bce])
+            }
+            ClassExpression::ObjectMaxCardinality { n, ope, bce } => {
+                tag(f, m, "ObjectMaxCardinality", &[n, ope, bce])
+            }
+            ClassExpression::ObjectExactCardinality { n, ope, bce } => {
+                tag(f, m, "ObjectExactCardinality", &[n, ope, bce])
+            }
+            ClassExpression::DataSomeValuesFrom { dp, dr } => {
+                tag(f, m, "DataSomeValuesFrom", &[dp, dr])
+            }
+            ClassExpression::DataAllValuesFrom { dp, dr } => {
+                tag(f, m, "DataAllValuesFrom", &[dp, dr])
+            }
+            ClassExpression::DataHasValue { dp, l } => tag(f, m, "DataHasValue", &[dp, l]),
+            ClassExpression::DataMinCardinality { n, dp, dr } => {
+                tag(f, m, "DataMinCardinality", &[n, dp, dr])
+            }
+            ClassExpression::DataMaxCardinality { n, dp, dr } => {
+                tag(f, m, "DataMaxCardinality", &[n, dp, dr])
+            }
+            ClassExpression::DataExactCardinality { n, dp, dr } => {
+                tag(f, m, "DataExactCardinality", &[n, dp, dr])
+            }
+        }
+    }
+}
+
+/// Render the contents of an axiom struct as a sequence of its
+/// fields.
+macro_rules! contents {
+    ($type:ty, $self:ident, [$($part:expr),*]) => {
+        impl Render for $type {
+            fn render(&$self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+                seq(f, m, &[$(&$part),*])
+            }
+        }
+    }
+}
+
+/// Render the contents of a declaration, which is the entity within
+/// its own tag.
+macro_rules! declaration {
+    ($type:ty, $name:expr) => {
+        impl Render for $type {
+            fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+                tag(f, m, $name, &[&self.0])
+            }
+        }
+    };
+}
+
+declaration!(DeclareClass, "Class");
+declaration!(DeclareObjectProperty, "ObjectProperty");
+declaration!(DeclareAnnotationProperty, "AnnotationProperty");
+declaration!(DeclareDataProperty, "DataProperty");
+declaration!(DeclareNamedIndividual, "NamedIndividual");
+declaration!(DeclareDatatype, "Datatype");
+
+contents!(OntologyAnnotation, self, [self.0.ap, self.0.av]);
+contents!(Import, self, [self.0]);
+contents!(SubClassOf, self, [self.sub, self.sup]);
+contents!(EquivalentClasses, self, [self.0]);
+contents!(DisjointClasses, self, [self.0]);
+contents!(DisjointUnion, self, [self.0, self.1]);
+contents!(SubObjectPropertyOf, self, [self.sub, self.sup]);
+contents!(EquivalentObjectProperties, self, [self.0]);
+contents!(DisjointObjectProperties, self, [self.0]);
+contents!(InverseObjectProperties, self, [self.0, self.1]);
+contents!(ObjectPropertyDomain, self, [self.ope, self.ce]);
+contents!(ObjectPropertyRange, self, [self.ope, self.ce]);
+contents!(FunctionalObjectProperty, self, [self.0]);
+contents!(InverseFunctionalObjectProperty, self, [self.0]);
+contents!(ReflexiveObjectProperty, self, [self.0]);
+contents!(IrreflexiveObjectProperty, self, [self.0]);
+contents!(SymmetricObjectProperty, self, [self.0]);
+contents!(AsymmetricObjectProperty, self, [self.0]);
+contents!(TransitiveObjectProperty, self, [self.0]);
+contents!(SubDataPropertyOf, self, [self.sub, self.sup]);
+contents!(EquivalentDataProperties, self, [self.0]);
+contents!(DisjointDataProperties, self, [self.0]);
+contents!(DataPropertyDomain, self, [self.dp, self.ce]);
+contents!(DataPropertyRange, self, [self.dp, self.dr]);
+contents!(FunctionalDataProperty, self, [self.0]);
+contents!(DatatypeDefinition, self, [self.kind, self.range]);
+contents!(SameIndividual, self, [self.0]);
+contents!(DifferentIndividuals, self, [self.0]);
+contents!(ClassAssertion, self, [self.ce, self.i]);
+contents!(
+    ObjectPropertyAssertion,
+    self,
+    [self.ope, self.from, self.to]
+);
+contents!(
+    NegativeObjectPropertyAssertion,
+    self,
+    [self.ope, self.from, self.to]
+);
+contents!(DataPropertyAssertion, self, [self.dp, self.from, self.to]);
+contents!(
+    NegativeDataPropertyAssertion,
+    self,
+    [self.dp, self.from, self.to]
+);
+contents!(
+    AnnotationAssertion,
+    self,
+    [self.ann.ap, self.subject, self.ann.av]
+);
+contents!(SubAnnotationPropertyOf, self, [self.sub, self.sup]);
+contents!(AnnotationPropertyDomain, self, [self.ap, self.iri]);
+contents!(AnnotationPropertyRange, self, [self.ap, self.iri]);
+
+impl Render for HasKey {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        // Object and data properties are rendered in separate groups
+        let (ope, dp): (Vec<&dyn Render>, Vec<&dyn Render>) = (
+            self.vpe
+                .iter()
+                .filter_map(|pe| match pe {
+                    PropertyExpression::ObjectPropertyExpression(ope) => Some(ope as &dyn Render),
+                    _ => None,
+                })
+                .collect(),
+            self.vpe
+                .iter()
+                .filter_map(|pe| match pe {
+                    PropertyExpression::DataProperty(dp) => Some(dp as &dyn Render),
+                    _ => None,
+                })
+                .collect(),
+        );
+
+        self.ce.render(f, m)?;
+        write!(f, " ")?;
+        tag(f, m, "", &ope)?;
+        write!(f, " ")?;
+        tag(f, m, "", &dp)
+    }
+}
+
+/// Fetch the name of the tag that is used to render `AxiomKind`
+fn tag_for_kind(axk: AxiomKind) -> &'static str {
+    match axk {
+        AxiomKind::Import => "Import",
+        AxiomKind::OntologyAnnotation => "Annotation",
+        AxiomKind::DeclareClass => "Declaration",
+        AxiomKind::DeclareObjectProperty => "Declaration",
+        AxiomKind::DeclareAnnotationProperty => "Declaration",
+        AxiomKind::DeclareDataProperty => "Declaration",
+        AxiomKind::DeclareNamedIndividual => "Declaration",
+        AxiomKind::DeclareDatatype => "Declaration",
+        AxiomKind::SubClassOf => "SubClassOf",
+        AxiomKind::EquivalentClasses => "EquivalentClasses",
+        AxiomKind::DisjointClasses => "DisjointClasses",
+        AxiomKind::DisjointUnion => "DisjointUnion",
+        AxiomKind::SubObjectPropertyOf => "SubObjectPropertyOf",
+        AxiomKind::EquivalentObjectProperties => "EquivalentObjectProperties",
+        AxiomKind::DisjointObjectProperties => "DisjointObjectProperties",
+        AxiomKind::InverseObjectProperties => "InverseObjectProperties",
+        AxiomKind::ObjectPropertyDomain => "ObjectPropertyDomain",
+        AxiomKind::ObjectPropertyRange => "ObjectPropertyRange",
+        AxiomKind::FunctionalObjectProperty => "FunctionalObjectProperty",
+        AxiomKind::InverseFunctionalObjectProperty => "InverseFunctionalObjectProperty",
+        AxiomKind::ReflexiveObjectProperty => "ReflexiveObjectProperty",
+        AxiomKind::IrreflexiveObjectProperty => "IrreflexiveObjectProperty",
+        AxiomKind::SymmetricObjectProperty => "SymmetricObjectProperty",
+        AxiomKind::AsymmetricObjectProperty => "AsymmetricObjectProperty",
+        AxiomKind::TransitiveObjectProperty => "TransitiveObjectProperty",
+        AxiomKind::SubDataPropertyOf => "SubDataPropertyOf",
+        AxiomKind::EquivalentDataProperties => "EquivalentDataProperties",
+        AxiomKind::DisjointDataProperties => "DisjointDataProperties",
+        AxiomKind::DataPropertyDomain => "DataPropertyDomain",
+        AxiomKind::DataPropertyRange => "DataPropertyRange",
+        AxiomKind::FunctionalDataProperty => "FunctionalDataProperty",
+        AxiomKind::DatatypeDefinition => "DatatypeDefinition",
+        AxiomKind::HasKey => "HasKey",
+        AxiomKind::SameIndividual => "SameIndividual",
+        AxiomKind::DifferentIndividuals => "DifferentIndividuals",
+        AxiomKind::ClassAssertion => "ClassAssertion",
+        AxiomKind::ObjectPropertyAssertion => "ObjectPropertyAssertion",
+        AxiomKind::NegativeObjectPropertyAssertion => "NegativeObjectPropertyAssertion",
+        AxiomKind::DataPropertyAssertion => "DataPropertyAssertion",
+        AxiomKind::NegativeDataPropertyAssertion => "NegativeDataPropertyAssertion",
+        AxiomKind::AnnotationAssertion => "AnnotationAssertion",
+        AxiomKind::SubAnnotationPropertyOf => "SubAnnotationPropertyOf",
+        AxiomKind::AnnotationPropertyDomain => "AnnotationPropertyDomain",
+        AxiomKind::AnnotationPropertyRange => "AnnotationPropertyRange",
+    }
+}
+
+/// The struct inside `ax`, which renders the contents of its tag.
+fn contents(ax: &Axiom) -> &dyn Render {
+    match ax {
+        Axiom::OntologyAnnotation(ax) => ax,
+        Axiom::Import(ax) => ax,
+        Axiom::DeclareClass(ax) => ax,
+        Axiom::DeclareObjectProperty(ax) => ax,
+        Axiom::DeclareAnnotationProperty(ax) => ax,
+        Axiom::DeclareDataProperty(ax) => ax,
+        Axiom::DeclareNamedIndividual(ax) => ax,
+        Axiom::DeclareDatatype(ax) => ax,
+        Axiom::SubClassOf(ax) => ax,
+        Axiom::EquivalentClasses(ax) => ax,
+        Axiom::DisjointClasses(ax) => ax,
+        Axiom::DisjointUnion(ax) => ax,
+        Axiom::SubObjectPropertyOf(ax) => ax,
+        Axiom::EquivalentObjectProperties(ax) => ax,
+        Axiom::DisjointObjectProperties(ax) => ax,
+        Axiom::InverseObjectProperties(ax) => ax,
+        Axiom::ObjectPropertyDomain(ax) => ax,
+        Axiom::ObjectPropertyRange(ax) => ax,
+        Axiom::FunctionalObjectProperty(ax) => ax,
+        Axiom::InverseFunctionalObjectProperty(ax) => ax,
+        Axiom::ReflexiveObjectProperty(ax) => ax,
+        Axiom::IrreflexiveObjectProperty(ax) => ax,
+        Axiom::SymmetricObjectProperty(ax) => ax,
+        Axiom::AsymmetricObjectProperty(ax) => ax,
+        Axiom::TransitiveObjectProperty(ax) => ax,
+        Axiom::SubDataPropertyOf(ax) => ax,
+        Axiom::EquivalentDataProperties(ax) => ax,
+        Axiom::DisjointDataProperties(ax) => ax,
+        Axiom::DataPropertyDomain(ax) => ax,
+        Axiom::DataPropertyRange(ax) => ax,
+        Axiom::FunctionalDataProperty(ax) => ax,
+        Axiom::DatatypeDefinition(ax) => ax,
+        Axiom::HasKey(ax) => ax,
+        Axiom::SameIndividual(ax) => ax,
+        Axiom::DifferentIndividuals(ax) => ax,
+        Axiom::ClassAssertion(ax) => ax,
+        Axiom::ObjectPropertyAssertion(ax) => ax,
+        Axiom::NegativeObjectPropertyAssertion(ax) => ax,
+        Axiom::DataPropertyAssertion(ax) => ax,
+        Axiom::NegativeDataPropertyAssertion(ax) => ax,
+        Axiom::AnnotationAssertion(ax) => ax,
+        Axiom::SubAnnotationPropertyOf(ax) => ax,
+        Axiom::AnnotationPropertyDomain(ax) => ax,
+        Axiom::AnnotationPropertyRange(ax) => ax,
+    }
+}
+
+impl Render for Axiom {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        tag(f, m, tag_for_kind(self.kind()), &[contents(self)])
+    }
+}
+
+impl Render for AnnotatedAxiom {
+    fn render(&self, f: &mut Formatter<'_>, m: &PrefixMapping) -> fmt::Result {
+        let mut parts: Vec<&dyn Render> = self.ann.iter().map(|a| a as &dyn Render).collect();
+        parts.push(contents(&self.axiom));
+
+        tag(f, m, tag_for_kind(self.axiom.kind()), &parts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::io::owx::reader::test::read_ok;
+
+    fn render<T: Render>(t: &T) -> String {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("", "http://www.example.com/").unwrap();
+        format!("{}", Functional(t, &mapping))
+    }
+
+    fn write_ofn(owx: &str) -> String {
+        let (ont, mapping) = read_ok(&mut owx.as_bytes());
+        let mut buf = Vec::new();
+        write(&mut buf, &ont, Some(&mapping)).unwrap();
+
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn iri() {
+        let b = Build::new();
+
+        assert_eq!(render(&b.iri("http://www.example.com/A")), ":A");
+        assert_eq!(
+            render(&b.iri("http://www.example.com/a/b")),
+            "<http://www.example.com/a/b>"
+        );
+        assert_eq!(render(&b.anonymous_individual("x")), "_:x");
+    }
+
+    #[test]
+    fn local() {
+        assert!(is_local("a-b.c"));
+        assert!(!is_local("-a"));
+        assert!(!is_local(".a"));
+        assert!(!is_local("a."));
+        assert!(!is_local("a/b"));
+    }
+
+    #[test]
+    fn literal() {
+        let b = Build::new();
+
+        let l = Literal::Simple {
+            literal: "a \"quoted\" \\ string".into(),
+        };
+        assert_eq!(render(&l), r#""a \"quoted\" \\ string""#);
+
+        let l = Literal::Language {
+            literal: "bonjour".into(),
+            lang: "fr".into(),
+        };
+        assert_eq!(render(&l), r#""bonjour"@fr"#);
+
+        let l = Literal::Datatype {
+            literal: "1".into(),
+            datatype_iri: b.iri("http://www.example.com/int"),
+        };
+        assert_eq!(render(&l), r#""1"^^:int"#);
+    }
+
+    #[test]
+    fn annotated_axiom() {
+        let b = Build::new();
+
+        let mut ax: AnnotatedAxiom = SubClassOf {
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::InverseObjectProperty(
+                    b.object_property("http://www.example.com/r"),
+                ),
+                bce: b.class("http://www.example.com/B").into(),
+            },
+            sub: b.class("http://www.example.com/A").into(),
+        }
+        .into();
+        ax.ann.insert(Annotation {
+            ap: b.annotation_property("http://www.example.com/label"),
+            av: b.iri("http://www.example.com/C").into(),
+        });
+
+        assert_eq!(
+            render(&ax),
+            "SubClassOf(Annotation(:label :C) :A ObjectSomeValuesFrom(ObjectInverseOf(:r) :B))"
+        );
+    }
+
+    #[test]
+    fn declaration() {
+        let ofn = write_ofn(include_str!("../../ont/owl-xml/class.owx"));
+
+        assert!(ofn.contains("Declaration(Class(o:C))"));
+        assert!(ofn.contains("Ontology(<http://www.example.com/iri>"));
+        assert!(ofn.trim_end().ends_with(')'));
+    }
+
+    #[test]
+    fn annotation_on_subclass() {
+        let ofn = write_ofn(include_str!("../../ont/owl-xml/annotation-on-subclass.owx"));
+
+        assert!(ofn.contains(
+            "SubClassOf(Annotation(rdfs:comment \"Annotation on subclass axiom\"@en) o:B o:A)"
+        ));
+    }
+
+    #[test]
+    fn has_key() {
+        let ofn = write_ofn(include_str!("../../ont/owl-xml/data-has-key.owx"));
+
+        assert!(ofn.contains("HasKey("));
+        assert!(ofn.contains(" () ("));
+    }
+
+    #[test]
+    fn import_first() {
+        let ofn = write_ofn(include_str!("../../ont/owl-xml/import.owx"));
+        let lines: Vec<_> = ofn.lines().collect();
+
+        let ont = lines
+            .iter()
+            .position(|l| l.starts_with("Ontology("))
+            .unwrap();
+        assert!(lines[ont + 1].starts_with("Import("));
+    }
+}
@@ -0,0 +1,792 @@
+//! Parsing of class expressions in Manchester Syntax
+//!
+//! An `ExpressionParser` reads a single class expression or data
+//! range, such as `hasTopping some (Cheese or Tomato)`, in the
+//! [OWL 2 Manchester
+//! Syntax](https://www.w3.org/TR/owl2-manchester-syntax/). Names are
+//! expanded with a `PrefixMapping`, which always includes the `owl`,
+//! `rdf`, `rdfs` and `xsd` prefixes.
+//!
+//! Manchester syntax does not say whether a property is an object or
+//! a data property. A property is taken to be a data property if it
+//! is declared as one in the ontology given to the parser, or if it
+//! is restricted to a literal or to a datatype in the `xsd`
+//! namespace; otherwise it is an object property.
+use curie::PrefixMapping;
+
+use crate::error::ParserError;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::{Namespace, WithIRI};
+
+use crate::error::HornedError;
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// One of `( ) { } [ ] ,`
+    Punct(char),
+    /// A facet symbol, such as `>=`
+    Symbol(&'static str),
+    Int(String),
+    /// A quoted literal, with its language tag or datatype
+    Literal(String, Option<String>, Option<Box<Token>>),
+    /// A full IRI, written in angle brackets
+    Iri(String),
+    /// A keyword or an abbreviated IRI
+    Word(String),
+}
+
+fn error<T>(message: String) -> Result<T, HornedError> {
+    Err(ParserError::Manchester(message).into())
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || "(){}[],\"<>".contains(c)
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, HornedError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    // Read a word or an IRI starting at `i`.
+    fn name(chars: &[char], i: &mut usize) -> Result<Token, HornedError> {
+        if chars.get(*i) == Some(&'<') {
+            let start = *i + 1;
+            match chars[start..].iter().position(|c| *c == '>') {
+                Some(n) => {
+                    *i = start + n + 1;
+                    Ok(Token::Iri(chars[start..start + n].iter().collect()))
+                }
+                None => error(format!("unterminated IRI at character {}", start - 1)),
+            }
+        } else {
+            let start = *i;
+            while *i < chars.len() && !is_delimiter(chars[*i]) {
+                *i += 1;
+            }
+            if start == *i {
+                return error(format!("expected a name at character {}", start));
+            }
+            Ok(Token::Word(chars[start..*i].iter().collect()))
+        }
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' | ')' | '{' | '}' | '[' | ']' | ',' => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+            '<' | '>' if next == Some('=') => {
+                tokens.push(Token::Symbol(if c == '<' { "<=" } else { ">=" }));
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Symbol(">"));
+                i += 1;
+            }
+            '<' if next.is_none_or(char::is_whitespace) => {
+                tokens.push(Token::Symbol("<"));
+                i += 1;
+            }
+            '"' => {
+                let mut literal = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') => break,
+                        Some('\\') if i + 1 < chars.len() => {
+                            literal.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(c) => {
+                            literal.push(*c);
+                            i += 1;
+                        }
+                        None => return error("unterminated literal".to_string()),
+                    }
+                }
+                i += 1;
+
+                let mut lang = None;
+                let mut datatype = None;
+                if chars.get(i) == Some(&'@') {
+                    i += 1;
+                    let start = i;
+                    while i < chars.len() && !is_delimiter(chars[i]) {
+                        i += 1;
+                    }
+                    lang = Some(chars[start..i].iter().collect());
+                } else if chars.get(i) == Some(&'^') && chars.get(i + 1) == Some(&'^') {
+                    i += 2;
+                    datatype = Some(Box::new(name(&chars, &mut i)?));
+                }
+                tokens.push(Token::Literal(literal, lang, datatype));
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                tokens.push(Token::Int(chars[start..i].iter().collect()));
+            }
+            _ => tokens.push(name(&chars, &mut i)?),
+        }
+    }
+    Ok(tokens)
+}
+
+fn keyword(t: Option<&Token>) -> Option<&str> {
+    match t {
+        Some(Token::Word(w)) => Some(w.as_str()),
+        _ => None,
+    }
+}
+
+fn is_restriction(kw: Option<&str>) -> bool {
+    matches!(
+        kw,
+        Some("some" | "only" | "value" | "Self" | "min" | "max" | "exactly")
+    )
+}
+
+fn facet(t: &Token) -> Option<Facet> {
+    Some(match t {
+        Token::Symbol(">=") => Facet::MinInclusive,
+        Token::Symbol(">") => Facet::MinExclusive,
+        Token::Symbol("<=") => Facet::MaxInclusive,
+        Token::Symbol("<") => Facet::MaxExclusive,
+        Token::Word(w) => match w.as_str() {
+            "length" => Facet::Length,
+            "minLength" => Facet::MinLength,
+            "maxLength" => Facet::MaxLength,
+            "pattern" => Facet::Pattern,
+            "totalDigits" => Facet::TotalDigits,
+            "fractionDigits" => Facet::FractionDigits,
+            "langRange" => Facet::LangRange,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// A parser for class expressions and data ranges in Manchester
+/// syntax.
+///
+/// # Examples
+/// ```
+/// # use curie::PrefixMapping;
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::omn::reader::ExpressionParser;
+/// let b = Build::new();
+/// let mut mapping = PrefixMapping::default();
+/// mapping.set_default("http://www.example.com/");
+///
+/// let ce = ExpressionParser::new(&b, &mapping)
+///     .class_expression("hasTopping some (Cheese or Tomato)")
+///     .unwrap();
+///
+/// assert_eq!(
+///     ce,
+///     ClassExpression::ObjectSomeValuesFrom {
+///         ope: b.object_property("http://www.example.com/hasTopping").into(),
+///         bce: Box::new(ClassExpression::ObjectUnionOf(vec![
+///             b.class("http://www.example.com/Cheese").into(),
+///             b.class("http://www.example.com/Tomato").into(),
+///         ])),
+///     }
+/// );
+/// ```
+pub struct ExpressionParser<'a> {
+    b: &'a Build,
+    mapping: PrefixMapping,
+    data_properties: BTreeSet<IRI>,
+    datatypes: BTreeSet<IRI>,
+}
+
+struct Tokens {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Tokens {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + n)
+    }
+
+    fn next(&mut self) -> Result<Token, HornedError> {
+        match self.tokens.get(self.pos) {
+            Some(t) => {
+                self.pos += 1;
+                Ok(t.clone())
+            }
+            None => error("unexpected end of expression".to_string()),
+        }
+    }
+
+    fn punct(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), HornedError> {
+        if self.punct(c) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("'{}'", c))
+        }
+    }
+
+    fn keyword(&mut self, kw: &str) -> bool {
+        if keyword(self.peek()) == Some(kw) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T, HornedError> {
+        match self.peek() {
+            Some(t) => error(format!(
+                "expected {} at token {}, found {:?}",
+                expected,
+                self.pos + 1,
+                t
+            )),
+            None => error(format!("expected {} at end of expression", expected)),
+        }
+    }
+}
+
+impl<'a> ExpressionParser<'a> {
+    /// Create a parser which expands names with `mapping`, and
+    /// creates IRIs with `b`.
+    pub fn new(b: &'a Build, mapping: &PrefixMapping) -> ExpressionParser<'a> {
+        let mut m = PrefixMapping::default();
+        for ns in &[
+            Namespace::OWL,
+            Namespace::RDF,
+            Namespace::RDFS,
+            Namespace::XSD,
+        ] {
+            let prefix = format!("{:?}", ns).to_lowercase();
+            m.add_prefix(&prefix, ns.iri_str()).unwrap();
+        }
+        for (prefix, ns) in mapping.mappings() {
+            m.add_prefix(prefix, ns).unwrap();
+        }
+        if let Ok(default) = mapping.expand_curie_string("") {
+            m.set_default(&default);
+        }
+
+        ExpressionParser {
+            b,
+            mapping: m,
+            data_properties: BTreeSet::new(),
+            datatypes: BTreeSet::new(),
+        }
+    }
+
+    /// Use the declarations of `ont` to tell data properties from
+    /// object properties.
+    pub fn ontology(mut self, ont: &AxiomMappedOntology) -> ExpressionParser<'a> {
+        self.data_properties
+            .extend(ont.i().declare_data_property().map(|d| d.0 .0.clone()));
+        self.datatypes
+            .extend(ont.i().declare_datatype().map(|d| d.0 .0.clone()));
+        self
+    }
+
+    /// Parse `s` as a class expression.
+    pub fn class_expression(&self, s: &str) -> Result<ClassExpression, HornedError> {
+        let mut t = Tokens {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        let ce = self.description(&mut t)?;
+        match t.peek() {
+            None => Ok(ce),
+            Some(_) => t.unexpected("end of expression"),
+        }
+    }
+
+    /// Parse `s` as a data range.
+    pub fn data_range(&self, s: &str) -> Result<DataRange, HornedError> {
+        let mut t = Tokens {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        let dr = self.data_range_of(&mut t)?;
+        match t.peek() {
+            None => Ok(dr),
+            Some(_) => t.unexpected("end of expression"),
+        }
+    }
+
+    fn iri(&self, t: &Token) -> Result<IRI, HornedError> {
+        match t {
+            Token::Iri(iri) => Ok(self.b.iri(iri.as_str())),
+            Token::Word(w) => {
+                let w = w.strip_prefix(':').unwrap_or(w);
+                match self.mapping.expand_curie_string(w) {
+                    Ok(iri) => Ok(self.b.iri(iri)),
+                    Err(_) => error(format!("cannot expand the name {}", w)),
+                }
+            }
+            t => error(format!("expected a name, found {:?}", t)),
+        }
+    }
+
+    fn is_datatype(&self, iri: &IRI) -> bool {
+        iri.as_str().starts_with(Namespace::XSD.iri_str())
+            || *iri.as_str() == format!("{}Literal", Namespace::RDFS.iri_str())
+            || self.datatypes.contains(iri)
+    }
+
+    /// Returns true if the tokens from the current position start a
+    /// data range, rather than a class expression.
+    fn at_data_range(&self, t: &Tokens) -> bool {
+        let mut n = 0;
+        loop {
+            match t.peek_at(n) {
+                Some(Token::Punct('(')) | Some(Token::Punct('{')) => n += 1,
+                Some(Token::Word(w)) if w == "not" => n += 1,
+                Some(Token::Literal(..)) | Some(Token::Int(_)) => return true,
+                Some(tok @ Token::Iri(_)) | Some(tok @ Token::Word(_)) => {
+                    return self.iri(tok).is_ok_and(|iri| self.is_datatype(&iri))
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    fn description(&self, t: &mut Tokens) -> Result<ClassExpression, HornedError> {
+        let mut v = vec![self.conjunction(t)?];
+        while t.keyword("or") {
+            v.push(self.conjunction(t)?);
+        }
+        Ok(if v.len() == 1 {
+            v.remove(0)
+        } else {
+            ClassExpression::ObjectUnionOf(v)
+        })
+    }
+
+    fn conjunction(&self, t: &mut Tokens) -> Result<ClassExpression, HornedError> {
+        let mut v = vec![self.primary(t)?];
+        while t.keyword("and") {
+            v.push(self.primary(t)?);
+        }
+        Ok(if v.len() == 1 {
+            v.remove(0)
+        } else {
+            ClassExpression::ObjectIntersectionOf(v)
+        })
+    }
+
+    fn primary(&self, t: &mut Tokens) -> Result<ClassExpression, HornedError> {
+        if t.keyword("not") {
+            return Ok(ClassExpression::ObjectComplementOf(Box::new(
+                self.primary(t)?,
+            )));
+        }
+        if t.punct('(') {
+            let ce = self.description(t)?;
+            t.expect(')')?;
+            return Ok(ce);
+        }
+        if t.punct('{') {
+            let mut v = vec![NamedIndividual(self.iri(&t.next()?)?)];
+            while t.punct(',') {
+                v.push(NamedIndividual(self.iri(&t.next()?)?));
+            }
+            t.expect('}')?;
+            return Ok(ClassExpression::ObjectOneOf(v));
+        }
+        if keyword(t.peek()) == Some("inverse") || is_restriction(keyword(t.peek_at(1))) {
+            return self.restriction(t);
+        }
+        match t.peek() {
+            Some(Token::Iri(_)) | Some(Token::Word(_)) => Ok(Class(self.iri(&t.next()?)?).into()),
+            _ => t.unexpected("a class expression"),
+        }
+    }
+
+    fn object_property(&self, t: &mut Tokens) -> Result<ObjectPropertyExpression, HornedError> {
+        if t.keyword("inverse") {
+            let paren = t.punct('(');
+            let op = ObjectProperty(self.iri(&t.next()?)?);
+            if paren {
+                t.expect(')')?;
+            }
+            Ok(ObjectPropertyExpression::InverseObjectProperty(op))
+        } else {
+            Ok(ObjectProperty(self.iri(&t.next()?)?).into())
+        }
+    }
+
+    fn restriction(&self, t: &mut Tokens) -> Result<ClassExpression, HornedError> {
+        use ClassExpression::*;
+
+        let inverse = keyword(t.peek()) == Some("inverse");
+        let start = t.pos;
+        let ope = self.object_property(t)?;
+        let kw = match t.next()? {
+            Token::Word(w) if is_restriction(Some(&w)) => w,
+            _ => {
+                t.pos -= 1;
+                return t.unexpected("a restriction");
+            }
+        };
+
+        // Decide whether this is a data property from its
+        // declaration, or from what it is restricted to
+        let is_data = !inverse
+            && match ope.as_property() {
+                Some(op) if self.data_properties.contains(&op.0) => true,
+                _ => match kw.as_str() {
+                    "value" => matches!(t.peek(), Some(Token::Literal(..)) | Some(Token::Int(_))),
+                    "Self" => false,
+                    "some" | "only" => self.at_data_range(t),
+                    _ => {
+                        matches!(t.peek(), Some(Token::Int(_))) && {
+                            let mut after = Tokens {
+                                tokens: t.tokens.clone(),
+                                pos: t.pos + 1,
+                            };
+                            after.pos = after.pos.min(after.tokens.len());
+                            self.at_data_range(&after)
+                        }
+                    }
+                },
+            };
+
+        if is_data {
+            let dp = DataProperty(self.iri(&t.tokens[start])?);
+            return Ok(match kw.as_str() {
+                "some" => DataSomeValuesFrom {
+                    dp,
+                    dr: self.data_primary(t)?,
+                },
+                "only" => DataAllValuesFrom {
+                    dp,
+                    dr: self.data_primary(t)?,
+                },
+                "value" => DataHasValue {
+                    dp,
+                    l: self.literal(t)?,
+                },
+                _ => {
+                    let n = self.cardinality(t)?;
+                    let dr = if self.at_data_range(t) {
+                        self.data_primary(t)?
+                    } else {
+                        Datatype(self.b.iri(format!("{}Literal", Namespace::RDFS.iri_str()))).into()
+                    };
+                    match kw.as_str() {
+                        "min" => DataMinCardinality { n, dp, dr },
+                        "max" => DataMaxCardinality { n, dp, dr },
+                        _ => DataExactCardinality { n, dp, dr },
+                    }
+                }
+            });
+        }
+
+        Ok(match kw.as_str() {
+            "some" => ObjectSomeValuesFrom {
+                ope,
+                bce: Box::new(self.primary(t)?),
+            },
+            "only" => ObjectAllValuesFrom {
+                ope,
+                bce: Box::new(self.primary(t)?),
+            },
+            "value" => ObjectHasValue {
+                ope,
+                i: NamedIndividual(self.iri(&t.next()?)?),
+            },
+            "Self" => ObjectHasSelf(ope),
+            _ => {
+                let n = self.cardinality(t)?;
+                let starts_primary = match t.peek() {
+                    Some(Token::Punct('(')) | Some(Token::Punct('{')) | Some(Token::Iri(_)) => true,
+                    Some(Token::Word(w)) => !matches!(w.as_str(), "and" | "or"),
+                    _ => false,
+                };
+                let bce = Box::new(if starts_primary {
+                    self.primary(t)?
+                } else {
+                    self.b
+                        .class(format!("{}Thing", Namespace::OWL.iri_str()))
+                        .into()
+                });
+                match kw.as_str() {
+                    "min" => ObjectMinCardinality { n, ope, bce },
+                    "max" => ObjectMaxCardinality { n, ope, bce },
+                    _ => ObjectExactCardinality { n, ope, bce },
+                }
+            }
+        })
+    }
+
+    fn cardinality(&self, t: &mut Tokens) -> Result<u32, HornedError> {
+        match t.next()? {
+            Token::Int(n) => match n.parse() {
+                Ok(n) => Ok(n),
+                Err(_) => error(format!("invalid cardinality {}", n)),
+            },
+            _ => {
+                t.pos -= 1;
+                t.unexpected("a cardinality")
+            }
+        }
+    }
+
+    fn literal(&self, t: &mut Tokens) -> Result<Literal, HornedError> {
+        match t.next()? {
+            Token::Literal(literal, Some(lang), _) => Ok(Literal::Language { literal, lang }),
+            Token::Literal(literal, None, Some(dt)) => Ok(Literal::Datatype {
+                literal,
+                datatype_iri: self.iri(&dt)?,
+            }),
+            Token::Literal(literal, None, None) => Ok(Literal::Simple { literal }),
+            Token::Int(literal) => Ok(Literal::Datatype {
+                literal,
+                datatype_iri: self.b.iri(format!("{}integer", Namespace::XSD.iri_str())),
+            }),
+            _ => {
+                t.pos -= 1;
+                t.unexpected("a literal")
+            }
+        }
+    }
+
+    fn data_range_of(&self, t: &mut Tokens) -> Result<DataRange, HornedError> {
+        let mut v = vec![self.data_conjunction(t)?];
+        while t.keyword("or") {
+            v.push(self.data_conjunction(t)?);
+        }
+        Ok(if v.len() == 1 {
+            v.remove(0)
+        } else {
+            DataRange::DataUnionOf(v)
+        })
+    }
+
+    fn data_conjunction(&self, t: &mut Tokens) -> Result<DataRange, HornedError> {
+        let mut v = vec![self.data_primary(t)?];
+        while t.keyword("and") {
+            v.push(self.data_primary(t)?);
+        }
+        Ok(if v.len() == 1 {
+            v.remove(0)
+        } else {
+            DataRange::DataIntersectionOf(v)
+        })
+    }
+
+    fn data_primary(&self, t: &mut Tokens) -> Result<DataRange, HornedError> {
+        if t.keyword("not") {
+            return Ok(DataRange::DataComplementOf(Box::new(self.data_primary(t)?)));
+        }
+        if t.punct('(') {
+            let dr = self.data_range_of(t)?;
+            t.expect(')')?;
+            return Ok(dr);
+        }
+        if t.punct('{') {
+            let mut v = vec![self.literal(t)?];
+            while t.punct(',') {
+                v.push(self.literal(t)?);
+            }
+            t.expect('}')?;
+            return Ok(DataRange::DataOneOf(v));
+        }
+
+        let dt = Datatype(self.iri(&t.next()?)?);
+        if !t.punct('[') {
+            return Ok(dt.into());
+        }
+        let mut v = vec![];
+        loop {
+            let f = match t.peek().and_then(facet) {
+                Some(f) => f,
+                None => return t.unexpected("a facet"),
+            };
+            t.pos += 1;
+            v.push(FacetRestriction {
+                f,
+                l: self.literal(t)?,
+            });
+            if !t.punct(',') {
+                break;
+            }
+        }
+        t.expect(']')?;
+        Ok(DataRange::DatatypeRestriction(dt, v))
+    }
+}
+
+/// Parse a class expression in Manchester syntax, with names written
+/// as full IRIs or with the `owl`, `rdf`, `rdfs` and `xsd` prefixes.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// let ce: ClassExpression = "<http://www.example.com/r> some owl:Thing".parse().unwrap();
+/// assert!(matches!(ce, ClassExpression::ObjectSomeValuesFrom { .. }));
+/// ```
+impl FromStr for ClassExpression {
+    type Err = HornedError;
+
+    fn from_str(s: &str) -> Result<ClassExpression, HornedError> {
+        ExpressionParser::new(&Build::new(), &PrefixMapping::default()).class_expression(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::omn::writer::Manchester;
+
+    fn mapping() -> PrefixMapping {
+        let mut m = PrefixMapping::default();
+        m.set_default("http://www.example.com/");
+        m.add_prefix("ex", "http://www.example.com/").unwrap();
+        m.add_prefix("xsd", "http://www.w3.org/2001/XMLSchema#")
+            .unwrap();
+        m
+    }
+
+    fn parse(s: &str) -> ClassExpression {
+        ExpressionParser::new(&Build::new(), &mapping())
+            .class_expression(s)
+            .unwrap()
+    }
+
+    fn roundtrip(s: &str) {
+        assert_eq!(Manchester(&parse(s), &mapping()).to_string(), s);
+    }
+
+    #[test]
+    fn precedence() {
+        roundtrip("ex:A and ex:B or ex:C");
+        roundtrip("(ex:A or ex:B) and ex:C");
+        roundtrip("not ex:r some not ex:A");
+        roundtrip("not (not ex:A)");
+        roundtrip("ex:r some ex:s only (ex:A and ex:B)");
+        assert_eq!(
+            parse("A and B"),
+            parse("(ex:A) and <http://www.example.com/B>")
+        );
+    }
+
+    #[test]
+    fn restrictions() {
+        roundtrip("inverse ex:r value ex:i");
+        roundtrip("ex:r Self");
+        roundtrip("ex:r exactly 2 {ex:i, ex:j}");
+        roundtrip("ex:age some xsd:integer[>= \"18\"^^xsd:integer, < \"65\"^^xsd:integer]");
+        roundtrip("ex:name value \"chat\"@fr");
+        roundtrip("ex:name only (xsd:string or not {\"a\", \"b\"})");
+
+        let b = Build::new();
+        assert_eq!(
+            parse("r min 1"),
+            ClassExpression::ObjectMinCardinality {
+                n: 1,
+                ope: b.object_property("http://www.example.com/r").into(),
+                bce: b.class("http://www.w3.org/2002/07/owl#Thing").into(),
+            }
+        );
+        assert_eq!(
+            parse("age value 18"),
+            ClassExpression::DataHasValue {
+                dp: b.data_property("http://www.example.com/age"),
+                l: Literal::Datatype {
+                    literal: "18".to_string(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn declared() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.data_property("http://www.example.com/age"));
+        o.declare(b.datatype("http://www.example.com/adult"));
+
+        let p = ExpressionParser::new(&b, &mapping()).ontology(&o);
+        assert!(matches!(
+            p.class_expression("age min 1").unwrap(),
+            ClassExpression::DataMinCardinality { .. }
+        ));
+        assert!(matches!(
+            p.class_expression("r some adult").unwrap(),
+            ClassExpression::DataSomeValuesFrom { .. }
+        ));
+        assert!(matches!(
+            p.data_range("adult or xsd:integer").unwrap(),
+            DataRange::DataUnionOf(_)
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let b = Build::new();
+        let p = ExpressionParser::new(&b, &PrefixMapping::default());
+        assert!(p.class_expression("A").is_err());
+        assert!(p.class_expression("").is_err());
+        assert!(p.class_expression("(<a> and <b>").is_err());
+        assert!(p.class_expression("<a> <b>").is_err());
+        assert!(p.class_expression("<r> min x").is_err());
+        assert!(p.class_expression("\"unterminated").is_err());
+        assert!("owl:Thing".parse::<ClassExpression>().is_ok());
+    }
+
+    #[cfg(feature = "proptest")]
+    mod prop {
+        use super::*;
+        use crate::signature::Signature;
+        use crate::strategy::class_expression;
+
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn writer_roundtrip(ce in class_expression()) {
+                let b = Build::new();
+                let mut m = PrefixMapping::default();
+                m.add_prefix("g", "http://www.example.com/generated#").unwrap();
+
+                let mut o = AxiomMappedOntology::default();
+                for e in ce.signature() {
+                    o.declare(e);
+                }
+
+                let s = Manchester(&ce, &m).to_string();
+                let p = ExpressionParser::new(&b, &m).ontology(&o);
+                prop_assert_eq!(p.class_expression(&s).unwrap(), ce);
+            }
+        }
+    }
+}
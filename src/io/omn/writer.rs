@@ -0,0 +1,1005 @@
+//! Rendering of class expressions, axioms and ontologies in
+//! Manchester Syntax
+//!
+//! The syntax is that of the [OWL 2 Manchester
+//! Syntax](https://www.w3.org/TR/owl2-manchester-syntax/), which is
+//! easier to read than functional syntax, and so better suited to
+//! error messages and command line output. IRIs are abbreviated with
+//! a `PrefixMapping`, and long expressions can be wrapped over
+//! several lines.
+use curie::PrefixMapping;
+
+use crate::error::{HornedError, ValidityError};
+use crate::io::ofn::writer::Functional;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::signature::Signature;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::io::Write as StdWrite;
+
+/// Displays a `ClassExpression` or `DataRange` in Manchester syntax,
+/// on a single line, abbreviating IRIs with the PrefixMapping.
+///
+/// # Examples
+/// ```
+/// # use curie::PrefixMapping;
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::omn::writer::Manchester;
+/// let b = Build::new();
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("ex", "http://www.example.com/").unwrap();
+///
+/// let ce = ClassExpression::ObjectSomeValuesFrom {
+///     ope: b.object_property("http://www.example.com/hasTopping").into(),
+///     bce: Box::new(ClassExpression::ObjectUnionOf(vec![
+///         b.class("http://www.example.com/Cheese").into(),
+///         b.class("http://www.example.com/Tomato").into(),
+///     ])),
+/// };
+///
+/// assert_eq!(
+///     Manchester(&ce, &mapping).to_string(),
+///     "ex:hasTopping some (ex:Cheese or ex:Tomato)"
+/// );
+/// ```
+pub struct Manchester<'a, T: ?Sized>(pub &'a T, pub &'a PrefixMapping);
+
+// How tightly each form of expression binds, loosest first. An
+// operand which binds less tightly than its position requires is
+// parenthesized.
+const OR: u8 = 0;
+const AND: u8 = 1;
+const NOT: u8 = 2;
+const RESTRICTION: u8 = 3;
+const ATOMIC: u8 = 4;
+
+// Continuation lines of a restriction are indented by this much.
+const INDENT: usize = 4;
+
+trait Expression {
+    fn level(&self) -> u8;
+
+    /// Render on a single line.
+    fn flat(&self, m: &PrefixMapping) -> String;
+
+    /// Render starting at column `indent`, breaking lines to stay
+    /// within `width` where possible.
+    fn wrapped(&self, m: &PrefixMapping, indent: usize, width: usize) -> String;
+}
+
+fn name<T: crate::io::ofn::writer::Render>(t: &T, m: &PrefixMapping) -> String {
+    Functional(t, m).to_string()
+}
+
+fn operand<E: Expression>(e: &E, min: u8, m: &PrefixMapping) -> String {
+    if e.level() < min {
+        format!("({})", e.flat(m))
+    } else {
+        e.flat(m)
+    }
+}
+
+fn wrapped_operand<E: Expression>(
+    e: &E,
+    min: u8,
+    m: &PrefixMapping,
+    indent: usize,
+    width: usize,
+) -> String {
+    if e.level() < min {
+        format!("({})", e.wrapped(m, indent + 1, width))
+    } else {
+        e.wrapped(m, indent, width)
+    }
+}
+
+fn nary_flat<E: Expression>(v: &[E], keyword: &str, min: u8, m: &PrefixMapping) -> String {
+    v.iter()
+        .map(|e| operand(e, min, m))
+        .collect::<Vec<_>>()
+        .join(&format!(" {} ", keyword))
+}
+
+/// Render one operand per line, with the keyword starting each line
+/// after the first.
+fn nary_wrapped<E: Expression>(
+    v: &[E],
+    keyword: &str,
+    min: u8,
+    m: &PrefixMapping,
+    indent: usize,
+    width: usize,
+) -> String {
+    let mut s = String::new();
+    for (i, e) in v.iter().enumerate() {
+        if i == 0 {
+            s.push_str(&wrapped_operand(e, min, m, indent, width));
+        } else {
+            s.push('\n');
+            s.push_str(&" ".repeat(indent));
+            s.push_str(keyword);
+            s.push(' ');
+            let at = indent + keyword.len() + 1;
+            s.push_str(&wrapped_operand(e, min, m, at, width));
+        }
+    }
+    s
+}
+
+/// Render `head`, followed by `filler` on the next line.
+fn restriction_wrapped<E: Expression>(
+    head: String,
+    filler: &E,
+    m: &PrefixMapping,
+    indent: usize,
+    width: usize,
+) -> String {
+    let at = indent + INDENT;
+    format!(
+        "{}\n{}{}",
+        head,
+        " ".repeat(at),
+        wrapped_operand(filler, NOT, m, at, width)
+    )
+}
+
+fn one_of<T: crate::io::ofn::writer::Render>(v: &[T], m: &PrefixMapping) -> String {
+    format!(
+        "{{{}}}",
+        v.iter().map(|t| name(t, m)).collect::<Vec<_>>().join(", ")
+    )
+}
+
+fn property(ope: &ObjectPropertyExpression, m: &PrefixMapping) -> String {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => name(op, m),
+        ObjectPropertyExpression::InverseObjectProperty(op) => format!("inverse {}", name(op, m)),
+    }
+}
+
+fn facet(f: &Facet) -> &'static str {
+    match f {
+        Facet::Length => "length",
+        Facet::MinLength => "minLength",
+        Facet::MaxLength => "maxLength",
+        Facet::Pattern => "pattern",
+        Facet::MinInclusive => ">=",
+        Facet::MinExclusive => ">",
+        Facet::MaxInclusive => "<=",
+        Facet::MaxExclusive => "<",
+        Facet::TotalDigits => "totalDigits",
+        Facet::FractionDigits => "fractionDigits",
+        Facet::LangRange => "langRange",
+    }
+}
+
+impl Expression for DataRange {
+    fn level(&self) -> u8 {
+        match self {
+            DataRange::DataUnionOf(_) => OR,
+            DataRange::DataIntersectionOf(_) => AND,
+            DataRange::DataComplementOf(_) => NOT,
+            _ => ATOMIC,
+        }
+    }
+
+    fn flat(&self, m: &PrefixMapping) -> String {
+        match self {
+            DataRange::Datatype(dt) => name(dt, m),
+            DataRange::DataIntersectionOf(v) => nary_flat(v, "and", NOT, m),
+            DataRange::DataUnionOf(v) => nary_flat(v, "or", AND, m),
+            DataRange::DataComplementOf(dr) => format!("not {}", operand(&**dr, ATOMIC, m)),
+            DataRange::DataOneOf(v) => one_of(v, m),
+            DataRange::DatatypeRestriction(dt, v) => format!(
+                "{}[{}]",
+                name(dt, m),
+                v.iter()
+                    .map(|fr| format!("{} {}", facet(&fr.f), name(&fr.l, m)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn wrapped(&self, m: &PrefixMapping, indent: usize, width: usize) -> String {
+        let flat = self.flat(m);
+        if indent + flat.len() <= width {
+            return flat;
+        }
+
+        match self {
+            DataRange::DataIntersectionOf(v) => nary_wrapped(v, "and", NOT, m, indent, width),
+            DataRange::DataUnionOf(v) => nary_wrapped(v, "or", AND, m, indent, width),
+            DataRange::DataComplementOf(dr) => format!(
+                "not {}",
+                wrapped_operand(&**dr, ATOMIC, m, indent + 4, width)
+            ),
+            _ => flat,
+        }
+    }
+}
+
+impl Expression for ClassExpression {
+    fn level(&self) -> u8 {
+        match self {
+            ClassExpression::ObjectUnionOf(_) => OR,
+            ClassExpression::ObjectIntersectionOf(_) => AND,
+            ClassExpression::ObjectComplementOf(_) => NOT,
+            ClassExpression::Class(_) | ClassExpression::ObjectOneOf(_) => ATOMIC,
+            _ => RESTRICTION,
+        }
+    }
+
+    fn flat(&self, m: &PrefixMapping) -> String {
+        use ClassExpression::*;
+        match self {
+            Class(c) => name(c, m),
+            ObjectIntersectionOf(v) => nary_flat(v, "and", NOT, m),
+            ObjectUnionOf(v) => nary_flat(v, "or", AND, m),
+            ObjectComplementOf(bce) => format!("not {}", operand(&**bce, RESTRICTION, m)),
+            ObjectOneOf(v) => one_of(v, m),
+            ObjectSomeValuesFrom { ope, bce } => {
+                format!("{} some {}", property(ope, m), operand(&**bce, NOT, m))
+            }
+            ObjectAllValuesFrom { ope, bce } => {
+                format!("{} only {}", property(ope, m), operand(&**bce, NOT, m))
+            }
+            ObjectHasValue { ope, i } => format!("{} value {}", property(ope, m), name(i, m)),
+            ObjectHasSelf(ope) => format!("{} Self", property(ope, m)),
+            ObjectMinCardinality { n, ope, bce } => {
+                format!("{} min {} {}", property(ope, m), n, operand(&**bce, NOT, m))
+            }
+            ObjectMaxCardinality { n, ope, bce } => {
+                format!("{} max {} {}", property(ope, m), n, operand(&**bce, NOT, m))
+            }
+            ObjectExactCardinality { n, ope, bce } => format!(
+                "{} exactly {} {}",
+                property(ope, m),
+                n,
+                operand(&**bce, NOT, m)
+            ),
+            DataSomeValuesFrom { dp, dr } => {
+                format!("{} some {}", name(dp, m), operand(dr, NOT, m))
+            }
+            DataAllValuesFrom { dp, dr } => {
+                format!("{} only {}", name(dp, m), operand(dr, NOT, m))
+            }
+            DataHasValue { dp, l } => format!("{} value {}", name(dp, m), name(l, m)),
+            DataMinCardinality { n, dp, dr } => {
+                format!("{} min {} {}", name(dp, m), n, operand(dr, NOT, m))
+            }
+            DataMaxCardinality { n, dp, dr } => {
+                format!("{} max {} {}", name(dp, m), n, operand(dr, NOT, m))
+            }
+            DataExactCardinality { n, dp, dr } => {
+                format!("{} exactly {} {}", name(dp, m), n, operand(dr, NOT, m))
+            }
+        }
+    }
+
+    fn wrapped(&self, m: &PrefixMapping, indent: usize, width: usize) -> String {
+        use ClassExpression::*;
+        let flat = self.flat(m);
+        if indent + flat.len() <= width {
+            return flat;
+        }
+
+        let head =
+            |ope: &ObjectPropertyExpression, kw: &str| format!("{} {}", property(ope, m), kw);
+        let card = |ope: &ObjectPropertyExpression, kw: &str, n: &u32| {
+            format!("{} {} {}", property(ope, m), kw, n)
+        };
+        match self {
+            ObjectIntersectionOf(v) => nary_wrapped(v, "and", NOT, m, indent, width),
+            ObjectUnionOf(v) => nary_wrapped(v, "or", AND, m, indent, width),
+            ObjectComplementOf(bce) => format!(
+                "not {}",
+                wrapped_operand(&**bce, RESTRICTION, m, indent + 4, width)
+            ),
+            ObjectSomeValuesFrom { ope, bce } => {
+                restriction_wrapped(head(ope, "some"), &**bce, m, indent, width)
+            }
+            ObjectAllValuesFrom { ope, bce } => {
+                restriction_wrapped(head(ope, "only"), &**bce, m, indent, width)
+            }
+            ObjectMinCardinality { n, ope, bce } => {
+                restriction_wrapped(card(ope, "min", n), &**bce, m, indent, width)
+            }
+            ObjectMaxCardinality { n, ope, bce } => {
+                restriction_wrapped(card(ope, "max", n), &**bce, m, indent, width)
+            }
+            ObjectExactCardinality { n, ope, bce } => {
+                restriction_wrapped(card(ope, "exactly", n), &**bce, m, indent, width)
+            }
+            DataSomeValuesFrom { dp, dr } => {
+                restriction_wrapped(format!("{} some", name(dp, m)), dr, m, indent, width)
+            }
+            DataAllValuesFrom { dp, dr } => {
+                restriction_wrapped(format!("{} only", name(dp, m)), dr, m, indent, width)
+            }
+            _ => flat,
+        }
+    }
+}
+
+impl<'a> Manchester<'a, ClassExpression> {
+    /// Render over several lines, breaking between the operands of
+    /// intersections and unions, and before the fillers of
+    /// restrictions, so that lines fit within `width` where
+    /// possible.
+    ///
+    /// # Examples
+    /// ```
+    /// # use curie::PrefixMapping;
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::io::omn::writer::Manchester;
+    /// let b = Build::new();
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("ex", "http://www.example.com/").unwrap();
+    ///
+    /// let ce = ClassExpression::ObjectIntersectionOf(vec![
+    ///     b.class("http://www.example.com/Pizza").into(),
+    ///     ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/hasTopping").into(),
+    ///         bce: b.class("http://www.example.com/Cheese").into(),
+    ///     },
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     Manchester(&ce, &mapping).wrap(40),
+    ///     "ex:Pizza\nand ex:hasTopping some ex:Cheese"
+    /// );
+    /// ```
+    pub fn wrap(&self, width: usize) -> String {
+        self.0.wrapped(self.1, 0, width)
+    }
+}
+
+impl<'a> Manchester<'a, DataRange> {
+    /// Render over several lines, so that lines fit within `width`
+    /// where possible.
+    pub fn wrap(&self, width: usize) -> String {
+        self.0.wrapped(self.1, 0, width)
+    }
+}
+
+impl<'a> Display for Manchester<'a, ClassExpression> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.flat(self.1))
+    }
+}
+
+impl<'a> Display for Manchester<'a, DataRange> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.flat(self.1))
+    }
+}
+
+impl<'a> Display for Manchester<'a, ObjectPropertyExpression> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&property(self.0, self.1))
+    }
+}
+
+fn list<E: Expression>(v: &[E], m: &PrefixMapping) -> String {
+    v.iter().map(|e| e.flat(m)).collect::<Vec<_>>().join(", ")
+}
+
+fn names<T: crate::io::ofn::writer::Render>(v: &[T], m: &PrefixMapping) -> String {
+    v.iter().map(|t| name(t, m)).collect::<Vec<_>>().join(", ")
+}
+
+fn properties(v: &[ObjectPropertyExpression], m: &PrefixMapping) -> String {
+    v.iter()
+        .map(|p| property(p, m))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn characteristic(ope: &ObjectPropertyExpression, c: &str, m: &PrefixMapping) -> Frame {
+    frame_of(
+        format!("ObjectProperty: {} Characteristics:", property(ope, m)),
+        c,
+    )
+}
+
+/// A Manchester syntax frame for one axiom: the head, which ends with
+/// the keyword of a section, and the body of that section. The body
+/// of a declaration is empty.
+struct Frame {
+    head: String,
+    body: String,
+}
+
+fn frame_of<B: Into<String>>(head: String, body: B) -> Frame {
+    Frame {
+        head,
+        body: body.into(),
+    }
+}
+
+impl Frame {
+    fn flat(&self) -> String {
+        if self.body.is_empty() {
+            self.head.clone()
+        } else {
+            format!("{} {}", self.head, self.body)
+        }
+    }
+
+    /// Render with `ann` on the body, if there is one to annotate.
+    fn annotated(&self, ann: &BTreeSet<Annotation>, m: &PrefixMapping) -> Option<String> {
+        if ann.is_empty() {
+            Some(self.flat())
+        } else if self.body.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "{} Annotations: {} {}",
+                self.head,
+                annotations(ann, m),
+                self.body
+            ))
+        }
+    }
+}
+
+fn annotation(ann: &Annotation, m: &PrefixMapping) -> String {
+    format!("{} {}", name(&ann.ap, m), name(&ann.av, m))
+}
+
+fn annotations<'a, I: IntoIterator<Item = &'a Annotation>>(ann: I, m: &PrefixMapping) -> String {
+    ann.into_iter()
+        .map(|a| annotation(a, m))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Return `ax` as a single Manchester syntax frame, if it has one.
+fn frame(ax: &Axiom, m: &PrefixMapping) -> Option<Frame> {
+    use ClassExpression::Class as Named;
+    use ObjectPropertyExpression::ObjectProperty as Op;
+    use SubObjectPropertyExpression as Sub;
+
+    Some(match ax {
+        Axiom::DeclareClass(DeclareClass(c)) => frame_of(format!("Class: {}", name(c, m)), ""),
+        Axiom::DeclareObjectProperty(DeclareObjectProperty(p)) => {
+            frame_of(format!("ObjectProperty: {}", name(p, m)), "")
+        }
+        Axiom::DeclareDataProperty(DeclareDataProperty(p)) => {
+            frame_of(format!("DataProperty: {}", name(p, m)), "")
+        }
+        Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(p)) => {
+            frame_of(format!("AnnotationProperty: {}", name(p, m)), "")
+        }
+        Axiom::DeclareNamedIndividual(DeclareNamedIndividual(i)) => {
+            frame_of(format!("Individual: {}", name(i, m)), "")
+        }
+        Axiom::DeclareDatatype(DeclareDatatype(d)) => {
+            frame_of(format!("Datatype: {}", name(d, m)), "")
+        }
+        Axiom::SubClassOf(SubClassOf { sub: Named(c), sup }) => {
+            frame_of(format!("Class: {} SubClassOf:", name(c, m)), sup.flat(m))
+        }
+        Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+            frame_of("EquivalentClasses:".to_string(), list(v, m))
+        }
+        Axiom::DisjointClasses(DisjointClasses(v)) => {
+            frame_of("DisjointClasses:".to_string(), list(v, m))
+        }
+        Axiom::DisjointUnion(DisjointUnion(c, v)) => frame_of(
+            format!("Class: {} DisjointUnionOf:", name(c, m)),
+            list(v, m),
+        ),
+        Axiom::HasKey(HasKey { ce: Named(c), vpe }) => {
+            let keys = vpe
+                .iter()
+                .map(|pe| match pe {
+                    PropertyExpression::ObjectPropertyExpression(ope) => Some(property(ope, m)),
+                    PropertyExpression::DataProperty(dp) => Some(name(dp, m)),
+                    PropertyExpression::AnnotationProperty(_) => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            frame_of(format!("Class: {} HasKey:", name(c, m)), keys.join(", "))
+        }
+        Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+            sub: Sub::ObjectPropertyExpression(Op(p)),
+            sup,
+        }) => frame_of(
+            format!("ObjectProperty: {} SubPropertyOf:", name(p, m)),
+            property(sup, m),
+        ),
+        Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+            sub: Sub::ObjectPropertyChain(chain),
+            sup: Op(p),
+        }) => frame_of(
+            format!("ObjectProperty: {} SubPropertyChain:", name(p, m)),
+            chain
+                .iter()
+                .map(|p| property(p, m))
+                .collect::<Vec<_>>()
+                .join(" o "),
+        ),
+        Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+            frame_of("EquivalentProperties:".to_string(), properties(v, m))
+        }
+        Axiom::DisjointObjectProperties(DisjointObjectProperties(v)) => {
+            frame_of("DisjointProperties:".to_string(), properties(v, m))
+        }
+        Axiom::InverseObjectProperties(InverseObjectProperties(p, q)) => frame_of(
+            format!("ObjectProperty: {} InverseOf:", name(p, m)),
+            name(q, m),
+        ),
+        Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope: Op(p), ce }) => frame_of(
+            format!("ObjectProperty: {} Domain:", name(p, m)),
+            ce.flat(m),
+        ),
+        Axiom::ObjectPropertyRange(ObjectPropertyRange { ope: Op(p), ce }) => {
+            frame_of(format!("ObjectProperty: {} Range:", name(p, m)), ce.flat(m))
+        }
+        Axiom::FunctionalObjectProperty(FunctionalObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Functional", m)
+        }
+        Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(p @ Op(_))) => {
+            characteristic(p, "InverseFunctional", m)
+        }
+        Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Reflexive", m)
+        }
+        Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Irreflexive", m)
+        }
+        Axiom::SymmetricObjectProperty(SymmetricObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Symmetric", m)
+        }
+        Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Asymmetric", m)
+        }
+        Axiom::TransitiveObjectProperty(TransitiveObjectProperty(p @ Op(_))) => {
+            characteristic(p, "Transitive", m)
+        }
+        Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => frame_of(
+            format!("DataProperty: {} SubPropertyOf:", name(sub, m)),
+            name(sup, m),
+        ),
+        Axiom::EquivalentDataProperties(EquivalentDataProperties(v)) => {
+            frame_of("EquivalentProperties:".to_string(), names(v, m))
+        }
+        Axiom::DisjointDataProperties(DisjointDataProperties(v)) => {
+            frame_of("DisjointProperties:".to_string(), names(v, m))
+        }
+        Axiom::DataPropertyDomain(DataPropertyDomain { dp, ce }) => {
+            frame_of(format!("DataProperty: {} Domain:", name(dp, m)), ce.flat(m))
+        }
+        Axiom::DataPropertyRange(DataPropertyRange { dp, dr }) => {
+            frame_of(format!("DataProperty: {} Range:", name(dp, m)), dr.flat(m))
+        }
+        Axiom::FunctionalDataProperty(FunctionalDataProperty(dp)) => frame_of(
+            format!("DataProperty: {} Characteristics:", name(dp, m)),
+            "Functional",
+        ),
+        Axiom::DatatypeDefinition(DatatypeDefinition { kind, range }) => frame_of(
+            format!("Datatype: {} EquivalentTo:", name(kind, m)),
+            range.flat(m),
+        ),
+        Axiom::SameIndividual(SameIndividual(v)) => {
+            frame_of("SameIndividual:".to_string(), names(v, m))
+        }
+        Axiom::DifferentIndividuals(DifferentIndividuals(v)) => {
+            frame_of("DifferentIndividuals:".to_string(), names(v, m))
+        }
+        Axiom::ClassAssertion(ClassAssertion { ce, i }) => {
+            frame_of(format!("Individual: {} Types:", name(i, m)), ce.flat(m))
+        }
+        Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion {
+            ope: Op(p),
+            from,
+            to,
+        }) => frame_of(
+            format!("Individual: {} Facts:", name(from, m)),
+            format!("{} {}", name(p, m), name(to, m)),
+        ),
+        Axiom::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+            ope: Op(p),
+            from,
+            to,
+        }) => frame_of(
+            format!("Individual: {} Facts:", name(from, m)),
+            format!("not {} {}", name(p, m), name(to, m)),
+        ),
+        Axiom::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => frame_of(
+            format!("Individual: {} Facts:", name(from, m)),
+            format!("{} {}", name(dp, m), name(to, m)),
+        ),
+        Axiom::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion { dp, from, to }) => {
+            frame_of(
+                format!("Individual: {} Facts:", name(from, m)),
+                format!("not {} {}", name(dp, m), name(to, m)),
+            )
+        }
+        Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) => frame_of(
+            format!("AnnotationProperty: {} SubPropertyOf:", name(sub, m)),
+            name(sup, m),
+        ),
+        Axiom::AnnotationPropertyDomain(AnnotationPropertyDomain { ap, iri }) => frame_of(
+            format!("AnnotationProperty: {} Domain:", name(ap, m)),
+            name(iri, m),
+        ),
+        Axiom::AnnotationPropertyRange(AnnotationPropertyRange { ap, iri }) => frame_of(
+            format!("AnnotationProperty: {} Range:", name(ap, m)),
+            name(iri, m),
+        ),
+        _ => return None,
+    })
+}
+
+fn keyword(kind: NamedEntityKind) -> &'static str {
+    match kind {
+        NamedEntityKind::Class => "Class",
+        NamedEntityKind::ObjectProperty => "ObjectProperty",
+        NamedEntityKind::DataProperty => "DataProperty",
+        NamedEntityKind::AnnotationProperty => "AnnotationProperty",
+        NamedEntityKind::NamedIndividual => "Individual",
+        NamedEntityKind::Datatype => "Datatype",
+    }
+}
+
+/// Write `ont` to `write` as a Manchester syntax document,
+/// abbreviating IRIs with `mapping`.
+///
+/// Each axiom is written as a frame of its own. An annotation
+/// assertion is written in the frame of an entity with its subject,
+/// so fails if there is none. Manchester syntax cannot express some
+/// other axioms, such as those whose sub class is not named, or
+/// annotations on a declaration or an import; for these an error is
+/// returned.
+pub fn write(
+    write: &mut dyn StdWrite,
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let default_mapper = PrefixMapping::default();
+    let m = mapping.unwrap_or(&default_mapper);
+    let unwritable = |ax: &AnnotatedAxiom| ValidityError::Manchester(Functional(ax, m).to_string());
+
+    let mut prefixes: Vec<_> = m.mappings().collect();
+    prefixes.sort();
+    for (prefix, ns) in prefixes {
+        writeln!(write, "Prefix: {}: <{}>", prefix, ns)?;
+    }
+    writeln!(write)?;
+
+    write!(write, "Ontology:")?;
+    let id = ont.id();
+    if let Some(iri) = &id.iri {
+        write!(write, " {}", name(iri, m))?;
+        if let Some(viri) = &id.viri {
+            write!(write, " {}", name(viri, m))?;
+        }
+    }
+    writeln!(write)?;
+
+    for ax in ont.i().annotated_axiom(AxiomKind::Import) {
+        match &ax.axiom {
+            Axiom::Import(import) if ax.ann.is_empty() => {
+                writeln!(write, "Import: {}", name(&import.0, m))?
+            }
+            _ => return Err(unwritable(ax).into()),
+        }
+    }
+
+    for ax in ont.i().annotated_axiom(AxiomKind::OntologyAnnotation) {
+        if let Axiom::OntologyAnnotation(oa) = &ax.axiom {
+            let fr = frame_of("Annotations:".to_string(), annotation(&oa.0, m));
+            writeln!(
+                write,
+                "{}",
+                fr.annotated(&ax.ann, m).ok_or_else(|| unwritable(ax))?
+            )?;
+        }
+    }
+
+    // The kind of entity for the subject of each annotation assertion
+    let kinds: BTreeMap<IRI, NamedEntityKind> = ont
+        .signature()
+        .into_iter()
+        .map(|e| (e.iri().clone(), e.kind()))
+        .collect();
+
+    for kind in AxiomKind::all_kinds() {
+        if matches!(kind, AxiomKind::Import | AxiomKind::OntologyAnnotation) {
+            continue;
+        }
+
+        for ax in ont.i().annotated_axiom(kind) {
+            let fr = match &ax.axiom {
+                Axiom::AnnotationAssertion(AnnotationAssertion { subject, ann }) => {
+                    let kind = if subject.node_id().is_some() {
+                        Some("Individual")
+                    } else {
+                        kinds.get(subject).map(|k| keyword(*k))
+                    };
+                    kind.map(|k| {
+                        frame_of(
+                            format!("{}: {} Annotations:", k, name(subject, m)),
+                            annotation(ann, m),
+                        )
+                    })
+                }
+                axiom => frame(axiom, m),
+            };
+
+            let s = fr
+                .and_then(|fr| fr.annotated(&ax.ann, m))
+                .ok_or_else(|| unwritable(ax))?;
+            writeln!(write, "{}", s)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Displays an `AnnotatedAxiom` in Manchester syntax, as a single
+/// frame on one line.
+///
+/// Manchester syntax has no frame for some axioms, such as those
+/// whose sub class is not named or which annotate an IRI; these, and
+/// axioms with annotations, are displayed in functional syntax.
+///
+/// # Examples
+/// ```
+/// # use curie::PrefixMapping;
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::omn::writer::Manchester;
+/// let b = Build::new();
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("ex", "http://www.example.com/").unwrap();
+///
+/// let ax: AnnotatedAxiom = SubClassOf {
+///     sub: b.class("http://www.example.com/Margherita").into(),
+///     sup: b.class("http://www.example.com/Pizza").into(),
+/// }
+/// .into();
+///
+/// assert_eq!(
+///     Manchester(&ax, &mapping).to_string(),
+///     "Class: ex:Margherita SubClassOf: ex:Pizza"
+/// );
+/// ```
+impl<'a> Display for Manchester<'a, AnnotatedAxiom> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match frame(&self.0.axiom, self.1) {
+            Some(fr) if self.0.ann.is_empty() => f.write_str(&fr.flat()),
+            _ => Functional(self.0, self.1).fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mapping() -> PrefixMapping {
+        let mut m = PrefixMapping::default();
+        m.add_prefix("ex", "http://www.example.com/").unwrap();
+        m.add_prefix("xsd", "http://www.w3.org/2001/XMLSchema#")
+            .unwrap();
+        m
+    }
+
+    fn c(b: &Build, s: &str) -> ClassExpression {
+        b.class(format!("http://www.example.com/{}", s)).into()
+    }
+
+    fn r(b: &Build, s: &str) -> ObjectPropertyExpression {
+        b.object_property(format!("http://www.example.com/{}", s))
+            .into()
+    }
+
+    fn render(ce: &ClassExpression) -> String {
+        Manchester(ce, &mapping()).to_string()
+    }
+
+    #[test]
+    fn precedence() {
+        use ClassExpression::*;
+        let b = Build::new();
+
+        let and = ObjectIntersectionOf(vec![c(&b, "A"), c(&b, "B")]);
+        let or = ObjectUnionOf(vec![and.clone(), c(&b, "C")]);
+        assert_eq!(render(&or), "ex:A and ex:B or ex:C");
+
+        let and_or = ObjectIntersectionOf(vec![or.clone(), c(&b, "D")]);
+        assert_eq!(render(&and_or), "(ex:A and ex:B or ex:C) and ex:D");
+
+        let not = ObjectComplementOf(Box::new(ObjectComplementOf(Box::new(c(&b, "A")))));
+        assert_eq!(render(&not), "not (not ex:A)");
+
+        let some = ObjectSomeValuesFrom {
+            ope: ObjectPropertyExpression::InverseObjectProperty(
+                b.object_property("http://www.example.com/r"),
+            ),
+            bce: Box::new(ObjectComplementOf(Box::new(c(&b, "A")))),
+        };
+        assert_eq!(render(&some), "inverse ex:r some not ex:A");
+        assert_eq!(
+            render(&ObjectComplementOf(Box::new(some))),
+            "not inverse ex:r some not ex:A"
+        );
+    }
+
+    #[test]
+    fn restrictions() {
+        use ClassExpression::*;
+        let b = Build::new();
+
+        assert_eq!(
+            render(&ObjectExactCardinality {
+                n: 2,
+                ope: r(&b, "r"),
+                bce: Box::new(ObjectOneOf(vec![
+                    b.named_individual("http://www.example.com/i"),
+                    b.named_individual("http://www.example.com/j"),
+                ])),
+            }),
+            "ex:r exactly 2 {ex:i, ex:j}"
+        );
+        assert_eq!(render(&ObjectHasSelf(r(&b, "r"))), "ex:r Self");
+
+        let dp = b.data_property("http://www.example.com/age");
+        let int = b.datatype("http://www.w3.org/2001/XMLSchema#integer");
+        let lit = |n: &str| Literal::Datatype {
+            literal: n.to_string(),
+            datatype_iri: int.0.clone(),
+        };
+        assert_eq!(
+            render(&DataSomeValuesFrom {
+                dp: dp.clone(),
+                dr: DataRange::DatatypeRestriction(
+                    int.clone(),
+                    vec![
+                        FacetRestriction {
+                            f: Facet::MinInclusive,
+                            l: lit("18"),
+                        },
+                        FacetRestriction {
+                            f: Facet::MaxExclusive,
+                            l: lit("65"),
+                        },
+                    ],
+                ),
+            }),
+            "ex:age some xsd:integer[>= \"18\"^^xsd:integer, < \"65\"^^xsd:integer]"
+        );
+        assert_eq!(
+            render(&DataHasValue {
+                dp,
+                l: Literal::Language {
+                    literal: "chat".to_string(),
+                    lang: "fr".to_string(),
+                },
+            }),
+            "ex:age value \"chat\"@fr"
+        );
+        assert_eq!(
+            render(&c(&b, "with space")),
+            "<http://www.example.com/with space>"
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        use ClassExpression::*;
+        let b = Build::new();
+        let m = mapping();
+
+        let ce = ObjectIntersectionOf(vec![
+            c(&b, "Pizza"),
+            ObjectSomeValuesFrom {
+                ope: r(&b, "hasTopping"),
+                bce: Box::new(ObjectUnionOf(vec![
+                    c(&b, "Mozzarella"),
+                    c(&b, "Tomato"),
+                    c(&b, "Basil"),
+                ])),
+            },
+        ]);
+
+        assert_eq!(Manchester(&ce, &m).wrap(100), render(&ce));
+        assert_eq!(
+            Manchester(&ce, &m).wrap(30),
+            "ex:Pizza
+and ex:hasTopping some
+        (ex:Mozzarella
+         or ex:Tomato
+         or ex:Basil)"
+        );
+        for line in Manchester(&ce, &m).wrap(30).lines() {
+            assert!(line.len() <= 30);
+        }
+    }
+
+    #[test]
+    fn axioms() {
+        use ClassExpression::*;
+        let b = Build::new();
+        let m = mapping();
+        let frame = |ax: AnnotatedAxiom| Manchester(&ax, &m).to_string();
+
+        assert_eq!(
+            frame(
+                SubClassOf {
+                    sub: c(&b, "A"),
+                    sup: ObjectComplementOf(Box::new(c(&b, "B"))),
+                }
+                .into()
+            ),
+            "Class: ex:A SubClassOf: not ex:B"
+        );
+        assert_eq!(
+            frame(EquivalentClasses(vec![c(&b, "A"), c(&b, "B")]).into()),
+            "EquivalentClasses: ex:A, ex:B"
+        );
+        assert_eq!(
+            frame(TransitiveObjectProperty(r(&b, "r")).into()),
+            "ObjectProperty: ex:r Characteristics: Transitive"
+        );
+        assert_eq!(
+            frame(
+                ObjectPropertyAssertion {
+                    ope: r(&b, "r"),
+                    from: b.named_individual("http://www.example.com/i"),
+                    to: b.named_individual("http://www.example.com/j"),
+                }
+                .into()
+            ),
+            "Individual: ex:i Facts: ex:r ex:j"
+        );
+
+        // Without a frame, or with annotations, functional syntax
+        let gci: AnnotatedAxiom = SubClassOf {
+            sub: ObjectUnionOf(vec![c(&b, "A"), c(&b, "B")]),
+            sup: c(&b, "C"),
+        }
+        .into();
+        assert_eq!(frame(gci.clone()), Functional(&gci, &m).to_string());
+
+        let mut ax: AnnotatedAxiom = DeclareClass(b.class("http://www.example.com/A")).into();
+        ax.ann.insert(Annotation {
+            ap: b.annotation_property("http://www.example.com/note"),
+            av: b.iri("http://www.example.com/B").into(),
+        });
+        assert_eq!(frame(ax.clone()), Functional(&ax, &m).to_string());
+    }
+
+    #[test]
+    fn document() {
+        let (ont, mapping) = crate::io::owx::reader::test::read_ok(
+            &mut include_str!("../../ont/owl-xml/class.owx").as_bytes(),
+        );
+        let mut buf = Vec::new();
+        write(&mut buf, &ont, Some(&mapping)).unwrap();
+        let omn = String::from_utf8(buf).unwrap();
+
+        assert!(omn.contains("Prefix: o: <http://www.example.com/iri#>\n"));
+        assert!(
+            omn.contains("Ontology: <http://www.example.com/iri> <http://www.example.com/viri>\n")
+        );
+        assert!(omn.contains("Class: o:C\n"));
+    }
+
+    #[test]
+    fn document_unwritable() {
+        let b = Build::new();
+        let mut ont = AxiomMappedOntology::default();
+        ont.insert(SubClassOf {
+            sub: ClassExpression::ObjectUnionOf(vec![c(&b, "A"), c(&b, "B")]),
+            sup: c(&b, "C"),
+        });
+
+        let mut buf = Vec::new();
+        assert!(write(&mut buf, &ont, Some(&mapping())).is_err());
+    }
+}
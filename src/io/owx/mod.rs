@@ -1,2 +1,49 @@
+//! OWL/XML syntax
+//!
+//! As well as the ontology, the reader can return the `Layout` of
+//! the document: the order of its prefix declarations and ontology
+//! annotations, and its comments. Writing an ontology with its
+//! layout reproduces these, so that an unchanged ontology differs
+//! little from the document it was read from. Only this syntax has
+//! a layout; the RDF readers keep neither the prefixes of a document,
+//! nor the order of its annotations, nor its comments.
+use super::{ParserOutput, SyntaxParser};
+use crate::model::{AnnotatedAxiom, Annotation, Build};
+
+use crate::error::HornedError;
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
 pub mod reader;
 pub mod writer;
+
+/// The `SyntaxParser` for OWL/XML.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Parser;
+
+impl SyntaxParser for Parser {
+    fn parse(&self, mut bufread: &mut dyn BufRead, b: &Build) -> Result<ParserOutput, HornedError> {
+        Ok(reader::read_with_build(&mut bufread, b)?.into())
+    }
+}
+
+/// The parts of an OWL/XML document which are not part of its
+/// ontology.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Layout {
+    /// The prefix declarations, in document order.
+    pub prefixes: Vec<(String, String)>,
+    /// The ontology annotations, in document order.
+    pub annotations: Vec<Annotation>,
+    /// Comments before the `Ontology` element.
+    pub prolog: Vec<String>,
+    /// Comments before the first axiom.
+    pub header: Vec<String>,
+    /// Comments immediately before later axioms. The comments before
+    /// each occurrence of an axiom which is repeated are kept, in
+    /// document order.
+    pub comments: BTreeMap<AnnotatedAxiom, Vec<String>>,
+    /// Comments after the last axiom.
+    pub footer: Vec<String>,
+}
@@ -1,5 +1,6 @@
 use curie::PrefixMapping;
 
+use super::Layout;
 use crate::model::*;
 use crate::vocab::Namespace::*;
 use crate::vocab::OWL2Datatype;
@@ -15,38 +16,7 @@ use quick_xml::events::BytesStart;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use failure::Error;
-
-#[derive(Debug, Fail)]
-enum ReadError {
-    #[fail(display = "End Tag Arrived Unexpectedly: {} at {}", tag, pos)]
-    UnexpectedEndTag { tag: String, pos: usize },
-
-    #[fail(display = "Missing End Tag: expected {} after {}", tag, pos)]
-    MissingEndTag { tag: String, pos: usize },
-
-    #[fail(display = "Missing element: Expected {} at {}", tag, pos)]
-    MissingElement { tag: String, pos: usize },
-
-    #[fail(display = "Missing attribute: Expected {} at {}", attribute, pos)]
-    MissingAttribute { attribute: String, pos: usize },
-
-    #[fail(
-        display = "Unknown Entity: Expected Kind of {}, found {} at {}",
-        kind, found, pos
-    )]
-    UnknownEntity {
-        kind: String,
-        found: String,
-        pos: usize,
-    },
-
-    #[fail(display = "Unexpected Tag: found {} at {}", tag, pos)]
-    UnexpectedTag { tag: String, pos: usize },
-
-    #[fail(display = "Unexpected End of File: {}", pos)]
-    UnexpectedEof { pos: usize },
-}
+use crate::error::{HornedError, ParserError, ValidityError};
 
 struct Read<'a, R>
 where
@@ -59,7 +29,7 @@ where
     ns_buf: Vec<u8>,
 }
 
-pub fn read<R: BufRead>(bufread: &mut R) -> Result<(SetOntology, PrefixMapping), Error> {
+pub fn read<R: BufRead>(bufread: &mut R) -> Result<(SetOntology, PrefixMapping), HornedError> {
     let b = Build::new();
     read_with_build(bufread, &b)
 }
@@ -67,10 +37,26 @@ pub fn read<R: BufRead>(bufread: &mut R) -> Result<(SetOntology, PrefixMapping),
 pub fn read_with_build<R: BufRead>(
     bufread: &mut R,
     build: &Build,
-) -> Result<(SetOntology, PrefixMapping), Error> {
+) -> Result<(SetOntology, PrefixMapping), HornedError> {
+    let (ont, mapping, _) = read_with_layout(bufread, build)?;
+    Ok((ont, mapping))
+}
+
+/// Read an ontology as `read_with_build`, also returning the
+/// `Layout` of the document, for use with
+/// [`write_with_layout`](../writer/fn.write_with_layout.html).
+pub fn read_with_layout<R: BufRead>(
+    bufread: &mut R,
+    build: &Build,
+) -> Result<(SetOntology, PrefixMapping, Layout), HornedError> {
     let reader: Reader<&mut R> = Reader::from_reader(bufread);
     let mut ont = SetOntology::default();
     let mapping = PrefixMapping::default();
+    let mut layout = Layout::default();
+
+    // Comments since the last axiom
+    let mut comments = vec![];
+    let mut in_ontology = false;
 
     let mut r = Read {
         reader,
@@ -87,9 +73,12 @@ pub fn read_with_build<R: BufRead>(
             {
                 match e.local_name() {
                     b"Ontology" => {
+                        layout.prolog = std::mem::take(&mut comments);
+                        in_ontology = true;
+
                         let s = attrib_value(&mut r, e, b"ontologyIRI")?;
-                        if s.is_some() {
-                            r.mapping.set_default(&s.unwrap());
+                        if let Some(s) = s {
+                            r.mapping.set_default(&s);
                         }
 
                         ont.mut_id().iri = read_a_iri_attr(&mut r, e, b"ontologyIRI")?;
@@ -101,6 +90,7 @@ pub fn read_with_build<R: BufRead>(
                         match (prefix, iri) {
                             (Some(p), Some(i)) => {
                                 r.mapping.add_prefix(&p, &i).ok();
+                                layout.prefixes.push((p, i));
                             }
                             (None, _) => {
                                 return Err(error_missing_attribute("IRI", &mut r));
@@ -111,21 +101,56 @@ pub fn read_with_build<R: BufRead>(
                         }
                     }
                     b"Import" => {
-                        ont.insert(Import(IRI::from_xml(&mut r, b"Import")?));
+                        let aa = Import(IRI::from_xml(&mut r, b"Import")?).into();
+                        insert_with_layout(&mut ont, &mut layout, aa, &mut comments);
                     }
                     _ => {
                         let aa = AnnotatedAxiom::from_start(&mut r, e)?;
-                        ont.insert(aa);
+                        insert_with_layout(&mut ont, &mut layout, aa, &mut comments);
                     }
                 }
             }
+            (_, Event::Comment(ref c)) => {
+                comments.push(String::from_utf8_lossy(c).into_owned());
+            }
             (ref ns, Event::End(ref e)) if is_owl_name(ns, e, b"Ontology") => {
                 break;
             }
             _ => {}
         }
     }
-    Ok((ont, r.mapping))
+
+    if in_ontology {
+        layout.footer = comments;
+    }
+    Ok((ont, r.mapping, layout))
+}
+
+/// Insert `aa` into `ont`, recording its position and the `comments`
+/// which precede it in `layout`.
+fn insert_with_layout(
+    ont: &mut SetOntology,
+    layout: &mut Layout,
+    aa: AnnotatedAxiom,
+    comments: &mut Vec<String>,
+) {
+    if let Axiom::OntologyAnnotation(oa) = &aa.axiom {
+        layout.annotations.push(oa.0.clone());
+    }
+    if !comments.is_empty() {
+        let comments = std::mem::take(comments);
+        if ont.iter().next().is_none() {
+            layout.header = comments;
+        } else {
+            // An axiom may be repeated, each time with comments
+            layout
+                .comments
+                .entry(aa.clone())
+                .or_default()
+                .extend(comments);
+        }
+    }
+    ont.insert(aa);
 }
 
 /// Read an event from the reader, which is unowned.
@@ -142,13 +167,13 @@ pub fn read_with_build<R: BufRead>(
 /// non-lexical lifetimes appears, it should be possible to make
 /// this a straight alias for `read_namespaced_event`, and still
 /// have it all work.
-fn read_event<R: BufRead>(read: &mut Read<R>) -> Result<(Vec<u8>, Event<'static>), Error> {
+fn read_event<R: BufRead>(read: &mut Read<R>) -> Result<(Vec<u8>, Event<'static>), HornedError> {
     let r = read
         .reader
         .read_namespaced_event(&mut read.buf, &mut read.ns_buf);
 
     match r {
-        Ok((_, Event::Eof)) => Err(ReadError::UnexpectedEof {
+        Ok((_, Event::Eof)) => Err(ParserError::UnexpectedEof {
             pos: read.reader.buffer_position(),
         }
         .into()),
@@ -172,7 +197,10 @@ fn expand_curie_maybe<R: BufRead>(r: &mut Read<R>, val: String) -> String {
     }
 }
 
-fn attrib_value_b<'a>(event: &'a BytesStart, tag: &[u8]) -> Result<Option<Cow<'a, [u8]>>, Error> {
+fn attrib_value_b<'a>(
+    event: &'a BytesStart,
+    tag: &[u8],
+) -> Result<Option<Cow<'a, [u8]>>, HornedError> {
     for res in event.attributes() {
         let attrib = res?;
         if attrib.key == tag {
@@ -187,11 +215,14 @@ fn attrib_value<R: BufRead>(
     r: &mut Read<R>,
     event: &BytesStart,
     tag: &[u8],
-) -> Result<Option<String>, Error> {
+) -> Result<Option<String>, HornedError> {
     attrib_value_b(event, tag).map(|res| res.map(|val| r.reader.decode(&val).into_owned()))
 }
 
-fn read_iri_attr<R: BufRead>(r: &mut Read<R>, event: &BytesStart) -> Result<Option<IRI>, Error> {
+fn read_iri_attr<R: BufRead>(
+    r: &mut Read<R>,
+    event: &BytesStart,
+) -> Result<Option<IRI>, HornedError> {
     let iri = read_a_iri_attr(r, event, b"IRI")?;
     Ok(if iri.is_some() {
         iri
@@ -204,7 +235,7 @@ fn read_a_iri_attr<R: BufRead>(
     r: &mut Read<R>,
     event: &BytesStart,
     tag: &[u8],
-) -> Result<Option<IRI>, Error> {
+) -> Result<Option<IRI>, HornedError> {
     Ok(
         // check for the attrib, if malformed return
         attrib_value(r, event, tag)?.
@@ -217,32 +248,35 @@ fn read_a_iri_attr<R: BufRead>(
     )
 }
 
-fn error_missing_end_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>, pos: usize) -> Error {
-    ReadError::MissingEndTag {
+fn error_missing_end_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>, pos: usize) -> HornedError {
+    ParserError::MissingEndTag {
         tag: r.reader.decode(tag).into_owned(),
         pos,
     }
     .into()
 }
 
-fn error_missing_attribute<A: Into<String>, R: BufRead>(attribute: A, r: &mut Read<R>) -> Error {
-    ReadError::MissingAttribute {
+fn error_missing_attribute<A: Into<String>, R: BufRead>(
+    attribute: A,
+    r: &mut Read<R>,
+) -> HornedError {
+    ParserError::MissingAttribute {
         attribute: attribute.into(),
         pos: r.reader.buffer_position(),
     }
     .into()
 }
 
-fn error_unexpected_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> Error {
-    ReadError::UnexpectedTag {
+fn error_unexpected_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> HornedError {
+    ParserError::UnexpectedTag {
         tag: r.reader.decode(tag).into_owned(),
         pos: r.reader.buffer_position(),
     }
     .into()
 }
 
-fn error_unexpected_end_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> Error {
-    ReadError::UnexpectedEndTag {
+fn error_unexpected_end_tag<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> HornedError {
+    ParserError::UnexpectedEndTag {
         tag: r.reader.decode(tag).into_owned(),
         pos: r.reader.buffer_position(),
     }
@@ -253,8 +287,8 @@ fn error_unknown_entity<A: Into<String>, R: BufRead>(
     kind: A,
     found: &[u8],
     r: &mut Read<R>,
-) -> Error {
-    ReadError::UnknownEntity {
+) -> HornedError {
+    ParserError::UnknownEntity {
         kind: kind.into(),
         found: r.reader.decode(found).into_owned(),
         pos: r.reader.buffer_position(),
@@ -262,8 +296,8 @@ fn error_unknown_entity<A: Into<String>, R: BufRead>(
     .into()
 }
 
-fn error_missing_element<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> Error {
-    ReadError::MissingElement {
+fn error_missing_element<R: BufRead>(tag: &[u8], r: &mut Read<R>) -> HornedError {
+    ParserError::MissingElement {
         tag: r.reader.decode(tag).into_owned(),
         pos: r.reader.buffer_position(),
     }
@@ -279,13 +313,16 @@ fn is_owl_name(ns: &[u8], e: &BytesEnd, tag: &[u8]) -> bool {
 }
 
 trait FromStart: Sized {
-    fn from_start<R: BufRead>(r: &mut Read<R>, e: &BytesStart) -> Result<Self, Error>;
+    fn from_start<R: BufRead>(r: &mut Read<R>, e: &BytesStart) -> Result<Self, HornedError>;
 }
 
 macro_rules! from_start {
     ($type:ident, $r:ident, $e:ident, $body:tt) => {
         impl FromStart for $type {
-            fn from_start<R: BufRead>($r: &mut Read<R>, $e: &BytesStart) -> Result<$type, Error> {
+            fn from_start<R: BufRead>(
+                $r: &mut Read<R>,
+                $e: &BytesStart,
+            ) -> Result<$type, HornedError> {
                 $body
             }
         }
@@ -293,7 +330,11 @@ macro_rules! from_start {
 }
 
 /// Potentially unbalanced
-fn named_entity_from_start<R, T>(r: &mut Read<R>, e: &BytesStart, tag: &[u8]) -> Result<T, Error>
+fn named_entity_from_start<R, T>(
+    r: &mut Read<R>,
+    e: &BytesStart,
+    tag: &[u8],
+) -> Result<T, HornedError>
 where
     R: BufRead,
     T: From<IRI>,
@@ -313,7 +354,7 @@ where
     Err(error_missing_element(b"IRI", r))
 }
 
-fn from_start<R: BufRead, T: FromStart>(r: &mut Read<R>, e: &BytesStart) -> Result<T, Error> {
+fn from_start<R: BufRead, T: FromStart>(r: &mut Read<R>, e: &BytesStart) -> Result<T, HornedError> {
     T::from_start(r, e)
 }
 
@@ -336,8 +377,8 @@ from_start! {
                 (Some(ref datatype_iri), Some(ref lang), ref literal)
                     if **datatype_iri == "http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral"
                     => Literal::Language{literal:literal.to_string(), lang:lang.to_string()},
-                (Some(_), Some(_), _)
-                    => bail!("Literal with language tag and incorrect datatype"),
+                (Some(_), Some(_), literal)
+                    => return Err(ValidityError::LanguageDatatype(literal).into()),
                 (Some(datatype_iri), None, literal)
                     => Literal::Datatype{literal, datatype_iri},
             })
@@ -356,6 +397,10 @@ from_start! {
                     IRI::from_xml(r, e.local_name())?
                     .into()
                 }
+                b"AnonymousIndividual" => {
+                    NamedIndividual::from_start(r, e)?
+                    .into()
+                }
                 _ => {
                     return Err
                         (error_unexpected_tag(e.local_name(), r));
@@ -376,7 +421,7 @@ fn axiom_from_start<R: BufRead>(
     r: &mut Read<R>,
     e: &BytesStart,
     axiom_kind: &[u8],
-) -> Result<Axiom, Error> {
+) -> Result<Axiom, HornedError> {
     Ok(match axiom_kind {
         b"Annotation" => OntologyAnnotation(Annotation {
             ap: from_start(r, e)?,
@@ -532,14 +577,16 @@ fn from_start_to_end<R: BufRead, T: FromStart>(
     r: &mut Read<R>,
     e: &BytesStart,
     end_tag: &[u8],
-) -> Result<Vec<T>, Error> {
-    let mut v = Vec::new();
-    v.push(from_start(r, e)?);
+) -> Result<Vec<T>, HornedError> {
+    let v = vec![from_start(r, e)?];
     till_end_with(r, end_tag, v)
 }
 
 // Keep reading entities, till end_tag is reached
-fn till_end<R: BufRead, T: FromStart>(r: &mut Read<R>, end_tag: &[u8]) -> Result<Vec<T>, Error> {
+fn till_end<R: BufRead, T: FromStart>(
+    r: &mut Read<R>,
+    end_tag: &[u8],
+) -> Result<Vec<T>, HornedError> {
     let operands: Vec<T> = Vec::new();
     till_end_with(r, end_tag, operands)
 }
@@ -549,7 +596,7 @@ fn till_end_with<R: BufRead, T: FromStart>(
     r: &mut Read<R>,
     end_tag: &[u8],
     mut operands: Vec<T>,
-) -> Result<Vec<T>, Error> {
+) -> Result<Vec<T>, HornedError> {
     loop {
         let e = read_event(r)?;
         match e {
@@ -573,7 +620,7 @@ fn object_cardinality_restriction<R: BufRead>(
     r: &mut Read<R>,
     e: &BytesStart,
     end_tag: &[u8],
-) -> Result<(u32, ObjectPropertyExpression, Box<ClassExpression>), Error> {
+) -> Result<(u32, ObjectPropertyExpression, Box<ClassExpression>), HornedError> {
     let n = attrib_value(r, e, b"cardinality")?;
     let n = n.ok_or_else(|| error_missing_attribute("cardinality", r))?;
 
@@ -595,9 +642,9 @@ fn data_cardinality_restriction<R: BufRead>(
     r: &mut Read<R>,
     e: &BytesStart,
     end_tag: &[u8],
-) -> Result<(u32, DataProperty, DataRange), Error> {
+) -> Result<(u32, DataProperty, DataRange), HornedError> {
     let n = attrib_value(r, e, b"cardinality")?;
-    let n = n.ok_or_else(|| (error_missing_attribute("cardinality", r)))?;
+    let n = n.ok_or_else(|| error_missing_attribute("cardinality", r) )?;
 
     let dp = from_next(r)?;
     let mut vdr: Vec<DataRange> = till_end(r, end_tag)?;
@@ -801,6 +848,12 @@ from_start! {
 from_start! {
     NamedIndividual, r, e,
     {
+        if e.local_name() == b"AnonymousIndividual" {
+            return match attrib_value(r, e, b"nodeID")? {
+                Some(id) => Ok(r.build.anonymous_individual(id)),
+                None => Err(error_missing_element(b"nodeID", r)),
+            };
+        }
         named_entity_from_start(r, e, b"NamedIndividual")
     }
 }
@@ -845,7 +898,7 @@ from_start! {
                     SubObjectPropertyExpression::ObjectPropertyChain(o)
 
                 }
-                b"ObjectProperty" => {
+                b"ObjectProperty" | b"ObjectInverseOf" => {
                     SubObjectPropertyExpression::
                     ObjectPropertyExpression(from_start(r, e)?)
                 }
@@ -893,6 +946,11 @@ from_start! {
                         till_end(r, b"DataIntersectionOf")?
                     )
                 }
+                b"DataUnionOf" => {
+                    DataRange::DataUnionOf(
+                        till_end(r, b"DataUnionOf")?
+                    )
+                }
                 b"DataComplementOf" => {
                     DataRange::DataComplementOf(
                         Box::new(from_next(r)?)
@@ -951,19 +1009,22 @@ from_start! {
 }
 
 trait FromXML: Sized {
-    fn from_xml<R: BufRead>(newread: &mut Read<R>, end_tag: &[u8]) -> Result<Self, Error> {
+    fn from_xml<R: BufRead>(newread: &mut Read<R>, end_tag: &[u8]) -> Result<Self, HornedError> {
         let s = Self::from_xml_nc(newread, end_tag);
         newread.buf.clear();
         s
     }
 
-    fn from_xml_nc<R: BufRead>(newread: &mut Read<R>, end_tag: &[u8]) -> Result<Self, Error>;
+    fn from_xml_nc<R: BufRead>(newread: &mut Read<R>, end_tag: &[u8]) -> Result<Self, HornedError>;
 }
 
 macro_rules! from_xml {
     ($type:ident, $r:ident, $end:ident, $body:tt) => {
         impl FromXML for $type {
-            fn from_xml_nc<R: BufRead>($r: &mut Read<R>, $end: &[u8]) -> Result<$type, Error> {
+            fn from_xml_nc<R: BufRead>(
+                $r: &mut Read<R>,
+                $end: &[u8],
+            ) -> Result<$type, HornedError> {
                 $body
             }
         }
@@ -1010,7 +1071,7 @@ from_xml! {
 
 }
 
-fn from_next<R: BufRead, T: FromStart>(r: &mut Read<R>) -> Result<T, Error> {
+fn from_next<R: BufRead, T: FromStart>(r: &mut Read<R>) -> Result<T, HornedError> {
     loop {
         let e = read_event(r)?;
         match e {
@@ -1022,7 +1083,7 @@ fn from_next<R: BufRead, T: FromStart>(r: &mut Read<R>) -> Result<T, Error> {
     }
 }
 
-fn discard_till<R: BufRead>(r: &mut Read<R>, end: &[u8]) -> Result<(), Error> {
+fn discard_till<R: BufRead>(r: &mut Read<R>, end: &[u8]) -> Result<(), HornedError> {
     let pos = r.reader.buffer_position();
     loop {
         let e = read_event(r)?;
@@ -1370,6 +1431,14 @@ pub mod test {
         assert_eq!(ont.i().sub_object_property().count(), 1);
     }
 
+    #[test]
+    fn test_sub_oproperty_inverse_sub() {
+        let ont_s = include_str!("../../ont/owl-xml/suboproperty-inverse-sub.owx");
+        let (ont, _) = read_ok(&mut ont_s.as_bytes());
+
+        assert_eq!(ont.i().sub_object_property().count(), 1);
+    }
+
     #[test]
     fn test_one_inverse_property() {
         let ont_s = include_str!("../../ont/owl-xml/inverse-properties.owx");
@@ -1523,7 +1592,7 @@ pub mod test {
         if let ClassExpression::ObjectHasValue { ope: _, i: _ } = ss.sup {
             return;
         }
-        assert!(false);
+        panic!("Expecting ObjectHasValue");
     }
 
     #[test]
@@ -1688,18 +1757,15 @@ pub mod test {
         assert_eq!(ont.i().datatype_definition().count(), 1);
         let dd = ont.i().datatype_definition().next().unwrap();
 
-        match dd {
-            DatatypeDefinition { kind, range } => {
-                assert_eq!(String::from(kind), "http://www.example.com/D");
+        let DatatypeDefinition { kind, range } = dd;
+        assert_eq!(String::from(kind), "http://www.example.com/D");
 
-                match range {
-                    DataRange::Datatype(real) => {
-                        assert_eq!(String::from(real), "http://www.w3.org/2002/07/owl#real");
-                    }
-                    _ => {
-                        panic!("Unexpected type from test");
-                    }
-                }
+        match range {
+            DataRange::Datatype(real) => {
+                assert_eq!(String::from(real), "http://www.w3.org/2002/07/owl#real");
+            }
+            _ => {
+                panic!("Unexpected type from test");
             }
         }
     }
@@ -1720,6 +1786,14 @@ pub mod test {
         assert_eq!(ont.i().datatype_definition().count(), 1);
     }
 
+    #[test]
+    fn datatype_union_of() {
+        let ont_s = include_str!("../../ont/owl-xml/datatype-union-of.owx");
+        let (ont, _) = read_ok(&mut ont_s.as_bytes());
+
+        assert_eq!(ont.i().datatype_definition().count(), 1);
+    }
+
     #[test]
     fn datatype_complement() {
         let ont_s = include_str!("../../ont/owl-xml/datatype-complement.owx");
@@ -1752,6 +1826,27 @@ pub mod test {
         assert_eq!(ont.i().sub_class_of().count(), 1);
     }
 
+    #[test]
+    fn facet_restriction_string() {
+        let ont_s = include_str!("../../ont/owl-xml/facet-restriction-string.owx");
+        let (ont, _) = read_ok(&mut ont_s.as_bytes());
+
+        let sc = ont.i().sub_class_of().next().unwrap();
+        if let ClassExpression::DataSomeValuesFrom {
+            dr: DataRange::DatatypeRestriction(dt, fr),
+            ..
+        } = &sc.sup
+        {
+            assert!(dt.is_s("http://www.w3.org/2001/XMLSchema#string"));
+            assert_eq!(fr.len(), 2);
+            assert_eq!(fr[0].f, Facet::MinLength);
+            assert_eq!(fr[1].f, Facet::Pattern);
+            assert_eq!(fr[1].l.literal(), "[A-Z]+");
+        } else {
+            panic!("Expected a datatype restriction");
+        }
+    }
+
     #[test]
     fn data_only() {
         let ont_s = include_str!("../../ont/owl-xml/data-only.owx");
@@ -1763,9 +1858,7 @@ pub mod test {
             dp: ref _dp,
             dr: ref _dr,
         } = cl
-        {
-            assert!(true);
-        } else {
+        {} else {
             panic!("Expecting DataAllValuesFrom");
         }
     }
@@ -1782,9 +1875,7 @@ pub mod test {
             dp: ref _dp,
             dr: ref _dr,
         } = cl
-        {
-            assert!(true);
-        } else {
+        {} else {
             panic!("Expecting DataExactCardinality");
         }
     }
@@ -1822,9 +1913,7 @@ pub mod test {
             dp: ref _dp,
             dr: ref _dr,
         } = cl
-        {
-            assert!(true);
-        } else {
+        {} else {
             panic!("Expecting DataMinCardinality");
         }
     }
@@ -1841,9 +1930,7 @@ pub mod test {
             dp: ref _dp,
             dr: ref _dr,
         } = cl
-        {
-            assert!(true);
-        } else {
+        {} else {
             panic!("Expecting DataMaxCardinality");
         }
     }
@@ -1880,4 +1967,24 @@ pub mod test {
         let ont_s = include_str!("../../ont/owl-xml/family.owx");
         let (_, _) = read_ok(&mut ont_s.as_bytes());
     }
+
+    #[test]
+    fn layout_repeated_comments() {
+        let owx = r#"<Ontology xmlns="http://www.w3.org/2002/07/owl#">
+    <Declaration><Class IRI="http://www.example.com/A"/></Declaration>
+    <!-- First -->
+    <Declaration><Class IRI="http://www.example.com/B"/></Declaration>
+    <!-- Second -->
+    <Declaration><Class IRI="http://www.example.com/B"/></Declaration>
+</Ontology>"#;
+        let (ont, _, layout) = read_with_layout(&mut owx.as_bytes(), &Build::new()).unwrap();
+        assert_eq!(ont.iter().count(), 2);
+
+        let b = Build::new();
+        let aa: AnnotatedAxiom = DeclareClass(b.class("http://www.example.com/B")).into();
+        assert_eq!(
+            layout.comments[&aa],
+            vec![" First ".to_string(), " Second ".to_string()]
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use curie::PrefixMapping;
 
+use super::Layout;
 use crate::model::Kinded;
 use crate::model::*;
 use crate::vocab::Namespace::*;
@@ -15,7 +16,7 @@ use quick_xml::Writer;
 use std::collections::BTreeSet;
 use std::io::Write as StdWrite;
 
-use failure::Error;
+use crate::error::HornedError;
 
 /// Write an Ontology to `write`, using the given PrefixMapping
 ///
@@ -25,7 +26,23 @@ pub fn write(
     write: &mut dyn StdWrite,
     ont: &AxiomMappedOntology,
     mapping: Option<&PrefixMapping>,
-) -> Result<(), Error> {
+) -> Result<(), HornedError> {
+    write_with_layout(write, ont, mapping, &Layout::default())
+}
+
+/// Write an Ontology to `write` as `write`, following `layout`.
+///
+/// Prefixes and ontology annotations in `layout` are written first,
+/// in its order, and its comments are written in their place, so
+/// that an ontology read with
+/// [`read_with_layout`](../reader/fn.read_with_layout.html), and
+/// unchanged, is written much as it was read.
+pub fn write_with_layout(
+    write: &mut dyn StdWrite,
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+    layout: &Layout,
+) -> Result<(), HornedError> {
     let mut writer = Writer::new_with_indent(write, b' ', 4);
 
     // Ensure we have a prefix mapping; the default is a no-op and
@@ -36,7 +53,7 @@ pub fn write(
         None => &default_mapper,
     };
 
-    render_ont(ont, &mut writer, &mapping)?;
+    render_ont(ont, &mut writer, mapping, layout)?;
 
     Ok(())
 }
@@ -45,11 +62,8 @@ pub fn write(
 ///
 /// `key` is the attribute name to use.
 fn iri_maybe(elem: &mut BytesStart, key: &str, iri: &Option<IRI>) {
-    match iri {
-        Some(iri) => {
-            elem.push_attribute((key, &(*iri)[..]));
-        }
-        None => {}
+    if let Some(iri) = iri {
+        elem.push_attribute((key, &(*iri)[..]));
     }
 }
 
@@ -61,23 +75,23 @@ where
 }
 
 /// Add an IRI or AbbreviatedIRI attribute to elem
-fn iri_or_curie<'a>(mapping: &'a PrefixMapping, elem: &mut BytesStart, iri: &str) {
+fn iri_or_curie(mapping: &PrefixMapping, elem: &mut BytesStart, iri: &str) {
     match mapping.shrink_iri(&(*iri)[..]) {
         Ok(curie) => {
             let curie = format!("{}", curie);
             elem.push_attribute(("abbreviatedIRI", &curie[..]));
         }
-        Err(_) => elem.push_attribute(("IRI", &iri[..])),
+        Err(_) => elem.push_attribute(("IRI", iri)),
     }
 }
 
 /// Write a tag with an IRI attribute.
-fn with_iri<'a, I, W>(
+fn with_iri<I, W>(
     w: &mut Writer<W>,
-    mapping: &'a PrefixMapping,
+    mapping: &PrefixMapping,
     tag: &[u8],
     into_iri: I,
-) -> Result<(), Error>
+) -> Result<(), HornedError>
 where
     I: Into<IRI>,
     W: StdWrite,
@@ -150,9 +164,14 @@ fn tag_for_kind(axk: AxiomKind) -> &'static [u8] {
 /// does not a `Declaration` tag, just the internal `Class` tag.
 trait Render<'a, W: StdWrite> {
     /// Render a entity to Write
-    fn render(&self, w: &mut Writer<W>, mapping: &'a PrefixMapping) -> Result<(), Error>;
+    fn render(&self, w: &mut Writer<W>, mapping: &'a PrefixMapping) -> Result<(), HornedError>;
 
-    fn within(&self, w: &mut Writer<W>, m: &'a PrefixMapping, tag: &[u8]) -> Result<(), Error> {
+    fn within(
+        &self,
+        w: &mut Writer<W>,
+        m: &'a PrefixMapping,
+        tag: &[u8],
+    ) -> Result<(), HornedError> {
         let open = BytesStart::borrowed(tag, tag.len());
         w.write_event(Event::Start(open))?;
 
@@ -168,7 +187,7 @@ trait Render<'a, W: StdWrite> {
         w: &mut Writer<W>,
         m: &'a PrefixMapping,
         open: BytesStart,
-    ) -> Result<(), Error> {
+    ) -> Result<(), HornedError> {
         let clone = open.clone();
         w.write_event(Event::Start(clone))?;
 
@@ -187,7 +206,7 @@ macro_rules! render {
 
         impl <'a, W:StdWrite> Render<'a, W> for $type {
             fn render(& $self, $write:&mut Writer<W>, $map: &'a PrefixMapping)
-                      -> Result<(),Error>
+                      -> Result<(),HornedError>
                 where W: StdWrite
                 $body
         }
@@ -210,29 +229,86 @@ macro_rules! content0 {
     };
 }
 
-fn render_ont<W>(o: &AxiomMappedOntology, w: &mut Writer<W>, m: &PrefixMapping) -> Result<(), Error>
+fn render_comments<W: StdWrite>(w: &mut Writer<W>, comments: &[String]) -> Result<(), HornedError> {
+    for c in comments {
+        w.write_event(Event::Comment(BytesText::from_escaped_str(&c[..])))?;
+    }
+    Ok(())
+}
+
+/// Render the prefixes of `m`, those in `layout` first, and then
+/// the rest sorted, so that the output is deterministic.
+fn render_prefixes<W: StdWrite>(
+    w: &mut Writer<W>,
+    m: &PrefixMapping,
+    layout: &Layout,
+) -> Result<(), HornedError> {
+    let mut mappings: Vec<_> = m.mappings().collect();
+    mappings.sort();
+    let position = |pre: &(&String, &String)| {
+        layout
+            .prefixes
+            .iter()
+            .position(|(p, i)| p == pre.0 && i == pre.1)
+            .unwrap_or(usize::MAX)
+    };
+    mappings.sort_by_key(position);
+
+    for pre in mappings {
+        let mut prefix = BytesStart::owned_name("Prefix");
+        prefix.push_attribute(("name", &pre.0[..]));
+        prefix.push_attribute(("IRI", &pre.1[..]));
+        w.write_event(Event::Empty(prefix))?;
+    }
+    Ok(())
+}
+
+fn render_ont<W>(
+    o: &AxiomMappedOntology,
+    w: &mut Writer<W>,
+    m: &PrefixMapping,
+    layout: &Layout,
+) -> Result<(), HornedError>
 where
     W: StdWrite,
 {
     w.write_event(Event::Decl(BytesDecl::new(&b"1.0"[..], None, None)))?;
+    render_comments(w, &layout.prolog)?;
 
     let mut elem = BytesStart::owned_name("Ontology");
     elem.push_attribute((b"xmlns" as &[u8], OWL.iri_b()));
     iri_maybe(&mut elem, "ontologyIRI", &o.id().iri);
-    iri_maybe(&mut elem, "versionIRI", &o.id().viri);
+    iri_maybe(&mut elem, "versionIRI", &o.id().version_iri().cloned());
 
     w.write_event(Event::Start(elem))?;
 
     let elem = BytesEnd::owned(b"Ontology".to_vec());
 
-    m.render(w, m)?;
+    render_prefixes(w, m, layout)?;
+    render_comments(w, &layout.header)?;
 
     for axk in AxiomKind::all_kinds() {
-        for ax in o.i().annotated_axiom(axk) {
+        let mut axioms: Vec<&AnnotatedAxiom> = o.i().annotated_axiom(axk).collect();
+        if axk == AxiomKind::OntologyAnnotation {
+            // Stable, so that others stay in sorted order
+            axioms.sort_by_key(|ax| match &ax.axiom {
+                Axiom::OntologyAnnotation(oa) => layout
+                    .annotations
+                    .iter()
+                    .position(|ann| *ann == oa.0)
+                    .unwrap_or(usize::MAX),
+                _ => usize::MAX,
+            });
+        }
+        for ax in axioms {
+            if let Some(comments) = layout.comments.get(ax) {
+                render_comments(w, comments)?;
+            }
             ax.render(w, m)?;
         }
     }
 
+    render_comments(w, &layout.footer)?;
     w.write_event(Event::End(elem))?;
 
     Ok(())
@@ -240,7 +316,7 @@ where
 
 // Render Impl for container and collection types
 impl<'a, T: Render<'a, W>, W: StdWrite> Render<'a, W> for BTreeSet<T> {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error> {
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError> {
         for item in self.iter() {
             item.render(w, m)?;
         }
@@ -250,7 +326,7 @@ impl<'a, T: Render<'a, W>, W: StdWrite> Render<'a, W> for BTreeSet<T> {
 }
 
 impl<'a, O: Render<'a, W>, W: StdWrite> Render<'a, W> for Vec<O> {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error>
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError>
     where
         W: StdWrite,
     {
@@ -263,7 +339,7 @@ impl<'a, O: Render<'a, W>, W: StdWrite> Render<'a, W> for Vec<O> {
 }
 
 impl<'a, T: Render<'a, W>, W: StdWrite> Render<'a, W> for Box<T> {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error> {
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError> {
         (**self).render(w, m)?;
 
         Ok(())
@@ -271,17 +347,17 @@ impl<'a, T: Render<'a, W>, W: StdWrite> Render<'a, W> for Box<T> {
 }
 
 impl<'a, A: Render<'a, W>, W: StdWrite> Render<'a, W> for (&'a A,) {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error> {
-        (&self.0).render(w, m)?;
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError> {
+        self.0.render(w, m)?;
 
         Ok(())
     }
 }
 
 impl<'a, A: Render<'a, W>, B: Render<'a, W>, W: StdWrite> Render<'a, W> for (&'a A, &'a B) {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error> {
-        (&self.0).render(w, m)?;
-        (&self.1).render(w, m)?;
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError> {
+        self.0.render(w, m)?;
+        self.1.render(w, m)?;
 
         Ok(())
     }
@@ -290,24 +366,10 @@ impl<'a, A: Render<'a, W>, B: Render<'a, W>, W: StdWrite> Render<'a, W> for (&'a
 impl<'a, A: Render<'a, W>, B: Render<'a, W>, C: Render<'a, W>, W: StdWrite> Render<'a, W>
     for (&'a A, &'a B, &'a C)
 {
-    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), Error> {
-        (&self.0).render(w, m)?;
-        (&self.1).render(w, m)?;
-        (&self.2).render(w, m)?;
-
-        Ok(())
-    }
-}
-
-render! {
-    PrefixMapping, self, w, _m,
-    {
-        for pre in self.mappings() {
-            let mut prefix = BytesStart::owned_name("Prefix");
-            prefix.push_attribute(("name", &pre.0[..]));
-            prefix.push_attribute(("IRI", &pre.1[..]));
-            w.write_event(Event::Empty(prefix))?;
-        }
+    fn render(&self, w: &mut Writer<W>, m: &'a PrefixMapping) -> Result<(), HornedError> {
+        self.0.render(w, m)?;
+        self.1.render(w, m)?;
+        self.2.render(w, m)?;
 
         Ok(())
     }
@@ -404,7 +466,14 @@ render! {
 render! {
     NamedIndividual, self, w, m,
     {
-        with_iri(w, m, b"NamedIndividual", self)?;
+        match self.0.node_id() {
+            Some(id) => {
+                let mut bytes_start = BytesStart::borrowed(b"AnonymousIndividual", 19);
+                bytes_start.push_attribute(("nodeID", id));
+                w.write_event(Event::Empty(bytes_start))?;
+            }
+            None => with_iri(w, m, b"NamedIndividual", self)?,
+        }
 
         Ok(())
     }
@@ -662,6 +731,9 @@ render! {
             AnnotationValue::Literal(l) => {
                 l.render(w, m)?;
             }
+            AnnotationValue::AnonymousIndividual(i) => {
+                i.render(w, m)?;
+            }
         }
 
         Ok(())
@@ -851,6 +923,46 @@ mod test {
         assert_eq!(ont.id().iri, ont2.id().iri);
     }
 
+    #[test]
+    fn test_anonymous_ont_rt() {
+        let mut ont = AxiomMappedOntology::default();
+        let build = Build::new();
+        ont.declare(build.class("http://www.example.com/A"));
+        // Not a valid ID, so the version IRI should not be written
+        ont.mut_id().viri = Some(build.iri("http://www.example.com/v1"));
+
+        let mut buf = vec![];
+        write(&mut buf, &ont, None).unwrap();
+        let (ont2, _) = read_ok(&mut buf.as_slice());
+
+        assert_eq!(ont2.id(), &OntologyID::anonymous());
+        assert_eq!(ont2.i().axiom(AxiomKind::DeclareClass).count(), 1);
+    }
+
+    #[test]
+    fn test_anonymous_individual_rt() {
+        let mut ont = AxiomMappedOntology::default();
+        let build = Build::new();
+        ont.insert(ClassAssertion {
+            ce: build.class("http://www.example.com/A").into(),
+            i: build.anonymous_individual("x"),
+        });
+        ont.insert(AnnotationAssertion {
+            subject: build.iri("http://www.example.com/A"),
+            ann: Annotation {
+                ap: build.annotation_property("http://www.example.com/p"),
+                av: build.anonymous_individual("y").into(),
+            },
+        });
+
+        let mut buf = vec![];
+        write(&mut buf, &ont, None).unwrap();
+        assert!(String::from_utf8_lossy(&buf).contains("<AnonymousIndividual nodeID=\"x\"/>"));
+
+        let (ont2, _) = read_ok(&mut buf.as_slice());
+        assert_eq!(ont.i().iter().collect::<Vec<_>>(), ont2.i().iter().collect::<Vec<_>>());
+    }
+
     fn roundtrip(
         ont: &str,
     ) -> (
@@ -875,7 +987,7 @@ mod test {
         let (ont_round, prefix_round) = read_ok(&mut BufReader::new(&file));
         temp_file.release();
 
-        return (ont_orig, prefix_orig, ont_round, prefix_round);
+        (ont_orig, prefix_orig, ont_round, prefix_round)
     }
 
     fn assert_round(
@@ -896,7 +1008,26 @@ mod test {
 
             assert_eq!(prefix_orig_map, prefix_round_map);
         }
-        return (ont_orig, prefix_orig, ont_round, prefix_round);
+        (ont_orig, prefix_orig, ont_round, prefix_round)
+    }
+
+    #[test]
+    fn deterministic() {
+        let (ont, mapping) = read_ok(&mut include_str!("../../ont/owl-xml/family.owx").as_bytes());
+        let mut first = Vec::new();
+        write(&mut first, &ont, Some(&mapping)).unwrap();
+
+        // Insert the axioms again in a different order
+        let mut axioms: Vec<_> = ont.i().iter().cloned().collect();
+        axioms.reverse();
+        let mut again: crate::ontology::set::SetOntology = axioms.into_iter().collect();
+        *again.mut_id() = ont.id().clone();
+        let again: AxiomMappedOntology = again.into();
+
+        let mut second = Vec::new();
+        write(&mut second, &again, Some(&mapping)).unwrap();
+
+        assert_eq!(first, second);
     }
 
     #[test]
@@ -1171,6 +1302,16 @@ mod test {
         assert_round(include_str!("../../ont/owl-xml/facet-restriction.owx"));
     }
 
+    #[test]
+    fn facet_restriction_complex() {
+        assert_round(include_str!("../../ont/owl-xml/facet-restriction-complex.owx"));
+    }
+
+    #[test]
+    fn facet_restriction_string() {
+        assert_round(include_str!("../../ont/owl-xml/facet-restriction-string.owx"));
+    }
+
     #[test]
     fn data_only() {
         assert_round(include_str!("../../ont/owl-xml/data-only.owx"));
@@ -1352,4 +1493,45 @@ mod test {
     fn family() {
         assert_round(include_str!("../../ont/owl-xml/family.owx"));
     }
+
+    #[test]
+    fn layout_rt() {
+        let original = r#"<?xml version="1.0"?>
+<!-- Maintained by hand -->
+<Ontology xmlns="http://www.w3.org/2002/07/owl#" ontologyIRI="http://www.example.com/o">
+    <Prefix name="o" IRI="http://www.example.com/iri#"/>
+    <Prefix name="dc" IRI="http://purl.org/dc/elements/1.1/"/>
+    <!-- Header -->
+    <Annotation>
+        <AnnotationProperty abbreviatedIRI="dc:title"/>
+        <Literal>Title</Literal>
+    </Annotation>
+    <Annotation>
+        <AnnotationProperty abbreviatedIRI="dc:creator"/>
+        <Literal>Creator</Literal>
+    </Annotation>
+    <!-- Classes -->
+    <Declaration>
+        <Class abbreviatedIRI="o:A"/>
+    </Declaration>
+    <!-- Trailer -->
+</Ontology>"#;
+
+        let (so, mapping, layout) =
+            read_with_layout(&mut original.as_bytes(), &Build::new()).unwrap();
+        assert_eq!(layout.prefixes[0].0, "o");
+        assert_eq!(layout.prolog, vec![" Maintained by hand ".to_string()]);
+
+        let amo: AxiomMappedOntology = so.into();
+        let mut w = vec![];
+        write_with_layout(&mut w, &amo, Some(&mapping), &layout).unwrap();
+        assert_eq!(String::from_utf8(w).unwrap(), original);
+
+        // Without the layout, prefixes and annotations are sorted
+        let mut w = vec![];
+        write(&mut w, &amo, Some(&mapping)).unwrap();
+        let sorted = String::from_utf8(w).unwrap();
+        assert!(sorted.find("dc:creator") < sorted.find("dc:title"));
+        assert!(!sorted.contains("<!--"));
+    }
 }
@@ -1 +1,39 @@
+//! RDF/XML and N-Triples syntaxes
+//!
+//! Unlike the OWL/XML reader, these readers do not return a `Layout`:
+//! writing an ontology which was read from RDF/XML, Turtle or
+//! N-Triples loses its prefixes, the order of its annotations and
+//! its comments.
+use super::{ParserOutput, SyntaxParser};
+use crate::model::Build;
+
+use crate::error::HornedError;
+
+use std::io::BufRead;
+
 pub mod reader;
+pub mod triple;
+pub mod writer;
+
+/// The `SyntaxParser` for RDF/XML.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XMLParser;
+
+impl SyntaxParser for XMLParser {
+    fn parse(&self, mut bufread: &mut dyn BufRead, b: &Build) -> Result<ParserOutput, HornedError> {
+        Ok(reader::read_with_build(&mut bufread, b)?.into())
+    }
+}
+
+/// The `SyntaxParser` for N-Triples.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NTParser;
+
+impl SyntaxParser for NTParser {
+    fn parse(&self, bufread: &mut dyn BufRead, b: &Build) -> Result<ParserOutput, HornedError> {
+        let ts = sophia::parser::nt::parse_bufread(bufread);
+        Ok(reader::OntologyParser::from_triple_source(b, ts)?
+            .parse()?
+            .into())
+    }
+}
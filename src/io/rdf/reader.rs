@@ -1,3 +1,13 @@
+//! Parsing of RDF triples into an ontology
+//!
+//! Blank nodes which are part of the OWL structure, such as
+//! restrictions, lists and reified axioms, are parsed into that
+//! structure. Any other blank node is an anonymous individual, and
+//! becomes the `NamedIndividual` `_:id` for its node ID `id` (see
+//! `IRI::node_id`). The [`writer`](../writer/index.html) labels its
+//! own blank nodes `genid-b0`, `genid-b1` and so on, so a node ID of
+//! this form is read as `agenid-b0`, `agenid-b1` and so on; this way
+//! parsing, writing and parsing again gives the same ontology.
 use Term::*;
 
 use crate::{model::Literal, ontology::axiom_mapped::AxiomMappedOntology};
@@ -17,11 +27,13 @@ use crate::{ontology::
                     update_or_insert_logically_equal_axiom,
                 },
             },
-            vocab::RDFS as VRDFS, resolve::strict_resolve_iri
+            vocab::RDFS as VRDFS,
 };
+#[cfg(feature = "remote")]
+use crate::resolve::strict_resolve_iri;
 
 use enum_meta::Meta;
-use failure::Error;
+use crate::error::{HornedError, ParserError};
 
 use sophia_api::term::TTerm;
 
@@ -29,12 +41,16 @@ use sophia::term::blank_node::BlankNode;
 use sophia::term::iri::Iri;
 use sophia::term::literal::Literal as SpLiteral;
 use sophia::term::variable::Variable;
+use sophia::parser::rio_common::StrictRioSource;
 use sophia::triple::stream::TripleSource;
 
+use rio_api::parser::TriplesParser;
+
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::io::BufRead;
+#[cfg(feature = "remote")]
 use std::io::Cursor;
 use std::rc::Rc;
 
@@ -90,7 +106,7 @@ impl Ord for Term {
             (RDFS(s), RDFS(o)) => s.cmp(o),
             (FacetTerm(s), FacetTerm(o)) => s.cmp(o),
             (Iri(s), Iri(o)) => s.to_string().cmp(&o.to_string()),
-            (BNode(s), BNode(o)) => (*s).cmp(&(*o)),
+            (BNode(s), BNode(o)) => (*s).cmp(o),
             (Literal(s), Literal(o)) => (s.txt()).cmp(o.txt()),
             (Variable(s), Variable(o)) => s.cmp(o),
             _ => self.ord().cmp(&other.ord()),
@@ -155,45 +171,6 @@ impl Convert for sophia::term::iri::Iri<&str> {
     }
 }
 
-trait TryBuild<N: From<IRI>> {
-    fn to_some_iri(&self, b: &Build) -> Option<IRI>;
-
-    fn to_iri_maybe(&self, b: &Build) -> Result<IRI, Error> {
-        match self.to_some_iri(b) {
-            Some(iri) => Ok(iri),
-            None => todo!("Fix this"),
-        }
-    }
-
-    fn try_build(&self, b: &Build) -> Result<N, Error> {
-        Ok(self.to_iri_maybe(b)?.into())
-    }
-}
-
-impl<N: From<IRI>> TryBuild<N> for Option<SpIri> {
-    fn to_some_iri(&self, b: &Build) -> Option<IRI> {
-        self.as_ref().map(|i| i.to_iri(b))
-    }
-}
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-enum OrTerm {
-    Term(Term),
-    ClassExpression(ClassExpression),
-}
-
-impl From<ClassExpression> for OrTerm {
-    fn from(c: ClassExpression) -> OrTerm {
-        OrTerm::ClassExpression(c)
-    }
-}
-
-impl From<Term> for OrTerm {
-    fn from(t: Term) -> OrTerm {
-        OrTerm::Term(t)
-    }
-}
-
 fn vocab_to_term<'a, V: WithIRI<'a>>(v: &V) -> SpTerm {
     // unwrap should be safe for all known WithIRIs
     sophia::term::Term::new_iri(Rc::from(v.iri_str())).unwrap()
@@ -237,6 +214,45 @@ fn to_term(t: &SpTerm, m: &HashMap<SpTerm, Term>, b: &Build) -> Term {
     }
 }
 
+/// Returns true if `t` shows that its subject is a blank node which
+/// is part of the OWL structure, rather than an individual.
+fn is_structural(t: &[Term; 3]) -> bool {
+    matches!(
+        t,
+        [BNode(_), RDF(VRDF::Type), OWL(_) | RDFS(_)]
+            | [BNode(_), RDF(VRDF::First) | RDF(VRDF::Rest), _]
+            | [BNode(_), FacetTerm(_), _]
+    ) || matches!(t, [BNode(_), OWL(p), _] if *p != VOWL::SameAs && *p != VOWL::DifferentFrom)
+}
+
+/// Replace each blank node which is an anonymous individual with its
+/// IRI.
+fn anonymous_individuals(triple: &mut [[Term; 3]], b: &Build) {
+    let structural: BTreeSet<SpBlankNode> = triple
+        .iter()
+        .filter(|t| is_structural(t))
+        .filter_map(|t| match &t[0] {
+            BNode(id) => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+
+    for t in triple.iter_mut() {
+        for term in t.iter_mut() {
+            if let BNode(id) = term {
+                if !structural.contains(id) {
+                    let id = id.value().to_string();
+                    *term = Iri(if super::writer::is_generated(&id) {
+                        b.anonymous_individual(format!("a{}", id)).0
+                    } else {
+                        b.anonymous_individual(id).0
+                    });
+                }
+            }
+        }
+    }
+}
+
 macro_rules! d {
     () => {
         Default::default()
@@ -280,6 +296,13 @@ pub struct IncompleteParse {
     pub ann_map: HashMap<[Term;3], BTreeSet<Annotation>>,
 }
 
+impl IncompleteParse {
+    /// Return the number of triples which could not be parsed.
+    pub fn unparsed(&self) -> usize {
+        self.simple.len() + self.bnode.iter().map(Vec::len).sum::<usize>()
+    }
+}
+
 pub struct OntologyParser<'a> {
     o: RDFOntology,
     b: &'a Build,
@@ -293,12 +316,14 @@ pub struct OntologyParser<'a> {
     object_property_expression: HashMap<SpBlankNode, ObjectPropertyExpression>,
     data_range: HashMap<SpBlankNode, DataRange>,
     ann_map: HashMap<[Term; 3], BTreeSet<Annotation>>,
+    anonymous: Option<SpBlankNode>,
     state: OntologyParserState,
-    error: Result<(),Error>,
+    error: Result<(),HornedError>,
 }
 
+#[allow(clippy::wrong_self_convention)]
 impl<'a> OntologyParser<'a> {
-    pub fn new(b: &'a Build, triple: Vec<[SpTerm; 3]>) -> OntologyParser {
+    pub fn new(b: &'a Build, triple: Vec<[SpTerm; 3]>) -> OntologyParser<'a> {
         OntologyParser {
             o: d!(),
             b,
@@ -311,23 +336,47 @@ impl<'a> OntologyParser<'a> {
             object_property_expression: d!(),
             data_range: d!(),
             ann_map: d!(),
+            anonymous: None,
             state: OntologyParserState::New,
             error: Ok(()),
         }
     }
 
     pub fn from_bufread<'b, R: BufRead>(b: &'a Build, bufread: &'b mut R)
-                                        -> OntologyParser<'a> {
+                                        -> Result<OntologyParser<'a>, HornedError> {
         let triple_iter = sophia::parser::xml::parse_bufread(bufread);
-        let triple_result: Result<Vec<_>, _> = triple_iter.collect_triples();
-        let triple_v: Vec<[SpTerm; 3]> = triple_result.unwrap();
-        OntologyParser::new(b, triple_v)
-    }
-
-
-    pub fn from_doc_iri(b: &'a Build, iri: &IRI) -> OntologyParser<'a> {
+        let triple_v: Vec<[SpTerm; 3]> = triple_iter
+            .collect_triples()
+            .map_err(|e| ParserError::RDF(e.to_string()))?;
+        Ok(OntologyParser::new(b, triple_v))
+    }
+
+    /// Create a parser from any sophia `TripleSource`.
+    pub fn from_triple_source<TS>(b: &'a Build, ts: TS) -> Result<OntologyParser<'a>, HornedError>
+    where
+        TS: TripleSource,
+        TS::Error: Send + Sync,
+    {
+        let triple_v: Vec<[SpTerm; 3]> = ts
+            .collect_triples()
+            .map_err(|e| ParserError::RDF(e.to_string()))?;
+        Ok(OntologyParser::new(b, triple_v))
+    }
+
+    /// Create a parser from any rio `TriplesParser`, such as the
+    /// turtle or n-triples parsers from `rio_turtle`.
+    pub fn from_rio_parser<P>(b: &'a Build, parser: P) -> Result<OntologyParser<'a>, HornedError>
+    where
+        P: TriplesParser,
+        P::Error: Send + Sync + 'static,
+    {
+        OntologyParser::from_triple_source(b, StrictRioSource::Parser(parser))
+    }
+
+    #[cfg(feature = "remote")]
+    pub fn from_doc_iri(b: &'a Build, iri: &IRI) -> Result<OntologyParser<'a>, HornedError> {
         OntologyParser::from_bufread(
-            b, &mut Cursor::new(strict_resolve_iri(iri))
+            b, &mut Cursor::new(strict_resolve_iri(iri)?)
         )
     }
 
@@ -343,7 +392,7 @@ impl<'a> OntologyParser<'a> {
         for t in &triple {
             match t {
                 [BNode(id), _, _] => {
-                    let v = bnode.entry(id.clone()).or_insert_with(Vec::new);
+                    let v = bnode.entry(id.clone()).or_default();
                     v.push(t.clone())
                 }
                 _ => {
@@ -357,7 +406,7 @@ impl<'a> OntologyParser<'a> {
         let mut extended = false;
 
         for (k, v) in std::mem::take(&mut self.bnode) {
-            let _ = match v.as_slice() {
+            match v.as_slice() {
                 [[_, Term::RDF(VRDF::First), val],
                  [_, Term::RDF(VRDF::Rest), Term::BNode(bnode_id)],
                  // Some sequences have a Type List, some do not
@@ -378,14 +427,14 @@ impl<'a> OntologyParser<'a> {
             };
         }
 
-        if extended && self.bnode.len() > 0 {
+        if extended && !self.bnode.is_empty() {
             self.stitch_seqs_1()
         }
     }
 
     fn stitch_seqs(&mut self) {
         for (k, v) in std::mem::take(&mut self.bnode) {
-            let _ = match v.as_slice() {
+            match v.as_slice() {
                 [[_, Term::RDF(VRDF::First), val],
                  [_, Term::RDF(VRDF::Rest), Term::RDF(VRDF::Nil)],
                  // Lists may or may not have a "list" RDF type
@@ -410,7 +459,7 @@ impl<'a> OntologyParser<'a> {
     fn resolve_imports(&mut self) {
         for t in std::mem::take(&mut self.simple) {
             match t {
-                [Term::Iri(_), Term::OWL(VOWL::Imports), Term::Iri(imp)] => {
+                [_, Term::OWL(VOWL::Imports), Term::Iri(imp)] => {
                     self.merge(AnnotatedAxiom {
                         axiom: Import(imp).into(),
                         ann: BTreeSet::new(),
@@ -423,6 +472,24 @@ impl<'a> OntologyParser<'a> {
         // Section 3.1.2/table 4 of RDF Graphs
     }
 
+    fn anonymous_header(&mut self) {
+        // An anonymous ontology has a blank node in place of its
+        // IRI. Move its triples back with the simple triples, so
+        // that imports and ontology annotations are found.
+        let header = self.bnode.iter().find_map(|(k, v)| {
+            v.iter()
+                .any(|t| matches!(t, [_, Term::RDF(VRDF::Type), Term::OWL(VOWL::Ontology)]))
+                .then(|| k.clone())
+        });
+
+        if let Some(k) = header {
+            if let Some(v) = self.bnode.remove(&k) {
+                self.simple.extend(v);
+            }
+            self.anonymous = Some(k);
+        }
+    }
+
     fn headers(&mut self) {
         //Section 3.1.2/table 4
         //   *:x rdf:type owl:Ontology .
@@ -435,6 +502,8 @@ impl<'a> OntologyParser<'a> {
                 [Term::Iri(s), Term::RDF(VRDF::Type), Term::OWL(VOWL::Ontology)] => {
                     iri = Some(s.clone());
                 }
+                [Term::BNode(s), Term::RDF(VRDF::Type), Term::OWL(VOWL::Ontology)]
+                    if self.anonymous.as_ref() == Some(&s) => {}
                 [Term::Iri(s), Term::OWL(VOWL::VersionIRI), Term::Iri(ob)]
                     if iri.as_ref() == Some(&s) =>
                 {
@@ -455,12 +524,12 @@ impl<'a> OntologyParser<'a> {
     fn parse_annotations(&self, triples: &[[Term; 3]]) -> BTreeSet<Annotation> {
         let mut ann = BTreeSet::default();
         for a in triples {
-            ann.insert(self.annotation(a));
+            ann.extend(self.annotation(a));
         }
         ann
     }
 
-    fn annotation(&self, t: &[Term; 3]) -> Annotation {
+    fn annotation(&self, t: &[Term; 3]) -> Option<Annotation> {
         match t {
             // We assume that anything passed to here is an
             // annotation built in type
@@ -472,21 +541,18 @@ impl<'a> OntologyParser<'a> {
                 let iri = self.b.iri(owl.iri_s());
                 self.annotation(&[s.clone(), Term::Iri(iri), b.clone()])
             }
-            [_, Iri(p), ob @ Term::Literal(_)] => Annotation {
+            [_, Iri(p), ob @ Term::Literal(_)] => Some(Annotation {
                 ap: AnnotationProperty(p.clone()),
-                av: self.to_literal(ob).unwrap().into(),
-            },
+                av: self.to_literal(ob)?.into(),
+            }),
             [_, Iri(p), Iri(ob)] => {
-                // IRI annotation value
-                Annotation {
+                // IRI annotation value, or an anonymous individual
+                Some(Annotation {
                     ap: AnnotationProperty(p.clone()),
-                    av: ob.clone().into(),
-                }
-            }
-            _ => {
-                dbg!(t);
-                todo!()
+                    av: NamedIndividual(ob.clone()).into(),
+                })
             }
+            _ => None,
         }
     }
 
@@ -543,7 +609,7 @@ impl<'a> OntologyParser<'a> {
                 let ann = self
                     .ann_map
                     .remove(&triple)
-                    .unwrap_or_else(|| BTreeSet::new());
+                    .unwrap_or_default();
                 let ne: NamedEntity = entity;
                 self.merge(AnnotatedAxiom {
                     axiom: ne.into(),
@@ -672,7 +738,7 @@ impl<'a> OntologyParser<'a> {
                     .insert(this_bnode.clone(), ope);
             }
 
-            if new_triple.len() > 0 {
+            if !new_triple.is_empty() {
                 self.bnode.insert(this_bnode, new_triple);
             }
         }
@@ -723,7 +789,7 @@ impl<'a> OntologyParser<'a> {
             .bnode_seq
             .remove(bnodeid)
             .as_ref()?
-            .into_iter()
+            .iter()
             .map(|tce| self.to_ce(tce))
             .collect();
 
@@ -736,7 +802,7 @@ impl<'a> OntologyParser<'a> {
             .bnode_seq
             .remove(bnodeid)
             .as_ref()?
-            .into_iter()
+            .iter()
             .map(|t| self.to_iri(t).map(|iri| NamedIndividual(iri.clone())))
             .collect();
 
@@ -748,7 +814,7 @@ impl<'a> OntologyParser<'a> {
             .bnode_seq
             .remove(bnodeid)
             .as_ref()?
-            .into_iter()
+            .iter()
             .map(|t| self.to_dr(t))
             .collect();
 
@@ -761,7 +827,7 @@ impl<'a> OntologyParser<'a> {
             .bnode_seq
             .remove(bnodeid)
             .as_ref()?
-            .into_iter()
+            .iter()
             .map(|t| self.to_literal(t))
             .collect();
 
@@ -775,7 +841,7 @@ impl<'a> OntologyParser<'a> {
                 Some(dt.into())
             }
             Term::BNode(id) => self.data_range.remove(id),
-            _ => todo!(),
+            _ => None,
         }
     }
 
@@ -812,6 +878,9 @@ impl<'a> OntologyParser<'a> {
     }
 
     fn find_declaration_kind(&mut self, iri: &IRI) -> Option<NamedEntityKind> {
+        if iri.node_id().is_some() {
+            return Some(NamedEntityKind::NamedIndividual);
+        }
         self.o.j().declaration_kind(iri)
     }
 
@@ -857,7 +926,7 @@ impl<'a> OntologyParser<'a> {
                                     dr: self.to_dr(ce_or_dr)?
                                 }
                             },
-                            _ => panic!("Unexpected Property Kind")
+                            _ => return None
                         }
                     }
                 },
@@ -869,7 +938,7 @@ impl<'a> OntologyParser<'a> {
                             PropertyExpression::ObjectPropertyExpression(ope) => {
                                 ClassExpression::ObjectHasValue {
                                     ope,
-                                    i: NamedIndividual(self.to_iri(val)?).into()
+                                    i: NamedIndividual(self.to_iri(val)?)
                                 }
                             },
                             PropertyExpression::DataProperty(dp) => {
@@ -878,7 +947,7 @@ impl<'a> OntologyParser<'a> {
                                     l: self.to_literal(val)?
                                 }
                             }
-                            _ => panic!("Unexpected Property kind"),
+                            _ => return None,
                         }
                     }
                 },
@@ -899,7 +968,7 @@ impl<'a> OntologyParser<'a> {
                                     dr: self.to_dr(ce_or_dr)?
                                 }
                             },
-                            _ => panic!("Unexpected Property Kind")
+                            _ => return None
                         }
                     }
                 },
@@ -933,7 +1002,7 @@ impl<'a> OntologyParser<'a> {
                  [_, Term::RDF(VRDF::Type), Term::OWL(VOWL::Class)]] => {
                     some!{
                         ClassExpression::ObjectComplementOf(
-                            self.to_ce(&tce)?.into()
+                            self.to_ce(tce)?.into()
                         )
                     }
                 },
@@ -1073,7 +1142,7 @@ impl<'a> OntologyParser<'a> {
                                     from: i.into(),
                                     to: self.to_literal(target)?,
                                 }.into(),
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1100,9 +1169,7 @@ impl<'a> OntologyParser<'a> {
         }
 
         for triple in std::mem::take(&mut self.simple).into_iter().chain(
-            std::mem::take(&mut self.bnode)
-                .into_iter()
-                .map(|(_k, v)| v)
+            std::mem::take(&mut self.bnode).into_values()
                 .flatten(),
         ) {
             let axiom: Option<Axiom> = match &triple {
@@ -1129,11 +1196,11 @@ impl<'a> OntologyParser<'a> {
                             }
                             NamedEntityKind::Datatype => {
                                 DatatypeDefinition{
-                                    kind: Datatype(a.clone()).into(),
+                                    kind: Datatype(a.clone()),
                                     range: self.to_dr(b)?,
                                 }.into()
                             }
-                            _=> todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1141,7 +1208,7 @@ impl<'a> OntologyParser<'a> {
                     some! {
                         {
                             let v:Vec<Option<PropertyExpression>> = self.bnode_seq
-                                .remove(&bnodeid)?
+                                .remove(bnodeid)?
                                 .into_iter()
                                 .map(|pr| self.find_property_kind(&pr))
                                 .collect();
@@ -1163,10 +1230,10 @@ impl<'a> OntologyParser<'a> {
                     }
                 }
                 [Term::Iri(p), Term::OWL(VOWL::InverseOf), Term::Iri(r)] => {
-                    some! {
+                    Some(
                         InverseObjectProperties (ObjectProperty(p.clone()),
                                                  ObjectProperty(r.clone())).into()
-                    }
+                    )
                 }
                 [pr, Term::RDF(VRDF::Type), Term::OWL(VOWL::TransitiveProperty)] => {
                     some! {
@@ -1182,7 +1249,7 @@ impl<'a> OntologyParser<'a> {
                             PropertyExpression::DataProperty(dp) => {
                                 FunctionalDataProperty(dp).into()
                             },
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1193,7 +1260,7 @@ impl<'a> OntologyParser<'a> {
                                 AsymmetricObjectProperty(ope).into()
                             },
 
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1204,7 +1271,7 @@ impl<'a> OntologyParser<'a> {
                                 SymmetricObjectProperty(ope).into()
                             },
 
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1215,7 +1282,7 @@ impl<'a> OntologyParser<'a> {
                                 ReflexiveObjectProperty(ope).into()
                             },
 
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1226,7 +1293,7 @@ impl<'a> OntologyParser<'a> {
                                 IrreflexiveObjectProperty(ope).into()
                             },
 
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1237,7 +1304,7 @@ impl<'a> OntologyParser<'a> {
                                 InverseFunctionalObjectProperty(ope).into()
                             },
 
-                            _ => todo!()
+                            _ => return None
                         }
                     }
                 }
@@ -1302,7 +1369,7 @@ impl<'a> OntologyParser<'a> {
                             }
                             .into(),
                             PropertyExpression::AnnotationProperty(ap) => AnnotationPropertyDomain {
-                                ap: ap,
+                                ap,
                                 iri: self.to_iri(t)?,
                             }
                             .into(),
@@ -1322,7 +1389,7 @@ impl<'a> OntologyParser<'a> {
                         }
                         .into(),
                         PropertyExpression::AnnotationProperty(ap) => AnnotationPropertyRange {
-                            ap: ap,
+                            ap,
                             iri: self.to_iri(t)?,
                         }
                         .into(),
@@ -1338,7 +1405,7 @@ impl<'a> OntologyParser<'a> {
                             vec![dp, self.to_dp(s)?]
                         )
                             .into(),
-                        _ => todo!()
+                        _ => return None
                     }
                 },
                 [r, Term::OWL(VOWL::EquivalentProperty), s] => some! {
@@ -1351,24 +1418,25 @@ impl<'a> OntologyParser<'a> {
                             vec![dp, self.to_dp(s)?]
                         )
                         .into(),
-                        _ => todo!()
+                        _ => return None
                     }
                 },
-                [Term::Iri(sub), Term::OWL(VOWL::SameAs), Term::Iri(obj)] => some! {
+                [Term::Iri(sub), Term::OWL(VOWL::SameAs), Term::Iri(obj)] => Some(
                     SameIndividual(vec![NamedIndividual(sub.clone()),
                                         NamedIndividual(obj.clone())]).into()
-                },
-                [Term::Iri(sub), Term::RDF(VRDF::Type), Term::Iri(obj)] => some! {
+                ),
+                [Term::Iri(sub), Term::RDF(VRDF::Type), Term::Iri(obj)] => Some(
                     ClassAssertion {
                         ce: Class(obj.clone()).into(),
-                        i: NamedIndividual(sub.clone()).into()
+                        i: NamedIndividual(sub.clone())
                     }.into()
-                },
-                [Term::Iri(s), Term::Iri(_), _] if self.o.id().iri.as_ref() == Some(&s) => some! {
-                    OntologyAnnotation(
-                        self.annotation(&triple)
-                    ).into()
-                },
+                ),
+                [Term::Iri(s), Term::Iri(_), _] if self.o.id().iri.as_ref() == Some(s) => {
+                    self.annotation(&triple).map(|ann| OntologyAnnotation(ann).into())
+                }
+                [Term::BNode(s), _, _] if self.anonymous.as_ref() == Some(s) => {
+                    self.annotation(&triple).map(|ann| OntologyAnnotation(ann).into())
+                }
 
                 [Term::Iri(sub), Term::Iri(pred), t @ Term::Literal(_)] => some! {
                     match (self.find_declaration_kind(sub)?,
@@ -1376,12 +1444,12 @@ impl<'a> OntologyParser<'a> {
                         (NamedEntityKind::NamedIndividual,
                          NamedEntityKind::DataProperty) => {
                             DataPropertyAssertion {
-                                dp: DataProperty(pred.clone()).into(),
+                                dp: DataProperty(pred.clone()),
                                 from: NamedIndividual(sub.clone()),
                                 to: self.to_literal(t)?
                             }.into()
                         }
-                        _ => todo!()
+                        _ => return None
                     }
                 },
                 [Term::Iri(sub), Term::Iri(pred), Term::Iri(obj)] => some! {
@@ -1397,7 +1465,7 @@ impl<'a> OntologyParser<'a> {
                                 to: NamedIndividual(obj.clone())
                             }.into()
                         }
-                        _ => todo!()
+                        _ => return None
                     }
                 },
                 _ => None,
@@ -1407,7 +1475,7 @@ impl<'a> OntologyParser<'a> {
                 let ann = self
                     .ann_map
                     .remove(&triple)
-                    .unwrap_or_else(|| BTreeSet::new());
+                    .unwrap_or_default();
                 self.merge(AnnotatedAxiom { axiom, ann })
             } else {
                 self.simple.push(triple)
@@ -1417,22 +1485,24 @@ impl<'a> OntologyParser<'a> {
 
     fn simple_annotations(&mut self) {
         for triple in std::mem::take(&mut self.simple) {
-            if let Some(iri) = match &triple {
+            if let Some((iri, annotation)) = match &triple {
                 [Term::Iri(iri), Term::RDFS(rdfs), _] if rdfs.is_builtin() => Some(iri),
                 [Term::Iri(iri), Term::OWL(VOWL::VersionInfo), _] => Some(iri),
-                [Term::Iri(iri), Term::Iri(ap), _] if self.o.j().is_annotation_property(&ap) => {
+                [Term::Iri(iri), Term::Iri(ap), _] if self.o.j().is_annotation_property(ap) => {
                     Some(iri)
                 }
                 _ => None,
-            } {
+            }
+            .and_then(|iri| Some((iri.clone(), self.annotation(&triple)?)))
+            {
                 let ann = self
                     .ann_map
                     .remove(&triple)
-                    .unwrap_or_else(|| BTreeSet::new());
+                    .unwrap_or_default();
                 self.merge(AnnotatedAxiom {
                     axiom: AnnotationAssertion {
-                        subject: iri.clone(),
-                        ann: self.annotation(&triple),
+                        subject: iri,
+                        ann: annotation,
                     }
                     .into(),
                     ann,
@@ -1445,12 +1515,12 @@ impl<'a> OntologyParser<'a> {
 
     /// Parse all imports and add to the Ontology.
     /// Return an error is we are in the wrong state
-    pub fn parse_imports(&mut self) -> Result<(), Error> {
+    pub fn parse_imports(&mut self) -> Result<(), HornedError> {
         match self.state {
             OntologyParserState::New => {
                 // move to our own Terms, with IRIs swapped
                 let m = vocab_lookup();
-                let triple: Vec<[Term; 3]> = std::mem::take(&mut self.triple)
+                let mut triple: Vec<[Term; 3]> = std::mem::take(&mut self.triple)
                     .into_iter()
                     .map(|t| {
                         [
@@ -1460,6 +1530,7 @@ impl<'a> OntologyParser<'a> {
                         ]
                     })
                     .collect();
+                anonymous_individuals(&mut triple, self.b);
 
                 Self::group_triples(triple, &mut self.simple, &mut self.bnode);
 
@@ -1468,6 +1539,8 @@ impl<'a> OntologyParser<'a> {
                     vec.sort();
                 }
 
+                self.anonymous_header();
+
                 self.stitch_seqs();
 
                 // Table 10
@@ -1476,13 +1549,13 @@ impl<'a> OntologyParser<'a> {
                 self.state = OntologyParserState::Imports;
                 Ok(())
             }
-            _ => todo!()
+            _ => Err(ParserError::WrongState("imports have already been parsed").into()),
         }
     }
 
     /// Parse all declarations and add to the ontology.
     /// Error if we are not in the right state
-    pub fn parse_declarations(&mut self) -> Result<(), Error> {
+    pub fn parse_declarations(&mut self) -> Result<(), HornedError> {
         match self.state {
             OntologyParserState::New => {
                 self.parse_imports().and_then(
@@ -1539,9 +1612,7 @@ impl<'a> OntologyParser<'a> {
                 self.state = OntologyParserState::Declarations;
                 Ok(())
             }
-            _ => {
-                todo!();
-            }
+            _ => Err(ParserError::WrongState("declarations have already been parsed").into()),
         }
     }
 
@@ -1550,7 +1621,7 @@ impl<'a> OntologyParser<'a> {
     /// rf is a Vec of references to the import closure. These RDF
     /// ontologies do not need to be completely parsed, but will be
     /// relied on to resolve declarations.
-    pub fn finish_parse(&mut self, _rf: Vec<&RDFOntology>) -> Result<(), Error> {
+    pub fn finish_parse(&mut self, _rf: Vec<&RDFOntology>) -> Result<(), HornedError> {
         // Table 10
         self.simple_annotations();
 
@@ -1569,10 +1640,8 @@ impl<'a> OntologyParser<'a> {
         Ok(())
     }
 
-    pub fn parse(mut self) -> Result<(RDFOntology, IncompleteParse),Error> {
-        if self.error.is_err() {
-            return Err(self.error.unwrap_err());
-        }
+    pub fn parse(mut self) -> Result<(RDFOntology, IncompleteParse),HornedError> {
+        std::mem::replace(&mut self.error, Ok(()))?;
 
         match self.state {
             OntologyParserState::New => {
@@ -1596,16 +1665,16 @@ impl<'a> OntologyParser<'a> {
     }
 
     /// Consume the parser and return an Ontology.
-    pub fn as_ontology(self) -> Result<RDFOntology, Error> {
+    pub fn as_ontology(self) -> Result<RDFOntology, HornedError> {
         self.error.and(Ok(self.o))
     }
 
     /// Consume the parser and return an Ontology and any data
     /// structures that have not been fully parsed
-    pub fn as_ontology_and_incomplete(mut self) -> Result<(RDFOntology, IncompleteParse), Error> {
-        if self.error.is_err() {
-            return Err(self.error.unwrap_err());
-        }
+    pub fn as_ontology_and_incomplete(
+        mut self,
+    ) -> Result<(RDFOntology, IncompleteParse), HornedError> {
+        std::mem::replace(&mut self.error, Ok(()))?;
 
         // Regroup so that they print out nicer
         let mut simple = vec![];
@@ -1630,21 +1699,21 @@ impl<'a> OntologyParser<'a> {
     }
 }
 
-pub fn parser_with_build<'a, 'b, R: BufRead>(
-    bufread: &'a mut R,
+pub fn parser_with_build<'b, R: BufRead>(
+    bufread: &mut R,
     build: &'b Build,
-) -> OntologyParser<'b> {
+) -> Result<OntologyParser<'b>, HornedError> {
     OntologyParser::from_bufread(build, bufread)
 }
 
 pub fn read_with_build<R: BufRead>(
     bufread: &mut R,
     build: &Build,
-) -> Result<(RDFOntology, IncompleteParse), Error> {
-    parser_with_build(bufread, build).parse()
+) -> Result<(RDFOntology, IncompleteParse), HornedError> {
+    parser_with_build(bufread, build)?.parse()
 }
 
-pub fn read<R: BufRead>(bufread: &mut R) -> Result<(RDFOntology, IncompleteParse), Error> {
+pub fn read<R: BufRead>(bufread: &mut R) -> Result<(RDFOntology, IncompleteParse), HornedError> {
     let b = Build::new();
     read_with_build(bufread, &b)
 }
@@ -1672,11 +1741,7 @@ mod test {
         let r = read(bufread);
 
         if let Err(e) = r {
-            panic!(
-                "Expected ontology, get failure: {:?} {:?}",
-                e,
-                e.backtrace()
-            );
+            panic!("Expected ontology, get failure: {:?}", e);
         }
 
         r.unwrap().0
@@ -1722,7 +1787,93 @@ mod test {
         //assert_eq!(rdfmapping, xmlmapping);
     }
 
+    fn slurp_ttlont(testttl: &str) -> std::string::String {
+        let dir_path_buf = PathBuf::from(file!());
+        let dir = dir_path_buf.parent().unwrap().to_string_lossy();
+
+        slurp::read_all_to_string(format!("{}/../../ont/owl-ttl/{}.ttl",
+                                           dir, testttl))
+                .unwrap()
+    }
+
+    fn xmlont(testowl: &str) -> SetOntology {
+        let dir_path_buf = PathBuf::from(file!());
+        let dir = dir_path_buf.parent().unwrap().to_string_lossy();
+        let xmlread = slurp::read_all_to_string(
+            format!("{}/../../ont/owl-xml/{}.owx", dir, testowl)
+        ).unwrap();
+
+        crate::io::owx::reader::test::read_ok(&mut xmlread.as_bytes()).0.into()
+    }
+
+    fn compare_rio(test: &str) {
+        let ttl = slurp_ttlont(test);
+        let b = Build::new();
+        let rio = rio_turtle::TurtleParser::new(ttl.as_bytes(), "").unwrap();
+        let rdfont: SetOntology = OntologyParser::from_rio_parser(&b, rio)
+            .unwrap().parse().unwrap().0.into();
+
+        assert_eq!(rdfont, xmlont(test));
+    }
+
+    fn compare_sophia(test: &str) {
+        let ttl = slurp_ttlont(test);
+        let b = Build::new();
+        let source = sophia::parser::turtle::parse_str(&ttl);
+        let rdfont: SetOntology = OntologyParser::from_triple_source(&b, source)
+            .unwrap().parse().unwrap().0.into();
+
+        assert_eq!(rdfont, xmlont(test));
+    }
+
     #[test]
+    fn rio_class() {
+        compare_rio("class");
+    }
+
+    #[test]
+    fn rio_some() {
+        compare_rio("some");
+    }
+
+    #[test]
+    fn rio_annotation_on_subclass() {
+        compare_rio("annotation-on-subclass");
+    }
+
+    #[test]
+    fn rio_facet_restriction_string() {
+        compare_rio("facet-restriction-string");
+    }
+
+    #[test]
+    fn sophia_class() {
+        compare_sophia("class");
+    }
+
+    #[test]
+    fn sophia_data_has_value() {
+        compare_sophia("data-has-value");
+    }
+
+    #[test]
+    fn rio_error() {
+        let b = Build::new();
+        let rio = rio_turtle::TurtleParser::new("<a> <b>".as_bytes(), "").unwrap();
+        assert!(OntologyParser::from_rio_parser(&b, rio).is_err());
+    }
+
+    #[test]
+    fn rdf_xml_error() {
+        let r = read(&mut "<rdf:RDF></owl:Class>".as_bytes());
+        assert!(matches!(
+            r,
+            Err(HornedError::ParserError(ParserError::RDF(_)))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
     fn read_iri() {
         let dir_path_buf = PathBuf::from(file!());
         let dir = dir_path_buf.parent().unwrap()
@@ -1734,9 +1885,8 @@ mod test {
             format!("file://{}/ont/owl-rdf/and.owl", cdir.to_string_lossy())
         );
 
-        let op = OntologyParser::from_doc_iri(&b, &i);
+        let op = OntologyParser::from_doc_iri(&b, &i).unwrap();
         let _o = op.parse().unwrap();
-        assert!(true);
     }
 
     #[test]
@@ -1951,7 +2101,7 @@ mod test {
     #[test]
     fn import_with_partial_parse(){
         let b = Build::new();
-        let mut p = parser_with_build(&mut slurp_rdfont("import").as_bytes(), &b);
+        let mut p = parser_with_build(&mut slurp_rdfont("import").as_bytes(), &b).unwrap();
         let _ = p.parse_imports();
 
         let rdfont = p.as_ontology().unwrap();
@@ -1963,7 +2113,7 @@ mod test {
     #[test]
     fn declaration_with_partial_parse(){
         let b = Build::new();
-        let mut p = parser_with_build(&mut slurp_rdfont("class").as_bytes(), &b);
+        let mut p = parser_with_build(&mut slurp_rdfont("class").as_bytes(), &b).unwrap();
         let _ = p.parse_declarations();
 
         let rdfont = p.as_ontology().unwrap();
@@ -2052,6 +2202,11 @@ mod test {
         compare("facet-restriction-complex");
     }
 
+    #[test]
+    fn facet_restriction_string() {
+        compare("facet-restriction-string");
+    }
+
     #[test]
     fn data_only() {
         compare("data-only");
@@ -2206,4 +2361,57 @@ mod test {
     // fn family() {
     //     compare("family");
     // }
+
+    #[test]
+    fn anonymous_ontology() {
+        let b = Build::new();
+        let ttl = "@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+_:o a owl:Ontology ; rdfs:comment \"Anonymous\" ; owl:imports <http://www.example.com/i> .
+<http://www.example.com/A> a owl:Class .";
+        let source = sophia::parser::turtle::parse_str(ttl);
+        let (o, inc) = OntologyParser::from_triple_source(&b, source)
+            .unwrap().parse().unwrap();
+        let amo: AxiomMappedOntology = o.into();
+
+        assert!(amo.id().is_anonymous());
+        assert!(inc.simple.is_empty() && inc.bnode.is_empty());
+        assert_eq!(amo.i().import().count(), 1);
+        assert_eq!(amo.i().axiom(AxiomKind::OntologyAnnotation).count(), 1);
+    }
+
+    #[test]
+    fn anonymous_individual() {
+        let b = Build::new();
+        let ttl = "@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix : <http://www.example.com/> .
+<http://www.example.com/o> a owl:Ontology .
+:A a owl:Class .
+:r a owl:ObjectProperty .
+:i a owl:NamedIndividual ; :r _:x .
+_:x a :A ; :r _:genid-b1 .
+:B a owl:Class ; owl:equivalentClass [ a owl:Class ; owl:oneOf ( _:x :i ) ] .";
+        let source = sophia::parser::turtle::parse_str(ttl);
+        let (o, inc) = OntologyParser::from_triple_source(&b, source)
+            .unwrap().parse().unwrap();
+        assert!(inc.simple.is_empty() && inc.bnode.is_empty(), "{:?}", inc);
+
+        let amo: AxiomMappedOntology = o.into();
+        let x = b.anonymous_individual("x");
+        assert!(amo.i().class_assertion().any(|ca| ca.i == x));
+        assert!(amo.i().object_property_assertion().any(|opa| opa.from == x &&
+                                                       opa.to == b.anonymous_individual("agenid-b1")));
+        assert_eq!(amo.i().object_property_assertion().count(), 2);
+        assert_eq!(amo.i().equivalent_class().count(), 1);
+
+        // Writing and reading again gives the same ontology
+        let triples: Vec<_> = crate::io::rdf::writer::triples(&amo)
+            .collect::<Result<_, _>>().unwrap();
+        let (again, inc) = OntologyParser::new(&b, triples).parse().unwrap();
+        assert!(inc.simple.is_empty() && inc.bnode.is_empty(), "{:?}", inc);
+
+        let orig: SetOntology = amo.into();
+        let again: SetOntology = again.into();
+        assert_eq!(orig, again);
+    }
 }
@@ -0,0 +1,257 @@
+//! A view of an ontology as RDF triples
+//!
+//! The triples follow the same mapping as the
+//! [`writer`](../writer/index.html), but use plain terms built from
+//! the `IRI` and `Literal` of the model, rather than those of an RDF
+//! library, so they can be passed to any triple store or library.
+//! Terms display in N-Triples syntax.
+use super::writer::{triples, SpTerm, TripleIter};
+
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::set::SetOntology;
+use crate::vocab::{Namespace, WithIRI};
+
+use crate::error::{HornedError, ParserError};
+use sophia_api::term::TTerm;
+
+use std::fmt;
+
+/// A term of an RDF triple.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Term {
+    IRI(IRI),
+    BlankNode(String),
+    Literal(Literal),
+}
+
+/// An RDF triple, as subject, predicate and object.
+pub type Triple = [Term; 3];
+
+fn escape(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '\\' => f.write_str("\\\\")?,
+            '"' => f.write_str("\\\"")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::IRI(iri) => write!(f, "<{}>", iri),
+            Term::BlankNode(id) => write!(f, "_:{}", id),
+            Term::Literal(l) => {
+                f.write_str("\"")?;
+                escape(l.literal(), f)?;
+                f.write_str("\"")?;
+                match l {
+                    Literal::Simple { .. } => Ok(()),
+                    Literal::Language { lang, .. } => write!(f, "@{}", lang),
+                    Literal::Datatype { datatype_iri, .. } => write!(f, "^^<{}>", datatype_iri),
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the triples of an ontology.
+pub struct Triples<'a> {
+    b: Build,
+    inner: Box<dyn Iterator<Item = Result<[SpTerm; 3], sophia::term::TermError>> + 'a>,
+}
+
+impl<'a> Triples<'a> {
+    fn term(&self, t: &SpTerm) -> Term {
+        match t {
+            sophia::term::Term::BNode(id) => Term::BlankNode(id.value().to_string()),
+            sophia::term::Term::Literal(l) => {
+                let literal = l.txt().to_string();
+                let dt = l.dt();
+                let dt = dt.value();
+                Term::Literal(match l.lang() {
+                    Some(lang) => Literal::Language {
+                        literal,
+                        lang: lang.to_string(),
+                    },
+                    None if *dt == format!("{}string", Namespace::XSD.iri_s()) => {
+                        Literal::Simple { literal }
+                    }
+                    None => Literal::Datatype {
+                        literal,
+                        datatype_iri: self.b.iri(&*dt),
+                    },
+                })
+            }
+            t => Term::IRI(self.b.iri(&*t.value())),
+        }
+    }
+}
+
+impl<'a> Iterator for Triples<'a> {
+    type Item = Result<Triple, HornedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.inner.next()? {
+            Ok([s, p, o]) => Ok([self.term(&s), self.term(&p), self.term(&o)]),
+            Err(e) => Err(ParserError::RDF(e.to_string()).into()),
+        })
+    }
+}
+
+/// Access to the triples of an ontology.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// use horned_owl::io::rdf::triple::TripleView;
+///
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.mut_id().iri = Some(b.iri("http://www.example.com"));
+/// o.declare(b.class("http://www.example.com/A"));
+///
+/// let nt: Vec<String> = o
+///     .triples()
+///     .map(|t| {
+///         let [s, p, o] = t.unwrap();
+///         format!("{} {} {} .", s, p, o)
+///     })
+///     .collect();
+/// assert_eq!(
+///     nt[1],
+///     "<http://www.example.com/A> \
+///      <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+///      <http://www.w3.org/2002/07/owl#Class> ."
+/// );
+/// ```
+pub trait TripleView {
+    /// Iterate over the triples of the ontology, starting with those
+    /// of its header.
+    fn triples(&self) -> Triples<'_>;
+}
+
+impl TripleView for AxiomMappedOntology {
+    fn triples(&self) -> Triples<'_> {
+        Triples {
+            b: Build::new(),
+            inner: Box::new(triples(self)),
+        }
+    }
+}
+
+impl TripleView for SetOntology {
+    fn triples(&self) -> Triples<'_> {
+        Triples {
+            b: Build::new(),
+            inner: Box::new(TripleIter::new(self.id(), self.iter_sorted())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::read;
+
+    use sophia::serializer::nt::NtSerializer;
+    use sophia::serializer::{Stringifier, TripleSerializer};
+
+    fn ntriples(owx: &str) {
+        let (so, _) = read(&mut owx.as_bytes()).unwrap();
+        let amo: AxiomMappedOntology = so.into();
+
+        let mut plain: Vec<String> = amo
+            .triples()
+            .map(|t| {
+                let [s, p, o] = t.unwrap();
+                format!("{} {} {}.", s, p, o)
+            })
+            .collect();
+
+        let mut ser = NtSerializer::new_stringifier();
+        let mut sophia: Vec<String> = ser
+            .serialize_triples(triples(&amo))
+            .unwrap()
+            .as_str()
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        plain.sort();
+        sophia.sort();
+        assert_eq!(plain, sophia);
+    }
+
+    #[test]
+    fn same_as_sophia() {
+        ntriples(include_str!("../../ont/owl-xml/label.owx"));
+        ntriples(include_str!("../../ont/owl-xml/literal-escaped.owx"));
+        ntriples(include_str!("../../ont/owl-xml/disjoint-union.owx"));
+        ntriples(include_str!(
+            "../../ont/owl-xml/facet-restriction-complex.owx"
+        ));
+    }
+
+    #[test]
+    fn invalid_iri() {
+        let (so, _) = read(&mut include_str!("../../ont/owl-xml/family.owx").as_bytes()).unwrap();
+        assert!(so.triples().any(|t| t.is_err()));
+    }
+
+    #[test]
+    fn literals() {
+        let b = Build::new();
+        let mut o = SetOntology::default();
+        let literals = vec![
+            Literal::Simple {
+                literal: "a \"quoted\"\nstring".to_string(),
+            },
+            Literal::Language {
+                literal: "chat".to_string(),
+                lang: "fr".to_string(),
+            },
+            Literal::Datatype {
+                literal: "1".to_string(),
+                datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+            },
+        ];
+        for l in &literals {
+            o.insert(AnnotationAssertion {
+                subject: b.iri("http://www.example.com/A"),
+                ann: Annotation {
+                    ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                    av: l.clone().into(),
+                },
+            });
+        }
+
+        let objects: Vec<Term> = o.triples().map(|t| t.unwrap()[2].clone()).collect();
+        for l in literals {
+            assert!(objects.contains(&Term::Literal(l)));
+        }
+        assert_eq!(
+            Term::Literal(Literal::Simple {
+                literal: "a \"b\"".to_string()
+            })
+            .to_string(),
+            "\"a \\\"b\\\"\""
+        );
+    }
+
+    #[test]
+    fn anonymous() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.example.com/A"));
+
+        let header = o.triples().next().unwrap().unwrap();
+        assert!(matches!(header[0], Term::BlankNode(_)));
+    }
+}
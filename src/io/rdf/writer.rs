@@ -0,0 +1,1178 @@
+//! Mapping of an Ontology to RDF triples
+//!
+//! The mapping follows the [OWL 2 Mapping to RDF
+//! Graphs](https://www.w3.org/TR/owl2-mapping-to-rdf/) and is the
+//! inverse of that performed by the [`reader`](../reader/index.html).
+//!
+//! Triples are returned as sophia terms. As an IRI in the ontology
+//! may not be valid for RDF, each triple is a `Result`; the iterator
+//! is, therefore, a sophia `TripleSource` and can be passed directly
+//! to any sophia serializer or graph.
+//!
+//! Blank nodes created by the mapping are labelled `genid-b0`,
+//! `genid-b1` and so on, in the order in which they are created while
+//! walking the axioms. An anonymous individual, with an IRI of the
+//! form `_:id`, is written as a blank node labelled `id`, unless `id`
+//! has the form of a created label, when it is written as `aid`, so
+//! the two are never merged.
+use crate::error::{HornedError, ValidityError};
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::Namespace;
+use crate::vocab::OWL2Datatype;
+use crate::vocab::WithIRI;
+use crate::vocab::OWL as VOWL;
+use crate::vocab::RDF as VRDF;
+use crate::vocab::RDFS as VRDFS;
+
+use curie::PrefixMapping;
+
+use sophia::term::iri::Iri;
+use sophia::term::TermError;
+use sophia_api::term::TTerm;
+
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::io::Write as StdWrite;
+use std::rc::Rc;
+
+/// A sophia term, as returned by the triple mapping.
+pub type SpTerm = sophia::term::Term<Rc<str>>;
+
+/// An RDF triple.
+pub type Triple = [SpTerm; 3];
+
+/// An iterator over the triples of an ontology.
+///
+/// Each axiom is only translated when the triples of the previous
+/// axiom have been consumed.
+pub struct TripleIter<'a, I>
+where
+    I: Iterator<Item = &'a AnnotatedAxiom>,
+{
+    mapper: Mapper,
+    axioms: I,
+    buffer: VecDeque<Result<Triple, TermError>>,
+}
+
+impl<'a, I> TripleIter<'a, I>
+where
+    I: Iterator<Item = &'a AnnotatedAxiom>,
+{
+    /// Create an iterator for the ontology with `id`, whose axioms
+    /// are `axioms`.
+    pub fn new(id: &OntologyID, axioms: I) -> TripleIter<'a, I> {
+        let mut mapper = Mapper::default();
+        let buffer = match mapper.header(id) {
+            Ok(()) => mapper.take(),
+            Err(e) => vec![Err(e)].into(),
+        };
+
+        TripleIter {
+            mapper,
+            axioms,
+            buffer,
+        }
+    }
+}
+
+impl<'a, I> Iterator for TripleIter<'a, I>
+where
+    I: Iterator<Item = &'a AnnotatedAxiom>,
+{
+    type Item = Result<Triple, TermError>;
+
+    fn next(&mut self) -> Option<Result<Triple, TermError>> {
+        while self.buffer.is_empty() {
+            let ax = self.axioms.next()?;
+            self.buffer = match self.mapper.annotated_axiom(ax) {
+                Ok(()) => self.mapper.take(),
+                Err(e) => {
+                    self.mapper.out.clear();
+                    vec![Err(e)].into()
+                }
+            };
+        }
+
+        self.buffer.pop_front()
+    }
+}
+
+/// Return an iterator over the triples of `ont`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::io::rdf::writer::triples;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.declare(b.class("http://www.example.com/A"));
+///
+/// // The ontology header and the declaration
+/// assert_eq!(triples(&o).count(), 2);
+/// ```
+pub fn triples(ont: &AxiomMappedOntology) -> TripleIter<'_, impl Iterator<Item = &AnnotatedAxiom>> {
+    TripleIter::new(ont.id(), ont.i().iter())
+}
+
+fn vocab<'a, V: WithIRI<'a>>(v: &V) -> SpTerm {
+    SpTerm::new_iri_unchecked(Rc::from(v.iri_str()))
+}
+
+fn xsd(local: &str) -> Iri<Rc<str>> {
+    Iri::new_unchecked(Rc::from(format!("{}{}", Namespace::XSD.iri_s(), local)))
+}
+
+/// Main triples of an axiom, to which any annotations are attached.
+enum Main {
+    /// The axiom is represented by these triples, each of which must
+    /// be reified to carry annotations.
+    Triples(Vec<Triple>),
+    /// The axiom is represented by a blank node which carries any
+    /// annotations directly.
+    Node(SpTerm),
+}
+
+/// Return true if `id` has the form of the labels of the blank nodes
+/// created by the mapping.
+pub(crate) fn is_generated(id: &str) -> bool {
+    id.strip_prefix("genid-b")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[derive(Default)]
+struct Mapper {
+    bnode: usize,
+    ont: Option<SpTerm>,
+    out: Vec<Triple>,
+}
+
+impl Mapper {
+    fn bnode(&mut self) -> SpTerm {
+        let b = SpTerm::new_bnode_unchecked(format!("genid-b{}", self.bnode));
+        self.bnode += 1;
+        b
+    }
+
+    fn emit(&mut self, s: SpTerm, p: SpTerm, o: SpTerm) {
+        self.out.push([s, p, o]);
+    }
+
+    fn take(&mut self) -> VecDeque<Result<Triple, TermError>> {
+        self.out.drain(..).map(Ok).collect()
+    }
+
+    fn iri(&self, iri: &IRI) -> Result<SpTerm, TermError> {
+        match iri.node_id() {
+            Some(id) if is_generated(id) => SpTerm::new_bnode(Rc::from(format!("a{}", id))),
+            Some(id) => SpTerm::new_bnode(Rc::from(id)),
+            None => SpTerm::new_iri(Rc::from(iri.as_ref())),
+        }
+    }
+
+    fn header(&mut self, id: &OntologyID) -> Result<(), TermError> {
+        let ont = match &id.iri {
+            Some(iri) => self.iri(iri)?,
+            None => self.bnode(),
+        };
+
+        self.emit(ont.clone(), vocab(&VRDF::Type), vocab(&VOWL::Ontology));
+        if let Some(viri) = id.version_iri() {
+            let viri = self.iri(viri)?;
+            self.emit(ont.clone(), vocab(&VOWL::VersionIRI), viri);
+        }
+
+        self.ont = Some(ont);
+        Ok(())
+    }
+
+    fn ontology(&self) -> SpTerm {
+        self.ont.clone().expect("Header must be written first")
+    }
+
+    fn literal(&self, l: &Literal) -> Result<SpTerm, TermError> {
+        match l {
+            Literal::Simple { literal } => {
+                SpTerm::new_literal_dt(Rc::from(literal.as_str()), xsd("string"))
+            }
+            Literal::Language { literal, lang } => {
+                SpTerm::new_literal_lang(Rc::from(literal.as_str()), Rc::from(lang.as_str()))
+            }
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => SpTerm::new_literal_dt(
+                Rc::from(literal.as_str()),
+                Iri::<Rc<str>>::new(Rc::from(datatype_iri.as_ref()))?,
+            ),
+        }
+    }
+
+    fn typed(&self, n: &str, dt: &str) -> SpTerm {
+        SpTerm::new_literal_dt_unchecked(Rc::from(n), xsd(dt))
+    }
+
+    fn annotation_value(&self, av: &AnnotationValue) -> Result<SpTerm, TermError> {
+        match av {
+            AnnotationValue::Literal(l) => self.literal(l),
+            AnnotationValue::IRI(iri) => self.iri(iri),
+            AnnotationValue::AnonymousIndividual(i) => self.iri(&i.0),
+        }
+    }
+
+    fn annotations(&mut self, s: &SpTerm, ann: &BTreeSet<Annotation>) -> Result<(), TermError> {
+        for a in ann {
+            let p = self.iri(&a.ap.0)?;
+            let o = self.annotation_value(&a.av)?;
+            self.emit(s.clone(), p, o);
+        }
+        Ok(())
+    }
+
+    /// Translate a sequence into an `rdf:List`, returning the head
+    fn seq<T, F>(&mut self, items: &[T], mut f: F) -> Result<SpTerm, TermError>
+    where
+        F: FnMut(&mut Mapper, &T) -> Result<SpTerm, TermError>,
+    {
+        let mut head = vocab(&VRDF::Nil);
+        let mut nodes = vec![];
+
+        for item in items {
+            let node = self.bnode();
+            let val = f(self, item)?;
+            nodes.push((node, val));
+        }
+
+        for (node, val) in nodes.into_iter().rev() {
+            self.emit(node.clone(), vocab(&VRDF::First), val);
+            self.emit(node.clone(), vocab(&VRDF::Rest), head);
+            head = node;
+        }
+
+        Ok(head)
+    }
+
+    fn ope(&mut self, ope: &ObjectPropertyExpression) -> Result<SpTerm, TermError> {
+        match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => self.iri(&op.0),
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                let node = self.bnode();
+                let op = self.iri(&op.0)?;
+                self.emit(node.clone(), vocab(&VOWL::InverseOf), op);
+                Ok(node)
+            }
+        }
+    }
+
+    fn pe(&mut self, pe: &PropertyExpression) -> Result<SpTerm, TermError> {
+        match pe {
+            PropertyExpression::ObjectPropertyExpression(ope) => self.ope(ope),
+            PropertyExpression::DataProperty(dp) => self.iri(&dp.0),
+            PropertyExpression::AnnotationProperty(ap) => self.iri(&ap.0),
+        }
+    }
+
+    fn dr(&mut self, dr: &DataRange) -> Result<SpTerm, TermError> {
+        match dr {
+            DataRange::Datatype(dt) => self.iri(&dt.0),
+            DataRange::DataIntersectionOf(v) => {
+                self.datatype_node(VOWL::IntersectionOf, v, |m, dr| m.dr(dr))
+            }
+            DataRange::DataUnionOf(v) => self.datatype_node(VOWL::UnionOf, v, |m, dr| m.dr(dr)),
+            DataRange::DataComplementOf(dr) => {
+                let node = self.bnode();
+                let dr = self.dr(dr)?;
+                self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VRDFS::Datatype));
+                self.emit(node.clone(), vocab(&VOWL::DatatypeComplementOf), dr);
+                Ok(node)
+            }
+            DataRange::DataOneOf(v) => self.datatype_node(VOWL::OneOf, v, |m, l| m.literal(l)),
+            DataRange::DatatypeRestriction(dt, v) => {
+                let node = self.bnode();
+                let dt = self.iri(&dt.0)?;
+                let restrictions = self.seq(v, |m, fr| {
+                    let fnode = m.bnode();
+                    let l = m.literal(&fr.l)?;
+                    m.emit(fnode.clone(), vocab(&fr.f), l);
+                    Ok(fnode)
+                })?;
+                self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VRDFS::Datatype));
+                self.emit(node.clone(), vocab(&VOWL::OnDatatype), dt);
+                self.emit(node.clone(), vocab(&VOWL::WithRestrictions), restrictions);
+                Ok(node)
+            }
+        }
+    }
+
+    fn datatype_node<T, F>(&mut self, p: VOWL, items: &[T], f: F) -> Result<SpTerm, TermError>
+    where
+        F: FnMut(&mut Mapper, &T) -> Result<SpTerm, TermError>,
+    {
+        let node = self.bnode();
+        let seq = self.seq(items, f)?;
+        self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VRDFS::Datatype));
+        self.emit(node.clone(), vocab(&p), seq);
+        Ok(node)
+    }
+
+    fn class_node<T, F>(&mut self, p: VOWL, items: &[T], f: F) -> Result<SpTerm, TermError>
+    where
+        F: FnMut(&mut Mapper, &T) -> Result<SpTerm, TermError>,
+    {
+        let node = self.bnode();
+        let seq = self.seq(items, f)?;
+        self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VOWL::Class));
+        self.emit(node.clone(), vocab(&p), seq);
+        Ok(node)
+    }
+
+    fn restriction(&mut self, p: SpTerm, rest: Vec<(VOWL, SpTerm)>) -> SpTerm {
+        let node = self.bnode();
+        self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VOWL::Restriction));
+        self.emit(node.clone(), vocab(&VOWL::OnProperty), p);
+        for (v, o) in rest {
+            self.emit(node.clone(), vocab(&v), o);
+        }
+        node
+    }
+
+    fn object_cardinality(
+        &mut self,
+        n: u32,
+        ope: &ObjectPropertyExpression,
+        bce: &ClassExpression,
+        unqualified: VOWL,
+        qualified: VOWL,
+    ) -> Result<SpTerm, TermError> {
+        let p = self.ope(ope)?;
+        let n = self.typed(&n.to_string(), "nonNegativeInteger");
+        match bce {
+            ClassExpression::Class(Class(iri)) if iri.as_ref() == VOWL::Thing.iri_str() => {
+                Ok(self.restriction(p, vec![(unqualified, n)]))
+            }
+            _ => {
+                let bce = self.ce(bce)?;
+                Ok(self.restriction(p, vec![(qualified, n), (VOWL::OnClass, bce)]))
+            }
+        }
+    }
+
+    fn data_cardinality(
+        &mut self,
+        n: u32,
+        dp: &DataProperty,
+        dr: &DataRange,
+        unqualified: VOWL,
+        qualified: VOWL,
+    ) -> Result<SpTerm, TermError> {
+        let p = self.iri(&dp.0)?;
+        let n = self.typed(&n.to_string(), "nonNegativeInteger");
+        match dr {
+            DataRange::Datatype(Datatype(iri))
+                if iri.as_ref() == OWL2Datatype::RDFSLiteral.iri_str() =>
+            {
+                Ok(self.restriction(p, vec![(unqualified, n)]))
+            }
+            _ => {
+                let dr = self.dr(dr)?;
+                Ok(self.restriction(p, vec![(qualified, n), (VOWL::OnDataRange, dr)]))
+            }
+        }
+    }
+
+    fn ce(&mut self, ce: &ClassExpression) -> Result<SpTerm, TermError> {
+        match ce {
+            ClassExpression::Class(c) => self.iri(&c.0),
+            ClassExpression::ObjectIntersectionOf(v) => {
+                self.class_node(VOWL::IntersectionOf, v, |m, ce| m.ce(ce))
+            }
+            ClassExpression::ObjectUnionOf(v) => {
+                self.class_node(VOWL::UnionOf, v, |m, ce| m.ce(ce))
+            }
+            ClassExpression::ObjectComplementOf(bce) => {
+                let node = self.bnode();
+                let bce = self.ce(bce)?;
+                self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VOWL::Class));
+                self.emit(node.clone(), vocab(&VOWL::ComplementOf), bce);
+                Ok(node)
+            }
+            ClassExpression::ObjectOneOf(v) => self.class_node(VOWL::OneOf, v, |m, i| m.iri(&i.0)),
+            ClassExpression::ObjectSomeValuesFrom { ope, bce } => {
+                let p = self.ope(ope)?;
+                let bce = self.ce(bce)?;
+                Ok(self.restriction(p, vec![(VOWL::SomeValuesFrom, bce)]))
+            }
+            ClassExpression::ObjectAllValuesFrom { ope, bce } => {
+                let p = self.ope(ope)?;
+                let bce = self.ce(bce)?;
+                Ok(self.restriction(p, vec![(VOWL::AllValuesFrom, bce)]))
+            }
+            ClassExpression::ObjectHasValue { ope, i } => {
+                let p = self.ope(ope)?;
+                let i = self.iri(&i.0)?;
+                Ok(self.restriction(p, vec![(VOWL::HasValue, i)]))
+            }
+            ClassExpression::ObjectHasSelf(ope) => {
+                let p = self.ope(ope)?;
+                let t = self.typed("true", "boolean");
+                Ok(self.restriction(p, vec![(VOWL::HasSelf, t)]))
+            }
+            ClassExpression::ObjectMinCardinality { n, ope, bce } => self.object_cardinality(
+                *n,
+                ope,
+                bce,
+                VOWL::MinCardinality,
+                VOWL::MinQualifiedCardinality,
+            ),
+            ClassExpression::ObjectMaxCardinality { n, ope, bce } => self.object_cardinality(
+                *n,
+                ope,
+                bce,
+                VOWL::MaxCardinality,
+                VOWL::MaxQualifiedCardinality,
+            ),
+            ClassExpression::ObjectExactCardinality { n, ope, bce } => {
+                self.object_cardinality(*n, ope, bce, VOWL::Cardinality, VOWL::QualifiedCardinality)
+            }
+            ClassExpression::DataSomeValuesFrom { dp, dr } => {
+                let p = self.iri(&dp.0)?;
+                let dr = self.dr(dr)?;
+                Ok(self.restriction(p, vec![(VOWL::SomeValuesFrom, dr)]))
+            }
+            ClassExpression::DataAllValuesFrom { dp, dr } => {
+                let p = self.iri(&dp.0)?;
+                let dr = self.dr(dr)?;
+                Ok(self.restriction(p, vec![(VOWL::AllValuesFrom, dr)]))
+            }
+            ClassExpression::DataHasValue { dp, l } => {
+                let p = self.iri(&dp.0)?;
+                let l = self.literal(l)?;
+                Ok(self.restriction(p, vec![(VOWL::HasValue, l)]))
+            }
+            ClassExpression::DataMinCardinality { n, dp, dr } => self.data_cardinality(
+                *n,
+                dp,
+                dr,
+                VOWL::MinCardinality,
+                VOWL::MinQualifiedCardinality,
+            ),
+            ClassExpression::DataMaxCardinality { n, dp, dr } => self.data_cardinality(
+                *n,
+                dp,
+                dr,
+                VOWL::MaxCardinality,
+                VOWL::MaxQualifiedCardinality,
+            ),
+            ClassExpression::DataExactCardinality { n, dp, dr } => {
+                self.data_cardinality(*n, dp, dr, VOWL::Cardinality, VOWL::QualifiedCardinality)
+            }
+        }
+    }
+
+    /// Translate an n-ary axiom as a chain of binary triples.
+    fn pairwise(&mut self, p: SpTerm, terms: Vec<SpTerm>) -> Result<Main, TermError> {
+        Ok(Main::Triples(
+            terms
+                .windows(2)
+                .map(|w| [w[0].clone(), p.clone(), w[1].clone()])
+                .collect(),
+        ))
+    }
+
+    /// Translate a disjointness axiom, using either a single triple
+    /// or a typed blank node with `members`.
+    fn disjoint(&mut self, binary: VOWL, all: VOWL, terms: Vec<SpTerm>) -> Result<Main, TermError> {
+        if terms.len() == 2 {
+            Ok(Main::Triples(vec![[
+                terms[0].clone(),
+                vocab(&binary),
+                terms[1].clone(),
+            ]]))
+        } else {
+            let node = self.bnode();
+            let members = self.seq(&terms, |_, t| Ok(t.clone()))?;
+            self.emit(node.clone(), vocab(&VRDF::Type), vocab(&all));
+            self.emit(node.clone(), vocab(&VOWL::Members), members);
+            Ok(Main::Node(node))
+        }
+    }
+
+    fn typed_node(&mut self, s: SpTerm, o: VOWL) -> Result<Main, TermError> {
+        Ok(Main::Triples(vec![[s, vocab(&VRDF::Type), vocab(&o)]]))
+    }
+
+    fn negative_assertion(
+        &mut self,
+        p: SpTerm,
+        from: SpTerm,
+        target: VOWL,
+        to: SpTerm,
+    ) -> Result<Main, TermError> {
+        let node = self.bnode();
+        self.emit(
+            node.clone(),
+            vocab(&VRDF::Type),
+            vocab(&VOWL::NegativePropertyAssertion),
+        );
+        self.emit(node.clone(), vocab(&VOWL::SourceIndividual), from);
+        self.emit(node.clone(), vocab(&VOWL::AssertionProperty), p);
+        self.emit(node.clone(), vocab(&target), to);
+        Ok(Main::Node(node))
+    }
+
+    fn axiom(&mut self, ax: &Axiom) -> Result<Main, TermError> {
+        macro_rules! triple {
+            ($s:expr, $p:expr, $o:expr) => {{
+                let s = $s;
+                let p = $p;
+                let o = $o;
+                Ok(Main::Triples(vec![[s, p, o]]))
+            }};
+        }
+
+        match ax {
+            Axiom::OntologyAnnotation(OntologyAnnotation(ann)) => {
+                triple!(
+                    self.ontology(),
+                    self.iri(&ann.ap.0)?,
+                    self.annotation_value(&ann.av)?
+                )
+            }
+            Axiom::Import(Import(iri)) => {
+                triple!(self.ontology(), vocab(&VOWL::Imports), self.iri(iri)?)
+            }
+            Axiom::DeclareClass(DeclareClass(c)) => {
+                let s = self.iri(&c.0)?;
+                self.typed_node(s, VOWL::Class)
+            }
+            Axiom::DeclareObjectProperty(DeclareObjectProperty(op)) => {
+                let s = self.iri(&op.0)?;
+                self.typed_node(s, VOWL::ObjectProperty)
+            }
+            Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => {
+                let s = self.iri(&ap.0)?;
+                self.typed_node(s, VOWL::AnnotationProperty)
+            }
+            Axiom::DeclareDataProperty(DeclareDataProperty(dp)) => {
+                let s = self.iri(&dp.0)?;
+                self.typed_node(s, VOWL::DatatypeProperty)
+            }
+            Axiom::DeclareNamedIndividual(DeclareNamedIndividual(i)) => {
+                let s = self.iri(&i.0)?;
+                self.typed_node(s, VOWL::NamedIndividual)
+            }
+            Axiom::DeclareDatatype(DeclareDatatype(dt)) => {
+                triple!(
+                    self.iri(&dt.0)?,
+                    vocab(&VRDF::Type),
+                    vocab(&VRDFS::Datatype)
+                )
+            }
+            Axiom::SubClassOf(SubClassOf { sup, sub }) => {
+                triple!(self.ce(sub)?, vocab(&VRDFS::SubClassOf), self.ce(sup)?)
+            }
+            Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+                // Reversed, so that the reader returns the same order
+                let terms = v
+                    .iter()
+                    .rev()
+                    .map(|ce| self.ce(ce))
+                    .collect::<Result<_, _>>()?;
+                self.pairwise(vocab(&VOWL::EquivalentClass), terms)
+            }
+            Axiom::DisjointClasses(DisjointClasses(v)) => {
+                let terms = v
+                    .iter()
+                    .rev()
+                    .map(|ce| self.ce(ce))
+                    .collect::<Result<_, _>>()?;
+                self.disjoint(VOWL::DisjointWith, VOWL::AllDisjointClasses, terms)
+            }
+            Axiom::DisjointUnion(DisjointUnion(c, v)) => {
+                let c = self.iri(&c.0)?;
+                let seq = self.seq(v, |m, ce| m.ce(ce))?;
+                triple!(c, vocab(&VOWL::DisjointUnionOf), seq)
+            }
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub }) => match sub {
+                SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                    triple!(self.ope(ope)?, vocab(&VRDFS::SubPropertyOf), self.ope(sup)?)
+                }
+                SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                    let sup = self.ope(sup)?;
+                    let seq = self.seq(v, |m, ope| m.ope(ope))?;
+                    triple!(sup, vocab(&VOWL::PropertyChainAxiom), seq)
+                }
+            },
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                let terms = v
+                    .iter()
+                    .map(|ope| self.ope(ope))
+                    .collect::<Result<_, _>>()?;
+                self.pairwise(vocab(&VOWL::EquivalentProperty), terms)
+            }
+            Axiom::DisjointObjectProperties(DisjointObjectProperties(v)) => {
+                let terms = v
+                    .iter()
+                    .map(|ope| self.ope(ope))
+                    .collect::<Result<_, _>>()?;
+                self.disjoint(
+                    VOWL::PropertyDisjointWith,
+                    VOWL::AllDisjointProperties,
+                    terms,
+                )
+            }
+            Axiom::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+                triple!(self.iri(&a.0)?, vocab(&VOWL::InverseOf), self.iri(&b.0)?)
+            }
+            Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                triple!(self.ope(ope)?, vocab(&VRDFS::Domain), self.ce(ce)?)
+            }
+            Axiom::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                triple!(self.ope(ope)?, vocab(&VRDFS::Range), self.ce(ce)?)
+            }
+            Axiom::FunctionalObjectProperty(FunctionalObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::FunctionalProperty)
+            }
+            Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::InverseFunctionalProperty)
+            }
+            Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::ReflexiveProperty)
+            }
+            Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::IrreflexiveProperty)
+            }
+            Axiom::SymmetricObjectProperty(SymmetricObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::SymmetricProperty)
+            }
+            Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::AsymmetricProperty)
+            }
+            Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                let s = self.ope(ope)?;
+                self.typed_node(s, VOWL::TransitiveProperty)
+            }
+            Axiom::SubDataPropertyOf(SubDataPropertyOf { sup, sub }) => {
+                triple!(
+                    self.iri(&sub.0)?,
+                    vocab(&VRDFS::SubPropertyOf),
+                    self.iri(&sup.0)?
+                )
+            }
+            Axiom::EquivalentDataProperties(EquivalentDataProperties(v)) => {
+                let terms = v
+                    .iter()
+                    .map(|dp| self.iri(&dp.0))
+                    .collect::<Result<_, _>>()?;
+                self.pairwise(vocab(&VOWL::EquivalentProperty), terms)
+            }
+            Axiom::DisjointDataProperties(DisjointDataProperties(v)) => {
+                let terms = v
+                    .iter()
+                    .map(|dp| self.iri(&dp.0))
+                    .collect::<Result<_, _>>()?;
+                self.disjoint(
+                    VOWL::PropertyDisjointWith,
+                    VOWL::AllDisjointProperties,
+                    terms,
+                )
+            }
+            Axiom::DataPropertyDomain(DataPropertyDomain { dp, ce }) => {
+                triple!(self.iri(&dp.0)?, vocab(&VRDFS::Domain), self.ce(ce)?)
+            }
+            Axiom::DataPropertyRange(DataPropertyRange { dp, dr }) => {
+                triple!(self.iri(&dp.0)?, vocab(&VRDFS::Range), self.dr(dr)?)
+            }
+            Axiom::FunctionalDataProperty(FunctionalDataProperty(dp)) => {
+                let s = self.iri(&dp.0)?;
+                self.typed_node(s, VOWL::FunctionalProperty)
+            }
+            Axiom::DatatypeDefinition(DatatypeDefinition { kind, range }) => {
+                triple!(
+                    self.iri(&kind.0)?,
+                    vocab(&VOWL::EquivalentClass),
+                    self.dr(range)?
+                )
+            }
+            Axiom::HasKey(HasKey { ce, vpe }) => {
+                let ce = self.ce(ce)?;
+                let seq = self.seq(vpe, |m, pe| m.pe(pe))?;
+                triple!(ce, vocab(&VOWL::HasKey), seq)
+            }
+            Axiom::SameIndividual(SameIndividual(v)) => {
+                let terms = v.iter().map(|i| self.iri(&i.0)).collect::<Result<_, _>>()?;
+                self.pairwise(vocab(&VOWL::SameAs), terms)
+            }
+            Axiom::DifferentIndividuals(DifferentIndividuals(v)) => {
+                let terms = v.iter().map(|i| self.iri(&i.0)).collect::<Result<_, _>>()?;
+                self.disjoint(VOWL::DifferentFrom, VOWL::AllDifferent, terms)
+            }
+            Axiom::ClassAssertion(ClassAssertion { ce, i }) => {
+                triple!(self.iri(&i.0)?, vocab(&VRDF::Type), self.ce(ce)?)
+            }
+            Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to }) => {
+                match ope {
+                    ObjectPropertyExpression::ObjectProperty(op) => {
+                        triple!(self.iri(&from.0)?, self.iri(&op.0)?, self.iri(&to.0)?)
+                    }
+                    ObjectPropertyExpression::InverseObjectProperty(op) => {
+                        triple!(self.iri(&to.0)?, self.iri(&op.0)?, self.iri(&from.0)?)
+                    }
+                }
+            }
+            Axiom::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+                ope,
+                from,
+                to,
+            }) => {
+                let p = self.ope(ope)?;
+                let from = self.iri(&from.0)?;
+                let to = self.iri(&to.0)?;
+                self.negative_assertion(p, from, VOWL::TargetIndividual, to)
+            }
+            Axiom::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => {
+                triple!(self.iri(&from.0)?, self.iri(&dp.0)?, self.literal(to)?)
+            }
+            Axiom::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion {
+                dp,
+                from,
+                to,
+            }) => {
+                let p = self.iri(&dp.0)?;
+                let from = self.iri(&from.0)?;
+                let to = self.literal(to)?;
+                self.negative_assertion(p, from, VOWL::TargetValue, to)
+            }
+            Axiom::AnnotationAssertion(AnnotationAssertion { subject, ann }) => {
+                triple!(
+                    self.iri(subject)?,
+                    self.iri(&ann.ap.0)?,
+                    self.annotation_value(&ann.av)?
+                )
+            }
+            Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sup, sub }) => {
+                triple!(
+                    self.iri(&sub.0)?,
+                    vocab(&VRDFS::SubPropertyOf),
+                    self.iri(&sup.0)?
+                )
+            }
+            Axiom::AnnotationPropertyDomain(AnnotationPropertyDomain { ap, iri }) => {
+                triple!(self.iri(&ap.0)?, vocab(&VRDFS::Domain), self.iri(iri)?)
+            }
+            Axiom::AnnotationPropertyRange(AnnotationPropertyRange { ap, iri }) => {
+                triple!(self.iri(&ap.0)?, vocab(&VRDFS::Range), self.iri(iri)?)
+            }
+        }
+    }
+
+    fn annotated_axiom(&mut self, ax: &AnnotatedAxiom) -> Result<(), TermError> {
+        match self.axiom(&ax.axiom)? {
+            Main::Triples(v) => {
+                for [s, p, o] in v {
+                    if !ax.ann.is_empty() {
+                        let node = self.bnode();
+                        self.emit(node.clone(), vocab(&VRDF::Type), vocab(&VOWL::Axiom));
+                        self.emit(node.clone(), vocab(&VOWL::AnnotatedSource), s.clone());
+                        self.emit(node.clone(), vocab(&VOWL::AnnotatedProperty), p.clone());
+                        self.emit(node.clone(), vocab(&VOWL::AnnotatedTarget), o.clone());
+                        self.annotations(&node, &ax.ann)?;
+                    }
+                    self.emit(s, p, o);
+                }
+            }
+            Main::Node(node) => {
+                self.annotations(&node, &ax.ann)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write `ont` to `write` as RDF/XML.
+///
+/// The triples are those of [`triples`](fn.triples.html); the
+/// triples of each subject are written in a single `rdf:Description`.
+/// The prefixes of `mapping` are declared as XML namespaces, and
+/// other namespaces are declared on the elements which use them.
+///
+/// This fails if a predicate cannot be written as an XML name.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::io::rdf::writer::write;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.declare(b.class("http://www.example.com/A"));
+///
+/// let mut buf = Vec::new();
+/// write(&mut buf, &o, None).unwrap();
+/// assert!(String::from_utf8(buf).unwrap().contains(
+///     r#"<rdf:Description rdf:about="http://www.example.com/A">"#
+/// ));
+/// ```
+pub fn write<W: StdWrite>(
+    write: &mut W,
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    let mut namespaces: Vec<(String, String)> = [
+        ("rdf", Namespace::RDF),
+        ("rdfs", Namespace::RDFS),
+        ("owl", Namespace::OWL),
+        ("xsd", Namespace::XSD),
+    ]
+    .iter()
+    .map(|(p, ns)| (p.to_string(), ns.iri_s().to_string()))
+    .collect();
+    if let Some(mapping) = mapping {
+        for (prefix, ns) in mapping.mappings() {
+            if is_ncname(prefix)
+                && !prefix.to_lowercase().starts_with("xml")
+                && namespaces.iter().all(|(p, n)| p != prefix && n != ns)
+            {
+                namespaces.push((prefix.clone(), ns.clone()));
+            }
+        }
+    }
+
+    writeln!(write, "<?xml version=\"1.0\"?>")?;
+    write!(write, "<rdf:RDF")?;
+    for (prefix, ns) in &namespaces {
+        write!(write, "\n    xmlns:{}=\"{}\"", prefix, escape(ns))?;
+    }
+    writeln!(write, ">")?;
+
+    let mut subject: Option<SpTerm> = None;
+    for t in triples(ont) {
+        let [s, p, o] = t.map_err(|e| ValidityError::RDF(e.to_string()))?;
+
+        if subject.as_ref() != Some(&s) {
+            if subject.is_some() {
+                writeln!(write, "    </rdf:Description>")?;
+            }
+            match &s {
+                SpTerm::BNode(_) => writeln!(
+                    write,
+                    "    <rdf:Description rdf:nodeID=\"{}\">",
+                    escape(&s.value())
+                )?,
+                _ => writeln!(
+                    write,
+                    "    <rdf:Description rdf:about=\"{}\">",
+                    escape(&s.value())
+                )?,
+            }
+            subject = Some(s);
+        }
+
+        let p = p.value();
+        let (ns, local) =
+            split_name(&p).ok_or_else(|| ValidityError::RDF(format!("Predicate {}", p)))?;
+        let (name, decl) = match namespaces.iter().find(|(_, n)| n == ns) {
+            Some((prefix, _)) => (format!("{}:{}", prefix, local), String::new()),
+            None => (
+                format!("ns:{}", local),
+                format!(" xmlns:ns=\"{}\"", escape(ns)),
+            ),
+        };
+
+        match &o {
+            SpTerm::Iri(_) => writeln!(
+                write,
+                "        <{}{} rdf:resource=\"{}\"/>",
+                name,
+                decl,
+                escape(&o.value())
+            )?,
+            SpTerm::BNode(_) => writeln!(
+                write,
+                "        <{}{} rdf:nodeID=\"{}\"/>",
+                name,
+                decl,
+                escape(&o.value())
+            )?,
+            _ => {
+                let attr = match (o.language(), o.datatype()) {
+                    (Some(lang), _) => format!(" xml:lang=\"{}\"", escape(lang)),
+                    (None, Some(dt)) if dt.value() != xsd("string").value() => {
+                        format!(" rdf:datatype=\"{}\"", escape(&dt.value()))
+                    }
+                    _ => String::new(),
+                };
+                writeln!(
+                    write,
+                    "        <{}{}{}>{}</{}>",
+                    name,
+                    decl,
+                    attr,
+                    escape(&o.value()),
+                    name
+                )?
+            }
+        }
+    }
+    if subject.is_some() {
+        writeln!(write, "    </rdf:Description>")?;
+    }
+    writeln!(write, "</rdf:RDF>")?;
+    Ok(())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ncname(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(is_name_start)
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// Split `iri` into a namespace and the longest local name which is
+/// an XML name.
+fn split_name(iri: &str) -> Option<(&str, &str)> {
+    let start = iri
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '-' || *c == '.')
+        .filter(|(_, c)| is_name_start(*c))
+        .map(|(i, _)| i)
+        .last()?;
+    Some(iri.split_at(start))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::io::owx::reader::test::read_ok;
+    use crate::io::rdf::reader::OntologyParser;
+    use crate::ontology::set::SetOntology;
+
+    use sophia::serializer::nt::NtSerializer;
+    use sophia::serializer::{Stringifier, TripleSerializer};
+
+    fn roundtrip(owx: &str) {
+        let (ont, _) = read_ok(&mut owx.as_bytes());
+
+        let b = Build::new();
+        let triples: Vec<Triple> = triples(&ont).collect::<Result<_, _>>().unwrap();
+        let (rdfo, incomplete) = OntologyParser::new(&b, triples).parse().unwrap();
+
+        assert!(incomplete.simple.is_empty(), "{:?}", incomplete.simple);
+        assert!(incomplete.bnode.is_empty(), "{:?}", incomplete.bnode);
+
+        let mut xml = Vec::new();
+        write(&mut xml, &ont, None).unwrap();
+        let (xmlo, incomplete) = crate::io::rdf::reader::read(&mut xml.as_slice()).unwrap();
+        assert!(incomplete.simple.is_empty(), "{:?}", incomplete.simple);
+        assert!(incomplete.bnode.is_empty(), "{:?}", incomplete.bnode);
+
+        let orig: SetOntology = ont.into();
+        let round: SetOntology = rdfo.into();
+        assert_eq!(orig, round);
+        let round: SetOntology = xmlo.into();
+        assert_eq!(orig, round);
+    }
+
+    macro_rules! roundtrip {
+        ($($name:ident: $file:expr),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    roundtrip(include_str!(concat!("../../ont/owl-xml/", $file, ".owx")));
+                }
+            )*
+        }
+    }
+
+    roundtrip! {
+        class: "class",
+        declaration_with_annotation: "declaration-with-annotation",
+        ont: "ont",
+        one_subclass: "one-subclass",
+        annotation_on_subclass: "annotation-on-subclass",
+        some: "some",
+        only: "only",
+        and: "and",
+        or: "or",
+        not: "not",
+        annotation: "annotation",
+        annotation_domain: "annotation-domain",
+        label: "label",
+        one_ontology_annotation: "one-ontology-annotation",
+        one_equivalent: "one-equivalent",
+        one_disjoint: "one-disjoint",
+        disjoint_union: "disjoint-union",
+        suboproperty_inverse: "suboproperty-inverse",
+        inverse_properties: "inverse-properties",
+        subproperty_chain: "subproperty-chain",
+        sub_annotation: "sub-annotation",
+        literal_escaped: "literal-escaped",
+        import: "import",
+        object_has_value: "object-has-value",
+        object_one_of: "object-one-of",
+        some_inverse: "some-inverse",
+        object_unqualified_max_cardinality: "object-unqualified-max-cardinality",
+        object_min_cardinality: "object-min-cardinality",
+        datatype_intersection: "datatype-intersection",
+        datatype_complement: "datatype-complement",
+        datatype_oneof: "datatype-oneof",
+        facet_restriction: "facet-restriction",
+        facet_restriction_complex: "facet-restriction-complex",
+        facet_restriction_string: "facet-restriction-string",
+        data_exact_cardinality: "data-exact-cardinality",
+        class_assertion: "class-assertion",
+        data_property_assertion: "data-property-assertion",
+        negative_object_property_assertion: "negative-object-property-assertion",
+        negative_data_property_assertion: "negative-data-property-assertion",
+        data_has_key: "data-has-key",
+        object_property_functional: "object-property-functional",
+        equivalent_object_properties: "equivalent-object-properties",
+        disjoint_object_properties: "disjoint-object-properties",
+        data_property_equivalent: "data-property-equivalent",
+        data_property_disjoint: "data-property-disjoint",
+        same_individual: "same-individual"
+    }
+
+    #[test]
+    fn xml_namespaces() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://www.example.com/A"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.example.com/p"),
+                av: b.iri("http://www.example.com/B").into(),
+            },
+        });
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://www.example.com/A"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.example.org/q"),
+                av: b.iri("http://www.example.com/B").into(),
+            },
+        });
+
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://www.example.com/").unwrap();
+        let mut xml = Vec::new();
+        write(&mut xml, &o, Some(&mapping)).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+
+        assert!(xml.contains(r#"xmlns:ex="http://www.example.com/""#));
+        assert!(xml.contains(r#"<ex:p rdf:resource="http://www.example.com/B"/>"#));
+        assert!(xml.contains(
+            r#"<ns:q xmlns:ns="http://www.example.org/" rdf:resource="http://www.example.com/B"/>"#
+        ));
+
+        let mut o = AxiomMappedOntology::default();
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://www.example.com/A"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.example.com/1"),
+                av: b.iri("http://www.example.com/B").into(),
+            },
+        });
+        assert!(write(&mut Vec::new(), &o, None).is_err());
+    }
+
+    #[test]
+    fn invalid_iri() {
+        // family.owx contains an IRI with two fragments
+        let (ont, _) = read_ok(&mut include_str!("../../ont/owl-xml/family.owx").as_bytes());
+
+        assert!(triples(&ont).any(|t| t.is_err()));
+    }
+
+    #[test]
+    fn anonymous_ontology() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.example.com/A"));
+
+        let v: Vec<Triple> = triples(&o).collect::<Result<_, _>>().unwrap();
+
+        // The header uses a blank node in place of the ontology IRI
+        assert_eq!(v.len(), 2);
+        assert!(matches!(v[0][0], SpTerm::BNode(_)));
+    }
+
+    #[test]
+    fn blank_node_labels() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        o.insert(SubClassOf {
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/r").into(),
+                bce: b.class("http://www.example.com/B").into(),
+            },
+            sub: b.class("http://www.example.com/A").into(),
+        });
+
+        let nt = NtSerializer::new_stringifier()
+            .serialize_triples(triples(&o))
+            .unwrap()
+            .to_string();
+
+        assert!(nt.contains("_:genid-b0 <http://www.w3.org/2002/07/owl#onProperty>"));
+        assert!(nt.contains(
+            "<http://www.example.com/A> <http://www.w3.org/2000/01/rdf-schema#subClassOf> _:genid-b0"
+        ));
+    }
+
+    #[test]
+    fn anonymous_individual_label() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        o.declare(b.class("http://www.example.com/A"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        o.insert(SubClassOf {
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/r").into(),
+                bce: b.class("http://www.example.com/A").into(),
+            },
+            sub: b.class("http://www.example.com/A").into(),
+        });
+        // The labels of the created blank node, in the old and the
+        // current scheme
+        for id in ["b0", "genid-b0"] {
+            o.insert(ClassAssertion {
+                ce: b.class("http://www.example.com/A").into(),
+                i: b.anonymous_individual(id),
+            });
+        }
+
+        let triples: Vec<Triple> = triples(&o).collect::<Result<_, _>>().unwrap();
+        let (rdfo, incomplete) = OntologyParser::new(&b, triples).parse().unwrap();
+        assert!(incomplete.simple.is_empty(), "{:?}", incomplete.simple);
+        assert!(incomplete.bnode.is_empty(), "{:?}", incomplete.bnode);
+
+        let round: AxiomMappedOntology = rdfo.into();
+        assert_eq!(round.i().class_assertion().count(), 2);
+        assert!(round
+            .i()
+            .class_assertion()
+            .any(|ca| ca.i == b.anonymous_individual("b0")));
+        assert_eq!(round.i().sub_class_of().count(), 1);
+    }
+}
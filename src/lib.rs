@@ -32,20 +32,42 @@
 //! implementation of the OWL2 DL specification. It appears to be
 //! highly performant, being between 1 and 2 orders of magnitude
 //! faster than the OWL API for some tasks.
+
 //extern crate curie;
 //extern crate enum_meta;
-#[macro_use]
-extern crate failure;
 //#[macro_use]
 extern crate indexmap;
 extern crate log;
 extern crate quick_xml;
 
+pub mod arena;
 pub mod collection;
 pub mod command;
+pub mod datatype;
+pub mod diff;
+pub mod dublin_core;
 pub mod error;
+pub mod expressivity;
+pub mod fresh;
+pub mod hierarchy;
 pub mod io;
+pub mod metrics;
 pub mod model;
+pub mod normalize;
+pub mod obo;
 pub mod ontology;
+pub mod query;
+pub mod reasoner;
+pub mod release;
 pub mod resolve;
+pub mod search;
+pub mod signature;
+pub mod similarity;
+pub mod skos;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "ofn")]
+pub mod structural;
+pub mod validate;
+pub mod version;
 pub mod vocab;
@@ -0,0 +1,334 @@
+//! Metrics of an ontology
+//!
+//! `Metrics` gathers the axiom and entity counts of an ontology, the
+//! shape of its asserted class hierarchy and the number of uses of
+//! each class expression and data range constructor.
+use crate::hierarchy::ClassHierarchy;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::signature::Signature;
+
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    pub axioms: usize,
+    /// Axioms other than declarations, imports and annotation axioms.
+    pub logical_axioms: usize,
+    pub axiom_types: BTreeMap<AxiomKind, usize>,
+    pub entities: BTreeMap<NamedEntityKind, usize>,
+    /// The number of classes on the longest path from a top level
+    /// class in the asserted hierarchy.
+    pub max_depth: usize,
+    pub average_depth: f64,
+    /// The most direct sub classes of any class.
+    pub max_branching: usize,
+    /// The mean number of direct sub classes of the classes which
+    /// have any.
+    pub average_branching: f64,
+    /// The number of uses of each constructor, by the name of its
+    /// `ClassExpression` or `DataRange` variant.
+    pub constructors: BTreeMap<&'static str, usize>,
+}
+
+/// Returns true for axioms with a logical meaning.
+pub fn is_logical(kind: AxiomKind) -> bool {
+    use AxiomKind::*;
+    !matches!(
+        kind,
+        Import
+            | OntologyAnnotation
+            | DeclareClass
+            | DeclareObjectProperty
+            | DeclareAnnotationProperty
+            | DeclareDataProperty
+            | DeclareNamedIndividual
+            | DeclareDatatype
+            | AnnotationAssertion
+            | SubAnnotationPropertyOf
+            | AnnotationPropertyDomain
+            | AnnotationPropertyRange
+    )
+}
+
+/// The name of the constructor of `ce`.
+pub fn class_constructor(ce: &ClassExpression) -> &'static str {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => "Class",
+        ObjectIntersectionOf(_) => "ObjectIntersectionOf",
+        ObjectUnionOf(_) => "ObjectUnionOf",
+        ObjectComplementOf(_) => "ObjectComplementOf",
+        ObjectOneOf(_) => "ObjectOneOf",
+        ObjectSomeValuesFrom { .. } => "ObjectSomeValuesFrom",
+        ObjectAllValuesFrom { .. } => "ObjectAllValuesFrom",
+        ObjectHasValue { .. } => "ObjectHasValue",
+        ObjectHasSelf(_) => "ObjectHasSelf",
+        ObjectMinCardinality { .. } => "ObjectMinCardinality",
+        ObjectMaxCardinality { .. } => "ObjectMaxCardinality",
+        ObjectExactCardinality { .. } => "ObjectExactCardinality",
+        DataSomeValuesFrom { .. } => "DataSomeValuesFrom",
+        DataAllValuesFrom { .. } => "DataAllValuesFrom",
+        DataHasValue { .. } => "DataHasValue",
+        DataMinCardinality { .. } => "DataMinCardinality",
+        DataMaxCardinality { .. } => "DataMaxCardinality",
+        DataExactCardinality { .. } => "DataExactCardinality",
+    }
+}
+
+/// The name of the constructor of `dr`.
+pub fn data_constructor(dr: &DataRange) -> &'static str {
+    match dr {
+        DataRange::Datatype(_) => "Datatype",
+        DataRange::DataIntersectionOf(_) => "DataIntersectionOf",
+        DataRange::DataUnionOf(_) => "DataUnionOf",
+        DataRange::DataComplementOf(_) => "DataComplementOf",
+        DataRange::DataOneOf(_) => "DataOneOf",
+        DataRange::DatatypeRestriction(..) => "DatatypeRestriction",
+    }
+}
+
+fn nested_data_ranges<'a>(dr: &'a DataRange, drs: &mut Vec<&'a DataRange>) {
+    drs.push(dr);
+    match dr {
+        DataRange::DataIntersectionOf(v) | DataRange::DataUnionOf(v) => {
+            for dr in v {
+                nested_data_ranges(dr, drs);
+            }
+        }
+        DataRange::DataComplementOf(dr) => nested_data_ranges(dr, drs),
+        _ => {}
+    }
+}
+
+fn nested<'a>(
+    ce: &'a ClassExpression,
+    ces: &mut Vec<&'a ClassExpression>,
+    drs: &mut Vec<&'a DataRange>,
+) {
+    use ClassExpression::*;
+    ces.push(ce);
+    match ce {
+        ObjectIntersectionOf(v) | ObjectUnionOf(v) => {
+            for ce in v {
+                nested(ce, ces, drs);
+            }
+        }
+        ObjectComplementOf(bce)
+        | ObjectSomeValuesFrom { bce, .. }
+        | ObjectAllValuesFrom { bce, .. }
+        | ObjectMinCardinality { bce, .. }
+        | ObjectMaxCardinality { bce, .. }
+        | ObjectExactCardinality { bce, .. } => nested(bce, ces, drs),
+        DataSomeValuesFrom { dr, .. }
+        | DataAllValuesFrom { dr, .. }
+        | DataMinCardinality { dr, .. }
+        | DataMaxCardinality { dr, .. }
+        | DataExactCardinality { dr, .. } => nested_data_ranges(dr, drs),
+        _ => {}
+    }
+}
+
+/// Return every class expression and data range in `ax`, including
+/// those nested inside others.
+pub fn expressions(ax: &Axiom) -> (Vec<&ClassExpression>, Vec<&DataRange>) {
+    let top: Vec<&ClassExpression> = match ax {
+        Axiom::SubClassOf(ax) => vec![&ax.sub, &ax.sup],
+        Axiom::EquivalentClasses(ax) => ax.0.iter().collect(),
+        Axiom::DisjointClasses(ax) => ax.0.iter().collect(),
+        Axiom::DisjointUnion(ax) => ax.1.iter().collect(),
+        Axiom::ObjectPropertyDomain(ax) => vec![&ax.ce],
+        Axiom::ObjectPropertyRange(ax) => vec![&ax.ce],
+        Axiom::DataPropertyDomain(ax) => vec![&ax.ce],
+        Axiom::HasKey(ax) => vec![&ax.ce],
+        Axiom::ClassAssertion(ax) => vec![&ax.ce],
+        _ => vec![],
+    };
+
+    let mut ces = vec![];
+    let mut drs = vec![];
+    for ce in top {
+        nested(ce, &mut ces, &mut drs);
+    }
+    match ax {
+        Axiom::DataPropertyRange(ax) => nested_data_ranges(&ax.dr, &mut drs),
+        Axiom::DatatypeDefinition(ax) => nested_data_ranges(&ax.range, &mut drs),
+        _ => {}
+    }
+    (ces, drs)
+}
+
+fn depth<'a>(
+    h: &'a ClassHierarchy,
+    c: &'a Class,
+    depths: &mut BTreeMap<&'a Class, usize>,
+) -> usize {
+    if let Some(d) = depths.get(c) {
+        return *d;
+    }
+
+    // Cycles are cut where they are found
+    depths.insert(c, 0);
+    let d = 1 + h
+        .direct_super_classes(c)
+        .map(|sup| depth(h, sup, depths))
+        .max()
+        .unwrap_or(0);
+    depths.insert(c, d);
+    d
+}
+
+/// Compute the metrics of `ont`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::metrics::metrics;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.insert(SubClassOf {
+///     sub: b.class("http://www.example.com/a").into(),
+///     sup: b.class("http://www.example.com/b").into(),
+/// });
+///
+/// let m = metrics(&o);
+/// assert_eq!(m.logical_axioms, 1);
+/// assert_eq!(m.max_depth, 2);
+/// ```
+pub fn metrics(ont: &AxiomMappedOntology) -> Metrics {
+    let mut m = Metrics::default();
+
+    for ax in ont.i().iter() {
+        let kind = ax.kind();
+        m.axioms += 1;
+        if is_logical(kind) {
+            m.logical_axioms += 1;
+        }
+        *m.axiom_types.entry(kind).or_default() += 1;
+
+        let (ces, drs) = expressions(&ax.axiom);
+        for ce in ces {
+            *m.constructors.entry(class_constructor(ce)).or_default() += 1;
+        }
+        for dr in drs {
+            *m.constructors.entry(data_constructor(dr)).or_default() += 1;
+        }
+    }
+
+    for e in ont.signature() {
+        *m.entities.entry(e.kind()).or_default() += 1;
+    }
+
+    let h = ClassHierarchy::asserted(ont);
+    let mut depths = BTreeMap::new();
+    let classes: Vec<&Class> = h.classes().collect();
+    if !classes.is_empty() {
+        let total: usize = classes.iter().map(|c| depth(&h, c, &mut depths)).sum();
+        m.max_depth = depths.values().copied().max().unwrap_or(0);
+        m.average_depth = total as f64 / classes.len() as f64;
+    }
+
+    let branching: Vec<usize> = classes
+        .iter()
+        .map(|c| h.direct_sub_classes(c).count())
+        .filter(|n| *n > 0)
+        .collect();
+    if !branching.is_empty() {
+        m.max_branching = branching.iter().copied().max().unwrap_or(0);
+        m.average_branching = branching.iter().sum::<usize>() as f64 / branching.len() as f64;
+    }
+    m
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::test::read_ok;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(c).into()
+    }
+
+    #[test]
+    fn counts() {
+        let (ont, _) = read_ok(&mut include_str!("ont/owl-xml/and.owx").as_bytes());
+        let m = metrics(&ont);
+
+        assert_eq!(m.axioms, ont.i().iter().count());
+        assert_eq!(m.axiom_types[&AxiomKind::DeclareClass], 4);
+        assert_eq!(m.entities[&NamedEntityKind::Class], 4);
+        assert_eq!(m.constructors["ObjectIntersectionOf"], 1);
+        assert_eq!(m.logical_axioms, 1);
+    }
+
+    #[test]
+    fn anonymous_individuals() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(ClassAssertion {
+            ce: class(&b, "http://www.example.com/A"),
+            i: b.anonymous_individual("x"),
+        });
+        o.insert(ClassAssertion {
+            ce: class(&b, "http://www.example.com/A"),
+            i: b.named_individual("http://www.example.com/i"),
+        });
+
+        let m = metrics(&o);
+        assert_eq!(m.entities[&NamedEntityKind::NamedIndividual], 1);
+    }
+
+    #[test]
+    fn nested() {
+        let b = Build::new();
+        let ax: Axiom = SubClassOf {
+            sub: class(&b, "http://ex.com/a"),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://ex.com/r").into(),
+                bce: Box::new(ClassExpression::DataAllValuesFrom {
+                    dp: b.data_property("http://ex.com/d"),
+                    dr: DataRange::DataComplementOf(Box::new(
+                        b.datatype("http://ex.com/dt").into(),
+                    )),
+                }),
+            },
+        }
+        .into();
+
+        let (ces, drs) = expressions(&ax);
+        assert_eq!(ces.len(), 3);
+        assert_eq!(drs.len(), 2);
+    }
+
+    #[test]
+    fn hierarchy() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        for (sub, sup) in &[("b", "a"), ("c", "a"), ("d", "a"), ("e", "b")] {
+            o.insert(SubClassOf {
+                sub: class(&b, &format!("http://ex.com/{}", sub)),
+                sup: class(&b, &format!("http://ex.com/{}", sup)),
+            });
+        }
+        let m = metrics(&o);
+
+        assert_eq!(m.max_depth, 3);
+        assert!((m.average_depth - 2.0).abs() < f64::EPSILON);
+        assert_eq!(m.max_branching, 3);
+        assert!((m.average_branching - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn cycle() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(EquivalentClasses(vec![
+            class(&b, "http://ex.com/a"),
+            class(&b, "http://ex.com/b"),
+        ]));
+
+        assert_eq!(metrics(&o).max_depth, 2);
+        assert_eq!(metrics(&AxiomMappedOntology::default()).max_depth, 0);
+    }
+}
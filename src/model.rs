@@ -14,7 +14,7 @@
 //! the different axioms that OWL2 supports.
 
 //! Efficiency is gained from the use of an IRI which is a newtype
-//! over an `Rc<String>`.
+//! over an `Arc<String>`.
 
 //! # Naming
 //!
@@ -28,14 +28,14 @@
 
 //! The rules are as follows:
 //! 1. Use tuples where all the entities are of the same type and
-//! semantically equivalent
+//!    semantically equivalent
 //! 2. Use structs where entities are not equivalent.
 //! 3. Where values are equivalent and bounded in number to two, use a
-//! tuple with two values.
+//!    tuple with two values.
 //! 4. Where values are equivalent, but unbounded, use a Vec, either as
-//! part of a tuple or struct.
+//!    part of a tuple or struct.
 //! 5. Where structs are used, variables names should be short,
-//! identify the type is unique, or be descriptive if not.
+//!    identify the type is unique, or be descriptive if not.
 
 //! # Example
 //! - Rule 1:
@@ -90,14 +90,16 @@
 //! };
 //! ```
 
-use std::{cell::RefCell, fmt::Formatter};
+use crate::error::{HornedError, ValidityError};
+
+use std::fmt::Formatter;
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::hash::Hasher;
-use std::ops::Deref;
-use std::rc::Rc;
+use std::ops::{Bound, Deref};
+use std::sync::{Arc, Mutex};
 
 /// An
 /// [IRI](https://en.wikipedia.org/wiki/Internationalized_Resource_Identifier)
@@ -106,12 +108,14 @@ use std::rc::Rc;
 /// Here, we represent it as a simple string. In Horned-OWL IRIs are
 /// created through `Build`; this caches the underlying String meaning
 /// that IRIs are light-weight to `clone`.
+///
+/// IRIs are ordered by their string.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
-pub struct IRI(Rc<String>);
+pub struct IRI(Arc<String>);
 
 impl AsRef<str> for IRI {
     fn as_ref(&self) -> &str {
-        &self.0.as_str()
+        self.0.as_str()
     }
 }
 
@@ -123,9 +127,29 @@ impl Deref for IRI {
     }
 }
 
+impl IRI {
+    /// Return the node ID, if this IRI stands for a blank node.
+    ///
+    /// There is no separate type for anonymous individuals; they are
+    /// a `NamedIndividual` with an IRI of the form `_:id`, and are
+    /// left out of the signature of an ontology.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new();
+    /// assert_eq!(b.anonymous_individual("x").0.node_id(), Some("x"));
+    /// assert_eq!(b.iri("http://www.example.com").node_id(), None);
+    /// ```
+    pub fn node_id(&self) -> Option<&str> {
+        self.0.strip_prefix("_:")
+    }
+}
+
 impl From<IRI> for String {
     fn from(i: IRI) -> String {
-        // Clone Rc'd value
+        // Clone Arc'd value
         (*i.0).clone()
     }
 }
@@ -151,17 +175,99 @@ impl Display for IRI {
 /// conserved across different `Build` instances, so entities from
 /// different instances can be combined within a single ontology
 /// without consequences except for increased memory use.
+///
+/// A `Build` may also rewrite every IRI it creates with an
+/// `IRIRewrite`, so that a parser using it maps IRIs as it reads.
 
-// Currently `Build` uses Rc/RefCell, as does IRI which limits this
-// library to a single thread, as does the use of Rc in IRI. One or
-// both could be replaced by traits or enums straight-forwardly
-// enough, to enable threading.
+// The cache is behind a `Mutex` so that a `Build`, and anything
+// holding one, can be shared between threads.
 #[derive(Debug, Default)]
-pub struct Build(Rc<RefCell<BTreeSet<IRI>>>);
+pub struct Build(Arc<Mutex<BTreeSet<IRI>>>, Option<Arc<IRIRewrite>>);
+
+/// A table of substitutions for IRIs.
+///
+/// An IRI which is in the table is replaced as a whole; otherwise,
+/// the longest matching prefix is substituted.
+///
+/// # Examples
+///
+/// ```
+/// # use horned_owl::model::*;
+/// let rewrite = IRIRewrite::new()
+///     .prefix("http://old.example.com/", "http://www.example.com/")
+///     .iri("http://old.example.com/Obsolete", "http://www.example.com/Current");
+///
+/// assert_eq!(rewrite.rewrite("http://old.example.com/A"), "http://www.example.com/A");
+/// assert_eq!(rewrite.rewrite("http://old.example.com/Obsolete"), "http://www.example.com/Current");
+/// assert_eq!(rewrite.rewrite("http://other.example.com/A"), "http://other.example.com/A");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IRIRewrite {
+    iris: BTreeMap<String, String>,
+    prefixes: BTreeMap<String, String>,
+}
+
+impl IRIRewrite {
+    pub fn new() -> IRIRewrite {
+        IRIRewrite::default()
+    }
+
+    /// Replace the IRI `from` with `to`.
+    pub fn iri<S: Into<String>, T: Into<String>>(mut self, from: S, to: T) -> IRIRewrite {
+        self.iris.insert(from.into(), to.into());
+        self
+    }
+
+    /// Replace the prefix `from` of any IRI with `to`.
+    pub fn prefix<S: Into<String>, T: Into<String>>(mut self, from: S, to: T) -> IRIRewrite {
+        self.prefixes.insert(from.into(), to.into());
+        self
+    }
+
+    /// Returns true if there are no substitutions.
+    pub fn is_empty(&self) -> bool {
+        self.iris.is_empty() && self.prefixes.is_empty()
+    }
+
+    /// Return `s` with any substitutions applied.
+    pub fn rewrite(&self, s: &str) -> String {
+        if let Some(to) = self.iris.get(s) {
+            return to.clone();
+        }
+
+        // Prefixes which are a prefix of `s` sort before it, and the
+        // longest comes last
+        match self
+            .prefixes
+            .range::<str, _>((Bound::Unbounded, Bound::Included(s)))
+            .rev()
+            .find(|(from, _)| s.starts_with(from.as_str()))
+        {
+            Some((from, to)) => format!("{}{}", to, &s[from.len()..]),
+            None => s.to_string(),
+        }
+    }
+}
 
 impl Build {
     pub fn new() -> Build {
-        Build(Rc::new(RefCell::new(BTreeSet::new())))
+        Build::default()
+    }
+
+    /// Returns a `Build` which shares the cache of this one, but
+    /// rewrites every IRI it creates with `rewrite`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new();
+    /// let rb = b.with_rewrite(IRIRewrite::new().prefix("http://old/", "http://new/"));
+    ///
+    /// assert_eq!(rb.iri("http://old/A"), b.iri("http://new/A"));
+    /// ```
+    pub fn with_rewrite(&self, rewrite: IRIRewrite) -> Build {
+        Build(self.0.clone(), Some(Arc::new(rewrite)))
     }
 
     /// Constructs a new `IRI`
@@ -178,9 +284,15 @@ impl Build {
     where
         S: Into<String>,
     {
-        let iri = IRI(Rc::new(s.into()));
-
-        let mut cache = self.0.borrow_mut();
+        let s = s.into();
+        let iri = IRI(Arc::new(match &self.1 {
+            Some(rewrite) => rewrite.rewrite(&s),
+            None => s,
+        }));
+
+        // The cache is always consistent, even if another thread
+        // panicked while holding it
+        let mut cache = self.0.lock().unwrap_or_else(|e| e.into_inner());
         if cache.contains(&iri) {
             return cache.get(&iri).unwrap().clone();
         }
@@ -190,7 +302,6 @@ impl Build {
     }
 
     /// Constructs a new `Class`.
-
     ///
     /// # Examples
     ///
@@ -286,6 +397,16 @@ impl Build {
         NamedIndividual(self.iri(s))
     }
 
+    /// Constructs an anonymous individual with the node ID `id`.
+    ///
+    /// See `IRI::node_id`.
+    pub fn anonymous_individual<S>(&self, id: S) -> NamedIndividual
+    where
+        S: Into<String>,
+    {
+        NamedIndividual(self.iri(format!("_:{}", id.into())))
+    }
+
     /// Constructs a new `Datatype`.
     ///
     /// # Examples
@@ -319,11 +440,27 @@ macro_rules! named {
         /// All variants in this enum are named after the struct
         /// equivalent form. The individual structs for each variant
         /// provide us types for use elsewhere in the library.
-        #[derive(Debug, Eq, PartialEq, Hash)]
+        #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
         pub enum NamedEntity{
             $($name($name)),*
         }
 
+        impl NamedEntity {
+            /// The IRI of this entity.
+            pub fn iri(&self) -> &IRI {
+                match self {
+                    $(NamedEntity::$name(n) => &n.0),*
+                }
+            }
+
+            /// The kind of this entity.
+            pub fn kind(&self) -> NamedEntityKind {
+                match self {
+                    $(NamedEntity::$name(_) => NamedEntityKind::$name),*
+                }
+            }
+        }
+
         $(
             $(#[$attr]) *
             #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -469,15 +606,6 @@ impl From<NamedEntity> for AnnotatedAxiom {
     }
 }
 
-/// An interface providing access to any `Annotation` attached to an
-/// entity.
-trait Annotated {
-    /// Return the annotation
-    ///
-    /// The returned `BTreeSet` may be empty.
-    fn annotation(&self) -> &BTreeSet<Annotation>;
-}
-
 /// An interface providing access to the `AxiomKind`
 ///
 /// An OWL ontology consists of a set of axioms of one of many
@@ -488,6 +616,36 @@ pub trait Kinded {
     fn kind(&self) -> AxiomKind;
 }
 
+/// An axiom struct, such as `SubClassOf`, of a single `AxiomKind`
+///
+/// This allows code to work generically over the different kinds of
+/// axiom, without matching on every variant of `Axiom`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// fn count<A: AxiomType>(axioms: &[Axiom]) -> usize {
+///     axioms.iter().filter_map(A::from_axiom).count()
+/// }
+///
+/// let b = Build::new();
+/// let axioms: Vec<Axiom> = vec![
+///     DeclareClass(b.class("http://www.example.com/a")).into(),
+///     DeclareClass(b.class("http://www.example.com/b")).into(),
+/// ];
+///
+/// assert_eq!(DeclareClass::KIND, AxiomKind::DeclareClass);
+/// assert_eq!(count::<DeclareClass>(&axioms), 2);
+/// assert_eq!(count::<SubClassOf>(&axioms), 0);
+/// ```
+pub trait AxiomType: Kinded + Into<Axiom> + Sized + 'static {
+    /// The kind of all axioms of this type.
+    const KIND: AxiomKind;
+
+    /// Return the axiom struct of `ax`, if it is of this type.
+    fn from_axiom(ax: &Axiom) -> Option<&Self>;
+}
+
 /// An `AnnotatedAxiom` is an `Axiom` with one orpmore `Annotation`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct AnnotatedAxiom {
@@ -518,7 +676,7 @@ impl AnnotatedAxiom {
         self.axiom == other.axiom
     }
 
-    pub fn logical_hash<H: Hasher>(&self, state: &mut H) -> () {
+    pub fn logical_hash<H: Hasher>(&self, state: &mut H) {
         self.axiom.hash(state)
     }
 }
@@ -538,9 +696,20 @@ impl Kinded for AnnotatedAxiom {
     }
 }
 
-/// Add `Kinded` and `From` for each axiom.
+/// Add `Kinded`, `AxiomType` and `From` for each axiom.
 macro_rules! axiomimpl {
     ($name:ident) => {
+        impl AxiomType for $name {
+            const KIND: AxiomKind = AxiomKind::$name;
+
+            fn from_axiom(ax: &Axiom) -> Option<&$name> {
+                match ax {
+                    Axiom::$name(n) => Some(n),
+                    _ => None,
+                }
+            }
+        }
+
         impl From<$name> for Axiom {
             fn from(ax: $name) -> Axiom {
                 Axiom::$name(ax)
@@ -591,11 +760,11 @@ macro_rules! axiom {
         }
 
         impl $name {
-            pub fn new($($field_name: $field_type),*)
+            pub fn new($($field_name: impl Into<$field_type>),*)
                 -> $name
             {
                 $name {
-                    $($field_name),*
+                    $($field_name: $field_name.into()),*
                 }
             }
 
@@ -661,6 +830,10 @@ macro_rules! axioms {
         /// (i.e. Axiom::SubClassOf(SubClassOf)), which is used as a union
         /// type for all structs. The struct and enum variants all
         /// share identical names.
+        ///
+        /// Axioms are ordered first by their `AxiomKind`, and then by
+        /// their content, so a sorted collection of axioms is grouped
+        /// by kind, and can be searched with `binary_search`.
         #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
         pub enum Axiom{
             $($name($name)),*
@@ -1084,6 +1257,61 @@ impl Literal {
             Literal::Datatype { literal, .. } => literal,
         }
     }
+
+    /// Return the language tag, if this is a language-tagged literal.
+    pub fn lang(&self) -> Option<&str> {
+        match self {
+            Literal::Language { lang, .. } => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// Return the IRI of the datatype, which is `xsd:string` for a
+    /// simple literal, and `rdf:langString` for a language-tagged
+    /// one.
+    pub fn datatype_str(&self) -> &str {
+        use crate::vocab::{OWL2Datatype, WithIRI};
+        match self {
+            Literal::Simple { .. } => OWL2Datatype::XSDString.iri_str(),
+            Literal::Language { .. } => OWL2Datatype::RDFLangString.iri_str(),
+            Literal::Datatype { datatype_iri, .. } => datatype_iri.as_str(),
+        }
+    }
+
+    /// Return true if this literal has the language tag `lang`,
+    /// ignoring case.
+    pub fn has_lang(&self, lang: &str) -> bool {
+        self.lang().is_some_and(|l| l.eq_ignore_ascii_case(lang))
+    }
+
+    /// Return true if `self` and `other` are the same literal, where
+    /// a simple literal is the same as an `xsd:string`, and language
+    /// tags are compared ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new();
+    /// let simple = Literal::Simple { literal: "a".to_string() };
+    /// let string = Literal::Datatype {
+    ///     literal: "a".to_string(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#string"),
+    /// };
+    /// let en = |lang: &str| Literal::Language { literal: "a".to_string(), lang: lang.to_string() };
+    ///
+    /// assert_ne!(simple, string);
+    /// assert!(simple.value_eq(&string));
+    /// assert!(en("en-GB").value_eq(&en("en-gb")));
+    /// assert!(!en("en").value_eq(&simple));
+    /// ```
+    pub fn value_eq(&self, other: &Literal) -> bool {
+        self.literal() == other.literal()
+            && self.datatype_str() == other.datatype_str()
+            && match (self.lang(), other.lang()) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (a, b) => a == b,
+            }
+    }
 }
 
 // #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -1104,12 +1332,49 @@ pub struct Annotation {
 }
 
 /// The value of an annotation
-///
-/// This Enum is currently not complete.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum AnnotationValue {
     Literal(Literal),
     IRI(IRI),
+    /// An anonymous individual; see `IRI::node_id`.
+    AnonymousIndividual(NamedIndividual),
+}
+
+impl AnnotationValue {
+    pub fn as_literal(&self) -> Option<&Literal> {
+        match self {
+            AnnotationValue::Literal(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    pub fn as_iri(&self) -> Option<&IRI> {
+        match self {
+            AnnotationValue::IRI(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    pub fn as_anonymous_individual(&self) -> Option<&NamedIndividual> {
+        match self {
+            AnnotationValue::AnonymousIndividual(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Return the lexical form of a literal value.
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_literal().map(|l| l.literal().as_str())
+    }
+
+    /// Return true if `self` and `other` are the same value,
+    /// comparing literals with `Literal::value_eq`.
+    pub fn value_eq(&self, other: &AnnotationValue) -> bool {
+        match (self, other) {
+            (AnnotationValue::Literal(a), AnnotationValue::Literal(b)) => a.value_eq(b),
+            _ => self == other,
+        }
+    }
 }
 
 impl From<Literal> for AnnotationValue {
@@ -1124,6 +1389,18 @@ impl From<IRI> for AnnotationValue {
     }
 }
 
+/// An anonymous individual becomes an `AnonymousIndividual`, and any
+/// other an `IRI`.
+impl From<NamedIndividual> for AnnotationValue {
+    fn from(i: NamedIndividual) -> AnnotationValue {
+        if i.0.node_id().is_some() {
+            AnnotationValue::AnonymousIndividual(i)
+        } else {
+            AnnotationValue::IRI(i.0)
+        }
+    }
+}
+
 /// A object property expression
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum ObjectPropertyExpression {
@@ -1137,6 +1414,12 @@ impl From<ObjectProperty> for ObjectPropertyExpression {
     }
 }
 
+impl From<&ObjectProperty> for ObjectPropertyExpression {
+    fn from(op: &ObjectProperty) -> ObjectPropertyExpression {
+        op.clone().into()
+    }
+}
+
 impl From<IRI> for ObjectPropertyExpression {
     fn from(iri: IRI) -> ObjectPropertyExpression {
         let op: ObjectProperty = iri.into();
@@ -1174,6 +1457,18 @@ impl From<ObjectPropertyExpression> for SubObjectPropertyExpression {
     }
 }
 
+impl From<ObjectProperty> for SubObjectPropertyExpression {
+    fn from(op: ObjectProperty) -> SubObjectPropertyExpression {
+        ObjectPropertyExpression::from(op).into()
+    }
+}
+
+impl From<Vec<ObjectPropertyExpression>> for SubObjectPropertyExpression {
+    fn from(chain: Vec<ObjectPropertyExpression>) -> SubObjectPropertyExpression {
+        SubObjectPropertyExpression::ObjectPropertyChain(chain)
+    }
+}
+
 /// A property expression
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum PropertyExpression {
@@ -1187,12 +1482,24 @@ impl From<ObjectPropertyExpression> for PropertyExpression {
         PropertyExpression::ObjectPropertyExpression(ope)
     }
 }
+impl From<ObjectProperty> for PropertyExpression {
+    fn from(op: ObjectProperty) -> PropertyExpression {
+        ObjectPropertyExpression::from(op).into()
+    }
+}
+
 impl From<DataProperty> for PropertyExpression {
     fn from(dp: DataProperty) -> PropertyExpression {
         PropertyExpression::DataProperty(dp)
     }
 }
 
+impl From<AnnotationProperty> for PropertyExpression {
+    fn from(ap: AnnotationProperty) -> PropertyExpression {
+        PropertyExpression::AnnotationProperty(ap)
+    }
+}
+
 // Data!!!
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FacetRestriction {
@@ -1231,6 +1538,18 @@ impl From<Datatype> for DataRange {
     }
 }
 
+impl From<&Datatype> for DataRange {
+    fn from(dr: &Datatype) -> DataRange {
+        DataRange::Datatype(dr.clone())
+    }
+}
+
+impl From<Datatype> for Box<DataRange> {
+    fn from(dr: Datatype) -> Box<DataRange> {
+        Box::new(dr.into())
+    }
+}
+
 /// A class expression
 ///
 /// As well as a named class, it is possible to define classes of
@@ -1420,12 +1739,70 @@ impl From<Class> for Box<ClassExpression> {
 /// An ontology is identified by an IRI which is expected to remain
 /// stable over the lifetime of the ontology, and a version IRI which
 /// is expected to change between versions.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct OntologyID {
     pub iri: Option<IRI>,
     pub viri: Option<IRI>,
 }
 
+impl OntologyID {
+    /// Create a new `OntologyID` with an IRI and, optionally, a
+    /// version IRI.
+    pub fn new(iri: IRI, viri: Option<IRI>) -> OntologyID {
+        OntologyID {
+            iri: Some(iri),
+            viri,
+        }
+    }
+
+    /// Create an `OntologyID` for an anonymous ontology.
+    pub fn anonymous() -> OntologyID {
+        OntologyID::default()
+    }
+
+    /// Return true if the ontology has no IRI.
+    ///
+    /// An anonymous ontology cannot have a version IRI, so any
+    /// version IRI is ignored.
+    pub fn is_anonymous(&self) -> bool {
+        self.iri.is_none()
+    }
+
+    /// Return the version IRI, if the ontology is not anonymous.
+    pub fn version_iri(&self) -> Option<&IRI> {
+        self.iri.as_ref().and(self.viri.as_ref())
+    }
+
+    /// Set the IRI, returning the previous IRI if there was one.
+    pub fn set_iri(&mut self, iri: IRI) -> Option<IRI> {
+        self.iri.replace(iri)
+    }
+
+    /// Set the version IRI, returning the previous version IRI if
+    /// there was one.
+    ///
+    /// An anonymous ontology cannot have a version IRI, so this
+    /// fails if the ontology is anonymous.
+    pub fn set_version_iri(&mut self, viri: IRI) -> Result<Option<IRI>, HornedError> {
+        if self.is_anonymous() {
+            return Err(ValidityError::AnonymousVersionIRI(viri.to_string()).into());
+        }
+        Ok(self.viri.replace(viri))
+    }
+
+    /// Remove the IRI and the version IRI, making the ontology
+    /// anonymous, and return the previous IRI.
+    pub fn clear_iri(&mut self) -> Option<IRI> {
+        self.viri = None;
+        self.iri.take()
+    }
+
+    /// Remove the version IRI, and return it.
+    pub fn clear_version_iri(&mut self) -> Option<IRI> {
+        self.viri.take()
+    }
+}
+
 /// Access or change the `OntologyID` of an `Ontology`
 pub trait Ontology {
     fn id(&self) -> &OntologyID;
@@ -1478,6 +1855,65 @@ pub trait MutableOntology {
         let ax: Axiom = ne.into();
         self.insert(ax)
     }
+
+    /// Insert many axioms into the ontology, returning the number
+    /// which were not already present.
+    ///
+    /// Implementations may update their indexes once for all of the
+    /// axioms, which is faster than calling `insert` for each. Like
+    /// `insert`, this does not validate the axioms; see `try_extend`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let n = o.extend(
+    ///     (0..10).map(|i| DeclareClass(b.class(format!("http://www.example.com/{}", i % 5)))),
+    /// );
+    ///
+    /// assert_eq!(n, 5);
+    /// ```
+    fn extend<I, A>(&mut self, axs: I) -> usize
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        let mut n = 0;
+        for ax in axs {
+            if self.insert(ax) {
+                n += 1;
+            }
+        }
+        n
+    }
+
+    /// Validate `axs` with `validate::validate_axioms`, and insert
+    /// them as `extend` does if there are no problems.
+    ///
+    /// Problems which depend on the rest of the ontology, such as
+    /// undeclared entities, are not checked.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    ///
+    /// assert!(o.try_extend(vec![DeclareClass(b.class("not an iri"))]).is_err());
+    /// assert_eq!(o.try_extend(vec![DeclareClass(b.class("http://www.example.com/a"))]).unwrap(), 1);
+    /// ```
+    fn try_extend<I, A>(&mut self, axs: I) -> Result<usize, HornedError>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        let axs: Vec<AnnotatedAxiom> = axs.into_iter().map(Into::into).collect();
+        crate::validate::validate_axioms(&axs).into_result()?;
+        Ok(self.extend(axs))
+    }
 }
 
 #[cfg(test)]
@@ -1505,10 +1941,10 @@ mod test {
         assert_eq!(iri1, iri2);
 
         // these are the same object in memory
-        assert!(Rc::ptr_eq(&iri1.0, &iri2.0));
+        assert!(Arc::ptr_eq(&iri1.0, &iri2.0));
 
         // iri1, iri2 and one in the cache == 3
-        assert_eq!(Rc::strong_count(&iri1.0), 3);
+        assert_eq!(Arc::strong_count(&iri1.0), 3);
     }
 
     #[test]
@@ -1520,7 +1956,7 @@ mod test {
         let iri_from_iri = build.iri(iri_static.clone());
 
         let s = "http://www.example.com";
-        let iri_str = build.iri(&s[..]);
+        let iri_str = build.iri(s);
 
         assert_eq!(iri_string, iri_static);
         assert_eq!(iri_string, iri_str);
@@ -1616,6 +2052,114 @@ mod test {
 
 
         decl1.ann.insert(ann);
-        assert!(!(decl1 == decl2));
+        assert!((decl1 != decl2));
+    }
+
+    #[test]
+    fn test_iri_rewrite() {
+        let rewrite = IRIRewrite::new()
+            .prefix("http://a/", "http://b/")
+            .prefix("http://a/x/", "http://c/")
+            .prefix("http://a/y", "http://d/");
+
+        assert_eq!(rewrite.rewrite("http://a/x/1"), "http://c/1");
+        assert_eq!(rewrite.rewrite("http://a/z"), "http://b/z");
+        assert_eq!(rewrite.rewrite("http://a/"), "http://b/");
+        assert_eq!(rewrite.rewrite("http://"), "http://");
+
+        let b = Build::new();
+        let rb = b.with_rewrite(rewrite);
+        assert!(Arc::ptr_eq(&rb.iri("http://a/x/1").0, &b.iri("http://c/1").0));
+    }
+
+    #[test]
+    fn test_ontology_id() {
+        let b = Build::new();
+        let mut id = OntologyID::anonymous();
+        assert!(id.is_anonymous());
+
+        assert_eq!(id.set_iri(b.iri("http://www.example.com/o")), None);
+        assert_eq!(
+            id.set_version_iri(b.iri("http://www.example.com/v1")).unwrap(),
+            None
+        );
+        assert_eq!(
+            id,
+            OntologyID::new(
+                b.iri("http://www.example.com/o"),
+                Some(b.iri("http://www.example.com/v1"))
+            )
+        );
+        assert_eq!(
+            id.set_version_iri(b.iri("http://www.example.com/v2")).unwrap(),
+            Some(b.iri("http://www.example.com/v1"))
+        );
+
+        assert_eq!(id.clear_iri(), Some(b.iri("http://www.example.com/o")));
+        assert!(id.is_anonymous());
+        assert_eq!(id.version_iri(), None);
+        assert_eq!(id, OntologyID::anonymous());
+    }
+
+    #[test]
+    fn test_anonymous_version_iri() {
+        let b = Build::new();
+        let mut id = OntologyID::anonymous();
+        assert!(matches!(
+            id.set_version_iri(b.iri("http://www.example.com/v1")),
+            Err(HornedError::ValidityError(ValidityError::AnonymousVersionIRI(_)))
+        ));
+        assert_eq!(id, OntologyID::anonymous());
+    }
+
+    #[test]
+    fn test_into_expression() {
+        let b = Build::new();
+        let a = b.class("http://www.example.com/a");
+        let r = b.object_property("http://www.example.com/r");
+
+        // Fields are in declaration order, so the super class is first
+        let sc = SubClassOf::new(
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: (&r).into(),
+                bce: a.clone().into(),
+            },
+            &a,
+        );
+        assert_eq!(sc.sub, ClassExpression::Class(a));
+
+        let chain: Vec<ObjectPropertyExpression> = vec![r.clone().into(), r.clone().into()];
+        let sop = SubObjectPropertyOf::new(r, chain);
+        assert!(matches!(
+            sop.sub,
+            SubObjectPropertyExpression::ObjectPropertyChain(_)
+        ));
+
+        let dt = b.datatype("http://www.example.com/d");
+        let dr = DataPropertyRange::new(b.data_property("http://www.example.com/p"), &dt);
+        assert_eq!(dr.dr, DataRange::Datatype(dt));
+    }
+
+    #[test]
+    fn test_axiom_order() {
+        let b = Build::new();
+        let a = b.class("http://www.example.com/a");
+        let z = b.class("http://www.example.com/z");
+
+        let mut v: Vec<Axiom> = vec![
+            SubClassOf::new(&a, &z).into(),
+            DeclareClass(z.clone()).into(),
+            SubClassOf::new(&z, &a).into(),
+            DeclareClass(a.clone()).into(),
+            DisjointClasses(vec![a.clone().into(), z.clone().into()]).into(),
+        ];
+        v.sort();
+
+        assert!(v.windows(2).all(|w| w[0].kind() <= w[1].kind()));
+        assert_eq!(v[0], DeclareClass(a.clone()).into());
+        assert!(v.binary_search(&SubClassOf::new(&z, &a).into()).is_ok());
+
+        let m = b.class("http://www.example.com/m");
+        assert_eq!(v.binary_search(&DeclareClass(m).into()), Err(1));
     }
 }
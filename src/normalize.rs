@@ -0,0 +1,397 @@
+//! Normalization of class axioms
+//!
+//! `normalize` rewrites the class axioms of an ontology into the
+//! normal forms used by consequence-based reasoners, such as ELK:
+//!
+//! - `A SubClassOf B`
+//! - `A1 and A2 SubClassOf B`
+//! - `A SubClassOf r some B`
+//! - `r some A SubClassOf B`
+//!
+//! where `A` and `B` are named classes, including `owl:Thing` and
+//! `owl:Nothing`. Complex class expressions are replaced with fresh
+//! named classes. Expressions with constructors outside of EL are
+//! given a named class on one side, but are otherwise left in place,
+//! as are all other axioms.
+use crate::fresh::{FreshIRI, Policy};
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::{WithIRI, OWL};
+
+use std::collections::BTreeSet;
+
+struct Normalizer<'a> {
+    b: &'a Build,
+    iris: FreshIRI<'a>,
+    fresh: Vec<Class>,
+    out: Vec<AnnotatedAxiom>,
+}
+
+fn is_named(ce: &ClassExpression) -> bool {
+    matches!(ce, ClassExpression::Class(_))
+}
+
+impl<'a> Normalizer<'a> {
+    fn fresh(&mut self) -> ClassExpression {
+        let c = Class(
+            self.iris
+                .iri()
+                .expect("an unpadded counter is never exhausted"),
+        );
+        self.fresh.push(c.clone());
+        c.into()
+    }
+
+    fn builtin(&self, builtin: OWL) -> ClassExpression {
+        self.b.class(builtin.iri_str()).into()
+    }
+
+    fn emit(&mut self, sub: ClassExpression, sup: ClassExpression, ann: &BTreeSet<Annotation>) {
+        self.out
+            .push(AnnotatedAxiom::new(SubClassOf { sub, sup }, ann.clone()));
+    }
+
+    /// Return `ce` if it is named, or a fresh class which is a sub
+    /// class of `ce`.
+    fn name_sup(&mut self, ce: &ClassExpression, ann: &BTreeSet<Annotation>) -> ClassExpression {
+        if is_named(ce) {
+            ce.clone()
+        } else {
+            let x = self.fresh();
+            self.sub_class_of(&x, ce, ann);
+            x
+        }
+    }
+
+    /// Return `ce` if it is named, or a fresh class which is a super
+    /// class of `ce`.
+    fn name_sub(&mut self, ce: &ClassExpression, ann: &BTreeSet<Annotation>) -> ClassExpression {
+        if is_named(ce) {
+            ce.clone()
+        } else {
+            let x = self.fresh();
+            self.sub_class_of(ce, &x, ann);
+            x
+        }
+    }
+
+    fn sub_class_of(
+        &mut self,
+        sub: &ClassExpression,
+        sup: &ClassExpression,
+        ann: &BTreeSet<Annotation>,
+    ) {
+        use ClassExpression::*;
+
+        if let ObjectIntersectionOf(v) = sup {
+            for ce in v {
+                self.sub_class_of(sub, ce, ann);
+            }
+            return;
+        }
+        if let ObjectUnionOf(v) = sub {
+            for ce in v {
+                self.sub_class_of(ce, sup, ann);
+            }
+            return;
+        }
+        if *sup == self.builtin(OWL::Thing) || *sub == self.builtin(OWL::Nothing) {
+            return;
+        }
+
+        match (is_named(sub), is_named(sup)) {
+            (false, false) => {
+                let x = self.fresh();
+                self.sub_class_of(sub, &x, ann);
+                self.sub_class_of(&x, sup, ann);
+            }
+            (true, _) => match sup {
+                ObjectSomeValuesFrom { ope, bce } => {
+                    let bce = Box::new(self.name_sup(bce, ann));
+                    let ope = ope.clone();
+                    self.emit(sub.clone(), ObjectSomeValuesFrom { ope, bce }, ann);
+                }
+                _ => self.emit(sub.clone(), sup.clone(), ann),
+            },
+            (false, true) => match sub {
+                ObjectSomeValuesFrom { ope, bce } => {
+                    let bce = Box::new(self.name_sub(bce, ann));
+                    let ope = ope.clone();
+                    self.emit(ObjectSomeValuesFrom { ope, bce }, sup.clone(), ann);
+                }
+                ObjectIntersectionOf(v) => {
+                    let mut named: Vec<ClassExpression> =
+                        v.iter().map(|ce| self.name_sub(ce, ann)).collect();
+                    self.conjunction(&mut named, sup, ann);
+                }
+                _ => self.emit(sub.clone(), sup.clone(), ann),
+            },
+        }
+    }
+
+    /// Split an intersection of named classes into binary
+    /// conjunctions.
+    fn conjunction(
+        &mut self,
+        named: &mut Vec<ClassExpression>,
+        sup: &ClassExpression,
+        ann: &BTreeSet<Annotation>,
+    ) {
+        match named.len() {
+            0 => self.emit(self.builtin(OWL::Thing), sup.clone(), ann),
+            1 => self.emit(named.remove(0), sup.clone(), ann),
+            2 => self.emit(
+                ClassExpression::ObjectIntersectionOf(named.clone()),
+                sup.clone(),
+                ann,
+            ),
+            _ => {
+                let first = named.remove(0);
+                let second = named.remove(0);
+                let x = self.fresh();
+                self.emit(
+                    ClassExpression::ObjectIntersectionOf(vec![first, second]),
+                    x.clone(),
+                    ann,
+                );
+                named.insert(0, x);
+                self.conjunction(named, sup, ann);
+            }
+        }
+    }
+
+    fn axiom(&mut self, ax: &AnnotatedAxiom) {
+        let ann = &ax.ann;
+        match &ax.axiom {
+            Axiom::SubClassOf(sc) => self.sub_class_of(&sc.sub, &sc.sup, ann),
+            Axiom::EquivalentClasses(ec) => {
+                if let Some((first, rest)) = ec.0.split_first() {
+                    for ce in rest {
+                        self.sub_class_of(first, ce, ann);
+                        self.sub_class_of(ce, first, ann);
+                    }
+                }
+            }
+            Axiom::DisjointClasses(dc) => self.disjoint(&dc.0, ann),
+            Axiom::DisjointUnion(du) => {
+                let c: ClassExpression = du.0.clone().into();
+                self.sub_class_of(&c, &ClassExpression::ObjectUnionOf(du.1.clone()), ann);
+                for ce in &du.1 {
+                    self.sub_class_of(ce, &c, ann);
+                }
+                self.disjoint(&du.1, ann);
+            }
+            Axiom::ObjectPropertyDomain(opd) => {
+                let some = ClassExpression::ObjectSomeValuesFrom {
+                    ope: opd.ope.clone(),
+                    bce: Box::new(self.builtin(OWL::Thing)),
+                };
+                self.sub_class_of(&some, &opd.ce, ann);
+            }
+            Axiom::ClassAssertion(ca) if !is_named(&ca.ce) => {
+                let x = self.name_sup(&ca.ce, ann);
+                self.out.push(AnnotatedAxiom::new(
+                    ClassAssertion {
+                        ce: x,
+                        i: ca.i.clone(),
+                    },
+                    ann.clone(),
+                ));
+            }
+            _ => self.out.push(ax.clone()),
+        }
+    }
+
+    fn disjoint(&mut self, v: &[ClassExpression], ann: &BTreeSet<Annotation>) {
+        let nothing = self.builtin(OWL::Nothing);
+        for (i, a) in v.iter().enumerate() {
+            for b in &v[i + 1..] {
+                let both = ClassExpression::ObjectIntersectionOf(vec![a.clone(), b.clone()]);
+                self.sub_class_of(&both, &nothing, ann);
+            }
+        }
+    }
+}
+
+/// Return a copy of `ont`, with its class axioms in normal form.
+///
+/// Fresh classes have IRIs formed from the ontology IRI, and are
+/// declared in the result.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::normalize::normalize;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.insert(SubClassOf {
+///     sub: b.class("http://www.example.com/a").into(),
+///     sup: ClassExpression::ObjectIntersectionOf(vec![
+///         b.class("http://www.example.com/b").into(),
+///         b.class("http://www.example.com/c").into(),
+///     ]),
+/// });
+///
+/// assert_eq!(normalize(&o).i().sub_class_of().count(), 2);
+/// ```
+pub fn normalize(ont: &AxiomMappedOntology) -> AxiomMappedOntology {
+    let b = Build::new();
+    let prefix = match &ont.id().iri {
+        Some(iri) => format!("{}#normalize-", iri.as_str().trim_end_matches('#')),
+        None => "urn:horned-owl:normalize:".to_string(),
+    };
+
+    let mut n = Normalizer {
+        b: &b,
+        iris: FreshIRI::new(&b, prefix, Policy::Numeric { width: 0 }).avoiding(ont),
+        fresh: vec![],
+        out: vec![],
+    };
+    for ax in ont.i().iter() {
+        n.axiom(ax);
+    }
+
+    let mut result = AxiomMappedOntology::default();
+    *result.mut_id() = ont.id().clone();
+    for c in n.fresh {
+        result.declare(c);
+    }
+    for ax in n.out {
+        result.insert(ax);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reasoner::structural::StructuralReasoner;
+    use crate::reasoner::Reasoner;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(c).into()
+    }
+
+    fn some(b: &Build, ce: ClassExpression) -> ClassExpression {
+        ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://ex.com/r").into(),
+            bce: Box::new(ce),
+        }
+    }
+
+    fn and(v: Vec<ClassExpression>) -> ClassExpression {
+        ClassExpression::ObjectIntersectionOf(v)
+    }
+
+    /// Returns true if every class axiom of `o` is in normal form.
+    fn is_normal(o: &AxiomMappedOntology) -> bool {
+        o.i().sub_class_of().all(|sc| match (&sc.sub, &sc.sup) {
+            (ClassExpression::Class(_), ClassExpression::Class(_)) => true,
+            (ClassExpression::ObjectIntersectionOf(v), ClassExpression::Class(_)) => {
+                v.len() == 2 && v.iter().all(is_named)
+            }
+            (ClassExpression::Class(_), ClassExpression::ObjectSomeValuesFrom { bce, .. })
+            | (ClassExpression::ObjectSomeValuesFrom { bce, .. }, ClassExpression::Class(_)) => {
+                is_named(bce)
+            }
+            _ => false,
+        }) && o.i().equivalent_class().count() == 0
+            && o.i().disjoint_class().count() == 0
+    }
+
+    #[test]
+    fn nested() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(EquivalentClasses(vec![
+            class(&b, "http://ex.com/a"),
+            and(vec![
+                class(&b, "http://ex.com/b"),
+                some(
+                    &b,
+                    and(vec![
+                        class(&b, "http://ex.com/c"),
+                        some(&b, class(&b, "http://ex.com/d")),
+                    ]),
+                ),
+                class(&b, "http://ex.com/e"),
+            ]),
+        ]));
+        o.insert(DisjointClasses(vec![
+            class(&b, "http://ex.com/b"),
+            class(&b, "http://ex.com/e"),
+        ]));
+        o.insert(ObjectPropertyDomain {
+            ope: b.object_property("http://ex.com/r").into(),
+            ce: class(&b, "http://ex.com/b"),
+        });
+
+        let n = normalize(&o);
+        assert!(is_normal(&n));
+        assert!(n.i().declare_class().count() > 0);
+    }
+
+    #[test]
+    fn entailments() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/a"),
+            sup: some(
+                &b,
+                and(vec![
+                    class(&b, "http://ex.com/b"),
+                    class(&b, "http://ex.com/c"),
+                ]),
+            ),
+        });
+        let r = StructuralReasoner::new(normalize(&o));
+
+        assert!(r.is_subclass(
+            &class(&b, "http://ex.com/a"),
+            &some(&b, class(&b, "http://ex.com/b"))
+        ));
+    }
+
+    #[test]
+    fn fresh() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.mut_id().iri = Some(b.iri("http://ex.com/o"));
+        o.declare(b.class("http://ex.com/o#normalize-1"));
+        o.insert(SubClassOf {
+            sub: some(&b, class(&b, "http://ex.com/a")),
+            sup: some(&b, class(&b, "http://ex.com/b")),
+        });
+
+        let n = normalize(&o);
+        assert!(is_normal(&n));
+        assert!(n
+            .i()
+            .declare_class()
+            .any(|dc| dc.0 == b.class("http://ex.com/o#normalize-2")));
+        assert_eq!(n.id(), o.id());
+    }
+
+    #[test]
+    fn outside_el() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let all = ClassExpression::ObjectAllValuesFrom {
+            ope: b.object_property("http://ex.com/r").into(),
+            bce: Box::new(class(&b, "http://ex.com/b")),
+        };
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/a"),
+            sup: all.clone(),
+        });
+        o.insert(TransitiveObjectProperty(
+            b.object_property("http://ex.com/r").into(),
+        ));
+
+        let n = normalize(&o);
+        assert!(n.i().sub_class_of().any(|sc| sc.sup == all));
+        assert_eq!(n.i().transitive_object_property().count(), 1);
+    }
+}
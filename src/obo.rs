@@ -0,0 +1,306 @@
+//! OBO-style identifiers
+//!
+//! Terms in OBO Foundry ontologies are usually referred to by an ID
+//! such as `GO:0008150`, made of an ID space and a local ID, rather
+//! than by their IRI, here
+//! `http://purl.obolibrary.org/obo/GO_0008150`. An `IdSpaceRegistry`
+//! converts between the two, using the OBO Foundry PURL for any ID
+//! space which has not been registered with another IRI prefix.
+//!
+//! The metadata of OBO terms, such as synonyms and cross references,
+//! is stored as annotations with the oboInOwl vocabulary; functions
+//! such as `synonyms_of` and `xrefs_of` return these.
+use crate::error::OBOError;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::{OBOInOwl, WithIRI};
+
+use crate::error::HornedError;
+
+use std::collections::BTreeMap;
+
+/// The IRI prefix of OBO Foundry PURLs.
+pub const PURL: &str = "http://purl.obolibrary.org/obo/";
+
+fn is_id_space(space: &str) -> bool {
+    !space.is_empty() && space.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_local_id(local: &str) -> bool {
+    !local.is_empty() && !local.chars().any(char::is_whitespace)
+}
+
+/// The IRI prefixes of ID spaces.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IdSpaceRegistry(BTreeMap<String, String>);
+
+impl IdSpaceRegistry {
+    pub fn new() -> IdSpaceRegistry {
+        IdSpaceRegistry::default()
+    }
+
+    /// Use `prefix` for the IRIs of `space`, so that the local ID is
+    /// appended to it.
+    pub fn register<S, P>(&mut self, space: S, prefix: P)
+    where
+        S: Into<String>,
+        P: Into<String>,
+    {
+        self.0.insert(space.into(), prefix.into());
+    }
+
+    /// Return the IRI prefix for `space`.
+    pub fn prefix(&self, space: &str) -> String {
+        self.0
+            .get(space)
+            .cloned()
+            .unwrap_or_else(|| format!("{}{}_", PURL, space))
+    }
+
+    /// Return the IRI for the OBO-style `id`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::Build;
+    /// # use horned_owl::obo::IdSpaceRegistry;
+    /// let mut reg = IdSpaceRegistry::new();
+    /// reg.register("EX", "http://www.example.com/ex#");
+    ///
+    /// let b = Build::new();
+    /// assert_eq!(
+    ///     reg.to_iri(&b, "GO:0008150").unwrap().as_str(),
+    ///     "http://purl.obolibrary.org/obo/GO_0008150"
+    /// );
+    /// assert_eq!(
+    ///     reg.to_iri(&b, "EX:1").unwrap().as_str(),
+    ///     "http://www.example.com/ex#1"
+    /// );
+    /// ```
+    pub fn to_iri(&self, b: &Build, id: &str) -> Result<IRI, HornedError> {
+        match id.split_once(':') {
+            Some((space, local)) if is_id_space(space) && is_local_id(local) => {
+                Ok(b.iri(format!("{}{}", self.prefix(space), local)))
+            }
+            _ => Err(OBOError::InvalidID(id.to_string()).into()),
+        }
+    }
+
+    /// Return the OBO-style ID for `iri`, if it is in a registered
+    /// ID space or is an OBO Foundry PURL.
+    pub fn to_id(&self, iri: &str) -> Option<String> {
+        // Prefer the longest registered prefix
+        let registered = self
+            .0
+            .iter()
+            .filter(|(_, prefix)| iri.starts_with(prefix.as_str()))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(space, prefix)| (space.as_str(), &iri[prefix.len()..]));
+
+        let (space, local) = registered.or_else(|| iri.strip_prefix(PURL)?.split_once('_'))?;
+        if is_id_space(space) && is_local_id(local) {
+            Some(format!("{}:{}", space, local))
+        } else {
+            None
+        }
+    }
+}
+
+/// Return the OBO Foundry PURL for `id`.
+pub fn id_to_iri(b: &Build, id: &str) -> Result<IRI, HornedError> {
+    IdSpaceRegistry::new().to_iri(b, id)
+}
+
+/// Return the OBO-style ID for `iri`, if it is an OBO Foundry PURL.
+pub fn iri_to_id(iri: &str) -> Option<String> {
+    IdSpaceRegistry::new().to_id(iri)
+}
+
+/// The scope of a synonym.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SynonymScope {
+    Exact,
+    Broad,
+    Narrow,
+    Related,
+}
+
+impl SynonymScope {
+    /// The annotation property for synonyms of this scope.
+    pub fn property(self) -> OBOInOwl {
+        match self {
+            SynonymScope::Exact => OBOInOwl::HasExactSynonym,
+            SynonymScope::Broad => OBOInOwl::HasBroadSynonym,
+            SynonymScope::Narrow => OBOInOwl::HasNarrowSynonym,
+            SynonymScope::Related => OBOInOwl::HasRelatedSynonym,
+        }
+    }
+}
+
+/// Iterate over the literal values of the annotation assertions on
+/// `subject` with the oboInOwl property `ap`.
+pub fn values_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    subject: &'a IRI,
+    ap: OBOInOwl,
+) -> impl Iterator<Item = &'a String> {
+    ont.i()
+        .annotation_assertion()
+        .filter(move |aa| &aa.subject == subject && aa.ann.ap.0.as_str() == ap.iri_str())
+        .filter_map(|aa| aa.ann.av.as_literal().map(Literal::literal))
+}
+
+/// Return the synonyms of `subject`, of any scope.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::obo::{synonyms_of, SynonymScope};
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// let c = b.class("http://purl.obolibrary.org/obo/GO_0008150");
+/// o.insert(AnnotationAssertion {
+///     subject: c.0.clone(),
+///     ann: Annotation {
+///         ap: b.annotation_property(
+///             "http://www.geneontology.org/formats/oboInOwl#hasExactSynonym",
+///         ),
+///         av: Literal::Simple { literal: "biological process".to_string() }.into(),
+///     },
+/// });
+///
+/// let synonyms = synonyms_of(&o, &c.0);
+/// assert_eq!(synonyms, vec![(SynonymScope::Exact, &"biological process".to_string())]);
+/// ```
+pub fn synonyms_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    subject: &'a IRI,
+) -> Vec<(SynonymScope, &'a String)> {
+    [
+        SynonymScope::Exact,
+        SynonymScope::Broad,
+        SynonymScope::Narrow,
+        SynonymScope::Related,
+    ]
+    .iter()
+    .flat_map(|scope| values_of(ont, subject, scope.property()).map(move |s| (*scope, s)))
+    .collect()
+}
+
+/// Return the database cross references of `subject`.
+pub fn xrefs_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a String> {
+    values_of(ont, subject, OBOInOwl::HasDbXref).collect()
+}
+
+/// Return the OBO namespace of `subject`, such as
+/// `biological_process`.
+pub fn namespace_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Option<&'a String> {
+    values_of(ont, subject, OBOInOwl::HasOBONamespace).next()
+}
+
+/// Return the OBO-style ID which is asserted for `subject`.
+pub fn id_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Option<&'a String> {
+    values_of(ont, subject, OBOInOwl::Id).next()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn purl() {
+        let b = Build::new();
+        let iri = id_to_iri(&b, "GO:0008150").unwrap();
+        assert_eq!(iri.as_str(), "http://purl.obolibrary.org/obo/GO_0008150");
+        assert_eq!(iri_to_id(&iri), Some("GO:0008150".to_string()));
+
+        assert_eq!(
+            iri_to_id("http://purl.obolibrary.org/obo/NCBITaxon_9606"),
+            Some("NCBITaxon:9606".to_string())
+        );
+    }
+
+    #[test]
+    fn registered() {
+        let b = Build::new();
+        let mut reg = IdSpaceRegistry::new();
+        reg.register("EX", "http://www.example.com/");
+        reg.register("EXT", "http://www.example.com/ext/");
+
+        let iri = reg.to_iri(&b, "EXT:a1").unwrap();
+        assert_eq!(iri.as_str(), "http://www.example.com/ext/a1");
+        assert_eq!(reg.to_id(&iri), Some("EXT:a1".to_string()));
+        assert_eq!(
+            reg.to_id("http://www.example.com/b2"),
+            Some("EX:b2".to_string())
+        );
+        assert_eq!(iri_to_id("http://www.example.com/b2"), None);
+    }
+
+    #[test]
+    fn invalid() {
+        let b = Build::new();
+        assert!(id_to_iri(&b, "GO").is_err());
+        assert!(id_to_iri(&b, ":0008150").is_err());
+        assert!(id_to_iri(&b, "GO:").is_err());
+        assert!(id_to_iri(&b, "G O:1").is_err());
+
+        assert_eq!(iri_to_id("http://purl.obolibrary.org/obo/GO"), None);
+        assert_eq!(iri_to_id("http://purl.obolibrary.org/obo/_1"), None);
+        assert_eq!(iri_to_id("http://www.example.com/GO_0008150"), None);
+    }
+
+    #[test]
+    fn metadata() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let go = id_to_iri(&b, "GO:0008150").unwrap();
+        let other = id_to_iri(&b, "GO:0003674").unwrap();
+
+        let annotate = |o: &mut AxiomMappedOntology, subject: &IRI, ap: OBOInOwl, v: &str| {
+            o.insert(AnnotationAssertion {
+                subject: subject.clone(),
+                ann: Annotation {
+                    ap: b.annotation_property(ap.iri_str()),
+                    av: Literal::Simple {
+                        literal: v.to_string(),
+                    }
+                    .into(),
+                },
+            });
+        };
+        annotate(&mut o, &go, OBOInOwl::HasExactSynonym, "biological process");
+        annotate(
+            &mut o,
+            &go,
+            OBOInOwl::HasRelatedSynonym,
+            "physiological process",
+        );
+        annotate(
+            &mut o,
+            &go,
+            OBOInOwl::HasDbXref,
+            "Wikipedia:Biological_process",
+        );
+        annotate(&mut o, &go, OBOInOwl::HasOBONamespace, "biological_process");
+        annotate(&mut o, &go, OBOInOwl::Id, "GO:0008150");
+        annotate(
+            &mut o,
+            &other,
+            OBOInOwl::HasExactSynonym,
+            "molecular function",
+        );
+
+        assert_eq!(
+            synonyms_of(&o, &go),
+            vec![
+                (SynonymScope::Exact, &"biological process".to_string()),
+                (SynonymScope::Related, &"physiological process".to_string()),
+            ]
+        );
+        assert_eq!(xrefs_of(&o, &go), vec!["Wikipedia:Biological_process"]);
+        assert_eq!(namespace_of(&o, &go).unwrap(), "biological_process");
+        assert_eq!(id_of(&o, &go).unwrap(), "GO:0008150");
+        assert_eq!(id_of(&o, &other), None);
+    }
+}
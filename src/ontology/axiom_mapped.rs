@@ -12,10 +12,10 @@
 //! `ObjectPropertyDomain` axioms respectively.
 use super::set::SetOntology;
 use crate::model::*;
+use crate::vocab::{AnnotationBuiltIn, WithIRI, OWL};
 use std::{
-    cell::RefCell,
     collections::{BTreeMap, BTreeSet, VecDeque},
-    rc::Rc,
+    sync::Arc,
 };
 
 use super::indexed::{rc_unwrap_or_clone, OneIndexedOntology, OntologyIndex};
@@ -52,7 +52,7 @@ macro_rules! onimpl {
 
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct AxiomMappedIndex {
-    axiom: RefCell<BTreeMap<AxiomKind, BTreeSet<Rc<AnnotatedAxiom>>>>,
+    axiom: BTreeMap<AxiomKind, BTreeSet<Arc<AnnotatedAxiom>>>,
 }
 
 impl AxiomMappedIndex {
@@ -69,43 +69,45 @@ impl AxiomMappedIndex {
     pub fn new() -> AxiomMappedIndex {
         AxiomMappedIndex::default()
     }
-    /// Fetch the axioms hashmap as a raw pointer.
+    /// Fetch the axioms for the given kind.
+    fn set_for_kind(&self, axk: AxiomKind) -> Option<&BTreeSet<Arc<AnnotatedAxiom>>> {
+        self.axiom.get(&axk)
+    }
+
+    /// Fetch the axioms for given kind as a mutable ref.
     ///
     /// This method also ensures that the BTreeSet for `axk` is
     /// instantiated, which means that it effects equality of the
     /// ontology. It should only be used where the intention is to
     /// update the ontology.
-    fn axioms_as_ptr(
-        &self,
-        axk: AxiomKind,
-    ) -> *mut BTreeMap<AxiomKind, BTreeSet<Rc<AnnotatedAxiom>>> {
-        self.axiom
-            .borrow_mut()
-            .entry(axk)
-            .or_insert_with(BTreeSet::new);
-        self.axiom.as_ptr()
-    }
-
-    /// Fetch the axioms for the given kind.
-    fn set_for_kind(&self, axk: AxiomKind) -> Option<&BTreeSet<Rc<AnnotatedAxiom>>> {
-        unsafe { (*self.axiom.as_ptr()).get(&axk) }
-    }
-
-    /// Fetch the axioms for given kind as a mutable ref.
-    fn mut_set_for_kind(&mut self, axk: AxiomKind) -> &mut BTreeSet<Rc<AnnotatedAxiom>> {
-        unsafe { (*self.axioms_as_ptr(axk)).get_mut(&axk).unwrap() }
+    fn mut_set_for_kind(&mut self, axk: AxiomKind) -> &mut BTreeSet<Arc<AnnotatedAxiom>> {
+        self.axiom.entry(axk).or_default()
     }
 
     /// Gets an iterator that visits the annotated axioms of the ontology.
-    pub fn iter(&self) -> AxiomMappedIter {
+    pub fn iter(&self) -> AxiomMappedIter<'_> {
         // TODO -- what can't this just use flat_map?
         AxiomMappedIter {
             ont: self,
             inner: None,
-            kinds: unsafe { (*self.axiom.as_ptr()).keys().collect() },
+            kinds: self.axiom.keys().collect(),
         }
     }
 
+    /// Gets an iterator that visits the axioms of the ontology,
+    /// without their annotations, grouped by kind.
+    pub fn axioms(&self) -> impl Iterator<Item = &Axiom> {
+        self.iter().map(|ann| &ann.axiom)
+    }
+
+    /// Gets a parallel iterator that visits the annotated axioms of
+    /// the ontology.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> rayon::vec::IntoIter<&AnnotatedAxiom> {
+        use rayon::prelude::*;
+        self.iter().collect::<Vec<_>>().into_par_iter()
+    }
+
     /// Fetch the AnnotatedAxiom for a given kind
     ///
     /// # Examples
@@ -149,6 +151,33 @@ impl AxiomMappedIndex {
     pub fn axiom(&self, axk: AxiomKind) -> impl Iterator<Item = &Axiom> {
         self.annotated_axiom(axk).map(|ann| &ann.axiom)
     }
+
+    /// Fetch the axioms of type `A`
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// o.insert::<DeclareClass>(DeclareClass(b.class("http://www.example.com/a")));
+    /// o.declare(b.object_property("http://www.example.com/r"));
+    ///
+    /// let dc: Vec<&DeclareClass> = o.i().axioms_of::<DeclareClass>().collect();
+    /// assert_eq!(dc, vec![&DeclareClass(b.class("http://www.example.com/a"))]);
+    /// ```
+    pub fn axioms_of<A: AxiomType>(&self) -> impl Iterator<Item = &A> {
+        self.axiom(A::KIND).filter_map(A::from_axiom)
+    }
+
+    /// Fetch the `AnnotatedAxiom` of type `A`, with their axiom
+    /// struct.
+    pub fn annotated_axioms_of<A: AxiomType>(
+        &self,
+    ) -> impl Iterator<Item = (&A, &BTreeSet<Annotation>)> {
+        self.annotated_axiom(A::KIND)
+            .filter_map(|ann| A::from_axiom(&ann.axiom).map(|a| (a, &ann.ann)))
+    }
 }
 // In the ideal world, we would have generated these onimpl! calls as
 // part of the axiom macro. This should be possible, as their is a
@@ -202,18 +231,490 @@ onimpl! {SubAnnotationPropertyOf, sub_annotation_property_of}
 onimpl! {AnnotationPropertyDomain, annotation_property_domain}
 onimpl! {AnnotationPropertyRange, annotation_property_range}
 
+impl AxiomMappedIndex {
+    /// Iterate over the class expressions which are equivalent to
+    /// `c`, excluding named classes.
+    pub fn definitions<'a>(&'a self, c: &'a Class) -> impl Iterator<Item = &'a ClassExpression> {
+        self.equivalent_class()
+            .filter(move |ec| {
+                ec.0.iter()
+                    .any(|ce| matches!(ce, ClassExpression::Class(d) if *d == *c))
+            })
+            .flat_map(|ec| ec.0.iter())
+            .filter(|ce| !matches!(ce, ClassExpression::Class(_)))
+    }
+
+    /// Return the logical definition of `c`, that is a class
+    /// expression which it is equivalent to.
+    ///
+    /// If `c` has more than one definition, the first in the sort
+    /// order is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let a = b.class("http://www.example.com/a");
+    /// let def = ClassExpression::ObjectSomeValuesFrom {
+    ///     ope: b.object_property("http://www.example.com/r").into(),
+    ///     bce: Box::new(b.class("http://www.example.com/b").into()),
+    /// };
+    /// o.insert(EquivalentClasses(vec![a.clone().into(), def.clone()]));
+    ///
+    /// assert_eq!(o.i().definition(&a), Some(&def));
+    /// ```
+    pub fn definition<'a>(&'a self, c: &'a Class) -> Option<&'a ClassExpression> {
+        self.definitions(c).next()
+    }
+
+    /// Iterate over the classes which have a definition, in sorted
+    /// order.
+    pub fn defined_classes(&self) -> impl Iterator<Item = &Class> {
+        let defined: BTreeSet<&Class> = self
+            .equivalent_class()
+            .filter(|ec| {
+                ec.0.iter()
+                    .any(|ce| !matches!(ce, ClassExpression::Class(_)))
+            })
+            .flat_map(|ec| ec.0.iter())
+            .filter_map(|ce| match ce {
+                ClassExpression::Class(c) => Some(c),
+                _ => None,
+            })
+            .collect();
+        defined.into_iter()
+    }
+
+    /// Return true if `c` has a definition.
+    pub fn is_defined(&self, c: &Class) -> bool {
+        self.definition(c).is_some()
+    }
+
+    /// Return true if `c` is a primitive class, that is one which has
+    /// no definition but is declared or in a `SubClassOf` axiom
+    /// between named classes.
+    pub fn is_primitive(&self, c: &Class) -> bool {
+        !self.is_defined(c) && self.hierarchy_classes().contains(c)
+    }
+
+    /// Partition the named classes into those which are defined and
+    /// those which are primitive, as by `is_defined` and
+    /// `is_primitive`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let parent = b.class("http://www.example.com/Parent");
+    /// let person = b.class("http://www.example.com/Person");
+    /// o.insert(SubClassOf {
+    ///     sub: parent.clone().into(),
+    ///     sup: person.clone().into(),
+    /// });
+    /// o.insert(EquivalentClasses(vec![
+    ///     parent.clone().into(),
+    ///     ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/hasChild").into(),
+    ///         bce: Box::new(person.clone().into()),
+    ///     },
+    /// ]));
+    ///
+    /// let (defined, primitive) = o.i().defined_primitive_classes();
+    /// assert_eq!(defined.into_iter().collect::<Vec<_>>(), vec![&parent]);
+    /// assert_eq!(primitive.into_iter().collect::<Vec<_>>(), vec![&person]);
+    /// ```
+    pub fn defined_primitive_classes(&self) -> (BTreeSet<&Class>, BTreeSet<&Class>) {
+        let defined: BTreeSet<&Class> = self.defined_classes().collect();
+        let primitive = self
+            .hierarchy_classes()
+            .into_iter()
+            .filter(|c| !defined.contains(c))
+            .collect();
+        (defined, primitive)
+    }
+
+    /// Iterate over the `SubClassOf` axioms whose subclass is a named
+    /// class.
+    pub fn named_subclass_axioms(&self) -> impl Iterator<Item = &SubClassOf> {
+        self.sub_class_of()
+            .filter(|sc| matches!(sc.sub, ClassExpression::Class(_)))
+    }
+
+    /// Iterate over the general concept inclusions, that is the
+    /// `SubClassOf` axioms whose subclass is a complex class
+    /// expression.
+    pub fn gcis(&self) -> impl Iterator<Item = &SubClassOf> {
+        self.sub_class_of()
+            .filter(|sc| !matches!(sc.sub, ClassExpression::Class(_)))
+    }
+
+    /// Return true if `iri` is annotated as `owl:deprecated`.
+    pub fn is_deprecated(&self, iri: &IRI) -> bool {
+        self.annotation_assertion()
+            .any(|aa| aa.subject == *iri && is_deprecation(&aa.ann))
+    }
+
+    /// Iterate over the IRIs which are annotated as `owl:deprecated`,
+    /// in sorted order.
+    pub fn deprecated(&self) -> impl Iterator<Item = &IRI> {
+        let deprecated: BTreeSet<&IRI> = self
+            .annotation_assertion()
+            .filter(|aa| is_deprecation(&aa.ann))
+            .map(|aa| &aa.subject)
+            .collect();
+        deprecated.into_iter()
+    }
+
+    /// Iterate over the pairs of named classes in a `SubClassOf`
+    /// axiom, as (sub, super).
+    fn named_sub_class_pairs(&self) -> impl Iterator<Item = (&Class, &Class)> {
+        self.sub_class_of()
+            .filter_map(|sc| match (&sc.sub, &sc.sup) {
+                (ClassExpression::Class(sub), ClassExpression::Class(sup)) => Some((sub, sup)),
+                _ => None,
+            })
+    }
+
+    /// Return the classes which are declared, or in a `SubClassOf`
+    /// axiom between named classes, other than `owl:Thing` and
+    /// `owl:Nothing`.
+    fn hierarchy_classes(&self) -> BTreeSet<&Class> {
+        self.declare_class()
+            .map(|dc| &dc.0)
+            .chain(
+                self.named_sub_class_pairs()
+                    .flat_map(|(sub, sup)| vec![sub, sup]),
+            )
+            .filter(|c| !is_builtin_class(c, OWL::Thing) && !is_builtin_class(c, OWL::Nothing))
+            .collect()
+    }
+
+    /// Iterate over the classes with no asserted named super class
+    /// other than `owl:Thing`, in sorted order.
+    ///
+    /// The classes considered are those which are declared, or in a
+    /// `SubClassOf` axiom between named classes; `owl:Thing` and
+    /// `owl:Nothing` are never returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let a = b.class("http://www.example.com/a");
+    /// let c = b.class("http://www.example.com/c");
+    /// o.insert(SubClassOf {
+    ///     sub: c.clone().into(),
+    ///     sup: a.clone().into(),
+    /// });
+    ///
+    /// assert_eq!(o.i().root_classes().collect::<Vec<_>>(), vec![&a]);
+    /// assert_eq!(o.i().leaf_classes().collect::<Vec<_>>(), vec![&c]);
+    /// ```
+    pub fn root_classes(&self) -> impl Iterator<Item = &Class> {
+        let with_super: BTreeSet<&Class> = self
+            .named_sub_class_pairs()
+            .filter(|(sub, sup)| sub != sup && !is_builtin_class(sup, OWL::Thing))
+            .map(|(sub, _)| sub)
+            .collect();
+        let roots: Vec<&Class> = self
+            .hierarchy_classes()
+            .into_iter()
+            .filter(|c| !with_super.contains(c))
+            .collect();
+        roots.into_iter()
+    }
+
+    /// Iterate over the classes with no asserted named sub class
+    /// other than `owl:Nothing`, in sorted order.
+    ///
+    /// The classes considered are as for `root_classes`.
+    pub fn leaf_classes(&self) -> impl Iterator<Item = &Class> {
+        let with_sub: BTreeSet<&Class> = self
+            .named_sub_class_pairs()
+            .filter(|(sub, sup)| sub != sup && !is_builtin_class(sub, OWL::Nothing))
+            .map(|(_, sup)| sup)
+            .collect();
+        let leaves: Vec<&Class> = self
+            .hierarchy_classes()
+            .into_iter()
+            .filter(|c| !with_sub.contains(c))
+            .collect();
+        leaves.into_iter()
+    }
+
+    /// Iterate over the classes, other than `c`, which share an
+    /// asserted direct named super class with `c`, in sorted order.
+    ///
+    /// `owl:Thing` is not counted as a shared super class, so the
+    /// root classes are not all siblings of each other.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let animal = b.class("http://www.example.com/animal");
+    /// let cat = b.class("http://www.example.com/cat");
+    /// let dog = b.class("http://www.example.com/dog");
+    /// for c in &[&cat, &dog] {
+    ///     o.insert(SubClassOf {
+    ///         sub: (*c).clone().into(),
+    ///         sup: animal.clone().into(),
+    ///     });
+    /// }
+    ///
+    /// assert_eq!(o.i().siblings(&cat).collect::<Vec<_>>(), vec![&dog]);
+    /// ```
+    pub fn siblings<'a>(&'a self, c: &Class) -> impl Iterator<Item = &'a Class> {
+        let parents: BTreeSet<&Class> = self
+            .named_sub_class_pairs()
+            .filter(|(sub, sup)| *sub == c && sub != sup && !is_builtin_class(sup, OWL::Thing))
+            .map(|(_, sup)| sup)
+            .collect();
+        let siblings: BTreeSet<&Class> = self
+            .named_sub_class_pairs()
+            .filter(|(sub, sup)| *sub != c && sub != sup && parents.contains(sup))
+            .map(|(sub, _)| sub)
+            .collect();
+        siblings.into_iter()
+    }
+
+    /// Return the `rdfs:label` of `iri` in the first language of
+    /// `langs` for which it has one.
+    ///
+    /// A language matches a label with the same tag, ignoring case,
+    /// or failing that, one with a more specific tag, so that `en`
+    /// matches `en-US`; the language `*` matches a label in any
+    /// language. If no language matches, a label without a language
+    /// tag is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let a = b.iri("http://www.example.com/a");
+    /// for (literal, lang) in &[("colour", "en-GB"), ("color", "en-US"), ("couleur", "fr")] {
+    ///     o.insert(AnnotationAssertion {
+    ///         subject: a.clone(),
+    ///         ann: Annotation {
+    ///             ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///             av: Literal::Language {
+    ///                 literal: literal.to_string(),
+    ///                 lang: lang.to_string(),
+    ///             }
+    ///             .into(),
+    ///         },
+    ///     });
+    /// }
+    ///
+    /// assert_eq!(o.i().label_with_lang(&a, &["en-GB", "en"]), Some("colour"));
+    /// assert_eq!(o.i().label_with_lang(&a, &["de", "fr"]), Some("couleur"));
+    /// assert_eq!(o.i().label_with_lang(&a, &["de"]), None);
+    /// ```
+    pub fn label_with_lang<'a>(&'a self, iri: &IRI, langs: &[&str]) -> Option<&'a str> {
+        let labels: Vec<&Literal> = self
+            .annotation_assertion()
+            .filter(|aa| {
+                aa.subject == *iri && aa.ann.ap.0.as_str() == AnnotationBuiltIn::LABEL.iri_str()
+            })
+            .filter_map(|aa| aa.ann.av.as_literal())
+            .collect();
+
+        literal_with_lang(&labels, langs)
+    }
+}
+
+fn is_builtin_class(c: &Class, owl: OWL) -> bool {
+    c.0.as_str() == owl.iri_str()
+}
+
+/// Return the first of `literals` in the first language of `langs`
+/// which has one, as for `label_with_lang`.
+pub(crate) fn literal_with_lang<'a>(literals: &[&'a Literal], langs: &[&str]) -> Option<&'a str> {
+    let tag = |l: &Literal| l.lang().map(str::to_lowercase);
+
+    langs
+        .iter()
+        .find_map(|want| {
+            let want = want.to_lowercase();
+            let prefix = format!("{}-", want);
+            literals
+                .iter()
+                .find(|l| tag(l).is_some_and(|t| t == want || want == "*"))
+                .or_else(|| {
+                    literals
+                        .iter()
+                        .find(|l| tag(l).is_some_and(|t| t.starts_with(&prefix)))
+                })
+        })
+        .or_else(|| literals.iter().find(|l| tag(l).is_none()))
+        .map(|l| l.literal().as_str())
+}
+
+/// Return true if `ann` is `owl:deprecated` with a true value.
+fn is_deprecation(ann: &Annotation) -> bool {
+    ann.ap.0.as_str() == AnnotationBuiltIn::DEPRECATED.iri_str()
+        && matches!(ann.av.as_str(), Some("true") | Some("1"))
+}
+
+fn invert(ope: &ObjectPropertyExpression) -> ObjectPropertyExpression {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => {
+            ObjectPropertyExpression::InverseObjectProperty(op.clone())
+        }
+        ObjectPropertyExpression::InverseObjectProperty(op) => {
+            ObjectPropertyExpression::ObjectProperty(op.clone())
+        }
+    }
+}
+
+impl AxiomMappedIndex {
+    fn direct_super_object_properties(
+        &self,
+        ope: &ObjectPropertyExpression,
+    ) -> Vec<ObjectPropertyExpression> {
+        // An inverse has the inverses of the super properties of its
+        // property
+        if let ObjectPropertyExpression::InverseObjectProperty(_) = ope {
+            return self
+                .direct_super_object_properties(&invert(ope))
+                .iter()
+                .map(invert)
+                .collect();
+        }
+
+        let subs = self.sub_object_property().filter_map(|sop| match &sop.sub {
+            SubObjectPropertyExpression::ObjectPropertyExpression(sub) if *sub == *ope => {
+                Some(sop.sup.clone())
+            }
+            _ => None,
+        });
+        let equivs = self
+            .equivalent_object_properties()
+            .filter(|eop| eop.0.contains(ope))
+            .flat_map(|eop| eop.0.iter().cloned());
+
+        subs.chain(equivs).collect()
+    }
+
+    /// Return `ope` and all of its super properties, through
+    /// `SubObjectPropertyOf` and `EquivalentObjectProperties` axioms.
+    pub fn super_object_properties(
+        &self,
+        ope: &ObjectPropertyExpression,
+    ) -> BTreeSet<ObjectPropertyExpression> {
+        let mut sups = BTreeSet::new();
+        let mut todo = vec![ope.clone()];
+        while let Some(next) = todo.pop() {
+            if sups.insert(next.clone()) {
+                todo.extend(self.direct_super_object_properties(&next));
+            }
+        }
+        sups
+    }
+
+    /// Return `dp` and all of its super properties, through
+    /// `SubDataPropertyOf` and `EquivalentDataProperties` axioms.
+    pub fn super_data_properties(&self, dp: &DataProperty) -> BTreeSet<DataProperty> {
+        let mut sups = BTreeSet::new();
+        let mut todo = vec![dp.clone()];
+        while let Some(next) = todo.pop() {
+            if sups.insert(next.clone()) {
+                todo.extend(
+                    self.sub_data_property_of()
+                        .filter(|sdp| sdp.sub == next)
+                        .map(|sdp| sdp.sup.clone()),
+                );
+                todo.extend(
+                    self.equivalent_data_properties()
+                        .filter(|edp| edp.0.contains(&next))
+                        .flat_map(|edp| edp.0.iter().cloned()),
+                );
+            }
+        }
+        sups
+    }
+
+    /// Return the domains of `ope`, including those inherited from
+    /// its super properties, and the ranges of its inverse.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let r: ObjectPropertyExpression = b.object_property("http://www.example.com/r").into();
+    /// let s: ObjectPropertyExpression = b.object_property("http://www.example.com/s").into();
+    /// let a: ClassExpression = b.class("http://www.example.com/A").into();
+    /// o.insert(SubObjectPropertyOf {
+    ///     sub: r.clone().into(),
+    ///     sup: s.clone(),
+    /// });
+    /// o.insert(ObjectPropertyDomain { ope: s, ce: a.clone() });
+    ///
+    /// assert!(o.i().domains_of(&r).contains(&a));
+    /// ```
+    pub fn domains_of(&self, ope: &ObjectPropertyExpression) -> BTreeSet<&ClassExpression> {
+        let sups = self.super_object_properties(ope);
+        let domains = self
+            .object_property_domain()
+            .filter(|opd| sups.contains(&opd.ope))
+            .map(|opd| &opd.ce);
+        let inverse_ranges = self
+            .object_property_range()
+            .filter(|opr| sups.contains(&invert(&opr.ope)))
+            .map(|opr| &opr.ce);
+
+        domains.chain(inverse_ranges).collect()
+    }
+
+    /// Return the ranges of `ope`, including those inherited from its
+    /// super properties, and the domains of its inverse.
+    pub fn ranges_of(&self, ope: &ObjectPropertyExpression) -> BTreeSet<&ClassExpression> {
+        self.domains_of(&invert(ope))
+    }
+
+    /// Return the domains of `dp`, including those inherited from its
+    /// super properties.
+    pub fn data_domains_of(&self, dp: &DataProperty) -> BTreeSet<&ClassExpression> {
+        let sups = self.super_data_properties(dp);
+        self.data_property_domain()
+            .filter(|dpd| sups.contains(&dpd.dp))
+            .map(|dpd| &dpd.ce)
+            .collect()
+    }
+
+    /// Return the ranges of `dp`, including those inherited from its
+    /// super properties.
+    pub fn data_ranges_of(&self, dp: &DataProperty) -> BTreeSet<&DataRange> {
+        let sups = self.super_data_properties(dp);
+        self.data_property_range()
+            .filter(|dpr| sups.contains(&dpr.dp))
+            .map(|dpr| &dpr.dr)
+            .collect()
+    }
+}
+
 
 /// An owning iterator over the annotated axioms of an `Ontology`.
 impl IntoIterator for AxiomMappedIndex {
     type Item = AnnotatedAxiom;
     type IntoIter = std::vec::IntoIter<AnnotatedAxiom>;
     fn into_iter(self) -> Self::IntoIter {
-        let btreemap = self.axiom.into_inner();
-        let v: Vec<AnnotatedAxiom> = btreemap
-            .into_iter()
-            .map(|(_k, v)| v)
+        let btreemap = self.axiom;
+        let v: Vec<AnnotatedAxiom> = btreemap.into_values()
             .flat_map(BTreeSet::into_iter)
-            .map(Rc::try_unwrap)
+            .map(Arc::try_unwrap)
             .map(Result::unwrap)
             .collect();
         v.into_iter()
@@ -225,7 +726,7 @@ impl IntoIterator for AxiomMappedIndex {
 pub struct AxiomMappedIter<'a> {
     ont: &'a AxiomMappedIndex,
     kinds: VecDeque<&'a AxiomKind>,
-    inner: Option<<&'a BTreeSet<Rc<AnnotatedAxiom>> as IntoIterator>::IntoIter>,
+    inner: Option<<&'a BTreeSet<Arc<AnnotatedAxiom>> as IntoIterator>::IntoIter>,
 }
 
 impl<'a> Iterator for AxiomMappedIter<'a> {
@@ -241,7 +742,7 @@ impl<'a> Iterator for AxiomMappedIter<'a> {
         if !self.kinds.is_empty() {
             let kind = self.kinds.pop_front().unwrap();
             self.inner = self.ont.set_for_kind(*kind).map(BTreeSet::iter);
-            self.next().map(|rc| &*rc)
+            self.next()
         } else {
             None
         }
@@ -256,13 +757,13 @@ impl<'a> IntoIterator for &'a AxiomMappedIndex {
         AxiomMappedIter {
             ont: self,
             inner: None,
-            kinds: unsafe { (*self.axiom.as_ptr()).keys().collect() },
+            kinds: self.axiom.keys().collect(),
         }
     }
 }
 
 impl OntologyIndex for AxiomMappedIndex {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         self.mut_set_for_kind(ax.kind()).insert(ax)
     }
 
@@ -275,10 +776,72 @@ impl OntologyIndex for AxiomMappedIndex {
     fn index_remove(&mut self, ax: &AnnotatedAxiom) -> bool {
         self.mut_set_for_kind(ax.kind()).remove(ax)
     }
+
+    fn index_extend(&mut self, axs: Vec<Arc<AnnotatedAxiom>>) -> usize {
+        let mut by_kind: BTreeMap<AxiomKind, Vec<Arc<AnnotatedAxiom>>> = BTreeMap::new();
+        for ax in axs {
+            by_kind.entry(ax.kind()).or_default().push(ax);
+        }
+
+        let mut n = 0;
+        for (axk, axs) in by_kind {
+            let set = self.mut_set_for_kind(axk);
+            let len = set.len();
+            if set.is_empty() {
+                // Building the set in one go sorts once, rather than
+                // rebalancing after each insertion
+                *set = axs.into_iter().collect();
+            } else {
+                set.extend(axs);
+            }
+            n += set.len() - len;
+        }
+        n
+    }
 }
 
 pub type AxiomMappedOntology = OneIndexedOntology<AxiomMappedIndex>;
 
+impl AxiomMappedOntology {
+    /// Return a new ontology, with the same ID, of the axioms whose
+    /// annotations satisfy `keep`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::default();
+    /// let b = Build::new();
+    /// let editor_note = b.annotation_property("http://www.example.com/editorOnly");
+    /// o.insert(DeclareClass(b.class("http://www.example.com/a")));
+    /// o.insert(AnnotatedAxiom::new(
+    ///     DeclareClass(b.class("http://www.example.com/draft")),
+    ///     vec![Annotation {
+    ///         ap: editor_note.clone(),
+    ///         av: Literal::Simple { literal: "true".to_string() }.into(),
+    ///     }]
+    ///     .into_iter()
+    ///     .collect(),
+    /// ));
+    ///
+    /// let release = o.filter_axioms(|anns| !anns.iter().any(|a| a.ap == editor_note));
+    /// assert_eq!(release.i().declare_class().count(), 1);
+    /// ```
+    pub fn filter_axioms<F>(&self, mut keep: F) -> AxiomMappedOntology
+    where
+        F: FnMut(&BTreeSet<Annotation>) -> bool,
+    {
+        let mut filtered = AxiomMappedOntology::default();
+        *filtered.mut_id() = self.id().clone();
+        for ax in self.i().iter() {
+            if keep(&ax.ann) {
+                filtered.insert(ax.clone());
+            }
+        }
+        filtered
+    }
+}
+
 /// An owning iterator over the annotated axioms of an `Ontology`.
 impl IntoIterator for AxiomMappedOntology {
     type Item = AnnotatedAxiom;
@@ -288,11 +851,19 @@ impl IntoIterator for AxiomMappedOntology {
     }
 }
 
+impl<'a> IntoIterator for &'a AxiomMappedOntology {
+    type Item = &'a AnnotatedAxiom;
+    type IntoIter = AxiomMappedIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.i().iter()
+    }
+}
+
 
 impl From<SetOntology> for AxiomMappedOntology {
     fn from(mut so: SetOntology) -> AxiomMappedOntology {
         let mut amo = AxiomMappedOntology::default();
-        std::mem::swap(amo.mut_id(), &mut so.mut_id());
+        std::mem::swap(amo.mut_id(), so.mut_id());
         for ax in so {
             amo.insert(ax);
         }
@@ -317,10 +888,170 @@ mod test {
     use super::AxiomMappedOntology;
     use crate::model::*;
 
+    #[test]
+    fn test_domains_ranges() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let r: ObjectPropertyExpression = b.object_property("http://www.example.com#r").into();
+        let s: ObjectPropertyExpression = b.object_property("http://www.example.com#s").into();
+        let t: ObjectPropertyExpression = b.object_property("http://www.example.com#t").into();
+        let a: ClassExpression = b.class("http://www.example.com#a").into();
+        let c: ClassExpression = b.class("http://www.example.com#c").into();
+
+        o.insert(SubObjectPropertyOf {
+            sub: r.clone().into(),
+            sup: s.clone(),
+        });
+        o.insert(EquivalentObjectProperties(vec![s.clone(), t.clone()]));
+        o.insert(ObjectPropertyDomain {
+            ope: t.clone(),
+            ce: a.clone(),
+        });
+        o.insert(ObjectPropertyRange {
+            ope: s.clone(),
+            ce: c.clone(),
+        });
+
+        assert_eq!(o.i().super_object_properties(&r).len(), 3);
+        assert_eq!(o.i().domains_of(&r).into_iter().collect::<Vec<_>>(), vec![&a]);
+        assert_eq!(o.i().ranges_of(&r).into_iter().collect::<Vec<_>>(), vec![&c]);
+        assert!(o.i().domains_of(&s).contains(&a));
+        assert!(o.i().ranges_of(&t).contains(&c));
+
+        // The range of r is the domain of its inverse
+        let inv = ObjectPropertyExpression::InverseObjectProperty(
+            b.object_property("http://www.example.com#r"),
+        );
+        assert_eq!(o.i().domains_of(&inv).into_iter().collect::<Vec<_>>(), vec![&c]);
+    }
+
+    #[test]
+    fn test_data_domains_ranges() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let p = b.data_property("http://www.example.com#p");
+        let q = b.data_property("http://www.example.com#q");
+        let dr: DataRange = b.datatype("http://www.w3.org/2001/XMLSchema#int").into();
+
+        o.insert(SubDataPropertyOf {
+            sub: p.clone(),
+            sup: q.clone(),
+        });
+        o.insert(DataPropertyRange {
+            dp: q.clone(),
+            dr: dr.clone(),
+        });
+        o.insert(DataPropertyDomain {
+            dp: p.clone(),
+            ce: b.class("http://www.example.com#a").into(),
+        });
+
+        assert!(o.i().data_ranges_of(&p).contains(&dr));
+        assert_eq!(o.i().data_domains_of(&p).len(), 1);
+        assert_eq!(o.i().data_domains_of(&q).len(), 0);
+    }
+
+    #[test]
+    fn test_definition() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let a = b.class("http://www.example.com#a");
+        let c = b.class("http://www.example.com#c");
+        let def = ClassExpression::ObjectIntersectionOf(vec![
+            b.class("http://www.example.com#b").into(),
+            c.clone().into(),
+        ]);
+
+        // Named classes which are equivalent are not definitions
+        o.insert(EquivalentClasses(vec![a.clone().into(), c.clone().into()]));
+        assert_eq!(o.i().definition(&a), None);
+        assert_eq!(o.i().defined_classes().count(), 0);
+
+        o.insert(EquivalentClasses(vec![def.clone(), a.clone().into()]));
+        assert_eq!(o.i().definition(&a), Some(&def));
+        assert_eq!(o.i().definition(&c), None);
+        assert_eq!(o.i().defined_classes().collect::<Vec<_>>(), vec![&a]);
+    }
+
+    #[test]
+    fn test_defined_primitive() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let class = |c: &str| b.class(format!("http://www.example.com/{}", c));
+        o.insert(SubClassOf {
+            sub: class("a").into(),
+            sup: class("b").into(),
+        });
+        o.insert(EquivalentClasses(vec![
+            class("a").into(),
+            ClassExpression::ObjectComplementOf(Box::new(class("c").into())),
+        ]));
+        o.insert(EquivalentClasses(vec![class("d").into(), class("e").into()]));
+        o.insert(DeclareClass(class("f")));
+
+        assert!(o.i().is_defined(&class("a")));
+        assert!(!o.i().is_primitive(&class("a")));
+        assert!(o.i().is_primitive(&class("b")));
+        assert!(!o.i().is_defined(&class("d")));
+        assert!(!o.i().is_primitive(&class("d")));
+
+        let (defined, primitive) = o.i().defined_primitive_classes();
+        assert_eq!(defined.into_iter().collect::<Vec<_>>(), vec![&class("a")]);
+        assert_eq!(
+            primitive.into_iter().collect::<Vec<_>>(),
+            vec![&class("b"), &class("f")]
+        );
+    }
+
+    #[test]
+    fn test_gcis() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let a: ClassExpression = b.class("http://www.example.com#a").into();
+        let c: ClassExpression = b.class("http://www.example.com#c").into();
+        let some = ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://www.example.com#r").into(),
+            bce: Box::new(c.clone()),
+        };
+
+        let named = SubClassOf::new(some.clone(), a.clone());
+        let gci = SubClassOf::new(a.clone(), some);
+        o.insert(named.clone());
+        o.insert(gci.clone());
+
+        assert_eq!(o.i().named_subclass_axioms().collect::<Vec<_>>(), vec![&named]);
+        assert_eq!(o.i().gcis().collect::<Vec<_>>(), vec![&gci]);
+    }
+
+    #[test]
+    fn test_axioms_of() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let sc = SubClassOf::new(
+            b.class("http://www.example.com#a"),
+            b.class("http://www.example.com#b"),
+        );
+        let ann: std::collections::BTreeSet<Annotation> = vec![Annotation {
+            ap: b.annotation_property("http://www.example.com#p"),
+            av: b.iri("http://www.example.com#v").into(),
+        }]
+        .into_iter()
+        .collect();
+        o.insert(AnnotatedAxiom::new(sc.clone(), ann.clone()));
+        o.declare(b.class("http://www.example.com#a"));
+
+        assert_eq!(o.i().axioms_of::<SubClassOf>().collect::<Vec<_>>(), vec![&sc]);
+        assert_eq!(o.i().axioms_of::<DeclareClass>().count(), 1);
+        assert_eq!(o.i().axioms_of::<DisjointClasses>().count(), 0);
+        assert_eq!(
+            o.i().annotated_axioms_of::<SubClassOf>().collect::<Vec<_>>(),
+            vec![(&sc, &ann)]
+        );
+    }
+
     #[test]
     fn test_ontology_cons() {
         let _ = AxiomMappedOntology::default();
-        assert!(true);
     }
 
     #[test]
@@ -411,7 +1142,7 @@ mod test {
         o.insert(decl3.clone());
 
         // Iteration is based on ascending order of axiom kinds.
-        let mut it = (&o).i().iter();
+        let mut it = o.i().iter();
         assert_eq!(
             it.next(),
             Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
@@ -435,4 +1166,214 @@ mod test {
         assert_eq!(it.next(), None);
         assert_eq!(it.next(), None);
     }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_ontology_par_iter() {
+        use rayon::prelude::*;
+
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        for n in 0..100 {
+            o.declare(b.class(format!("http://www.example.com/{}", n)));
+        }
+
+        let par: std::collections::BTreeSet<&AnnotatedAxiom> = o.i().par_iter().collect();
+        assert_eq!(par, o.i().iter().collect());
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let deprecated = |iri: &str, value: &str| AnnotationAssertion {
+            subject: b.iri(iri),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2002/07/owl#deprecated"),
+                av: Literal::Datatype {
+                    literal: value.to_string(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#boolean"),
+                }
+                .into(),
+            },
+        };
+        o.insert(deprecated("http://www.example.com/b", "true"));
+        o.insert(deprecated("http://www.example.com/a", "1"));
+        o.insert(deprecated("http://www.example.com/c", "false"));
+
+        assert!(o.i().is_deprecated(&b.iri("http://www.example.com/a")));
+        assert!(!o.i().is_deprecated(&b.iri("http://www.example.com/c")));
+        assert_eq!(
+            o.i().deprecated().collect::<Vec<_>>(),
+            vec![
+                &b.iri("http://www.example.com/a"),
+                &b.iri("http://www.example.com/b")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_root_leaf_classes() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let class = |c: &str| b.class(format!("http://www.example.com/{}", c));
+        let sc = |sub: &Class, sup: &Class| SubClassOf {
+            sub: sub.clone().into(),
+            sup: sup.clone().into(),
+        };
+        let thing = b.class("http://www.w3.org/2002/07/owl#Thing");
+        let nothing = b.class("http://www.w3.org/2002/07/owl#Nothing");
+
+        o.insert(sc(&class("b"), &class("a")));
+        o.insert(sc(&class("c"), &class("b")));
+        o.insert(sc(&class("a"), &thing));
+        o.insert(sc(&nothing, &class("c")));
+        o.insert(sc(&class("d"), &class("d")));
+        o.insert(DeclareClass(class("e")));
+        o.insert(SubClassOf {
+            sub: class("f").into(),
+            sup: ClassExpression::ObjectComplementOf(Box::new(class("a").into())),
+        });
+
+        assert_eq!(
+            o.i().root_classes().collect::<Vec<_>>(),
+            vec![&class("a"), &class("d"), &class("e")]
+        );
+        assert_eq!(
+            o.i().leaf_classes().collect::<Vec<_>>(),
+            vec![&class("c"), &class("d"), &class("e")]
+        );
+    }
+
+    #[test]
+    fn test_siblings() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let class = |c: &str| b.class(format!("http://www.example.com/{}", c));
+        let sc = |sub: &Class, sup: &Class| SubClassOf {
+            sub: sub.clone().into(),
+            sup: sup.clone().into(),
+        };
+        let thing = b.class("http://www.w3.org/2002/07/owl#Thing");
+
+        o.insert(sc(&class("b"), &class("a")));
+        o.insert(sc(&class("c"), &class("a")));
+        o.insert(sc(&class("c"), &class("x")));
+        o.insert(sc(&class("d"), &class("x")));
+        o.insert(sc(&class("d"), &class("d")));
+        o.insert(sc(&class("a"), &thing));
+        o.insert(sc(&class("x"), &thing));
+
+        assert_eq!(
+            o.i().siblings(&class("c")).collect::<Vec<_>>(),
+            vec![&class("b"), &class("d")]
+        );
+        assert_eq!(
+            o.i().siblings(&class("d")).collect::<Vec<_>>(),
+            vec![&class("c")]
+        );
+        assert_eq!(o.i().siblings(&class("a")).count(), 0);
+        assert_eq!(o.i().siblings(&class("y")).count(), 0);
+    }
+
+    #[test]
+    fn test_filter_axioms() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        let status = b.annotation_property("http://www.example.com/status");
+        let annotated = |ax: Axiom, value: &str| {
+            AnnotatedAxiom::new(
+                ax,
+                vec![Annotation {
+                    ap: status.clone(),
+                    av: Literal::Simple {
+                        literal: value.to_string(),
+                    }
+                    .into(),
+                }]
+                .into_iter()
+                .collect(),
+            )
+        };
+        o.insert(annotated(
+            DeclareClass(b.class("http://www.example.com/a")).into(),
+            "ready",
+        ));
+        o.insert(annotated(
+            DeclareClass(b.class("http://www.example.com/b")).into(),
+            "pending",
+        ));
+        o.insert(DeclareClass(b.class("http://www.example.com/c")));
+
+        let ready = o.filter_axioms(|anns| {
+            !anns
+                .iter()
+                .any(|a| a.ap == status && a.av.as_str() == Some("pending"))
+        });
+        assert_eq!(ready.id(), o.id());
+        assert_eq!(ready.i().iter().count(), 2);
+        assert!(!ready
+            .i()
+            .declare_class()
+            .any(|dc| dc.0 == b.class("http://www.example.com/b")));
+        assert_eq!(o.filter_axioms(|anns| !anns.is_empty()).i().iter().count(), 2);
+    }
+
+    #[test]
+    fn test_label_with_lang() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let a = b.iri("http://www.example.com/a");
+        let label = |l: Literal| AnnotationAssertion {
+            subject: a.clone(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: l.into(),
+            },
+        };
+        let lang = |literal: &str, lang: &str| Literal::Language {
+            literal: literal.to_string(),
+            lang: lang.to_string(),
+        };
+
+        o.insert(label(Literal::Simple {
+            literal: "plain".to_string(),
+        }));
+        assert_eq!(o.i().label_with_lang(&a, &["en"]), Some("plain"));
+
+        o.insert(label(lang("american", "en-US")));
+        assert_eq!(o.i().label_with_lang(&a, &["en-GB", "en"]), Some("american"));
+
+        o.insert(label(lang("english", "EN")));
+        assert_eq!(o.i().label_with_lang(&a, &["en-GB", "en"]), Some("english"));
+        assert_eq!(o.i().label_with_lang(&a, &["en-us"]), Some("american"));
+        assert_eq!(o.i().label_with_lang(&a, &["fr"]), Some("plain"));
+        assert!(o.i().label_with_lang(&a, &["fr", "*"]).is_some());
+        assert_eq!(
+            o.i().label_with_lang(&b.iri("http://www.example.com/b"), &["*"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ontology_axioms() {
+        let build = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(build.object_property("http://www.example.com#r"));
+        o.declare(build.class("http://www.example.com#a"));
+
+        let kinds: Vec<AxiomKind> = o.i().axioms().map(|ax| ax.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![AxiomKind::DeclareClass, AxiomKind::DeclareObjectProperty]
+        );
+
+        let mut n = 0;
+        for ax in &o {
+            assert!(o.i().iter().any(|a| a == ax));
+            n += 1;
+        }
+        assert_eq!(n, 2);
+    }
 }
@@ -0,0 +1,196 @@
+//! Changes to an ontology, and notification of them
+//!
+//! An `OntologyChange` describes a single mutation of an
+//! ontology. Wrapping any `MutableOntology` in an
+//! `ObservableOntology` allows `OntologyChangeListener`s to be
+//! notified of each change as it is made.
+use crate::model::{AnnotatedAxiom, MutableOntology, Ontology, OntologyID, IRI};
+
+/// A single change to an ontology.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum OntologyChange {
+    AddAxiom(AnnotatedAxiom),
+    RemoveAxiom(AnnotatedAxiom),
+}
+
+impl OntologyChange {
+    /// Return the change which reverses this one.
+    pub fn inverse(&self) -> OntologyChange {
+        match self {
+            OntologyChange::AddAxiom(ax) => OntologyChange::RemoveAxiom(ax.clone()),
+            OntologyChange::RemoveAxiom(ax) => OntologyChange::AddAxiom(ax.clone()),
+        }
+    }
+
+    /// Apply this change to `o`, returning true if `o` was changed.
+    pub fn apply<O: MutableOntology>(&self, o: &mut O) -> bool {
+        match self {
+            OntologyChange::AddAxiom(ax) => o.insert(ax.clone()),
+            OntologyChange::RemoveAxiom(ax) => o.remove(ax),
+        }
+    }
+
+    pub fn axiom(&self) -> &AnnotatedAxiom {
+        match self {
+            OntologyChange::AddAxiom(ax) | OntologyChange::RemoveAxiom(ax) => ax,
+        }
+    }
+}
+
+/// Receive notification of changes to an `ObservableOntology`.
+pub trait OntologyChangeListener {
+    fn changed(&mut self, change: &OntologyChange);
+}
+
+impl<F: FnMut(&OntologyChange)> OntologyChangeListener for F {
+    fn changed(&mut self, change: &OntologyChange) {
+        self(change)
+    }
+}
+
+/// Wrap a `MutableOntology`, notifying listeners of every change.
+///
+/// Listeners are only notified of changes which take effect; for
+/// example, inserting an axiom which is already present does nothing.
+#[derive(Default)]
+pub struct ObservableOntology<O> {
+    ont: O,
+    listeners: Vec<Box<dyn OntologyChangeListener>>,
+}
+
+impl<O: MutableOntology> ObservableOntology<O> {
+    pub fn new(ont: O) -> ObservableOntology<O> {
+        ObservableOntology {
+            ont,
+            listeners: vec![],
+        }
+    }
+
+    pub fn add_listener<L: OntologyChangeListener + 'static>(&mut self, listener: L) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Return the wrapped ontology.
+    pub fn ontology(&self) -> &O {
+        &self.ont
+    }
+
+    pub fn into_inner(self) -> O {
+        self.ont
+    }
+
+    /// Apply `change`, returning true and notifying listeners if the
+    /// ontology was changed.
+    pub fn apply(&mut self, change: OntologyChange) -> bool {
+        let changed = change.apply(&mut self.ont);
+        if changed {
+            self.notify(&change);
+        }
+        changed
+    }
+
+    fn notify(&mut self, change: &OntologyChange) {
+        for l in self.listeners.iter_mut() {
+            l.changed(change);
+        }
+    }
+}
+
+impl<O: Ontology> Ontology for ObservableOntology<O> {
+    fn id(&self) -> &OntologyID {
+        self.ont.id()
+    }
+
+    fn mut_id(&mut self) -> &mut OntologyID {
+        self.ont.mut_id()
+    }
+
+    fn doc_iri(&self) -> &Option<IRI> {
+        self.ont.doc_iri()
+    }
+
+    fn mut_doc_iri(&mut self) -> &Option<IRI> {
+        self.ont.mut_doc_iri()
+    }
+}
+
+impl<O: MutableOntology> MutableOntology for ObservableOntology<O> {
+    fn insert<A>(&mut self, ax: A) -> bool
+    where
+        A: Into<AnnotatedAxiom>,
+    {
+        self.apply(OntologyChange::AddAxiom(ax.into()))
+    }
+
+    fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
+        let taken = self.ont.take(ax);
+        if let Some(ax) = &taken {
+            self.notify(&OntologyChange::RemoveAxiom(ax.clone()));
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::*;
+    use crate::ontology::set::SetOntology;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn observed() -> (
+        ObservableOntology<SetOntology>,
+        Rc<RefCell<Vec<OntologyChange>>>,
+    ) {
+        let log = Rc::new(RefCell::new(vec![]));
+        let mut o = ObservableOntology::new(SetOntology::new());
+        let l = log.clone();
+        o.add_listener(move |c: &OntologyChange| l.borrow_mut().push(c.clone()));
+        (o, log)
+    }
+
+    #[test]
+    fn add_remove() {
+        let b = Build::new();
+        let (mut o, log) = observed();
+
+        let ax: AnnotatedAxiom = DeclareClass(b.class("http://www.example.com/a")).into();
+        assert!(o.insert(ax.clone()));
+        assert!(o.remove(&ax));
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                OntologyChange::AddAxiom(ax.clone()),
+                OntologyChange::RemoveAxiom(ax)
+            ]
+        );
+    }
+
+    #[test]
+    fn no_change() {
+        let b = Build::new();
+        let (mut o, log) = observed();
+
+        let ax: AnnotatedAxiom = DeclareClass(b.class("http://www.example.com/a")).into();
+        assert!(!o.remove(&ax));
+        o.insert(ax.clone());
+        assert!(!o.insert(ax));
+
+        assert_eq!(log.borrow().len(), 1);
+    }
+
+    #[test]
+    fn inverse() {
+        let b = Build::new();
+        let mut o = SetOntology::new();
+
+        let change =
+            OntologyChange::AddAxiom(DeclareClass(b.class("http://www.example.com/a")).into());
+        assert!(change.apply(&mut o));
+        assert!(change.inverse().apply(&mut o));
+        assert_eq!(o.iter().count(), 0);
+    }
+}
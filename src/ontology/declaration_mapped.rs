@@ -9,7 +9,7 @@ use super::indexed::OntologyIndex;
 
 use std::{
     collections::HashMap,
-    rc::Rc,
+    sync::Arc,
 };
 
 #[derive(Debug, Default)]
@@ -17,10 +17,7 @@ pub struct DeclarationMappedIndex(HashMap<IRI, NamedEntityKind>);
 
 impl DeclarationMappedIndex {
     pub fn is_annotation_property(&self, iri: &IRI) -> bool {
-        match self.declaration_kind(iri) {
-            Some(NamedEntityKind::AnnotationProperty) => true,
-            _ => false,
-        }
+        matches!(self.declaration_kind(iri), Some(NamedEntityKind::AnnotationProperty))
     }
 
     pub fn declaration_kind(&self, iri: &IRI) -> Option<NamedEntityKind>{
@@ -81,10 +78,10 @@ macro_rules! some {
 
 
 impl OntologyIndex for DeclarationMappedIndex {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         let s = some!{
-            self.0.insert(self.aa_to_iri(&*ax)?,
-                          self.aa_to_ne(&*ax)?)
+            self.0.insert(self.aa_to_iri(&ax)?,
+                          self.aa_to_ne(&ax)?)
         };
         s.is_some()
     }
@@ -130,7 +127,6 @@ mod test{
     #[test]
     fn test_cons() {
         let _d = DeclarationMappedIndex::default();
-        assert!(true);
     }
 
     #[test]
@@ -0,0 +1,192 @@
+//! An ontology which records its changes, allowing undo and redo
+use super::change::OntologyChange;
+use crate::model::{AnnotatedAxiom, MutableOntology, Ontology, OntologyID, IRI};
+
+/// Wrap a `MutableOntology`, recording each change so that it can be
+/// undone and redone.
+///
+/// Each `insert` or `take` which changes the ontology is one step of
+/// the history; `apply_all` records several changes as a single step.
+/// Making a new change discards anything which could be redone.
+#[derive(Debug, Default)]
+pub struct HistoryOntology<O> {
+    ont: O,
+    undo: Vec<Vec<OntologyChange>>,
+    redo: Vec<Vec<OntologyChange>>,
+}
+
+impl<O: MutableOntology> HistoryOntology<O> {
+    pub fn new(ont: O) -> HistoryOntology<O> {
+        HistoryOntology {
+            ont,
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+
+    /// Return the wrapped ontology.
+    pub fn ontology(&self) -> &O {
+        &self.ont
+    }
+
+    pub fn into_inner(self) -> O {
+        self.ont
+    }
+
+    /// Apply `changes` as a single step, returning true if the
+    /// ontology was changed.
+    ///
+    /// Changes which have no effect are not recorded.
+    pub fn apply_all<I>(&mut self, changes: I) -> bool
+    where
+        I: IntoIterator<Item = OntologyChange>,
+    {
+        let applied: Vec<_> = changes
+            .into_iter()
+            .filter(|c| c.apply(&mut self.ont))
+            .collect();
+
+        if applied.is_empty() {
+            return false;
+        }
+
+        self.undo.push(applied);
+        self.redo.clear();
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverse the last step, returning the changes that were undone.
+    pub fn undo(&mut self) -> Option<&[OntologyChange]> {
+        let step = self.undo.pop()?;
+        for c in step.iter().rev() {
+            c.inverse().apply(&mut self.ont);
+        }
+        self.redo.push(step);
+        self.redo.last().map(|v| v.as_slice())
+    }
+
+    /// Reapply the last step that was undone, returning its changes.
+    pub fn redo(&mut self) -> Option<&[OntologyChange]> {
+        let step = self.redo.pop()?;
+        for c in step.iter() {
+            c.apply(&mut self.ont);
+        }
+        self.undo.push(step);
+        self.undo.last().map(|v| v.as_slice())
+    }
+
+    /// Forget all history.
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<O: Ontology> Ontology for HistoryOntology<O> {
+    fn id(&self) -> &OntologyID {
+        self.ont.id()
+    }
+
+    fn mut_id(&mut self) -> &mut OntologyID {
+        self.ont.mut_id()
+    }
+
+    fn doc_iri(&self) -> &Option<IRI> {
+        self.ont.doc_iri()
+    }
+
+    fn mut_doc_iri(&mut self) -> &Option<IRI> {
+        self.ont.mut_doc_iri()
+    }
+}
+
+impl<O: MutableOntology> MutableOntology for HistoryOntology<O> {
+    fn insert<A>(&mut self, ax: A) -> bool
+    where
+        A: Into<AnnotatedAxiom>,
+    {
+        self.apply_all(Some(OntologyChange::AddAxiom(ax.into())))
+    }
+
+    fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
+        let taken = self.ont.take(ax)?;
+        self.undo
+            .push(vec![OntologyChange::RemoveAxiom(taken.clone())]);
+        self.redo.clear();
+        Some(taken)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::*;
+    use crate::ontology::set::SetOntology;
+
+    fn ax(b: &Build, s: &str) -> AnnotatedAxiom {
+        DeclareClass(b.class(format!("http://www.example.com/{}", s))).into()
+    }
+
+    #[test]
+    fn undo_redo() {
+        let b = Build::new();
+        let mut o = HistoryOntology::new(SetOntology::new());
+
+        o.insert(ax(&b, "a"));
+        o.insert(ax(&b, "b"));
+        o.remove(&ax(&b, "a"));
+        assert_eq!(o.ontology().iter().count(), 1);
+
+        assert!(o.undo().is_some());
+        assert!(o.ontology().iter().any(|a| *a == ax(&b, "a")));
+
+        o.undo();
+        o.undo();
+        assert_eq!(o.ontology().iter().count(), 0);
+        assert!(o.undo().is_none());
+
+        o.redo();
+        o.redo();
+        assert_eq!(o.ontology().iter().count(), 2);
+        assert!(o.can_redo());
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let b = Build::new();
+        let mut o = HistoryOntology::new(SetOntology::new());
+
+        o.insert(ax(&b, "a"));
+        o.undo();
+        assert!(o.can_redo());
+
+        o.insert(ax(&b, "b"));
+        assert!(!o.can_redo());
+    }
+
+    #[test]
+    fn apply_all_single_step() {
+        let b = Build::new();
+        let mut o = HistoryOntology::new(SetOntology::new());
+
+        assert!(o.apply_all(vec![
+            OntologyChange::AddAxiom(ax(&b, "a")),
+            OntologyChange::AddAxiom(ax(&b, "b")),
+            OntologyChange::AddAxiom(ax(&b, "a")),
+        ]));
+        assert_eq!(o.undo().unwrap().len(), 2);
+        assert_eq!(o.ontology().iter().count(), 0);
+
+        // Nothing changes, so nothing is recorded
+        assert!(!o.remove(&ax(&b, "a")));
+        assert!(!o.can_undo());
+    }
+}
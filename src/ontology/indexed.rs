@@ -9,7 +9,7 @@
 //! An indexed `MutableOntology` is one that uses one or more
 //! `OntologyIndex` objects as the backing store for its
 //! `AnnotatedAxiom`. These `AnnotatedAxiom` objects are shared
-//! between different `OntologyIndex` objects using `Rc`. The
+//! between different `OntologyIndex` objects using `Arc`. The
 //! `OntologyIndex` interace does not provide any mechanisms for
 //! searching or querying the index which need to be provided by
 //! concrete implementations.
@@ -19,7 +19,7 @@
 //! named tuple, allowing differently typed `OntologyIndex` objects to
 //! be added.
 use crate::model::{AnnotatedAxiom, MutableOntology, Ontology, OntologyID, IRI};
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// An `OntologyIndex` object.
 ///
@@ -31,7 +31,7 @@ use std::rc::Rc;
 /// or log time, not linear).
 ///
 /// A given `OntologyIndex` object is not bound to keep references to
-/// all `Rc<AnnotatedAxiom>` that are inserted into it, although at
+/// all `Arc<AnnotatedAxiom>` that are inserted into it, although at
 /// least one `OntologyIndex` object for an `IndexedOntology` should
 /// do, or the it will be dropped entirely. The `SetIndex` is a simple
 /// way to achieving this.
@@ -41,7 +41,7 @@ pub trait OntologyIndex {
     /// If the index did not have this value present, true is returned.
     ///
     /// If the index did have this value present, false is returned.
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool;
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool;
 
     /// Remove an AnnotatedAxiom from the index.
     ///
@@ -58,6 +58,17 @@ pub trait OntologyIndex {
     ///
     /// Return None if it does not.
     fn index_take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom>;
+
+    /// Insert many AnnotatedAxioms to the index.
+    ///
+    /// Return the number of axioms which the index did not have
+    /// present. Indexes should override this where they can update
+    /// more efficiently in bulk.
+    fn index_extend(&mut self, axs: Vec<Arc<AnnotatedAxiom>>) -> usize {
+        axs.into_iter()
+            .filter(|ax| self.index_insert(ax.clone()))
+            .count()
+    }
 }
 
 /// A NullOntologyIndex which does nothing.
@@ -65,7 +76,7 @@ pub trait OntologyIndex {
 pub struct NullIndex();
 impl OntologyIndex for NullIndex {
     /// Insert an item, always returns false
-    fn index_insert(&mut self, _ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, _ax: Arc<AnnotatedAxiom>) -> bool {
         false
     }
 
@@ -119,10 +130,18 @@ impl<I: OntologyIndex> Ontology for OneIndexedOntology<I> {
 
 impl<I: OntologyIndex> MutableOntology for OneIndexedOntology<I> {
     fn insert<A: Into<AnnotatedAxiom>>(&mut self, ax: A) -> bool {
-        let rc = Rc::new(ax.into());
+        let rc = Arc::new(ax.into());
         self.0.index_insert(rc)
     }
 
+    fn extend<T, A>(&mut self, axs: T) -> usize
+    where
+        T: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        self.0.index_extend(arcs(axs))
+    }
+
     fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
         self.0.index_take(ax)
     }
@@ -172,17 +191,25 @@ impl<I: OntologyIndex, J: OntologyIndex> Ontology for TwoIndexedOntology<I, J> {
 
 impl<I: OntologyIndex, J: OntologyIndex> MutableOntology for TwoIndexedOntology<I, J> {
     fn insert<A: Into<AnnotatedAxiom>>(&mut self, ax: A) -> bool {
-        let rc = Rc::new(ax.into());
+        let rc = Arc::new(ax.into());
         self.index_insert(rc)
     }
 
+    fn extend<T, A>(&mut self, axs: T) -> usize
+    where
+        T: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        self.index_extend(arcs(axs))
+    }
+
     fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
         self.index_take(ax)
     }
 }
 
 impl<I: OntologyIndex, J: OntologyIndex> OntologyIndex for TwoIndexedOntology<I, J> {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         let rtn = self.0.index_insert(ax.clone());
         // Don't short cirtuit
         self.1.index_insert(ax) || rtn
@@ -192,6 +219,11 @@ impl<I: OntologyIndex, J: OntologyIndex> OntologyIndex for TwoIndexedOntology<I,
         let rtn = self.0.index_take(ax);
         self.1.index_take(ax).or(rtn)
     }
+
+    fn index_extend(&mut self, axs: Vec<Arc<AnnotatedAxiom>>) -> usize {
+        let rtn = self.0.index_extend(axs.clone());
+        self.1.index_extend(axs).max(rtn)
+    }
 }
 
 /// ThreeIndexedOntology supports three indexes.
@@ -211,15 +243,15 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex> ThreeIndexedOntology<
     }
 
     pub fn i(&self) -> &I {
-        (&self.0).i()
+        self.0.i()
     }
 
     pub fn j(&self) -> &J {
-        (&self.0).j().i()
+        self.0.j().i()
     }
 
     pub fn k(&self) -> &K {
-        &(self.0).j().j()
+        (self.0).j().j()
     }
 
     pub fn index(self) -> (I, J, K) {
@@ -255,6 +287,14 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex> MutableOntology
         self.0.insert(ax)
     }
 
+    fn extend<T, A>(&mut self, axs: T) -> usize
+    where
+        T: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        self.0.extend(axs)
+    }
+
     fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
         self.0.take(ax)
     }
@@ -263,7 +303,7 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex> MutableOntology
 impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex> OntologyIndex
     for ThreeIndexedOntology<I, J, K>
 {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         let rtn = (self.0).0.index_insert(ax.clone());
         // Don't short cirtuit
         (self.0).1.index_insert(ax) || rtn
@@ -273,6 +313,10 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex> OntologyIndex
         let rtn = (self.0).0.index_take(ax);
         (self.0).1.index_take(ax).or(rtn)
     }
+
+    fn index_extend(&mut self, axs: Vec<Arc<AnnotatedAxiom>>) -> usize {
+        self.0.index_extend(axs)
+    }
 }
 
 /// FourIndexedOntology supports three indexes.
@@ -298,19 +342,19 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex, L: OntologyIndex>
     }
 
     pub fn i(&self) -> &I {
-        (&self.0).i()
+        self.0.i()
     }
 
     pub fn j(&self) -> &J {
-        (&self.0).j().i()
+        self.0.j().i()
     }
 
     pub fn k(&self) -> &K {
-        &(self.0).j().j()
+        (self.0).j().j()
     }
 
     pub fn l(&self) -> &L {
-        &(self.0).j().k()
+        (self.0).j().k()
     }
 
     pub fn index(self) -> (I, J, K, L) {
@@ -346,14 +390,30 @@ impl<I: OntologyIndex, J: OntologyIndex, K: OntologyIndex, L: OntologyIndex> Mut
         self.0.insert(ax)
     }
 
+    fn extend<T, A>(&mut self, axs: T) -> usize
+    where
+        T: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        self.0.extend(axs)
+    }
+
     fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
         self.0.take(ax)
     }
 }
 
 // Utility
-pub(crate) fn rc_unwrap_or_clone(rcax: Rc<AnnotatedAxiom>) -> AnnotatedAxiom {
-    Rc::try_unwrap(rcax).unwrap_or_else(|rcax| (*rcax).clone())
+fn arcs<T, A>(axs: T) -> Vec<Arc<AnnotatedAxiom>>
+where
+    T: IntoIterator<Item = A>,
+    A: Into<AnnotatedAxiom>,
+{
+    axs.into_iter().map(|ax| Arc::new(ax.into())).collect()
+}
+
+pub(crate) fn rc_unwrap_or_clone(rcax: Arc<AnnotatedAxiom>) -> AnnotatedAxiom {
+    Arc::try_unwrap(rcax).unwrap_or_else(|rcax| (*rcax).clone())
 }
 
 #[cfg(test)]
@@ -365,7 +425,7 @@ mod test {
     };
     use crate::{
         model::{AnnotatedAxiom, Build, MutableOntology, NamedEntity},
-        ontology::set::SetIndex,
+        ontology::{axiom_mapped::AxiomMappedIndex, set::SetIndex},
     };
 
     fn stuff() -> (AnnotatedAxiom, AnnotatedAxiom, AnnotatedAxiom) {
@@ -380,7 +440,6 @@ mod test {
     #[test]
     fn one_cons() {
         let _o = OneIndexedOntology::new(SetIndex::default());
-        assert!(true);
     }
 
     #[test]
@@ -413,18 +472,31 @@ mod test {
         assert!(!o.remove(&e.2));
     }
 
+    #[test]
+    fn two_extend() {
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::default(),
+            AxiomMappedIndex::default(),
+            Default::default(),
+        );
+        let e = stuff();
+        o.insert(e.0.clone());
+
+        assert_eq!(o.extend(vec![e.0, e.1.clone(), e.2, e.1]), 2);
+        assert_eq!(o.i().into_iter().count(), 3);
+        assert_eq!(o.j().iter().count(), 3);
+    }
+
     #[test]
     fn two_cons() {
         let _o =
             TwoIndexedOntology::new(SetIndex::default(), SetIndex::default(), Default::default());
-        assert!(true);
 
         let _o = TwoIndexedOntology::new(
             SetIndex::default(),
             NullIndex::default(),
             Default::default(),
         );
-        assert!(true);
     }
 
     #[test]
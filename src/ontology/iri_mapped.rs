@@ -0,0 +1,344 @@
+//! Access `AnnotatedAxiom` by the IRIs that they use.
+
+//! # Overview
+//!
+//! This module provides an `IRIMappedIndex` which provides rapid
+//! access to all axioms which use an IRI, either as an entity in
+//! their signature, or as the subject of an annotation assertion.
+use crate::model::*;
+use crate::signature::Signature;
+
+use super::axiom_mapped::AxiomMappedIndex;
+use super::indexed::{rc_unwrap_or_clone, OntologyIndex, TwoIndexedOntology};
+use super::set::SetOntology;
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct IRIMappedIndex(HashMap<IRI, BTreeSet<Arc<AnnotatedAxiom>>>);
+
+/// Return the IRIs used by `ax`.
+pub(crate) fn iris(ax: &AnnotatedAxiom) -> BTreeSet<IRI> {
+    let mut iris: BTreeSet<IRI> = ax
+        .signature()
+        .into_iter()
+        .map(|e| e.iri().clone())
+        .collect();
+
+    if let Axiom::AnnotationAssertion(aa) = &ax.axiom {
+        iris.insert(aa.subject.clone());
+    }
+    iris
+}
+
+impl IRIMappedIndex {
+    pub fn new() -> IRIMappedIndex {
+        IRIMappedIndex::default()
+    }
+
+    /// Iterate over the axioms which use `iri`, as any kind of
+    /// entity or as an annotation subject.
+    pub fn iri_usages(&self, iri: &IRI) -> impl Iterator<Item = &AnnotatedAxiom> {
+        self.0
+            .get(iri)
+            .into_iter()
+            .flat_map(|s| s.iter())
+            .map(|rc| &**rc)
+    }
+
+    /// Iterate over the axioms which use `e`, including its
+    /// declaration and any annotation assertions on its IRI.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::iri_mapped::IRIMappedOntology;
+    /// let mut o = IRIMappedOntology::default();
+    /// let b = Build::new();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.declare(a.clone());
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.j().usages(&a.into()).count(), 2);
+    /// ```
+    pub fn usages<'a>(&'a self, e: &'a NamedEntity) -> impl Iterator<Item = &'a AnnotatedAxiom> {
+        // Skip the axioms which only use a pun of `e`
+        self.iri_usages(e.iri()).filter(move |ax| match &ax.axiom {
+            Axiom::AnnotationAssertion(aa) if &aa.subject == e.iri() => true,
+            _ => ax.signature().contains(e),
+        })
+    }
+
+    /// Returns true if `iri` is used by any axiom.
+    pub fn is_used(&self, iri: &IRI) -> bool {
+        self.0.get(iri).is_some_and(|s| !s.is_empty())
+    }
+
+    /// Insert `ax` for each of `iris`, which should be those that it
+    /// uses.
+    fn insert_iris(&mut self, ax: Arc<AnnotatedAxiom>, iris: BTreeSet<IRI>) -> bool {
+        let mut inserted = false;
+        for iri in iris {
+            inserted |= self.0.entry(iri).or_default().insert(ax.clone());
+        }
+        inserted
+    }
+}
+
+impl OntologyIndex for IRIMappedIndex {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
+        let iris = iris(&ax);
+        self.insert_iris(ax, iris)
+    }
+
+    fn index_take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
+        let mut taken = None;
+        for iri in iris(ax) {
+            if let Some(s) = self.0.get_mut(&iri) {
+                taken = s.take(ax).or(taken);
+                if s.is_empty() {
+                    self.0.remove(&iri);
+                }
+            }
+        }
+        taken.map(rc_unwrap_or_clone)
+    }
+}
+
+/// An ontology which supports iteration by `AxiomKind` through `i()`
+/// and look up of usages through `j()`.
+pub type IRIMappedOntology = TwoIndexedOntology<AxiomMappedIndex, IRIMappedIndex>;
+
+/// What `remove_entity` does with the other axioms which refer to
+/// the entity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DanglingStrategy {
+    /// Remove the axioms.
+    Remove,
+    /// Leave the axioms in the ontology.
+    Keep,
+}
+
+/// Returns true if `ax` declares an entity with the IRI `iri`, or is
+/// an annotation assertion on it.
+fn describes(ax: &AnnotatedAxiom, iri: &IRI) -> bool {
+    match &ax.axiom {
+        Axiom::DeclareClass(DeclareClass(e)) => e.0 == *iri,
+        Axiom::DeclareObjectProperty(DeclareObjectProperty(e)) => e.0 == *iri,
+        Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => e.0 == *iri,
+        Axiom::DeclareDataProperty(DeclareDataProperty(e)) => e.0 == *iri,
+        Axiom::DeclareNamedIndividual(DeclareNamedIndividual(e)) => e.0 == *iri,
+        Axiom::DeclareDatatype(DeclareDatatype(e)) => e.0 == *iri,
+        Axiom::AnnotationAssertion(aa) => aa.subject == *iri,
+        _ => false,
+    }
+}
+
+impl IRIMappedOntology {
+    /// Remove the entities with the IRI `iri`, that is their
+    /// declarations and the annotation assertions on `iri`.
+    ///
+    /// The other axioms which refer to `iri` are removed or kept
+    /// according to `strategy`, and returned, so that they can be
+    /// reported or repaired.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::iri_mapped::{DanglingStrategy, IRIMappedOntology};
+    /// let mut o = IRIMappedOntology::default();
+    /// let b = Build::new();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.declare(a.clone());
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// let dangling = o.remove_entity(&a.0, DanglingStrategy::Keep);
+    /// assert_eq!(dangling.len(), 1);
+    /// assert_eq!(o.i().iter().count(), 1);
+    /// ```
+    pub fn remove_entity(&mut self, iri: &IRI, strategy: DanglingStrategy) -> Vec<AnnotatedAxiom> {
+        let (own, dangling): (Vec<AnnotatedAxiom>, Vec<AnnotatedAxiom>) = self
+            .j()
+            .iri_usages(iri)
+            .cloned()
+            .partition(|ax| describes(ax, iri));
+
+        for ax in &own {
+            self.remove(ax);
+        }
+        if strategy == DanglingStrategy::Remove {
+            for ax in &dangling {
+                self.remove(ax);
+            }
+        }
+        dangling
+    }
+}
+
+impl From<SetOntology> for IRIMappedOntology {
+    #[cfg(not(feature = "rayon"))]
+    fn from(mut so: SetOntology) -> IRIMappedOntology {
+        let mut imo = IRIMappedOntology::default();
+        std::mem::swap(imo.mut_id(), so.mut_id());
+        for ax in so {
+            imo.insert(ax);
+        }
+        imo
+    }
+
+    // Finding the IRIs of each axiom is most of the work, so do
+    // that in parallel, and then build the indexes.
+    #[cfg(feature = "rayon")]
+    fn from(mut so: SetOntology) -> IRIMappedOntology {
+        use rayon::prelude::*;
+
+        let id = std::mem::take(so.mut_id());
+        let axioms: Vec<AnnotatedAxiom> = so.into_iter().collect();
+        let used: Vec<BTreeSet<IRI>> = axioms.par_iter().map(iris).collect();
+
+        let mut i = AxiomMappedIndex::new();
+        let mut j = IRIMappedIndex::new();
+        for (ax, iris) in axioms.into_iter().zip(used) {
+            let rc = Arc::new(ax);
+            i.index_insert(rc.clone());
+            j.insert_iris(rc, iris);
+        }
+        IRIMappedOntology::new(i, j, id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn usages() {
+        let b = Build::new();
+        let a = b.class("http://www.example.com/a");
+        let mut o = IRIMappedOntology::default();
+
+        o.declare(a.clone());
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/b").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/r").into(),
+                bce: Box::new(a.clone().into()),
+            },
+        });
+        o.insert(AnnotationAssertion {
+            subject: a.0.clone(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: "a".to_string(),
+                }),
+            },
+        });
+        o.declare(b.class("http://www.example.com/c"));
+
+        let a: NamedEntity = a.into();
+        assert_eq!(o.j().usages(&a).count(), 3);
+        assert_eq!(
+            o.j()
+                .usages(&b.object_property("http://www.example.com/r").into())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn punned() {
+        let b = Build::new();
+        let mut o = IRIMappedOntology::default();
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.named_individual("http://www.example.com/a"));
+
+        let iri = b.iri("http://www.example.com/a");
+        assert_eq!(o.j().iri_usages(&iri).count(), 2);
+        assert_eq!(
+            o.j()
+                .usages(&b.named_individual("http://www.example.com/a").into())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let b = Build::new();
+        let mut o = IRIMappedOntology::default();
+        let ax: AnnotatedAxiom = SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/b").into(),
+        }
+        .into();
+
+        o.insert(ax.clone());
+        assert!(o.j().is_used(&b.iri("http://www.example.com/b")));
+
+        assert_eq!(o.take(&ax), Some(ax));
+        assert!(!o.j().is_used(&b.iri("http://www.example.com/a")));
+        assert_eq!(o.i().iter().count(), 0);
+    }
+
+    #[test]
+    fn from_set() {
+        let (so, _) = crate::io::owx::reader::read(
+            &mut include_str!("../ont/owl-xml/family.owx").as_bytes(),
+        )
+        .unwrap();
+        let n = so.iter().count();
+        let id = so.id().clone();
+
+        let o = IRIMappedOntology::from(so);
+        assert_eq!(o.id(), &id);
+        assert_eq!(o.i().iter().count(), n);
+        assert!(o.j().is_used(&Build::new().iri("http://example.com/owl/families/Person")));
+    }
+
+    #[test]
+    fn remove_entity() {
+        let b = Build::new();
+        let mut o = IRIMappedOntology::default();
+        let a = b.class("http://www.example.com/a");
+        let sc: AnnotatedAxiom = SubClassOf {
+            sub: b.class("http://www.example.com/b").into(),
+            sup: a.clone().into(),
+        }
+        .into();
+
+        o.declare(a.clone());
+        o.declare(b.named_individual("http://www.example.com/a"));
+        o.insert(AnnotationAssertion {
+            subject: a.0.clone(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: "a".to_string(),
+                }),
+            },
+        });
+        o.insert(sc.clone());
+        o.declare(b.class("http://www.example.com/b"));
+
+        let mut kept = IRIMappedOntology::default();
+        for ax in o.i().iter() {
+            kept.insert(ax.clone());
+        }
+
+        assert_eq!(kept.remove_entity(&a.0, DanglingStrategy::Keep), vec![sc.clone()]);
+        assert_eq!(kept.i().iter().count(), 2);
+        assert!(kept.j().is_used(&a.0));
+
+        assert_eq!(o.remove_entity(&a.0, DanglingStrategy::Remove), vec![sc]);
+        assert_eq!(o.i().iter().count(), 1);
+        assert!(!o.j().is_used(&a.0));
+    }
+}
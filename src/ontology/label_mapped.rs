@@ -0,0 +1,241 @@
+//! Access entity IRIs by their labels.
+
+//! # Overview
+//!
+//! This module provides a `LabelMappedIndex` which indexes the
+//! literal values of annotation assertions with a given set of
+//! properties, by default `rdfs:label`, so that an entity can be
+//! looked up by its label or synonyms in constant time.
+use crate::model::*;
+use crate::vocab::{AnnotationBuiltIn, WithIRI, SKOS};
+
+use super::axiom_mapped::AxiomMappedIndex;
+use super::indexed::{rc_unwrap_or_clone, OntologyIndex, TwoIndexedOntology};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct LabelMappedIndex {
+    properties: BTreeSet<IRI>,
+    labels: HashMap<String, BTreeMap<IRI, BTreeSet<Arc<AnnotatedAxiom>>>>,
+}
+
+impl Default for LabelMappedIndex {
+    fn default() -> LabelMappedIndex {
+        let b = Build::new();
+        LabelMappedIndex::new(vec![b.iri(AnnotationBuiltIn::LABEL.iri_str())])
+    }
+}
+
+impl LabelMappedIndex {
+    /// Create an index of the values of the annotation properties
+    /// `properties`, such as `rdfs:label` and any synonym properties.
+    pub fn new<I: IntoIterator<Item = IRI>>(properties: I) -> LabelMappedIndex {
+        LabelMappedIndex {
+            properties: properties.into_iter().collect(),
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Create an index of `rdfs:label` and `skos:prefLabel`, for
+    /// ontologies which use SKOS labels.
+    pub fn with_skos() -> LabelMappedIndex {
+        let b = Build::new();
+        LabelMappedIndex::new(vec![
+            b.iri(AnnotationBuiltIn::LABEL.iri_str()),
+            b.iri(SKOS::PrefLabel.iri_str()),
+        ])
+    }
+
+    /// The annotation properties which are indexed.
+    pub fn properties(&self) -> &BTreeSet<IRI> {
+        &self.properties
+    }
+
+    fn label<'a>(&self, ax: &'a AnnotatedAxiom) -> Option<(&'a String, &'a IRI)> {
+        match &ax.axiom {
+            Axiom::AnnotationAssertion(AnnotationAssertion {
+                subject,
+                ann:
+                    Annotation {
+                        ap,
+                        av: AnnotationValue::Literal(l),
+                    },
+            }) if self.properties.contains(&ap.0) => Some((l.literal(), subject)),
+            _ => None,
+        }
+    }
+
+    /// Return an entity with the label `label`.
+    ///
+    /// If more than one entity has this label, the first IRI in the
+    /// sort order is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::label_mapped::LabelMappedOntology;
+    /// let mut o = LabelMappedOntology::default();
+    /// let b = Build::new();
+    /// o.insert(AnnotationAssertion {
+    ///     subject: b.iri("http://www.example.com/a"),
+    ///     ann: Annotation {
+    ///         ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///         av: Literal::Simple { literal: "apoptosis".to_string() }.into(),
+    ///     },
+    /// });
+    ///
+    /// assert_eq!(
+    ///     o.j().entity_by_label("apoptosis"),
+    ///     Some(&b.iri("http://www.example.com/a"))
+    /// );
+    /// ```
+    pub fn entity_by_label(&self, label: &str) -> Option<&IRI> {
+        self.entities_by_label(label).next()
+    }
+
+    /// Iterate over all entities with the label `label`, in sorted
+    /// order.
+    pub fn entities_by_label(&self, label: &str) -> impl Iterator<Item = &IRI> {
+        self.labels.get(label).into_iter().flat_map(|m| m.keys())
+    }
+
+    /// Iterate over all indexed labels, in an arbitrary order.
+    pub fn labels(&self) -> impl Iterator<Item = (&String, impl Iterator<Item = &IRI>)> {
+        self.labels.iter().map(|(l, m)| (l, m.keys()))
+    }
+}
+
+impl OntologyIndex for LabelMappedIndex {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
+        match self.label(&ax) {
+            Some((label, subject)) => self
+                .labels
+                .entry(label.clone())
+                .or_default()
+                .entry(subject.clone())
+                .or_default()
+                .insert(ax.clone()),
+            None => false,
+        }
+    }
+
+    fn index_take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
+        let (label, subject) = self.label(ax)?;
+        let subjects = self.labels.get_mut(label)?;
+        let axioms = subjects.get_mut(subject)?;
+
+        // Several axioms may give the same label, for example with
+        // different language tags
+        let taken = axioms.take(ax)?;
+        if axioms.is_empty() {
+            subjects.remove(subject);
+        }
+        if subjects.is_empty() {
+            self.labels.remove(label);
+        }
+        Some(rc_unwrap_or_clone(taken))
+    }
+}
+
+/// An ontology which supports iteration by `AxiomKind` through `i()`
+/// and look up by label through `j()`.
+pub type LabelMappedOntology = TwoIndexedOntology<AxiomMappedIndex, LabelMappedIndex>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn label(b: &Build, iri: &str, ap: &str, l: Literal) -> AnnotationAssertion {
+        AnnotationAssertion {
+            subject: b.iri(iri),
+            ann: Annotation {
+                ap: b.annotation_property(ap),
+                av: l.into(),
+            },
+        }
+    }
+
+    fn simple(s: &str) -> Literal {
+        Literal::Simple {
+            literal: s.to_string(),
+        }
+    }
+
+    const LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+    const SYNONYM: &str = "http://www.geneontology.org/formats/oboInOwl#hasExactSynonym";
+
+    #[test]
+    fn lookup() {
+        let b = Build::new();
+        let mut o = LabelMappedOntology::default();
+        o.insert(label(&b, "http://www.example.com/a", LABEL, simple("a")));
+        o.insert(label(&b, "http://www.example.com/b", SYNONYM, simple("b")));
+
+        assert_eq!(
+            o.j().entity_by_label("a"),
+            Some(&b.iri("http://www.example.com/a"))
+        );
+        assert_eq!(o.j().entity_by_label("b"), None);
+        assert_eq!(o.j().entity_by_label("c"), None);
+        assert_eq!(o.i().annotation_assertion().count(), 2);
+    }
+
+    #[test]
+    fn synonyms() {
+        let b = Build::new();
+        let index = LabelMappedIndex::new(vec![b.iri(LABEL), b.iri(SYNONYM)]);
+        let mut o = LabelMappedOntology::new(Default::default(), index, Default::default());
+        o.insert(label(&b, "http://www.example.com/a", LABEL, simple("a")));
+        o.insert(label(&b, "http://www.example.com/a", SYNONYM, simple("b")));
+        o.insert(label(&b, "http://www.example.com/c", SYNONYM, simple("b")));
+
+        assert_eq!(o.j().entities_by_label("b").count(), 2);
+        assert_eq!(o.j().labels().count(), 2);
+    }
+
+    #[test]
+    fn skos() {
+        let b = Build::new();
+        let pref = "http://www.w3.org/2004/02/skos/core#prefLabel";
+        let mut o = LabelMappedOntology::new(
+            Default::default(),
+            LabelMappedIndex::with_skos(),
+            Default::default(),
+        );
+        o.insert(label(&b, "http://www.example.com/a", LABEL, simple("a")));
+        o.insert(label(&b, "http://www.example.com/b", pref, simple("b")));
+
+        assert_eq!(
+            o.j().entity_by_label("b"),
+            Some(&b.iri("http://www.example.com/b"))
+        );
+        assert!(o.j().entity_by_label("a").is_some());
+    }
+
+    #[test]
+    fn remove() {
+        let b = Build::new();
+        let mut o = LabelMappedOntology::default();
+        let en = label(
+            &b,
+            "http://www.example.com/a",
+            LABEL,
+            Literal::Language {
+                literal: "a".to_string(),
+                lang: "en".to_string(),
+            },
+        );
+        o.insert(en.clone());
+        o.insert(en.clone());
+        o.insert(label(&b, "http://www.example.com/a", LABEL, simple("a")));
+
+        o.remove(&en.into());
+        assert!(o.j().entity_by_label("a").is_some());
+
+        o.remove(&label(&b, "http://www.example.com/a", LABEL, simple("a")).into());
+        assert!(o.j().entity_by_label("a").is_none());
+        assert_eq!(o.j().labels().count(), 0);
+    }
+}
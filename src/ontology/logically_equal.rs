@@ -6,7 +6,7 @@ use crate::model::{
 
 use std::convert::AsRef;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 use super::indexed::{
     rc_unwrap_or_clone,
     OntologyIndex,
@@ -15,10 +15,10 @@ use super::indexed::{
 };
 
 #[derive(Debug,Default)]
-pub struct LogicallyEqualIndex(HashMap<Axiom, Rc<AnnotatedAxiom>>);
+pub struct LogicallyEqualIndex(HashMap<Axiom, Arc<AnnotatedAxiom>>);
 
 impl OntologyIndex for LogicallyEqualIndex {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         self.0.insert(ax.axiom.clone(), ax).is_some()
     }
 
@@ -40,7 +40,7 @@ impl LogicallyEqualIndex {
         self.0.get(&ax.axiom).map(|rcax| &**rcax)
     }
 
-    pub fn logical_get_rc(&self, ax: &AnnotatedAxiom) -> Option<Rc<AnnotatedAxiom>> {
+    pub fn logical_get_rc(&self, ax: &AnnotatedAxiom) -> Option<Arc<AnnotatedAxiom>> {
         self.0.get(&ax.axiom).cloned()
     }
 }
@@ -63,7 +63,7 @@ where I: OntologyIndex,
 }
 
 
-pub fn update_or_insert_logically_equal_axiom<'a, O>(o: &mut O, axiom: AnnotatedAxiom)
+pub fn update_or_insert_logically_equal_axiom<O>(o: &mut O, axiom: AnnotatedAxiom)
 where O:MutableOntology+AsRef<LogicallyEqualIndex>
 {
     if let Some(axiom) = update_logically_equal_axiom(o, axiom) {
@@ -71,7 +71,7 @@ where O:MutableOntology+AsRef<LogicallyEqualIndex>
     }
 }
 
-pub fn update_logically_equal_axiom<'a, O> (o: &mut O, mut axiom: AnnotatedAxiom)
+pub fn update_logically_equal_axiom<O> (o: &mut O, mut axiom: AnnotatedAxiom)
                                             -> Option<AnnotatedAxiom>
 where O:MutableOntology+AsRef<LogicallyEqualIndex> {
 
@@ -80,12 +80,12 @@ where O:MutableOntology+AsRef<LogicallyEqualIndex> {
     // Does the logically equal axiom exist
     if let Some(rc) = src {
         // Remove the rc from everywhere
-        o.remove(&*rc);
+        o.remove(&rc);
         //dbg!(&rc);
-        //dbg!(Rc::strong_count(&rc));
+        //dbg!(Arc::strong_count(&rc));
 
         // Un-rc
-        let mut logical_axiom = Rc::try_unwrap(rc).unwrap();
+        let mut logical_axiom = Arc::try_unwrap(rc).unwrap();
         // Extend it
         logical_axiom.ann.append(&mut axiom.ann);
         // Insert it
@@ -109,7 +109,6 @@ mod test {
     #[test]
     fn cons() {
         let _lei = LogicallyEqualIndex::default();
-        assert!(true);
     }
 
     #[test]
@@ -124,9 +123,9 @@ mod test {
         let decl3:AnnotatedAxiom =
             DeclareClass(build.class("http://www.example.com#c")).into();
 
-        o.index_insert(Rc::new(decl1.clone()));
-        o.index_insert(Rc::new(decl2.clone()));
-        o.index_insert(Rc::new(decl3.clone()));
+        o.index_insert(Arc::new(decl1.clone()));
+        o.index_insert(Arc::new(decl2.clone()));
+        o.index_insert(Arc::new(decl3.clone()));
 
         assert!(o.logical_contains(&decl1));
         assert!(o.logical_contains(&decl2));
@@ -223,7 +222,7 @@ mod test {
 
             let aa = o.i().into_iter().next().unwrap();
 
-            assert_eq!(aa.ann.iter().count(), 2);
+            assert_eq!(aa.ann.len(), 2);
         }
     }
 
@@ -0,0 +1,451 @@
+//! A read-only ontology stored in an index file
+//!
+//! # Overview
+//!
+//! `MmappedOntology` reads axioms from an index file as they are
+//! needed, so that a very large ontology can be queried without
+//! holding all of it in memory. With the `memmap2` feature,
+//! `MmappedOntology::open` memory maps the file, so that only the
+//! pages which are used are read from disk.
+//!
+//! The index file, written by `write`, uses the encoding of the
+//! [`hob`](../../io/hob/index.html) format, with tables of offsets
+//! so that any IRI or axiom can be found directly. Axioms are grouped
+//! by `AxiomKind`, and each IRI has a list of the axioms which use
+//! it, as in an `IRIMappedIndex`.
+use crate::io::hob::{invalid, Decode, Encode, Reader, Writer};
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::iri_mapped::iris;
+
+use crate::error::HornedError;
+
+use std::convert::TryInto;
+use std::io::Write as StdWrite;
+
+/// The first bytes of an index file, including its version.
+pub const MAGIC: &[u8] = b"HOBI\x01\0\0\0";
+
+// The magic, then the number of axiom kinds, IRIs, axioms and
+// usages, and the length of the ontology ID
+const HEADER: usize = 8 + 5 * 8;
+
+fn write_u64s(buf: &mut Vec<u8>, v: impl IntoIterator<Item = usize>) {
+    for n in v {
+        buf.extend_from_slice(&(n as u64).to_le_bytes());
+    }
+}
+
+/// Write `ont` to `write` as an index file.
+pub fn write(write: &mut dyn StdWrite, ont: &AxiomMappedOntology) -> Result<(), HornedError> {
+    let mut axioms: Vec<&AnnotatedAxiom> = ont.i().iter().collect();
+    axioms.sort_by_key(|ax| ax.kind());
+
+    // Find every IRI, so that the table can be sorted for look up
+    let mut w = Writer::new();
+    ont.id().encode(&mut w);
+    for ax in &axioms {
+        ax.encode(&mut w);
+    }
+    let mut table = w.table;
+    table.sort();
+
+    let mut w = Writer::with_table(table);
+    let mut id = vec![];
+    ont.id().encode(&mut w);
+    std::mem::swap(&mut id, &mut w.buf);
+
+    let mut axiom_offsets = vec![0];
+    let mut usages: Vec<Vec<u32>> = vec![vec![]; w.table.len()];
+    for (n, ax) in axioms.iter().enumerate() {
+        ax.encode(&mut w);
+        axiom_offsets.push(w.buf.len());
+        for iri in iris(ax) {
+            if let Ok(i) = w.table.binary_search(&iri) {
+                usages[i].push(n as u32);
+            }
+        }
+    }
+
+    let kinds = AxiomKind::all_kinds();
+    let mut kind_starts = vec![];
+    let mut n = 0;
+    for k in &kinds {
+        while n < axioms.len() && axioms[n].kind() < *k {
+            n += 1;
+        }
+        kind_starts.push(n);
+    }
+    kind_starts.push(axioms.len());
+
+    let mut iri_offsets = vec![0];
+    let mut iri_data = vec![];
+    for iri in &w.table {
+        iri_data.extend_from_slice(iri.as_bytes());
+        iri_offsets.push(iri_data.len());
+    }
+
+    let mut usage_offsets = vec![0];
+    let mut n = 0;
+    for u in &usages {
+        n += u.len();
+        usage_offsets.push(n);
+    }
+
+    let mut head = MAGIC.to_vec();
+    write_u64s(
+        &mut head,
+        vec![kinds.len(), w.table.len(), axioms.len(), n, id.len()],
+    );
+    write_u64s(&mut head, iri_offsets);
+    write_u64s(&mut head, axiom_offsets);
+    write_u64s(&mut head, kind_starts);
+    write_u64s(&mut head, usage_offsets);
+    for u in usages.into_iter().flatten() {
+        head.extend_from_slice(&u.to_le_bytes());
+    }
+    head.extend_from_slice(&id);
+
+    write.write_all(&head)?;
+    write.write_all(&iri_data)?;
+    write.write_all(&w.buf)?;
+    Ok(())
+}
+
+/// A read-only ontology, which decodes axioms from `D` on demand.
+///
+/// Axioms are returned by value, as they are decoded each time that
+/// they are read. The IRIs are interned, but only as they are used.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::ontology::mmapped::{write, MmappedOntology};
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// let a = b.class("http://www.example.com/a");
+/// o.declare(a.clone());
+/// o.insert(SubClassOf {
+///     sub: a.clone().into(),
+///     sup: b.class("http://www.example.com/b").into(),
+/// });
+///
+/// let mut buf = vec![];
+/// write(&mut buf, &o).unwrap();
+///
+/// let m = MmappedOntology::new(buf).unwrap();
+/// assert_eq!(m.len(), 2);
+/// assert_eq!(m.annotated_axiom(AxiomKind::SubClassOf).count(), 1);
+/// assert_eq!(m.iri_usages(&a.0).count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct MmappedOntology<D> {
+    data: D,
+    b: Build,
+    id: OntologyID,
+    n_iris: usize,
+    n_axioms: usize,
+    n_usages: usize,
+    kinds: usize,
+    // The offsets of each section
+    iri_offsets: usize,
+    axiom_offsets: usize,
+    kind_starts: usize,
+    usage_offsets: usize,
+    usages: usize,
+    iri_data: usize,
+    axiom_data: usize,
+}
+
+#[cfg(feature = "memmap2")]
+impl MmappedOntology<memmap2::Mmap> {
+    /// Open the index file at `path`, by memory mapping it.
+    ///
+    /// The file must not be changed while it is open.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, HornedError> {
+        let file = std::fs::File::open(path)?;
+        // Safety: the mapping is only read, and changes to the file
+        // are documented as being disallowed.
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        MmappedOntology::new(mmap)
+    }
+}
+
+fn read_u64(bytes: &[u8], pos: usize) -> Result<usize, HornedError> {
+    let b = bytes
+        .get(pos..pos + 8)
+        .ok_or_else(|| invalid("file is truncated"))?;
+    Ok(u64::from_le_bytes(b.try_into().unwrap()) as usize)
+}
+
+impl<D: AsRef<[u8]>> MmappedOntology<D> {
+    /// Return an ontology reading from `data`, which must have been
+    /// written by `write`.
+    pub fn new(data: D) -> Result<Self, HornedError> {
+        let bytes = data.as_ref();
+        if !bytes.starts_with(MAGIC) {
+            return Err(invalid("not a Horned-OWL index file, or the wrong version"));
+        }
+
+        let field = |n: usize| read_u64(bytes, MAGIC.len() + n * 8);
+        let (kinds, n_iris, n_axioms, n_usages, id_len) =
+            (field(0)?, field(1)?, field(2)?, field(3)?, field(4)?);
+        if kinds != AxiomKind::all_kinds().len() {
+            return Err(invalid("axiom kinds do not match this version"));
+        }
+
+        // The length of a table of `n` entries of `width` bytes
+        let size = |n: usize, width: usize| {
+            n.checked_mul(width)
+                .filter(|n| *n <= bytes.len())
+                .ok_or_else(|| invalid("file is truncated"))
+        };
+        let iri_offsets = HEADER;
+        let axiom_offsets = iri_offsets + size(n_iris.saturating_add(1), 8)?;
+        let kind_starts = axiom_offsets + size(n_axioms.saturating_add(1), 8)?;
+        let usage_offsets = kind_starts + size(kinds + 1, 8)?;
+        let usages = usage_offsets + size(n_iris.saturating_add(1), 8)?;
+        let id = usages + size(n_usages, 4)?;
+        let iri_data = id + size(id_len, 1)?;
+        let axiom_data = read_u64(bytes, iri_offsets + n_iris * 8)?.checked_add(iri_data);
+        let axiom_len = read_u64(bytes, axiom_offsets + n_axioms * 8)?;
+        let axiom_data = match axiom_data {
+            Some(a) if a.checked_add(axiom_len) == Some(bytes.len()) => a,
+            _ => return Err(invalid("file has the wrong length")),
+        };
+
+        let mut m = MmappedOntology {
+            data,
+            b: Build::new(),
+            id: OntologyID::default(),
+            n_iris,
+            n_axioms,
+            n_usages,
+            kinds,
+            iri_offsets,
+            axiom_offsets,
+            kind_starts,
+            usage_offsets,
+            usages,
+            iri_data,
+            axiom_data,
+        };
+        m.id = m.decode(&m.bytes()[id..iri_data])?;
+        Ok(m)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    fn u64_at(&self, table: usize, n: usize) -> Result<usize, HornedError> {
+        read_u64(self.bytes(), table + n * 8)
+    }
+
+    /// Return the bytes from `base` between the `n`th and next
+    /// offsets in `table`.
+    fn span(&self, base: usize, table: usize, n: usize) -> Result<&[u8], HornedError> {
+        let start = self.u64_at(table, n)?.checked_add(base);
+        let end = self.u64_at(table, n + 1)?.checked_add(base);
+        start
+            .zip(end)
+            .and_then(|(start, end)| self.bytes().get(start..end))
+            .ok_or_else(|| invalid("offset is outside the file"))
+    }
+
+    fn decode<T: Decode>(&self, data: &[u8]) -> Result<T, HornedError> {
+        let iri = |n| self.iri(n).ok();
+        let mut r = Reader::new(data, &iri);
+        let t = T::decode(&mut r)?;
+        if !r.data.is_empty() {
+            return Err(invalid("trailing data"));
+        }
+        Ok(t)
+    }
+
+    fn iri_str(&self, n: usize) -> Result<&str, HornedError> {
+        let s = self.span(self.iri_data, self.iri_offsets, n)?;
+        std::str::from_utf8(s).map_err(|_| invalid("string is not UTF-8"))
+    }
+
+    fn iri(&self, n: usize) -> Result<IRI, HornedError> {
+        if n >= self.n_iris {
+            return Err(invalid("IRI is not in the table"));
+        }
+        Ok(self.b.iri(self.iri_str(n)?))
+    }
+
+    /// Return the index of `iri` in the sorted IRI table.
+    fn find_iri(&self, iri: &IRI) -> Option<usize> {
+        let (mut lo, mut hi) = (0, self.n_iris);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.iri_str(mid).ok()?.cmp(iri.as_str()) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    pub fn id(&self) -> &OntologyID {
+        &self.id
+    }
+
+    /// The number of axioms.
+    pub fn len(&self) -> usize {
+        self.n_axioms
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n_axioms == 0
+    }
+
+    /// Decode the `n`th axiom.
+    pub fn get(&self, n: usize) -> Result<AnnotatedAxiom, HornedError> {
+        if n >= self.n_axioms {
+            return Err(invalid("axiom is not in the file"));
+        }
+        self.decode(self.span(self.axiom_data, self.axiom_offsets, n)?)
+    }
+
+    /// Iterate over all of the axioms, decoding each in turn.
+    pub fn iter(&self) -> impl Iterator<Item = Result<AnnotatedAxiom, HornedError>> + '_ {
+        (0..self.n_axioms).map(move |n| self.get(n))
+    }
+
+    /// Iterate over the axioms of kind `kind`.
+    pub fn annotated_axiom(
+        &self,
+        kind: AxiomKind,
+    ) -> impl Iterator<Item = Result<AnnotatedAxiom, HornedError>> + '_ {
+        let k = kind as usize;
+        let range = if k < self.kinds {
+            let start = self.u64_at(self.kind_starts, k).unwrap_or(0);
+            let end = self.u64_at(self.kind_starts, k + 1).unwrap_or(0);
+            start..end.min(self.n_axioms)
+        } else {
+            0..0
+        };
+        range.map(move |n| self.get(n))
+    }
+
+    /// Iterate over the axioms which use `iri`, as any kind of
+    /// entity or as an annotation subject.
+    pub fn iri_usages(
+        &self,
+        iri: &IRI,
+    ) -> impl Iterator<Item = Result<AnnotatedAxiom, HornedError>> + '_ {
+        let range = match self.find_iri(iri) {
+            Some(i) => {
+                let start = self.u64_at(self.usage_offsets, i).unwrap_or(0);
+                let end = self.u64_at(self.usage_offsets, i + 1).unwrap_or(0);
+                start..end
+            }
+            None => 0..0,
+        };
+        range.map(move |u| {
+            if u >= self.n_usages {
+                return Err(invalid("usage is not in the file"));
+            }
+            let pos = self.usages + u * 4;
+            let b = &self.bytes()[pos..pos + 4];
+            self.get(u32::from_le_bytes(b.try_into().unwrap()) as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::test::read_ok;
+    use crate::ontology::set::SetOntology;
+
+    fn index(owx: &str) -> (AxiomMappedOntology, MmappedOntology<Vec<u8>>) {
+        let (ont, _) = read_ok(&mut owx.as_bytes());
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        (ont, MmappedOntology::new(buf).unwrap())
+    }
+
+    #[test]
+    fn roundtrip() {
+        let (ont, m) = index(include_str!("../ont/owl-xml/family.owx"));
+
+        assert_eq!(m.id(), ont.id());
+        assert_eq!(m.len(), ont.i().iter().count());
+        let round: SetOntology = m.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            round.iter_sorted().collect::<Vec<_>>(),
+            ont.i().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        let (ont, m) = index(include_str!("../ont/owl-xml/family.owx"));
+
+        for k in AxiomKind::all_kinds() {
+            let axioms: Vec<_> = m.annotated_axiom(k).collect::<Result<_, _>>().unwrap();
+            assert_eq!(
+                axioms,
+                ont.i().annotated_axiom(k).cloned().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn usages() {
+        let (ont, m) = index(include_str!("../ont/owl-xml/family.owx"));
+        let imo: crate::ontology::iri_mapped::IRIMappedOntology = SetOntology::from(ont).into();
+
+        let b = Build::new();
+        let person = b.iri("http://example.com/owl/families/Person");
+        let mut found: Vec<_> = m.iri_usages(&person).collect::<Result<_, _>>().unwrap();
+        let mut expected: Vec<_> = imo.j().iri_usages(&person).cloned().collect();
+        found.sort();
+        expected.sort();
+        assert!(!found.is_empty());
+        assert_eq!(found, expected);
+
+        assert_eq!(
+            m.iri_usages(&b.iri("http://www.example.com/missing"))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "memmap2")]
+    fn open() {
+        let (ont, _) = read_ok(&mut include_str!("../ont/owl-xml/family.owx").as_bytes());
+        let temp = mktemp::Temp::new_file().unwrap();
+        write(&mut std::fs::File::create(&temp).unwrap(), &ont).unwrap();
+
+        let m = MmappedOntology::open(&temp).unwrap();
+        assert_eq!(m.len(), ont.i().iter().count());
+    }
+
+    #[test]
+    fn empty() {
+        let mut buf = vec![];
+        write(&mut buf, &AxiomMappedOntology::default()).unwrap();
+        let m = MmappedOntology::new(buf).unwrap();
+
+        assert!(m.is_empty());
+        assert!(m.get(0).is_err());
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(MmappedOntology::new(b"HOBI".to_vec()).is_err());
+
+        let mut buf = vec![];
+        let (ont, _) = read_ok(&mut include_str!("../ont/owl-xml/class.owx").as_bytes());
+        write(&mut buf, &ont).unwrap();
+        buf.pop();
+        assert!(MmappedOntology::new(buf).is_err());
+    }
+}
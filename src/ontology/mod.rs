@@ -27,6 +27,30 @@
 //! `OntologyIndex` that allows rapid retrieval of all
 //! `AnnotatedAxiom` instances of a given kind.
 
+//! Iteration over a `SetOntology` or `SetIndex` is in an arbitrary
+//! order, which may change between runs; use `iter_sorted` where this
+//! matters. An `AxiomMappedIndex` is backed by `BTreeSet`s and always
+//! iterates by `AxiomKind` and then in sorted order, so the writers,
+//! which use it, produce identical output for the same ontology.
+
+//! The [`iri_mapped`](iri_mapped.html) package provides an
+//! `OntologyIndex` that allows rapid retrieval of all
+//! `AnnotatedAxiom` instances which use an IRI or entity, and the
+//! [`label_mapped`](label_mapped.html) package one which looks up
+//! entities by their label.
+
+//! The [`mmapped`](mmapped.html) package provides a read-only
+//! ontology which decodes axioms on demand from an index file, for
+//! ontologies too large to hold in memory.
+
+//! All of the ontologies are `Send` and `Sync`, so they can be
+//! shared between threads for reading.
+
+//! The [`change`](change.html) package describes changes to an
+//! ontology, and allows listeners to be notified of them. The
+//! [`history`](history.html) package uses these to support undo and
+//! redo.
+
 //! Other indexes are less general purpose. The
 //! [`declaration_mapped`](declaration_mapped.html) indexes only
 //! declaration axioms, allowing rapid look up of the declarated type
@@ -34,9 +58,14 @@
 //! provide iteration.
 
 pub mod axiom_mapped;
+pub mod change;
 pub mod declaration_mapped;
+pub mod history;
 pub mod indexed;
+pub mod iri_mapped;
+pub mod label_mapped;
 pub mod logically_equal;
+pub mod mmapped;
 pub mod set;
 
 // There isn't a very formal interface here, but a set of traits that
@@ -49,3 +78,25 @@ pub mod set;
 // Ontology implementations should provide what ever other accessors
 // they choose, but should be biased toward providing those accessor
 // functions that they can implement with good efficiency.
+
+#[cfg(test)]
+mod test {
+    use super::axiom_mapped::AxiomMappedOntology;
+    use super::iri_mapped::IRIMappedOntology;
+    use super::label_mapped::LabelMappedOntology;
+    use super::mmapped::MmappedOntology;
+    use super::set::SetOntology;
+    use crate::model::Build;
+
+    fn is_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync() {
+        is_send_sync::<Build>();
+        is_send_sync::<SetOntology>();
+        is_send_sync::<AxiomMappedOntology>();
+        is_send_sync::<IRIMappedOntology>();
+        is_send_sync::<LabelMappedOntology>();
+        is_send_sync::<MmappedOntology<Vec<u8>>>();
+    }
+}
@@ -1,5 +1,5 @@
 //! Rapid, simple, in-memory `Ontology` and `OntologyIndex`
-use std::{collections::HashSet, iter::FromIterator, rc::Rc};
+use std::{collections::HashSet, iter::FromIterator, sync::Arc};
 
 use super::indexed::{rc_unwrap_or_clone, OntologyIndex};
 use crate::model::*;
@@ -9,7 +9,7 @@ use crate::model::*;
 /// of whether an equivalent axiom exists, and is iterable.
 ///
 /// It should be more rapid that the using `SetIndex` inside
-/// `OneIndexedOntology`, as it involves no `Rc` overhead.
+/// `OneIndexedOntology`, as it involves no `Arc` overhead.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct SetOntology {
     id: OntologyID,
@@ -39,6 +39,36 @@ impl SetOntology {
     pub fn iter(&self) -> SetIter<'_> {
         SetIter(self.axiom.iter())
     }
+
+    /// Gets an iterator that visits the axioms of the ontology,
+    /// without their annotations.
+    pub fn axioms(&self) -> impl Iterator<Item = &Axiom> {
+        self.iter().map(|ann| &ann.axiom)
+    }
+
+    /// Gets an iterator that visits the axioms of kind `axk`.
+    ///
+    /// This checks every axiom of the ontology; an
+    /// `AxiomMappedOntology` finds them directly.
+    pub fn axiom(&self, axk: AxiomKind) -> impl Iterator<Item = &Axiom> {
+        self.axioms().filter(move |ax| ax.kind() == axk)
+    }
+
+    /// Gets an iterator that visits the annotated axioms of the
+    /// ontology in sorted order, which is the same between runs.
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<&AnnotatedAxiom> {
+        let mut v: Vec<_> = self.axiom.iter().collect();
+        v.sort();
+        v.into_iter()
+    }
+
+    /// Gets a parallel iterator that visits the annotated axioms of
+    /// the ontology.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> rayon::collections::hash_set::Iter<'_, AnnotatedAxiom> {
+        use rayon::prelude::*;
+        self.axiom.par_iter()
+    }
 }
 
 impl Ontology for SetOntology {
@@ -123,6 +153,16 @@ impl MutableOntology for SetOntology {
     fn take(&mut self, ax: &AnnotatedAxiom) -> Option<AnnotatedAxiom> {
         self.axiom.take(ax)
     }
+
+    fn extend<I, A>(&mut self, axs: I) -> usize
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AnnotatedAxiom>,
+    {
+        let len = self.axiom.len();
+        self.axiom.extend(axs.into_iter().map(Into::into));
+        self.axiom.len() - len
+    }
 }
 
 impl FromIterator<AnnotatedAxiom> for SetOntology {
@@ -159,10 +199,10 @@ where I:Iterator<Item=AnnotatedAxiom>,
 /// combined with an `IndexedOntology` this should be nearly as
 /// fastest as `SetOntology`.
 #[derive(Debug, Default, Eq, PartialEq)]
-pub struct SetIndex(HashSet<Rc<AnnotatedAxiom>>);
+pub struct SetIndex(HashSet<Arc<AnnotatedAxiom>>);
 
 impl OntologyIndex for SetIndex {
-    fn index_insert(&mut self, ax: Rc<AnnotatedAxiom>) -> bool {
+    fn index_insert(&mut self, ax: Arc<AnnotatedAxiom>) -> bool {
         self.0.insert(ax)
     }
 
@@ -173,12 +213,26 @@ impl OntologyIndex for SetIndex {
     fn index_remove(&mut self, ax: &AnnotatedAxiom) -> bool {
         self.0.remove(ax)
     }
+
+    fn index_extend(&mut self, axs: Vec<Arc<AnnotatedAxiom>>) -> usize {
+        let len = self.0.len();
+        self.0.extend(axs);
+        self.0.len() - len
+    }
 }
 
 impl SetIndex {
     pub fn contains(&self, ax:&AnnotatedAxiom) -> bool {
         self.0.contains(ax)
     }
+
+    /// Gets an iterator that visits the annotated axioms in sorted
+    /// order.
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<&AnnotatedAxiom> {
+        let mut v: Vec<_> = self.0.iter().map(|rcax| &**rcax).collect();
+        v.sort();
+        v.into_iter()
+    }
 }
 
 impl IntoIterator for SetIndex {
@@ -188,7 +242,7 @@ impl IntoIterator for SetIndex {
         let v: Vec<AnnotatedAxiom> = self
             .0
             .into_iter()
-            .map(Rc::try_unwrap)
+            .map(Arc::try_unwrap)
             .map(Result::unwrap)
             .collect();
         v.into_iter()
@@ -212,7 +266,6 @@ mod test {
     #[test]
     fn test_ontology_cons() {
         let _ = SetOntology::new();
-        assert!(true);
     }
 
     #[test]
@@ -370,7 +423,6 @@ mod test {
     #[test]
     fn test_index_cons() {
         let _ = SetIndex::default();
-        assert!(true);
     }
 
     #[test]
@@ -489,4 +541,40 @@ mod test {
         assert_eq!(it.next(), None);
         assert_eq!(it.next(), None);
     }
+
+    #[test]
+    fn test_ontology_iter_sorted() {
+        let build = Build::new();
+        let mut o = SetOntology::new();
+        for i in (0..10).rev() {
+            o.declare(build.class(format!("http://www.example.com#{}", i)));
+        }
+
+        let v: Vec<_> = o.iter_sorted().collect();
+        assert_eq!(v.len(), 10);
+        assert!(v.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_ontology_axioms() {
+        let build = Build::new();
+        let mut o = SetOntology::new();
+        let decl = DeclareClass(build.class("http://www.example.com#a"));
+        o.insert(AnnotatedAxiom::new(
+            decl.clone(),
+            vec![Annotation {
+                ap: build.annotation_property("http://www.example.com#ap"),
+                av: build.iri("http://www.example.com#v").into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+        o.declare(build.object_property("http://www.example.com#r"));
+
+        assert_eq!(o.axioms().count(), 2);
+        assert_eq!(
+            o.axiom(AxiomKind::DeclareClass).collect::<Vec<_>>(),
+            vec![&Axiom::DeclareClass(decl)]
+        );
+    }
 }
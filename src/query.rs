@@ -0,0 +1,603 @@
+//! Query axioms with patterns
+//!
+//! A pattern is an ordinary `Axiom` in which some IRIs are variables,
+//! written as IRIs starting with `?`, such as those returned by
+//! `var`. A variable matches any IRI, and in the position of a class,
+//! object property or datatype it matches any class expression,
+//! object property expression or data range. The variable `?_`
+//! matches anything without being bound.
+//!
+//! Matching is structural, so the members of an `EquivalentClasses`
+//! or an intersection must be in the same order as the pattern.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! # use horned_owl::query::{query, var, Value};
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! let part_of = b.object_property("http://www.example.com/partOf");
+//! o.insert(SubClassOf {
+//!     sub: b.class("http://www.example.com/Wheel").into(),
+//!     sup: ClassExpression::ObjectSomeValuesFrom {
+//!         ope: part_of.clone().into(),
+//!         bce: Box::new(b.class("http://www.example.com/Car").into()),
+//!     },
+//! });
+//!
+//! // Any SubClassOf whose superclass is `partOf some ?X`
+//! let pattern = SubClassOf {
+//!     sub: Class(var(&b, "_")).into(),
+//!     sup: ClassExpression::ObjectSomeValuesFrom {
+//!         ope: part_of.into(),
+//!         bce: Box::new(Class(var(&b, "X")).into()),
+//!     },
+//! }
+//! .into();
+//!
+//! let (_, bindings) = query(&o, &pattern).next().unwrap();
+//! assert_eq!(
+//!     bindings["X"],
+//!     Value::ClassExpression(b.class("http://www.example.com/Car").into())
+//! );
+//! ```
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+
+use std::collections::BTreeMap;
+
+/// The value bound to a variable.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    IRI(IRI),
+    ClassExpression(ClassExpression),
+    ObjectPropertyExpression(ObjectPropertyExpression),
+    DataRange(DataRange),
+}
+
+/// The values bound to variables, by name without the `?`.
+pub type Bindings = BTreeMap<String, Value>;
+
+/// Return the variable IRI for `name`.
+pub fn var(b: &Build, name: &str) -> IRI {
+    b.iri(format!("?{}", name))
+}
+
+/// Return the name of `iri` if it is a variable.
+fn var_name(iri: &IRI) -> Option<&str> {
+    iri.strip_prefix('?')
+}
+
+/// Bind `iri`, if it is a variable, to `value`, returning false if it
+/// is already bound to something else. Return `None` if `iri` is not
+/// a variable.
+fn bind(iri: &IRI, value: impl FnOnce() -> Value, bindings: &mut Bindings) -> Option<bool> {
+    let name = var_name(iri)?;
+    if name == "_" {
+        return Some(true);
+    }
+
+    let value = value();
+    Some(match bindings.get(name) {
+        Some(bound) => *bound == value,
+        None => {
+            bindings.insert(name.to_string(), value);
+            true
+        }
+    })
+}
+
+/// Match a pattern against a value of the same type.
+trait Match {
+    /// Return true if `self`, as a pattern, matches `value`, adding
+    /// the variables that this binds to `bindings`.
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool;
+}
+
+impl Match for IRI {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        bind(self, || Value::IRI(value.clone()), bindings).unwrap_or(self == value)
+    }
+}
+
+impl<T: Match> Match for Vec<T> {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        self.len() == value.len()
+            && self
+                .iter()
+                .zip(value.iter())
+                .all(|(p, v)| p.match_into(v, bindings))
+    }
+}
+
+impl<T: Match> Match for Box<T> {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        (**self).match_into(value, bindings)
+    }
+}
+
+/// Implement `Match` by equality, for types with no variables.
+macro_rules! match_eq {
+    ($($name:ty),*) => {
+        $(
+            impl Match for $name {
+                fn match_into(&self, value: &Self, _: &mut Bindings) -> bool {
+                    self == value
+                }
+            }
+        )*
+    };
+}
+
+match_eq!(u32, Literal, Facet);
+
+/// Implement `Match` for structs by matching each field in turn.
+macro_rules! match_struct {
+    ($($name:ident {$($f:tt),*}),* $(,)?) => {
+        $(
+            impl Match for $name {
+                fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+                    true $(&& self.$f.match_into(&value.$f, bindings))*
+                }
+            }
+        )*
+    };
+}
+
+match_struct! {
+    Class { 0 },
+    ObjectProperty { 0 },
+    DataProperty { 0 },
+    AnnotationProperty { 0 },
+    NamedIndividual { 0 },
+    Datatype { 0 },
+    FacetRestriction { f, l },
+    Annotation { ap, av },
+
+    OntologyAnnotation { 0 },
+    Import { 0 },
+    DeclareClass { 0 },
+    DeclareObjectProperty { 0 },
+    DeclareAnnotationProperty { 0 },
+    DeclareDataProperty { 0 },
+    DeclareNamedIndividual { 0 },
+    DeclareDatatype { 0 },
+    SubClassOf { sup, sub },
+    EquivalentClasses { 0 },
+    DisjointClasses { 0 },
+    DisjointUnion { 0, 1 },
+    SubObjectPropertyOf { sup, sub },
+    EquivalentObjectProperties { 0 },
+    DisjointObjectProperties { 0 },
+    InverseObjectProperties { 0, 1 },
+    ObjectPropertyDomain { ope, ce },
+    ObjectPropertyRange { ope, ce },
+    FunctionalObjectProperty { 0 },
+    InverseFunctionalObjectProperty { 0 },
+    ReflexiveObjectProperty { 0 },
+    IrreflexiveObjectProperty { 0 },
+    SymmetricObjectProperty { 0 },
+    AsymmetricObjectProperty { 0 },
+    TransitiveObjectProperty { 0 },
+    SubDataPropertyOf { sup, sub },
+    EquivalentDataProperties { 0 },
+    DisjointDataProperties { 0 },
+    DataPropertyDomain { dp, ce },
+    DataPropertyRange { dp, dr },
+    FunctionalDataProperty { 0 },
+    DatatypeDefinition { kind, range },
+    HasKey { ce, vpe },
+    SameIndividual { 0 },
+    DifferentIndividuals { 0 },
+    ClassAssertion { ce, i },
+    ObjectPropertyAssertion { ope, from, to },
+    NegativeObjectPropertyAssertion { ope, from, to },
+    DataPropertyAssertion { dp, from, to },
+    NegativeDataPropertyAssertion { dp, from, to },
+    AnnotationAssertion { subject, ann },
+    SubAnnotationPropertyOf { sup, sub },
+    AnnotationPropertyDomain { ap, iri },
+    AnnotationPropertyRange { ap, iri },
+}
+
+/// Implement `Match` for enums whose variants each have one field.
+macro_rules! match_enum {
+    ($name:ident {$($variant:ident),* $(,)?}) => {
+        impl Match for $name {
+            fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+                match (self, value) {
+                    $(
+                        ($name::$variant(p), $name::$variant(v)) => p.match_into(v, bindings),
+                    )*
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+match_enum!(AnnotationValue {
+    Literal,
+    IRI,
+    AnonymousIndividual
+});
+match_enum!(SubObjectPropertyExpression {
+    ObjectPropertyChain,
+    ObjectPropertyExpression
+});
+match_enum!(PropertyExpression {
+    ObjectPropertyExpression,
+    DataProperty,
+    AnnotationProperty
+});
+
+match_enum!(Axiom {
+    OntologyAnnotation,
+    Import,
+    DeclareClass,
+    DeclareObjectProperty,
+    DeclareAnnotationProperty,
+    DeclareDataProperty,
+    DeclareNamedIndividual,
+    DeclareDatatype,
+    SubClassOf,
+    EquivalentClasses,
+    DisjointClasses,
+    DisjointUnion,
+    SubObjectPropertyOf,
+    EquivalentObjectProperties,
+    DisjointObjectProperties,
+    InverseObjectProperties,
+    ObjectPropertyDomain,
+    ObjectPropertyRange,
+    FunctionalObjectProperty,
+    InverseFunctionalObjectProperty,
+    ReflexiveObjectProperty,
+    IrreflexiveObjectProperty,
+    SymmetricObjectProperty,
+    AsymmetricObjectProperty,
+    TransitiveObjectProperty,
+    SubDataPropertyOf,
+    EquivalentDataProperties,
+    DisjointDataProperties,
+    DataPropertyDomain,
+    DataPropertyRange,
+    FunctionalDataProperty,
+    DatatypeDefinition,
+    HasKey,
+    SameIndividual,
+    DifferentIndividuals,
+    ClassAssertion,
+    ObjectPropertyAssertion,
+    NegativeObjectPropertyAssertion,
+    DataPropertyAssertion,
+    NegativeDataPropertyAssertion,
+    AnnotationAssertion,
+    SubAnnotationPropertyOf,
+    AnnotationPropertyDomain,
+    AnnotationPropertyRange,
+});
+
+impl Match for ObjectPropertyExpression {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        use ObjectPropertyExpression::*;
+        if let ObjectProperty(op) = self {
+            if let Some(bound) = bind(
+                &op.0,
+                || Value::ObjectPropertyExpression(value.clone()),
+                bindings,
+            ) {
+                return bound;
+            }
+        }
+
+        match (self, value) {
+            (ObjectProperty(p), ObjectProperty(v))
+            | (InverseObjectProperty(p), InverseObjectProperty(v)) => p.match_into(v, bindings),
+            _ => false,
+        }
+    }
+}
+
+impl Match for DataRange {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        use DataRange::*;
+        if let Datatype(dt) = self {
+            if let Some(bound) = bind(&dt.0, || Value::DataRange(value.clone()), bindings) {
+                return bound;
+            }
+        }
+
+        match (self, value) {
+            (Datatype(p), Datatype(v)) => p.match_into(v, bindings),
+            (DataIntersectionOf(p), DataIntersectionOf(v)) | (DataUnionOf(p), DataUnionOf(v)) => {
+                p.match_into(v, bindings)
+            }
+            (DataComplementOf(p), DataComplementOf(v)) => p.match_into(v, bindings),
+            (DataOneOf(p), DataOneOf(v)) => p.match_into(v, bindings),
+            (DatatypeRestriction(pdt, pv), DatatypeRestriction(vdt, vv)) => {
+                pdt.match_into(vdt, bindings) && pv.match_into(vv, bindings)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Match for ClassExpression {
+    fn match_into(&self, value: &Self, bindings: &mut Bindings) -> bool {
+        use ClassExpression::*;
+        if let Class(c) = self {
+            if let Some(bound) = bind(&c.0, || Value::ClassExpression(value.clone()), bindings) {
+                return bound;
+            }
+        }
+
+        match (self, value) {
+            (Class(p), Class(v)) => p.match_into(v, bindings),
+            (ObjectIntersectionOf(p), ObjectIntersectionOf(v))
+            | (ObjectUnionOf(p), ObjectUnionOf(v)) => p.match_into(v, bindings),
+            (ObjectComplementOf(p), ObjectComplementOf(v)) => p.match_into(v, bindings),
+            (ObjectOneOf(p), ObjectOneOf(v)) => p.match_into(v, bindings),
+            (
+                ObjectSomeValuesFrom { ope: po, bce: pc },
+                ObjectSomeValuesFrom { ope: vo, bce: vc },
+            )
+            | (
+                ObjectAllValuesFrom { ope: po, bce: pc },
+                ObjectAllValuesFrom { ope: vo, bce: vc },
+            ) => po.match_into(vo, bindings) && pc.match_into(vc, bindings),
+            (ObjectHasValue { ope: po, i: pi }, ObjectHasValue { ope: vo, i: vi }) => {
+                po.match_into(vo, bindings) && pi.match_into(vi, bindings)
+            }
+            (ObjectHasSelf(p), ObjectHasSelf(v)) => p.match_into(v, bindings),
+            (
+                ObjectMinCardinality {
+                    n: pn,
+                    ope: po,
+                    bce: pc,
+                },
+                ObjectMinCardinality {
+                    n: vn,
+                    ope: vo,
+                    bce: vc,
+                },
+            )
+            | (
+                ObjectMaxCardinality {
+                    n: pn,
+                    ope: po,
+                    bce: pc,
+                },
+                ObjectMaxCardinality {
+                    n: vn,
+                    ope: vo,
+                    bce: vc,
+                },
+            )
+            | (
+                ObjectExactCardinality {
+                    n: pn,
+                    ope: po,
+                    bce: pc,
+                },
+                ObjectExactCardinality {
+                    n: vn,
+                    ope: vo,
+                    bce: vc,
+                },
+            ) => pn == vn && po.match_into(vo, bindings) && pc.match_into(vc, bindings),
+            (DataSomeValuesFrom { dp: pp, dr: pr }, DataSomeValuesFrom { dp: vp, dr: vr })
+            | (DataAllValuesFrom { dp: pp, dr: pr }, DataAllValuesFrom { dp: vp, dr: vr }) => {
+                pp.match_into(vp, bindings) && pr.match_into(vr, bindings)
+            }
+            (DataHasValue { dp: pp, l: pl }, DataHasValue { dp: vp, l: vl }) => {
+                pp.match_into(vp, bindings) && pl == vl
+            }
+            (
+                DataMinCardinality {
+                    n: pn,
+                    dp: pp,
+                    dr: pr,
+                },
+                DataMinCardinality {
+                    n: vn,
+                    dp: vp,
+                    dr: vr,
+                },
+            )
+            | (
+                DataMaxCardinality {
+                    n: pn,
+                    dp: pp,
+                    dr: pr,
+                },
+                DataMaxCardinality {
+                    n: vn,
+                    dp: vp,
+                    dr: vr,
+                },
+            )
+            | (
+                DataExactCardinality {
+                    n: pn,
+                    dp: pp,
+                    dr: pr,
+                },
+                DataExactCardinality {
+                    n: vn,
+                    dp: vp,
+                    dr: vr,
+                },
+            ) => pn == vn && pp.match_into(vp, bindings) && pr.match_into(vr, bindings),
+            _ => false,
+        }
+    }
+}
+
+/// Return the bindings if `pattern` matches `ax`.
+pub fn matches(pattern: &Axiom, ax: &Axiom) -> Option<Bindings> {
+    let mut bindings = Bindings::new();
+    if pattern.match_into(ax, &mut bindings) {
+        Some(bindings)
+    } else {
+        None
+    }
+}
+
+/// Iterate over the axioms of `ont` which match `pattern`, with the
+/// bindings of each.
+pub fn query<'a>(
+    ont: &'a AxiomMappedOntology,
+    pattern: &'a Axiom,
+) -> impl Iterator<Item = (&'a AnnotatedAxiom, Bindings)> {
+    ont.i()
+        .annotated_axiom(pattern.kind())
+        .filter_map(move |ax| Some((ax, matches(pattern, &ax.axiom)?)))
+}
+
+/// Return every set of bindings for which all of `patterns` match
+/// some axiom of `ont`, where variables shared between patterns must
+/// be bound to the same value.
+pub fn query_all(ont: &AxiomMappedOntology, patterns: &[Axiom]) -> Vec<Bindings> {
+    let mut solutions = vec![Bindings::new()];
+    for pattern in patterns {
+        let mut next = vec![];
+        for bindings in &solutions {
+            for ax in ont.i().axiom(pattern.kind()) {
+                let mut extended = bindings.clone();
+                if pattern.match_into(ax, &mut extended) {
+                    next.push(extended);
+                }
+            }
+        }
+        solutions = next;
+    }
+    solutions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ex(b: &Build, s: &str) -> ClassExpression {
+        b.class(format!("http://www.example.com/{}", s)).into()
+    }
+
+    fn sub(b: &Build, sub: &str, sup: &str) -> Axiom {
+        SubClassOf {
+            sub: ex(b, sub),
+            sup: ex(b, sup),
+        }
+        .into()
+    }
+
+    fn ontology(b: &Build) -> AxiomMappedOntology {
+        let mut o = AxiomMappedOntology::default();
+        o.insert(sub(b, "Wheel", "Part"));
+        o.insert(sub(b, "Part", "Thing"));
+        o.insert(SubClassOf {
+            sub: ex(b, "Wheel"),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/partOf").into(),
+                bce: Box::new(ClassExpression::ObjectUnionOf(vec![
+                    ex(b, "Car"),
+                    ex(b, "Bike"),
+                ])),
+            },
+        });
+        o
+    }
+
+    #[test]
+    fn variables() {
+        let b = Build::new();
+        let o = ontology(&b);
+
+        let pattern = SubClassOf {
+            sub: Class(var(&b, "X")).into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectProperty(var(&b, "R")).into(),
+                bce: Box::new(Class(var(&b, "Y")).into()),
+            },
+        }
+        .into();
+
+        let results: Vec<_> = query(&o, &pattern).collect();
+        assert_eq!(results.len(), 1);
+        let bindings = &results[0].1;
+        assert_eq!(bindings["X"], Value::ClassExpression(ex(&b, "Wheel")));
+        assert_eq!(
+            bindings["R"],
+            Value::ObjectPropertyExpression(
+                b.object_property("http://www.example.com/partOf").into()
+            )
+        );
+        assert!(matches!(
+            &bindings["Y"],
+            Value::ClassExpression(ClassExpression::ObjectUnionOf(_))
+        ));
+    }
+
+    #[test]
+    fn repeated() {
+        let b = Build::new();
+        let mut o = ontology(&b);
+        o.insert(sub(&b, "Loop", "Loop"));
+
+        let pattern = SubClassOf {
+            sub: Class(var(&b, "X")).into(),
+            sup: Class(var(&b, "X")).into(),
+        }
+        .into();
+        assert_eq!(query(&o, &pattern).count(), 1);
+
+        let any = SubClassOf {
+            sub: Class(var(&b, "_")).into(),
+            sup: Class(var(&b, "_")).into(),
+        }
+        .into();
+        assert_eq!(query(&o, &any).count(), 4);
+    }
+
+    #[test]
+    fn constants() {
+        let b = Build::new();
+        let o = ontology(&b);
+
+        let pattern = SubClassOf {
+            sub: Class(var(&b, "X")).into(),
+            sup: ex(&b, "Thing"),
+        }
+        .into();
+        let results: Vec<_> = query(&o, &pattern).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1["X"], Value::ClassExpression(ex(&b, "Part")));
+
+        assert!(matches(&sub(&b, "A", "B"), &sub(&b, "A", "C")).is_none());
+    }
+
+    #[test]
+    fn join() {
+        let b = Build::new();
+        let o = ontology(&b);
+
+        let chain = [
+            SubClassOf {
+                sub: Class(var(&b, "X")).into(),
+                sup: Class(var(&b, "Y")).into(),
+            }
+            .into(),
+            SubClassOf {
+                sub: Class(var(&b, "Y")).into(),
+                sup: Class(var(&b, "Z")).into(),
+            }
+            .into(),
+        ];
+
+        let solutions = query_all(&o, &chain);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0]["X"], Value::ClassExpression(ex(&b, "Wheel")));
+        assert_eq!(solutions[0]["Z"], Value::ClassExpression(ex(&b, "Thing")));
+    }
+}
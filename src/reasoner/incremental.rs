@@ -0,0 +1,603 @@
+//! Incremental classification
+//!
+//! `IncrementalReasoner` keeps the classification of a
+//! `StructuralReasoner`, the named super classes of each named class,
+//! and updates it as axioms are added and removed, rather than
+//! classifying the whole ontology again.
+//!
+//! The super classes of a named class can only be derived from the
+//! told subsumptions above it. These form a graph, with an edge from
+//! each entity on the sub side of a logical axiom to each entity on
+//! its super side; an axiom which is not a subsumption, such as
+//! `EquivalentClasses`, has all of its entities on both sides. The
+//! graph is kept up to date as axioms are added and removed, and
+//! after a change only the classes below the sub side of a changed
+//! axiom are classified again, against the classes above them.
+//!
+//! An entity which is contravariant, inside an `ObjectComplementOf`
+//! or as the property of an `ObjectAllValuesFrom`, depends on the
+//! entities below it as well, so the classes connected to it in
+//! either direction are included. Classes which subsume, or are
+//! subsumed by, every class, such as those equivalent to `owl:Thing`
+//! or `owl:Nothing`, are found by comparing them with a fresh class,
+//! and kept apart.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! # use horned_owl::ontology::change::OntologyChange;
+//! use horned_owl::reasoner::incremental::IncrementalReasoner;
+//!
+//! let b = Build::new();
+//! let a = b.class("http://www.example.com/a");
+//! let c = b.class("http://www.example.com/c");
+//! let mut r = IncrementalReasoner::new(AxiomMappedOntology::default());
+//!
+//! let changed = r.apply(vec![OntologyChange::AddAxiom(
+//!     SubClassOf {
+//!         sub: a.clone().into(),
+//!         sup: c.clone().into(),
+//!     }
+//!     .into(),
+//! )]);
+//!
+//! assert_eq!(changed.len(), 1);
+//! assert!(r.super_classes(&a).contains(&c));
+//! ```
+use super::structural::StructuralReasoner;
+use super::Reasoner;
+
+use crate::metrics::is_logical;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::change::OntologyChange;
+use crate::signature::Signature;
+use crate::vocab::{WithIRI, OWL};
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+
+type Edges = BTreeMap<NamedEntity, BTreeMap<NamedEntity, usize>>;
+
+/// The told subsumptions between the entities of an ontology, with
+/// the number of axioms which give each one.
+#[derive(Debug, Default)]
+struct Told {
+    /// The edges from each sub side entity
+    sups: Edges,
+    /// The edges from each super side entity
+    subs: Edges,
+    /// The entities which are contravariant in some axiom
+    contra: BTreeMap<NamedEntity, usize>,
+    /// The entities which are used in some axiom
+    uses: BTreeMap<NamedEntity, usize>,
+}
+
+fn count<K: Ord>(map: &mut BTreeMap<K, usize>, k: K, add: bool) {
+    if add {
+        *map.entry(k).or_default() += 1;
+    } else if let Entry::Occupied(mut e) = map.entry(k) {
+        *e.get_mut() -= 1;
+        if *e.get() == 0 {
+            e.remove();
+        }
+    }
+}
+
+fn count_edge(edges: &mut Edges, from: &NamedEntity, to: &NamedEntity, add: bool) {
+    if let Some(tos) = edges.get_mut(from) {
+        count(tos, to.clone(), add);
+        if tos.is_empty() {
+            edges.remove(from);
+        }
+    } else if add {
+        count(edges.entry(from.clone()).or_default(), to.clone(), add);
+    }
+}
+
+/// The entities on the sub side and on the super side of `ax`.
+fn sides(ax: &Axiom) -> (BTreeSet<NamedEntity>, BTreeSet<NamedEntity>) {
+    match ax {
+        Axiom::SubClassOf(ax) => (ax.sub.signature(), ax.sup.signature()),
+        Axiom::SubObjectPropertyOf(ax) => (ax.sub.signature(), ax.sup.signature()),
+        Axiom::SubDataPropertyOf(ax) => (ax.sub.signature(), ax.sup.signature()),
+        Axiom::ObjectPropertyDomain(ax) => (ax.ope.signature(), ax.ce.signature()),
+        Axiom::ObjectPropertyRange(ax) => (ax.ope.signature(), ax.ce.signature()),
+        Axiom::DataPropertyDomain(ax) => (ax.dp.signature(), ax.ce.signature()),
+        Axiom::ClassAssertion(ax) => (ax.i.signature(), ax.ce.signature()),
+        _ => {
+            let sig = ax.signature();
+            (sig.clone(), sig)
+        }
+    }
+}
+
+/// Add the entities which are contravariant in `ce` to `sig`.
+fn contravariant(ce: &ClassExpression, sig: &mut BTreeSet<NamedEntity>) {
+    use ClassExpression::*;
+
+    match ce {
+        ObjectComplementOf(ce) => ce.signature_into(sig),
+        ObjectAllValuesFrom { ope, bce } => {
+            ope.signature_into(sig);
+            contravariant(bce, sig);
+        }
+        ObjectIntersectionOf(v) | ObjectUnionOf(v) => {
+            for ce in v {
+                contravariant(ce, sig);
+            }
+        }
+        ObjectSomeValuesFrom { bce, .. }
+        | ObjectMinCardinality { bce, .. }
+        | ObjectMaxCardinality { bce, .. }
+        | ObjectExactCardinality { bce, .. } => contravariant(bce, sig),
+        _ => {}
+    }
+}
+
+/// The class expressions used by `ax`.
+fn class_expressions(ax: &Axiom) -> Vec<&ClassExpression> {
+    match ax {
+        Axiom::SubClassOf(ax) => vec![&ax.sub, &ax.sup],
+        Axiom::EquivalentClasses(ax) => ax.0.iter().collect(),
+        Axiom::DisjointClasses(ax) => ax.0.iter().collect(),
+        Axiom::DisjointUnion(ax) => ax.1.iter().collect(),
+        Axiom::ObjectPropertyDomain(ax) => vec![&ax.ce],
+        Axiom::ObjectPropertyRange(ax) => vec![&ax.ce],
+        Axiom::DataPropertyDomain(ax) => vec![&ax.ce],
+        Axiom::ClassAssertion(ax) => vec![&ax.ce],
+        Axiom::HasKey(ax) => vec![&ax.ce],
+        _ => vec![],
+    }
+}
+
+/// Return the entities which can be reached from `from`, including
+/// themselves, through any of `edges`.
+fn reach<I>(from: I, edges: &[&Edges]) -> BTreeSet<NamedEntity>
+where
+    I: IntoIterator<Item = NamedEntity>,
+{
+    let mut seen = BTreeSet::new();
+    let mut todo: Vec<NamedEntity> = from.into_iter().collect();
+    while let Some(e) = todo.pop() {
+        if seen.insert(e.clone()) {
+            for edges in edges {
+                todo.extend(edges.get(&e).into_iter().flat_map(|m| m.keys().cloned()));
+            }
+        }
+    }
+    seen
+}
+
+impl Told {
+    fn new(ont: &AxiomMappedOntology) -> Told {
+        let mut told = Told::default();
+        for ax in ont.i().iter() {
+            told.update(ax, true);
+        }
+        told
+    }
+
+    fn update(&mut self, ax: &AnnotatedAxiom, add: bool) {
+        for e in ax.signature() {
+            count(&mut self.uses, e, add);
+        }
+        if !is_logical(ax.kind()) {
+            return;
+        }
+
+        let (sub, sup) = sides(&ax.axiom);
+        for a in &sub {
+            for b in sup.iter().filter(|b| *b != a) {
+                count_edge(&mut self.sups, a, b, add);
+                count_edge(&mut self.subs, b, a, add);
+            }
+        }
+
+        let mut contra = BTreeSet::new();
+        for ce in class_expressions(&ax.axiom) {
+            contravariant(ce, &mut contra);
+        }
+        for e in contra {
+            count(&mut self.contra, e, add);
+        }
+    }
+
+    /// Return the contravariant entities in `es`.
+    fn contra<'a>(&'a self, es: BTreeSet<NamedEntity>) -> impl Iterator<Item = NamedEntity> + 'a {
+        es.into_iter().filter(move |e| self.contra.contains_key(e))
+    }
+
+    /// Return the entities whose super classes may change when those
+    /// of `seeds` do: those below them, and those below any
+    /// contravariant entity above them.
+    fn affected(&self, seeds: &BTreeSet<NamedEntity>) -> BTreeSet<NamedEntity> {
+        let above = reach(seeds.iter().cloned(), &[&self.sups]);
+        reach(
+            seeds.iter().cloned().chain(self.contra(above)),
+            &[&self.subs],
+        )
+    }
+
+    /// Return the entities which may subsume `e`: those above it, and
+    /// those connected to any contravariant entity above it.
+    fn candidates(&self, e: &NamedEntity) -> BTreeSet<NamedEntity> {
+        let mut above = reach(std::iter::once(e.clone()), &[&self.sups]);
+        let connected = reach(self.contra(above.clone()), &[&self.sups, &self.subs]);
+        above.extend(connected);
+        above
+    }
+}
+
+/// Return the class `e`, unless it is not a class, or is `owl:Thing`
+/// or `owl:Nothing`.
+fn named_class(e: &NamedEntity) -> Option<&Class> {
+    match e {
+        NamedEntity::Class(c)
+            if c.0.as_str() != OWL::Thing.iri_str() && c.0.as_str() != OWL::Nothing.iri_str() =>
+        {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
+/// A class which is in no ontology. A class which subsumes it
+/// subsumes every class, and a class which it subsumes is subsumed
+/// by every class.
+fn fresh() -> ClassExpression {
+    Build::new()
+        .class("urn:x-horned-owl:incremental#fresh")
+        .into()
+}
+
+/// A `StructuralReasoner` which keeps its classification up to date
+/// as the ontology changes.
+#[derive(Debug, Default)]
+pub struct IncrementalReasoner {
+    reasoner: StructuralReasoner,
+    told: Told,
+    classes: BTreeSet<Class>,
+    /// The super classes of each class which are above it.
+    supers: BTreeMap<Class, BTreeSet<Class>>,
+    /// The classes which subsume every class.
+    top: BTreeSet<Class>,
+    /// The classes which are subsumed by every class.
+    bottom: BTreeSet<Class>,
+}
+
+impl IncrementalReasoner {
+    /// Classify `ont`.
+    pub fn new(ont: AxiomMappedOntology) -> IncrementalReasoner {
+        let mut r = IncrementalReasoner {
+            told: Told::new(&ont),
+            reasoner: StructuralReasoner::new(ont),
+            ..Default::default()
+        };
+        r.classes = r
+            .told
+            .uses
+            .keys()
+            .filter_map(named_class)
+            .cloned()
+            .collect();
+        let all = r.classes.clone();
+        r.classify(&all);
+        r
+    }
+
+    pub fn ontology(&self) -> &AxiomMappedOntology {
+        self.reasoner.ontology()
+    }
+
+    pub fn into_ontology(self) -> AxiomMappedOntology {
+        self.reasoner.into_ontology()
+    }
+
+    /// Return the named super classes of `c`, other than itself.
+    pub fn super_classes(&self, c: &Class) -> BTreeSet<&Class> {
+        if !self.classes.contains(c) {
+            return BTreeSet::new();
+        }
+        self.classes
+            .iter()
+            .filter(|d| *d != c && self.is_named_subclass(c, d))
+            .collect()
+    }
+
+    fn is_named_subclass(&self, a: &Class, b: &Class) -> bool {
+        a == b
+            || self.bottom.contains(a)
+            || self.top.contains(b)
+            || self.supers.get(a).is_some_and(|s| s.contains(b))
+    }
+
+    fn forget(&mut self, c: &Class) {
+        self.supers.remove(c);
+        self.top.remove(c);
+        self.bottom.remove(c);
+    }
+
+    /// Classify `classes` again, against the classes above them.
+    fn classify(&mut self, classes: &BTreeSet<Class>) {
+        let fresh = fresh();
+        for c in classes {
+            self.forget(c);
+            let sub: ClassExpression = c.clone().into();
+            if self.reasoner.is_subclass(&fresh, &sub) {
+                self.top.insert(c.clone());
+            }
+            if self.reasoner.is_subclass(&sub, &fresh) {
+                self.bottom.insert(c.clone());
+            }
+
+            let sups: BTreeSet<Class> = self
+                .told
+                .candidates(&c.clone().into())
+                .iter()
+                .filter_map(named_class)
+                .filter(|d| {
+                    *d != c
+                        && self.classes.contains(*d)
+                        && self.reasoner.is_subclass(&sub, &(*d).clone().into())
+                })
+                .cloned()
+                .collect();
+            if !sups.is_empty() {
+                self.supers.insert(c.clone(), sups);
+            }
+        }
+    }
+
+    /// Apply `changes` to the ontology, and classify again the
+    /// classes which they may affect.
+    ///
+    /// Return the classes which were classified again.
+    pub fn apply<I>(&mut self, changes: I) -> BTreeSet<Class>
+    where
+        I: IntoIterator<Item = OntologyChange>,
+    {
+        let mut ont = std::mem::take(&mut self.reasoner).into_ontology();
+        let applied: Vec<OntologyChange> = changes
+            .into_iter()
+            .filter(|change| change.apply(&mut ont))
+            .collect();
+        self.reasoner = StructuralReasoner::new(ont);
+
+        let mut seeds = BTreeSet::new();
+        let mut sig = BTreeSet::new();
+        for change in &applied {
+            let ax = change.axiom();
+            if is_logical(ax.kind()) {
+                seeds.extend(sides(&ax.axiom).0);
+            }
+            ax.signature_into(&mut sig);
+        }
+
+        // The classes below the changes, before and after them
+        let mut affected = self.told.affected(&seeds);
+        for change in &applied {
+            let add = matches!(change, OntologyChange::AddAxiom(_));
+            self.told.update(change.axiom(), add);
+        }
+        affected.extend(self.told.affected(&seeds));
+
+        for c in sig.iter().filter_map(named_class) {
+            if self.told.uses.contains_key(&c.clone().into()) {
+                self.classes.insert(c.clone());
+            } else {
+                self.classes.remove(c);
+                self.forget(c);
+            }
+        }
+
+        let affected: BTreeSet<Class> = affected
+            .iter()
+            .filter_map(named_class)
+            .filter(|c| self.classes.contains(*c))
+            .cloned()
+            .collect();
+        self.classify(&affected);
+        affected
+    }
+
+    /// Add `ax` to the ontology; see `apply`.
+    pub fn insert<A: Into<AnnotatedAxiom>>(&mut self, ax: A) -> BTreeSet<Class> {
+        self.apply(vec![OntologyChange::AddAxiom(ax.into())])
+    }
+
+    /// Remove `ax` from the ontology; see `apply`.
+    pub fn remove(&mut self, ax: &AnnotatedAxiom) -> BTreeSet<Class> {
+        self.apply(vec![OntologyChange::RemoveAxiom(ax.clone())])
+    }
+}
+
+impl Reasoner for IncrementalReasoner {
+    fn is_subclass(&self, sub: &ClassExpression, sup: &ClassExpression) -> bool {
+        match (sub, sup) {
+            (ClassExpression::Class(a), ClassExpression::Class(b))
+                if self.classes.contains(a) && self.classes.contains(b) =>
+            {
+                self.is_named_subclass(a, b)
+            }
+            _ => self.reasoner.is_subclass(sub, sup),
+        }
+    }
+
+    fn is_instance(&self, i: &NamedIndividual, ce: &ClassExpression) -> bool {
+        self.reasoner.is_instance(i, ce)
+    }
+
+    fn is_asserted(&self, ax: &Axiom) -> bool {
+        self.reasoner.is_asserted(ax)
+    }
+
+    fn classes(&self) -> BTreeSet<Class> {
+        self.classes.clone()
+    }
+
+    fn individuals(&self) -> BTreeSet<NamedIndividual> {
+        self.reasoner.individuals()
+    }
+
+    fn is_sub_object_property(
+        &self,
+        sub: &ObjectPropertyExpression,
+        sup: &ObjectPropertyExpression,
+    ) -> bool {
+        self.reasoner.is_sub_object_property(sub, sup)
+    }
+
+    fn object_properties(&self) -> BTreeSet<ObjectProperty> {
+        self.reasoner.object_properties()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hierarchy::ClassHierarchy;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(format!("http://ex.com/{}", c)).into()
+    }
+
+    fn sc(b: &Build, sub: &str, sup: &str) -> AnnotatedAxiom {
+        SubClassOf {
+            sub: class(b, sub),
+            sup: class(b, sup),
+        }
+        .into()
+    }
+
+    fn assert_classified(r: &IncrementalReasoner) {
+        let o = r.ontology().filter_axioms(|_| true);
+        assert_eq!(
+            ClassHierarchy::inferred(r),
+            ClassHierarchy::inferred(&StructuralReasoner::new(o))
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(sc(&b, "b", "a"));
+        o.insert(sc(&b, "c", "b"));
+        o.insert(sc(&b, "y", "x"));
+        let mut r = IncrementalReasoner::new(o);
+        assert_classified(&r);
+
+        // Only the new class is below the change
+        let changed = r.insert(sc(&b, "d", "c"));
+        assert_eq!(
+            changed,
+            vec![b.class("http://ex.com/d")].into_iter().collect()
+        );
+        assert!(r
+            .super_classes(&b.class("http://ex.com/d"))
+            .contains(&b.class("http://ex.com/a")));
+        assert_classified(&r);
+
+        let changed = r.remove(&sc(&b, "b", "a"));
+        assert!(changed.contains(&b.class("http://ex.com/b")));
+        assert!(!r.classes().contains(&b.class("http://ex.com/a")));
+        assert!(!changed.contains(&b.class("http://ex.com/x")));
+        assert!(r.super_classes(&b.class("http://ex.com/d")).len() == 2);
+        assert_classified(&r);
+
+        // Joining two components
+        r.insert(EquivalentClasses(vec![
+            class(&b, "x"),
+            ClassExpression::ObjectIntersectionOf(vec![class(&b, "b"), class(&b, "a")]),
+        ]));
+        r.insert(sc(&b, "b", "a"));
+        assert_classified(&r);
+        assert!(r.is_subclass(&class(&b, "c"), &class(&b, "x")));
+
+        // owl:Thing and owl:Nothing relate to every class
+        r.insert(EquivalentClasses(vec![
+            class(&b, "top"),
+            ClassExpression::ObjectUnionOf(vec![
+                b.class("http://www.w3.org/2002/07/owl#Thing").into(),
+                class(&b, "z"),
+            ]),
+        ]));
+        r.insert(SubClassOf {
+            sub: class(&b, "y"),
+            sup: b.class("http://www.w3.org/2002/07/owl#Nothing").into(),
+        });
+        assert_classified(&r);
+        assert!(r.is_subclass(&class(&b, "a"), &class(&b, "top")));
+        assert!(r.is_subclass(&class(&b, "y"), &class(&b, "d")));
+
+        // A change with no effect classifies nothing
+        assert!(r.insert(sc(&b, "b", "a")).is_empty());
+    }
+
+    #[test]
+    fn subtree() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        for (sub, sup) in [
+            ("a", "root"),
+            ("b", "root"),
+            ("a1", "a"),
+            ("a2", "a"),
+            ("a3", "a1"),
+            ("b1", "b"),
+            ("b2", "b"),
+        ] {
+            o.insert(sc(&b, sub, sup));
+        }
+        let mut r = IncrementalReasoner::new(o);
+        let classes = |cs: &[&str]| -> BTreeSet<Class> {
+            cs.iter()
+                .map(|c| b.class(format!("http://ex.com/{}", c)))
+                .collect()
+        };
+
+        // Though every class is connected through the root, only
+        // those below the changed axiom are classified again
+        let changed = r.insert(sc(&b, "a", "x"));
+        assert_eq!(changed, classes(&["a", "a1", "a2", "a3"]));
+        assert!(r.is_subclass(&class(&b, "a3"), &class(&b, "x")));
+        assert_classified(&r);
+
+        let changed = r.remove(&sc(&b, "a1", "a"));
+        assert_eq!(changed, classes(&["a1", "a3"]));
+        assert!(!r.is_subclass(&class(&b, "a3"), &class(&b, "x")));
+        assert_classified(&r);
+
+        let changed = r.insert(EquivalentClasses(vec![class(&b, "b1"), class(&b, "b2")]));
+        assert_eq!(changed, classes(&["b1", "b2"]));
+        assert_classified(&r);
+    }
+
+    #[test]
+    fn contravariant() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "c"),
+            sup: ClassExpression::ObjectComplementOf(Box::new(class(&b, "a"))),
+        });
+        o.insert(EquivalentClasses(vec![
+            class(&b, "d"),
+            ClassExpression::ObjectComplementOf(Box::new(class(&b, "e"))),
+        ]));
+        let mut r = IncrementalReasoner::new(o);
+        assert!(!r.is_subclass(&class(&b, "c"), &class(&b, "d")));
+
+        // c is not below e, but depends on what is below a
+        let changed = r.insert(sc(&b, "e", "a"));
+        assert!(changed.contains(&b.class("http://ex.com/c")));
+        assert!(r.is_subclass(&class(&b, "c"), &class(&b, "d")));
+        assert_classified(&r);
+
+        r.remove(&sc(&b, "e", "a"));
+        assert!(!r.is_subclass(&class(&b, "c"), &class(&b, "d")));
+        assert_classified(&r);
+    }
+}
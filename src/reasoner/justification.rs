@@ -0,0 +1,263 @@
+//! Justifications of entailments
+//!
+//! A justification is a minimal set of the asserted axioms of an
+//! ontology from which an entailment follows. They are computed
+//! here by treating the reasoner as a black box: axioms are removed
+//! one at a time, and the entailment rechecked, until no more can be
+//! removed. All justifications are found by repeating this for the
+//! ontology without each of the axioms of one that is found, using
+//! Reiter's hitting set tree.
+use crate::metrics::is_logical;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+
+use super::structural::StructuralReasoner;
+use super::Reasoner;
+
+use std::collections::BTreeSet;
+
+struct Justifier<'a, F, E> {
+    // Non-logical axioms, which are always included
+    fixed: Vec<&'a AnnotatedAxiom>,
+    reasoner: F,
+    entailed: E,
+}
+
+impl<'a, R, F, E> Justifier<'a, F, E>
+where
+    F: Fn(AxiomMappedOntology) -> R,
+    E: Fn(&R) -> bool,
+{
+    fn holds(&self, axioms: &BTreeSet<&AnnotatedAxiom>) -> bool {
+        let mut o = AxiomMappedOntology::default();
+        for ax in self.fixed.iter().chain(axioms.iter()) {
+            o.insert((*ax).clone());
+        }
+        (self.entailed)(&(self.reasoner)(o))
+    }
+
+    fn one(&self, axioms: &BTreeSet<&'a AnnotatedAxiom>) -> Option<BTreeSet<&'a AnnotatedAxiom>> {
+        if !self.holds(axioms) {
+            return None;
+        }
+
+        let mut j = axioms.clone();
+        for ax in axioms {
+            j.remove(ax);
+            if !self.holds(&j) {
+                j.insert(ax);
+            }
+        }
+        Some(j)
+    }
+
+    fn all(
+        &self,
+        axioms: &BTreeSet<&'a AnnotatedAxiom>,
+        path: &mut Vec<&'a AnnotatedAxiom>,
+        closed: &mut Vec<BTreeSet<&'a AnnotatedAxiom>>,
+        found: &mut Vec<BTreeSet<&'a AnnotatedAxiom>>,
+        limit: Option<usize>,
+    ) {
+        if limit.is_some_and(|l| found.len() >= l) {
+            return;
+        }
+
+        // A path which contains a closed path cannot find anything new
+        let here: BTreeSet<_> = path.iter().copied().collect();
+        if closed.iter().any(|c| c.is_subset(&here)) {
+            return;
+        }
+
+        // Reuse a justification which does not intersect the path
+        let j = match found.iter().find(|j| j.is_disjoint(&here)) {
+            Some(j) => Some(j.clone()),
+            None => self.one(axioms),
+        };
+
+        match j {
+            None => closed.push(here),
+            Some(j) => {
+                if !found.contains(&j) {
+                    found.push(j.clone());
+                }
+                for ax in j {
+                    let mut rest = axioms.clone();
+                    rest.remove(ax);
+                    path.push(ax);
+                    self.all(&rest, path, closed, found, limit);
+                    path.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Return at most `limit` justifications of an entailment in `ont`.
+///
+/// `reasoner` builds a reasoner for a subset of the axioms of
+/// `ont`, and `entailed` checks whether the entailment holds for
+/// it. Only logical axioms are included in the justifications.
+pub fn justifications<R, F, E>(
+    ont: &AxiomMappedOntology,
+    reasoner: F,
+    entailed: E,
+    limit: Option<usize>,
+) -> Vec<BTreeSet<AnnotatedAxiom>>
+where
+    F: Fn(AxiomMappedOntology) -> R,
+    E: Fn(&R) -> bool,
+{
+    let (logical, fixed): (Vec<&AnnotatedAxiom>, Vec<&AnnotatedAxiom>) =
+        ont.i().iter().partition(|ax| is_logical(ax.kind()));
+
+    let justifier = Justifier {
+        fixed,
+        reasoner,
+        entailed,
+    };
+    let mut found = vec![];
+    justifier.all(
+        &logical.into_iter().collect(),
+        &mut vec![],
+        &mut vec![],
+        &mut found,
+        limit,
+    );
+
+    found
+        .into_iter()
+        .map(|j| j.into_iter().cloned().collect())
+        .collect()
+}
+
+/// Return all justifications of `ax` in `ont`, using the reasoners
+/// built by `reasoner`.
+pub fn axiom_justifications<R, F>(
+    ont: &AxiomMappedOntology,
+    ax: &Axiom,
+    reasoner: F,
+) -> Vec<BTreeSet<AnnotatedAxiom>>
+where
+    R: Reasoner,
+    F: Fn(AxiomMappedOntology) -> R,
+{
+    justifications(ont, reasoner, |r: &R| r.is_entailed(ax), None)
+}
+
+/// Return all justifications of `sub SubClassOf sup` in `ont`, using
+/// a `StructuralReasoner`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::reasoner::justification::subclass_justifications;
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// let a: ClassExpression = b.class("http://www.example.com/a").into();
+/// let c: ClassExpression = b.class("http://www.example.com/c").into();
+/// o.insert(SubClassOf { sub: a.clone(), sup: b.class("http://www.example.com/b").into() });
+/// o.insert(SubClassOf { sub: b.class("http://www.example.com/b").into(), sup: c.clone() });
+///
+/// let js = subclass_justifications(&o, &a, &c);
+/// assert_eq!(js.len(), 1);
+/// assert_eq!(js[0].len(), 2);
+/// ```
+pub fn subclass_justifications(
+    ont: &AxiomMappedOntology,
+    sub: &ClassExpression,
+    sup: &ClassExpression,
+) -> Vec<BTreeSet<AnnotatedAxiom>> {
+    justifications(
+        ont,
+        StructuralReasoner::new,
+        |r: &StructuralReasoner| r.is_subclass(sub, sup),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(format!("http://ex.com/{}", c)).into()
+    }
+
+    fn sub(b: &Build, sub: &str, sup: &str) -> AnnotatedAxiom {
+        SubClassOf {
+            sub: class(b, sub),
+            sup: class(b, sup),
+        }
+        .into()
+    }
+
+    #[test]
+    fn two_paths() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://ex.com/a"));
+        for (x, y) in &[("a", "b"), ("b", "d"), ("a", "c"), ("c", "d"), ("d", "e")] {
+            o.insert(sub(&b, x, y));
+        }
+        o.insert(sub(&b, "x", "a"));
+
+        let js = subclass_justifications(&o, &class(&b, "a"), &class(&b, "e"));
+        assert_eq!(js.len(), 2);
+        for j in &js {
+            assert_eq!(j.len(), 3);
+            assert!(j.contains(&sub(&b, "d", "e")));
+        }
+    }
+
+    #[test]
+    fn not_entailed() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(sub(&b, "a", "b"));
+
+        assert!(subclass_justifications(&o, &class(&b, "b"), &class(&b, "a")).is_empty());
+    }
+
+    #[test]
+    fn limit() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(sub(&b, "a", "b"));
+        o.insert(EquivalentClasses(vec![class(&b, "a"), class(&b, "b")]));
+
+        let (a, c) = (class(&b, "a"), class(&b, "b"));
+        let entailed = |r: &StructuralReasoner| r.is_subclass(&a, &c);
+        assert_eq!(
+            justifications(&o, StructuralReasoner::new, entailed, None).len(),
+            2
+        );
+        assert_eq!(
+            justifications(&o, StructuralReasoner::new, entailed, Some(1)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn axiom() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(sub(&b, "a", "b"));
+        o.insert(sub(&b, "c", "d"));
+        o.insert(ClassAssertion {
+            ce: class(&b, "a"),
+            i: b.named_individual("http://ex.com/i"),
+        });
+
+        let ax = ClassAssertion {
+            ce: class(&b, "b"),
+            i: b.named_individual("http://ex.com/i"),
+        }
+        .into();
+        let js = axiom_justifications(&o, &ax, StructuralReasoner::new);
+        assert_eq!(js.len(), 1);
+        assert_eq!(js[0].len(), 2);
+        assert!(js[0].contains(&sub(&b, "a", "b")));
+    }
+}
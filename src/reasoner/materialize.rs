@@ -0,0 +1,284 @@
+//! Assert the inferences of a reasoner as axioms
+//!
+//! `materialize` adds the inferred class hierarchy of a `Reasoner` to
+//! an ontology, as the direct `SubClassOf` and `EquivalentClasses`
+//! axioms between named classes. Optionally, it also adds the
+//! inferred object property hierarchy, and the direct types of each
+//! named individual, producing the "inferred view" of an ontology.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! use horned_owl::reasoner::materialize::{materialize, MaterializeOptions};
+//! use horned_owl::reasoner::structural::StructuralReasoner;
+//!
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! let a: ClassExpression = b.class("http://www.example.com/a").into();
+//! let c: ClassExpression = b.class("http://www.example.com/c").into();
+//! o.insert(EquivalentClasses(vec![
+//!     a.clone(),
+//!     ClassExpression::ObjectIntersectionOf(vec![
+//!         b.class("http://www.example.com/b").into(),
+//!         c.clone(),
+//!     ]),
+//! ]));
+//!
+//! let reasoner = StructuralReasoner::new(o);
+//! let mut inferred = AxiomMappedOntology::default();
+//! materialize(&reasoner, &mut inferred, &MaterializeOptions::default());
+//!
+//! assert!(inferred.i().sub_class_of().any(|sc| sc.sub == a && sc.sup == c));
+//! ```
+use super::Reasoner;
+
+use crate::hierarchy::ClassHierarchy;
+use crate::model::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Options for `materialize`.
+#[derive(Clone, Debug, Default)]
+pub struct MaterializeOptions {
+    /// Also assert the inferred object property hierarchy.
+    pub properties: bool,
+    /// Also assert the direct types of each named individual.
+    pub instances: bool,
+}
+
+fn class_axioms<R: Reasoner>(reasoner: &R, axioms: &mut Vec<Axiom>) {
+    let h = ClassHierarchy::inferred(reasoner);
+    for c in h.classes() {
+        for sup in h.direct_super_classes(c) {
+            if h.direct_super_classes(sup).any(|s| s == c) {
+                // Each is a direct super class of the other
+                if c < sup {
+                    axioms
+                        .push(EquivalentClasses(vec![c.clone().into(), sup.clone().into()]).into());
+                }
+            } else {
+                axioms.push(
+                    SubClassOf {
+                        sub: c.clone().into(),
+                        sup: sup.clone().into(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+}
+
+fn property_axioms<R: Reasoner>(reasoner: &R, axioms: &mut Vec<Axiom>) {
+    let properties: Vec<ObjectPropertyExpression> = reasoner
+        .object_properties()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let supers: BTreeMap<&ObjectPropertyExpression, BTreeSet<&ObjectPropertyExpression>> =
+        properties
+            .iter()
+            .map(|r| {
+                let sups = properties
+                    .iter()
+                    .filter(|s| r != *s && reasoner.is_sub_object_property(r, s))
+                    .collect();
+                (r, sups)
+            })
+            .collect();
+
+    // As for `ClassHierarchy::inferred`
+    for (r, sups) in &supers {
+        for s in sups {
+            let direct = !sups.iter().any(|t| {
+                t != s && supers[t].contains(s) && !supers[s].contains(t) && !supers[t].contains(r)
+            });
+            if !direct {
+                continue;
+            }
+            if supers[s].contains(r) {
+                if r < s {
+                    axioms
+                        .push(EquivalentObjectProperties(vec![(*r).clone(), (*s).clone()]).into());
+                }
+            } else {
+                axioms.push(
+                    SubObjectPropertyOf {
+                        sub: (*r).clone().into(),
+                        sup: (*s).clone(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+}
+
+fn instance_axioms<R: Reasoner>(reasoner: &R, axioms: &mut Vec<Axiom>) {
+    let classes: Vec<ClassExpression> = reasoner.classes().into_iter().map(Into::into).collect();
+    for i in reasoner.individuals() {
+        let types: Vec<&ClassExpression> = classes
+            .iter()
+            .filter(|c| reasoner.is_instance(&i, c))
+            .collect();
+
+        // A type is direct unless a strictly more specific one holds
+        for c in &types {
+            let direct = !types
+                .iter()
+                .any(|d| d != c && reasoner.is_subclass(d, c) && !reasoner.is_subclass(c, d));
+            if direct {
+                axioms.push(
+                    ClassAssertion {
+                        ce: (*c).clone(),
+                        i: i.clone(),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+}
+
+/// Return the axioms for the inferences of `reasoner`.
+pub fn inferred_axioms<R: Reasoner>(reasoner: &R, options: &MaterializeOptions) -> Vec<Axiom> {
+    let mut axioms = vec![];
+    class_axioms(reasoner, &mut axioms);
+    if options.properties {
+        property_axioms(reasoner, &mut axioms);
+    }
+    if options.instances {
+        instance_axioms(reasoner, &mut axioms);
+    }
+    axioms
+}
+
+/// Insert the axioms for the inferences of `reasoner` into `ont`.
+///
+/// Return the number of axioms which were not already present.
+pub fn materialize<R, O>(reasoner: &R, ont: &mut O, options: &MaterializeOptions) -> usize
+where
+    R: Reasoner,
+    O: MutableOntology,
+{
+    ont.extend(inferred_axioms(reasoner, options))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::axiom_mapped::AxiomMappedOntology;
+    use crate::reasoner::structural::StructuralReasoner;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(format!("http://ex.com/{}", c)).into()
+    }
+
+    fn property(b: &Build, r: &str) -> ObjectPropertyExpression {
+        b.object_property(format!("http://ex.com/{}", r)).into()
+    }
+
+    fn ontology(b: &Build) -> AxiomMappedOntology {
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(b, "a"),
+            sup: class(b, "b"),
+        });
+        o.insert(SubClassOf {
+            sub: class(b, "b"),
+            sup: class(b, "c"),
+        });
+        o.insert(EquivalentClasses(vec![class(b, "c"), class(b, "d")]));
+        o.insert(SubObjectPropertyOf {
+            sub: property(b, "r").into(),
+            sup: property(b, "s"),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: property(b, "s").into(),
+            sup: property(b, "t"),
+        });
+        o.insert(ClassAssertion {
+            ce: class(b, "a"),
+            i: b.named_individual("http://ex.com/i"),
+        });
+        o
+    }
+
+    #[test]
+    fn classes() {
+        let b = Build::new();
+        let axioms = inferred_axioms(
+            &StructuralReasoner::new(ontology(&b)),
+            &MaterializeOptions::default(),
+        );
+
+        assert_eq!(axioms.len(), 4);
+        assert!(axioms.contains(
+            &SubClassOf {
+                sub: class(&b, "b"),
+                sup: class(&b, "c"),
+            }
+            .into()
+        ));
+        assert!(axioms.contains(
+            &SubClassOf {
+                sub: class(&b, "b"),
+                sup: class(&b, "d"),
+            }
+            .into()
+        ));
+        assert!(axioms.contains(&EquivalentClasses(vec![class(&b, "c"), class(&b, "d")]).into()));
+    }
+
+    #[test]
+    fn properties_instances() {
+        let b = Build::new();
+        let reasoner = StructuralReasoner::new(ontology(&b));
+        let axioms = inferred_axioms(
+            &reasoner,
+            &MaterializeOptions {
+                properties: true,
+                instances: true,
+            },
+        );
+
+        let sops: Vec<_> = axioms
+            .iter()
+            .filter(|ax| ax.kind() == AxiomKind::SubObjectPropertyOf)
+            .collect();
+        assert_eq!(sops.len(), 2);
+        assert!(!axioms.contains(
+            &SubObjectPropertyOf {
+                sub: property(&b, "r").into(),
+                sup: property(&b, "t"),
+            }
+            .into()
+        ));
+
+        let cas: Vec<_> = axioms
+            .iter()
+            .filter(|ax| ax.kind() == AxiomKind::ClassAssertion)
+            .collect();
+        assert_eq!(
+            cas,
+            vec![&ClassAssertion {
+                ce: class(&b, "a"),
+                i: b.named_individual("http://ex.com/i"),
+            }
+            .into()]
+        );
+
+        let mut o = ontology(&b);
+        let n = materialize(
+            &reasoner,
+            &mut o,
+            &MaterializeOptions {
+                properties: true,
+                instances: true,
+            },
+        );
+        // The told axioms are already present
+        assert_eq!(n, axioms.len() - 6);
+    }
+}
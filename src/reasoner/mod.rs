@@ -0,0 +1,421 @@
+//! Reasoning over ontologies
+//!
+//! The `Reasoner` trait describes the inferences that a reasoner
+//! can make about class expressions and individuals. Queries which
+//! can be built from these, such as a DL query, are provided over
+//! any `Reasoner`.
+//!
+//! The [`structural`](structural/index.html) module provides a
+//! simple and incomplete reasoner, which makes structural inferences
+//! from the told axioms of an ontology. The
+//! [`justification`](justification/index.html) module finds the
+//! axioms responsible for an entailment, using any `Reasoner`, and
+//! the [`materialize`](materialize/index.html) module adds the
+//! inferences of a `Reasoner` to an ontology as axioms. The
+//! [`incremental`](incremental/index.html) module keeps the
+//! classification of the structural reasoner up to date as an
+//! ontology is edited.
+use crate::error::{HornedError, ReasonerError};
+use crate::metrics::is_logical;
+use crate::model::*;
+use crate::vocab::{WithIRI, OWL};
+
+use std::collections::BTreeSet;
+
+pub mod incremental;
+pub mod justification;
+pub mod materialize;
+pub mod structural;
+
+/// The result of a DL query.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QueryResult {
+    /// Named classes equivalent to the query.
+    pub equivalent_classes: BTreeSet<Class>,
+    /// Named classes which strictly subsume the query.
+    pub super_classes: BTreeSet<Class>,
+    /// Named classes strictly subsumed by the query.
+    pub sub_classes: BTreeSet<Class>,
+    /// Named individuals which are instances of the query.
+    pub instances: BTreeSet<NamedIndividual>,
+}
+
+pub trait Reasoner {
+    /// Return true if `sub` is subsumed by `sup`.
+    fn is_subclass(&self, sub: &ClassExpression, sup: &ClassExpression) -> bool;
+
+    /// Return true if `i` is an instance of `ce`.
+    fn is_instance(&self, i: &NamedIndividual, ce: &ClassExpression) -> bool;
+
+    /// The named classes of the ontology.
+    fn classes(&self) -> BTreeSet<Class>;
+
+    /// The named individuals of the ontology.
+    fn individuals(&self) -> BTreeSet<NamedIndividual>;
+
+    /// Return true if `sub` is a sub property of `sup`.
+    fn is_sub_object_property(
+        &self,
+        sub: &ObjectPropertyExpression,
+        sup: &ObjectPropertyExpression,
+    ) -> bool;
+
+    /// The named object properties of the ontology.
+    fn object_properties(&self) -> BTreeSet<ObjectProperty>;
+
+    /// Return true if `ax` is asserted in the ontology of this
+    /// reasoner.
+    fn is_asserted(&self, _ax: &Axiom) -> bool {
+        false
+    }
+
+    /// Return true if `a` and `b` subsume each other.
+    fn is_equivalent(&self, a: &ClassExpression, b: &ClassExpression) -> bool {
+        self.is_subclass(a, b) && self.is_subclass(b, a)
+    }
+
+    /// Return true if `ax` is entailed.
+    ///
+    /// Asserted axioms, and axioms without a logical meaning, such
+    /// as declarations, are always entailed. Otherwise, axioms which
+    /// cannot be expressed as subsumption, instance or object
+    /// property hierarchy checks are never entailed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// # use horned_owl::reasoner::Reasoner;
+    /// # use horned_owl::reasoner::structural::StructuralReasoner;
+    /// let b = Build::new();
+    /// let mut o = AxiomMappedOntology::default();
+    /// let i = b.named_individual("http://www.example.com/i");
+    /// o.insert(ClassAssertion {
+    ///     ce: b.class("http://www.example.com/a").into(),
+    ///     i: i.clone(),
+    /// });
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// let reasoner = StructuralReasoner::new(o);
+    /// assert!(reasoner.is_entailed(&ClassAssertion {
+    ///     ce: b.class("http://www.example.com/b").into(),
+    ///     i,
+    /// }.into()));
+    /// ```
+    fn is_entailed(&self, ax: &Axiom) -> bool {
+        if self.is_asserted(ax) {
+            return true;
+        }
+
+        match ax {
+            Axiom::SubClassOf(SubClassOf { sub, sup }) => self.is_subclass(sub, sup),
+            Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+                v.windows(2).all(|w| self.is_equivalent(&w[0], &w[1]))
+            }
+            Axiom::DisjointClasses(DisjointClasses(v)) => {
+                let nothing = Build::new().class(OWL::Nothing.iri_str()).into();
+                v.iter().enumerate().all(|(n, a)| {
+                    v[n + 1..].iter().all(|b| {
+                        self.is_subclass(
+                            &ClassExpression::ObjectIntersectionOf(vec![a.clone(), b.clone()]),
+                            &nothing,
+                        )
+                    })
+                })
+            }
+            Axiom::ClassAssertion(ClassAssertion { ce, i }) => self.is_instance(i, ce),
+            Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to }) => self
+                .is_instance(
+                    from,
+                    &ClassExpression::ObjectHasValue {
+                        ope: ope.clone(),
+                        i: to.clone(),
+                    },
+                ),
+            Axiom::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => self
+                .is_instance(
+                    from,
+                    &ClassExpression::DataHasValue {
+                        dp: dp.clone(),
+                        l: to.clone(),
+                    },
+                ),
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+                sup,
+            }) => self.is_sub_object_property(sub, sup),
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                v.windows(2).all(|w| {
+                    self.is_sub_object_property(&w[0], &w[1])
+                        && self.is_sub_object_property(&w[1], &w[0])
+                })
+            }
+            _ => !is_logical(ax.kind()),
+        }
+    }
+
+    /// Return the named classes related to `ce`, and its instances.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// # use horned_owl::reasoner::Reasoner;
+    /// # use horned_owl::reasoner::structural::StructuralReasoner;
+    /// let b = Build::new();
+    /// let mut o = AxiomMappedOntology::default();
+    /// let a = b.class("http://www.example.com/a");
+    /// let r = b.object_property("http://www.example.com/r");
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: r.clone().into(),
+    ///         bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///     },
+    /// });
+    ///
+    /// let reasoner = StructuralReasoner::new(o);
+    /// let result = reasoner.query(&ClassExpression::ObjectSomeValuesFrom {
+    ///     ope: r.into(),
+    ///     bce: Box::new(ClassExpression::Class(b.class("http://www.w3.org/2002/07/owl#Thing"))),
+    /// });
+    /// assert!(result.sub_classes.contains(&a));
+    /// ```
+    fn query(&self, ce: &ClassExpression) -> QueryResult {
+        let mut result = QueryResult::default();
+        for c in self.classes() {
+            let named = ClassExpression::Class(c.clone());
+            match (self.is_subclass(&named, ce), self.is_subclass(ce, &named)) {
+                (true, true) => result.equivalent_classes.insert(c),
+                (true, false) => result.sub_classes.insert(c),
+                (false, true) => result.super_classes.insert(c),
+                (false, false) => false,
+            };
+        }
+
+        result.instances = self
+            .individuals()
+            .into_iter()
+            .filter(|i| self.is_instance(i, ce))
+            .collect();
+        result
+    }
+}
+
+/// The failures from checking a suite of entailments.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EntailmentReport {
+    /// Expected axioms which are not entailed.
+    pub missing: Vec<Axiom>,
+    /// Forbidden axioms which are entailed.
+    pub unexpected: Vec<Axiom>,
+}
+
+impl EntailmentReport {
+    /// Return true if every entailment was as expected.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+
+    /// Return an error holding this report, unless every
+    /// entailment was as expected.
+    pub fn into_result(self) -> Result<(), HornedError> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(ReasonerError::Entailments(self).into())
+        }
+    }
+}
+
+/// Check that every axiom in `expected` is entailed by `reasoner`,
+/// and none in `forbidden` are.
+pub fn check_entailments<R: Reasoner + ?Sized>(
+    reasoner: &R,
+    expected: &[Axiom],
+    forbidden: &[Axiom],
+) -> EntailmentReport {
+    EntailmentReport {
+        missing: expected
+            .iter()
+            .filter(|ax| !reasoner.is_entailed(ax))
+            .cloned()
+            .collect(),
+        unexpected: forbidden
+            .iter()
+            .filter(|ax| reasoner.is_entailed(ax))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::structural::StructuralReasoner;
+    use super::*;
+    use crate::ontology::axiom_mapped::AxiomMappedOntology;
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(format!("http://ex.com/{}", c)).into()
+    }
+
+    #[test]
+    fn entailments() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "a"),
+            sup: class(&b, "b"),
+        });
+        o.insert(SubClassOf {
+            sub: class(&b, "b"),
+            sup: class(&b, "a"),
+        });
+        o.insert(ObjectPropertyAssertion {
+            ope: b.object_property("http://ex.com/r").into(),
+            from: b.named_individual("http://ex.com/i"),
+            to: b.named_individual("http://ex.com/j"),
+        });
+        let r = StructuralReasoner::new(o);
+
+        assert!(r.is_entailed(&EquivalentClasses(vec![class(&b, "a"), class(&b, "b")]).into()));
+        assert!(r.is_entailed(&DeclareClass(b.class("http://ex.com/c")).into()));
+        assert!(r.is_entailed(
+            &ClassAssertion {
+                ce: ClassExpression::ObjectHasValue {
+                    ope: b.object_property("http://ex.com/r").into(),
+                    i: b.named_individual("http://ex.com/j"),
+                },
+                i: b.named_individual("http://ex.com/i"),
+            }
+            .into()
+        ));
+        assert!(!r.is_entailed(&DisjointClasses(vec![class(&b, "a"), class(&b, "b")]).into()));
+        assert!(!r.is_entailed(
+            &TransitiveObjectProperty(b.object_property("http://ex.com/r").into()).into()
+        ));
+    }
+
+    #[test]
+    fn asserted() {
+        let b = Build::new();
+        let op = |r: &str| -> ObjectPropertyExpression {
+            b.object_property(format!("http://ex.com/{}", r)).into()
+        };
+        let i = b.named_individual("http://ex.com/i");
+        let l = Literal::Simple {
+            literal: "x".to_string(),
+        };
+
+        let mut o = AxiomMappedOntology::default();
+        o.insert(DataPropertyAssertion {
+            dp: b.data_property("http://ex.com/p"),
+            from: i.clone(),
+            to: l.clone(),
+        });
+        o.insert(SubDataPropertyOf {
+            sub: b.data_property("http://ex.com/p"),
+            sup: b.data_property("http://ex.com/q"),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: op("r").into(),
+            sup: op("s"),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: op("s").into(),
+            sup: op("t"),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: op("t").into(),
+            sup: op("s"),
+        });
+        let chain: Axiom = SubObjectPropertyOf {
+            sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![op("r"), op("s")]),
+            sup: op("t"),
+        }
+        .into();
+        o.insert(chain.clone());
+        let r = StructuralReasoner::new(o);
+
+        assert!(r.is_entailed(
+            &DataPropertyAssertion {
+                dp: b.data_property("http://ex.com/p"),
+                from: i.clone(),
+                to: l.clone(),
+            }
+            .into()
+        ));
+        assert!(r.is_entailed(
+            &DataPropertyAssertion {
+                dp: b.data_property("http://ex.com/q"),
+                from: i.clone(),
+                to: l,
+            }
+            .into()
+        ));
+        assert!(r.is_entailed(
+            &SubObjectPropertyOf {
+                sub: op("r").into(),
+                sup: op("s"),
+            }
+            .into()
+        ));
+        assert!(r.is_entailed(
+            &SubObjectPropertyOf {
+                sub: op("r").into(),
+                sup: op("t"),
+            }
+            .into()
+        ));
+        assert!(!r.is_entailed(
+            &SubObjectPropertyOf {
+                sub: op("s").into(),
+                sup: op("r"),
+            }
+            .into()
+        ));
+        assert!(r.is_entailed(&EquivalentObjectProperties(vec![op("s"), op("t")]).into()));
+        assert!(!r.is_entailed(&EquivalentObjectProperties(vec![op("r"), op("s")]).into()));
+        assert!(r.is_entailed(&chain));
+    }
+
+    #[test]
+    fn suite() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "a"),
+            sup: class(&b, "b"),
+        });
+        let r = StructuralReasoner::new(o);
+
+        let ab: Axiom = SubClassOf {
+            sub: class(&b, "a"),
+            sup: class(&b, "b"),
+        }
+        .into();
+        let ba: Axiom = SubClassOf {
+            sub: class(&b, "b"),
+            sup: class(&b, "a"),
+        }
+        .into();
+
+        let (ab, ba) = (vec![ab], vec![ba]);
+
+        assert!(check_entailments(&r, &ab, &ba).is_ok());
+
+        let report = check_entailments(&r, &ba, &ab);
+        assert!(!report.is_ok());
+        assert_eq!(report.missing, ba);
+        assert_eq!(report.unexpected, ab);
+
+        assert!(check_entailments(&r, &ab, &ba).into_result().is_ok());
+        assert!(matches!(
+            report.into_result(),
+            Err(HornedError::ReasonerError(ReasonerError::Entailments(_)))
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+//! A structural reasoner
+//!
+//! `StructuralReasoner` makes inferences by comparing the structure
+//! of class expressions, following told `SubClassOf` and
+//! `EquivalentClasses` axioms, the property hierarchy and property
+//! domains and ranges. It is sound but incomplete: it will never
+//! report a subsumption which does not hold, but may miss some which
+//! do.
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::signature::Signature;
+use crate::vocab::{Namespace, WithIRI, OWL};
+
+use super::Reasoner;
+
+use std::collections::BTreeSet;
+
+#[derive(Debug, Default)]
+pub struct StructuralReasoner {
+    ont: AxiomMappedOntology,
+}
+
+// Subsumptions currently being tested, which guards against cycles in
+// the told axioms
+type Goals = BTreeSet<(ClassExpression, ClassExpression)>;
+
+fn is_builtin(ce: &ClassExpression, builtin: OWL) -> bool {
+    matches!(ce, ClassExpression::Class(c) if c.0.as_str() == builtin.iri_str())
+}
+
+impl StructuralReasoner {
+    pub fn new(ont: AxiomMappedOntology) -> StructuralReasoner {
+        StructuralReasoner { ont }
+    }
+
+    pub fn ontology(&self) -> &AxiomMappedOntology {
+        &self.ont
+    }
+
+    pub fn into_ontology(self) -> AxiomMappedOntology {
+        self.ont
+    }
+
+    /// The class expressions which `c` is told to be subsumed by.
+    fn told_super_classes<'a>(&'a self, c: &'a Class) -> impl Iterator<Item = &'a ClassExpression> {
+        let subs = self
+            .ont
+            .i()
+            .sub_class_of()
+            .filter_map(move |sc| match &sc.sub {
+                ClassExpression::Class(d) if *d == *c => Some(&sc.sup),
+                _ => None,
+            });
+
+        let equivs = self
+            .ont
+            .i()
+            .equivalent_class()
+            .filter(move |ec| {
+                ec.0.iter()
+                    .any(|ce| matches!(ce, ClassExpression::Class(d) if *d == *c))
+            })
+            .flat_map(|ec| ec.0.iter());
+
+        subs.chain(equivs)
+    }
+
+    fn subsumes(&self, sub: &ClassExpression, sup: &ClassExpression, goals: &mut Goals) -> bool {
+        use ClassExpression::*;
+
+        if sub == sup || is_builtin(sup, OWL::Thing) || is_builtin(sub, OWL::Nothing) {
+            return true;
+        }
+
+        let goal = (sub.clone(), sup.clone());
+        if !goals.insert(goal.clone()) {
+            return false;
+        }
+
+        let result = match (sub, sup) {
+            (_, ObjectIntersectionOf(v)) => v.iter().all(|ce| self.subsumes(sub, ce, goals)),
+            (ObjectUnionOf(v), _) => v.iter().all(|ce| self.subsumes(ce, sup, goals)),
+            _ => self.subsumes_by_parts(sub, sup, goals),
+        };
+
+        goals.remove(&goal);
+        result
+    }
+
+    fn subsumes_by_parts(
+        &self,
+        sub: &ClassExpression,
+        sup: &ClassExpression,
+        goals: &mut Goals,
+    ) -> bool {
+        use ClassExpression::*;
+
+        let structural = match (sub, sup) {
+            (_, ObjectUnionOf(v)) => v.iter().any(|ce| self.subsumes(sub, ce, goals)),
+            (ObjectIntersectionOf(v), _) => v.iter().any(|ce| self.subsumes(ce, sup, goals)),
+            (ObjectComplementOf(a), ObjectComplementOf(b)) => self.subsumes(b, a, goals),
+            (ObjectOneOf(a), ObjectOneOf(b)) => a.iter().all(|i| b.contains(i)),
+            (ObjectSomeValuesFrom { ope: r, bce: c }, ObjectSomeValuesFrom { ope: s, bce: d }) => {
+                self.is_sub_object_property(r, s) && self.subsumes(c, d, goals)
+            }
+            (ObjectAllValuesFrom { ope: r, bce: c }, ObjectAllValuesFrom { ope: s, bce: d }) => {
+                self.is_sub_object_property(s, r) && self.subsumes(c, d, goals)
+            }
+            (ObjectHasValue { ope: r, i }, ObjectSomeValuesFrom { ope: s, bce }) => {
+                self.is_sub_object_property(r, s) && self.instance(i, bce, goals)
+            }
+            (DataSomeValuesFrom { dp: p, dr: a }, DataSomeValuesFrom { dp: q, dr: b }) => {
+                a == b && self.ont.i().super_data_properties(p).contains(q)
+            }
+            _ => false,
+        };
+        if structural {
+            return true;
+        }
+
+        // Unfold the told super classes of `sub`, the definitions of
+        // `sup` and the domains of any property restriction
+        let told = match sub {
+            Class(c) => self.told_super_classes(c).any(|ce| self.subsumes(ce, sup, goals)),
+            ObjectSomeValuesFrom { ope, .. }
+            | ObjectHasValue { ope, .. }
+            | ObjectMinCardinality { ope, .. }
+            | ObjectExactCardinality { ope, .. }
+                if !matches!(
+                    sub,
+                    ObjectMinCardinality { n: 0, .. } | ObjectExactCardinality { n: 0, .. }
+                ) =>
+            {
+                self.ont
+                    .i()
+                    .domains_of(ope)
+                    .into_iter()
+                    .any(|ce| self.subsumes(ce, sup, goals))
+            }
+            _ => false,
+        };
+
+        told || match sup {
+            Class(c) => self.ont.i().definitions(c).any(|ce| self.subsumes(sub, ce, goals)),
+            _ => false,
+        }
+    }
+
+    fn instance(&self, i: &NamedIndividual, ce: &ClassExpression, goals: &mut Goals) -> bool {
+        use ClassExpression::*;
+
+        let structural = match ce {
+            ObjectIntersectionOf(v) => v.iter().all(|ce| self.instance(i, ce, goals)),
+            ObjectUnionOf(v) => v.iter().any(|ce| self.instance(i, ce, goals)),
+            ObjectOneOf(v) => v.contains(i),
+            ObjectHasValue { ope, i: to } => self.ont.i().object_property_assertion().any(|opa| {
+                opa.from == *i && opa.to == *to && self.is_sub_object_property(&opa.ope, ope)
+            }),
+            DataHasValue { dp, l } => self.ont.i().data_property_assertion().any(|dpa| {
+                dpa.from == *i
+                    && dpa.to == *l
+                    && self.ont.i().super_data_properties(&dpa.dp).contains(dp)
+            }),
+            _ => is_builtin(ce, OWL::Thing),
+        };
+
+        structural
+            || self
+                .ont
+                .i()
+                .class_assertion()
+                .any(|ca| ca.i == *i && self.subsumes(&ca.ce, ce, goals))
+            || self.ont.i().object_property_assertion().any(|opa| {
+                (opa.from == *i
+                    && self
+                        .ont
+                        .i()
+                        .domains_of(&opa.ope)
+                        .into_iter()
+                        .any(|d| self.subsumes(d, ce, goals)))
+                    || (opa.to == *i
+                        && self
+                            .ont
+                            .i()
+                            .ranges_of(&opa.ope)
+                            .into_iter()
+                            .any(|r| self.subsumes(r, ce, goals)))
+            })
+    }
+}
+
+impl Reasoner for StructuralReasoner {
+    fn is_subclass(&self, sub: &ClassExpression, sup: &ClassExpression) -> bool {
+        self.subsumes(sub, sup, &mut Goals::new())
+    }
+
+    fn is_instance(&self, i: &NamedIndividual, ce: &ClassExpression) -> bool {
+        self.instance(i, ce, &mut Goals::new())
+    }
+
+    fn is_asserted(&self, ax: &Axiom) -> bool {
+        self.ont.i().axiom(ax.kind()).any(|a| a == ax)
+    }
+
+    fn classes(&self) -> BTreeSet<Class> {
+        self.ont
+            .signature()
+            .into_iter()
+            .filter_map(|e| match e {
+                NamedEntity::Class(c) => Some(c),
+                _ => None,
+            })
+            .filter(|c| {
+                let ce = ClassExpression::Class(c.clone());
+                !is_builtin(&ce, OWL::Thing) && !is_builtin(&ce, OWL::Nothing)
+            })
+            .collect()
+    }
+
+    fn individuals(&self) -> BTreeSet<NamedIndividual> {
+        self.ont
+            .signature()
+            .into_iter()
+            .filter_map(|e| match e {
+                NamedEntity::NamedIndividual(i) => Some(i),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_sub_object_property(
+        &self,
+        sub: &ObjectPropertyExpression,
+        sup: &ObjectPropertyExpression,
+    ) -> bool {
+        self.ont.i().super_object_properties(sub).contains(sup)
+    }
+
+    fn object_properties(&self) -> BTreeSet<ObjectProperty> {
+        self.ont
+            .signature()
+            .into_iter()
+            .filter_map(|e| match e {
+                NamedEntity::ObjectProperty(op) => Some(op),
+                _ => None,
+            })
+            .filter(|op| {
+                let local = op.0.as_str().strip_prefix(Namespace::OWL.iri_str());
+                local != Some("topObjectProperty") && local != Some("bottomObjectProperty")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn some(b: &Build, r: &str, c: &str) -> ClassExpression {
+        ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property(r).into(),
+            bce: Box::new(b.class(c).into()),
+        }
+    }
+
+    fn class(b: &Build, c: &str) -> ClassExpression {
+        b.class(c).into()
+    }
+
+    fn pizza() -> (Build, StructuralReasoner) {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/Margherita"),
+            sup: class(&b, "http://ex.com/Pizza"),
+        });
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/Margherita"),
+            sup: some(&b, "http://ex.com/hasTopping", "http://ex.com/Mozzarella"),
+        });
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/Mozzarella"),
+            sup: class(&b, "http://ex.com/Cheese"),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: ObjectPropertyExpression::from(b.object_property("http://ex.com/hasTopping"))
+                .into(),
+            sup: b.object_property("http://ex.com/hasIngredient").into(),
+        });
+        o.insert(EquivalentClasses(vec![
+            class(&b, "http://ex.com/CheesyPizza"),
+            ClassExpression::ObjectIntersectionOf(vec![
+                class(&b, "http://ex.com/Pizza"),
+                some(&b, "http://ex.com/hasIngredient", "http://ex.com/Cheese"),
+            ]),
+        ]));
+        o.insert(ClassAssertion {
+            ce: class(&b, "http://ex.com/Margherita"),
+            i: b.named_individual("http://ex.com/lunch"),
+        });
+        (b, StructuralReasoner::new(o))
+    }
+
+    #[test]
+    fn subsumption() {
+        let (b, r) = pizza();
+        let margherita = class(&b, "http://ex.com/Margherita");
+        assert!(r.is_subclass(&margherita, &class(&b, "http://ex.com/Pizza")));
+        assert!(r.is_subclass(&margherita, &class(&b, "http://ex.com/CheesyPizza")));
+        assert!(!r.is_subclass(&class(&b, "http://ex.com/Pizza"), &margherita));
+        assert!(r.is_subclass(
+            &margherita,
+            &some(&b, "http://ex.com/hasIngredient", "http://ex.com/Cheese")
+        ));
+    }
+
+    #[test]
+    fn cycle() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/A"),
+            sup: class(&b, "http://ex.com/B"),
+        });
+        o.insert(SubClassOf {
+            sub: class(&b, "http://ex.com/B"),
+            sup: class(&b, "http://ex.com/A"),
+        });
+        let r = StructuralReasoner::new(o);
+
+        assert!(r.is_equivalent(&class(&b, "http://ex.com/A"), &class(&b, "http://ex.com/B")));
+        assert!(!r.is_subclass(&class(&b, "http://ex.com/A"), &class(&b, "http://ex.com/C")));
+    }
+
+    #[test]
+    fn query() {
+        let (b, r) = pizza();
+        let result = r.query(&ClassExpression::ObjectIntersectionOf(vec![
+            class(&b, "http://ex.com/Pizza"),
+            some(&b, "http://ex.com/hasIngredient", "http://ex.com/Cheese"),
+        ]));
+
+        assert_eq!(
+            result.equivalent_classes,
+            vec![b.class("http://ex.com/CheesyPizza")]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            result.sub_classes,
+            vec![b.class("http://ex.com/Margherita")]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            result.super_classes,
+            vec![b.class("http://ex.com/Pizza")].into_iter().collect()
+        );
+        assert_eq!(
+            result.instances,
+            vec![b.named_individual("http://ex.com/lunch")]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn instances() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.insert(ObjectPropertyDomain {
+            ope: b.object_property("http://ex.com/r").into(),
+            ce: class(&b, "http://ex.com/A"),
+        });
+        o.insert(ObjectPropertyAssertion {
+            ope: b.object_property("http://ex.com/r").into(),
+            from: b.named_individual("http://ex.com/i"),
+            to: b.named_individual("http://ex.com/j"),
+        });
+        let r = StructuralReasoner::new(o);
+
+        let i = b.named_individual("http://ex.com/i");
+        assert!(r.is_instance(&i, &class(&b, "http://ex.com/A")));
+        assert!(r.is_instance(
+            &i,
+            &ClassExpression::ObjectHasValue {
+                ope: b.object_property("http://ex.com/r").into(),
+                i: b.named_individual("http://ex.com/j"),
+            }
+        ));
+        assert!(!r.is_instance(
+            &b.named_individual("http://ex.com/j"),
+            &class(&b, "http://ex.com/A")
+        ));
+    }
+
+    #[test]
+    fn threads() {
+        let (b, r) = pizza();
+        let margherita = class(&b, "http://ex.com/Margherita");
+        let cheesy = class(&b, "http://ex.com/CheesyPizza");
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| r.is_subclass(&margherita, &cheesy)))
+                .collect();
+            for h in handles {
+                assert!(h.join().unwrap());
+            }
+        });
+    }
+}
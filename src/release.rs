@@ -0,0 +1,204 @@
+//! Release an ontology
+//!
+//! Releasing an ontology usually means stamping it with a dated
+//! version IRI and `owl:versionInfo`, optionally merging its imports
+//! and asserting its inferred hierarchy, and then writing it in each
+//! of the formats that are published. `release` does the first of
+//! these in one step, and `write_release` the last.
+//!
+//! In the patterns of `ReleaseOptions`, `{date}` is replaced by the
+//! release date.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! use horned_owl::collection::OntologyManager;
+//! use horned_owl::release::{release, ReleaseOptions};
+//! use horned_owl::version::version_info;
+//!
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+//!
+//! let mut m = OntologyManager::new();
+//! let iri = m.insert(o).unwrap();
+//! let options = ReleaseOptions {
+//!     version_iri: Some("http://www.example.com/o/releases/{date}/o.owl".to_string()),
+//!     version_info: Some("{date}".to_string()),
+//!     ..Default::default()
+//! };
+//! let released = release(&mut m, &iri, "2020-01-01", &options).unwrap();
+//!
+//! assert_eq!(
+//!     released.id().viri,
+//!     Some(b.iri("http://www.example.com/o/releases/2020-01-01/o.owl"))
+//! );
+//! assert_eq!(version_info(&released), Some(&"2020-01-01".to_string()));
+//! ```
+use crate::collection::OntologyManager;
+use crate::command::{format_name, write_as};
+use crate::error::ManagerError;
+use crate::io::ResourceType;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::reasoner::materialize::{materialize, MaterializeOptions};
+use crate::reasoner::structural::StructuralReasoner;
+use crate::version::set_version_info;
+
+use curie::PrefixMapping;
+
+use crate::error::HornedError;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Options for `release`.
+#[derive(Clone, Debug, Default)]
+pub struct ReleaseOptions {
+    /// The pattern of the version IRI, or `None` to leave it as is.
+    pub version_iri: Option<String>,
+    /// The pattern of the `owl:versionInfo`, or `None` to leave it as
+    /// is.
+    pub version_info: Option<String>,
+    /// Merge the imports closure into the released ontology.
+    pub merge_imports: bool,
+    /// Assert the inferences of a `StructuralReasoner`, or `None` to
+    /// release the told axioms alone.
+    pub materialize: Option<MaterializeOptions>,
+}
+
+/// Replace `{date}` in `pattern` with `date`.
+pub fn expand_pattern(pattern: &str, date: &str) -> String {
+    pattern.replace("{date}", date)
+}
+
+/// Return today's date in UTC, as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    iso_date(secs / 86400)
+}
+
+/// Return the `YYYY-MM-DD` date of `days` after 1970-01-01.
+fn iso_date(days: u64) -> String {
+    // After Howard Hinnant's civil_from_days, from a 0000-03-01 epoch
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let doe = days % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Return the release of the ontology `iri` of `manager` on `date`.
+///
+/// The ontology in `manager` is not changed, though merging may load
+/// its imports.
+pub fn release(
+    manager: &mut OntologyManager,
+    iri: &IRI,
+    date: &str,
+    options: &ReleaseOptions,
+) -> Result<AxiomMappedOntology, HornedError> {
+    let mut ont = if options.merge_imports {
+        manager.flatten_imports(iri)?
+    } else {
+        manager
+            .get(iri)
+            .ok_or_else(|| ManagerError::UnknownOntology(iri.to_string()))?
+            .filter_axioms(|_| true)
+    };
+
+    if let Some(pattern) = &options.version_iri {
+        let viri = manager.build().iri(expand_pattern(pattern, date));
+        ont.mut_id().set_version_iri(viri)?;
+    }
+    if let Some(pattern) = &options.version_info {
+        set_version_info(&mut ont, Some(expand_pattern(pattern, date)));
+    }
+    if let Some(mo) = &options.materialize {
+        let reasoner = StructuralReasoner::new(ont.filter_axioms(|_| true));
+        materialize(&reasoner, &mut ont, mo);
+    }
+
+    Ok(ont)
+}
+
+/// Write `ont` in each of `formats`, to `stem` with the extension of
+/// each format, and return the paths written.
+pub fn write_release(
+    ont: &AxiomMappedOntology,
+    mapping: Option<&PrefixMapping>,
+    stem: &Path,
+    formats: &[ResourceType],
+) -> Result<Vec<PathBuf>, HornedError> {
+    let mut paths = vec![];
+    for rt in formats {
+        let path = stem.with_extension(format_name(*rt));
+        let mut w = BufWriter::new(File::create(&path)?);
+        write_as(&mut w, ont, mapping, *rt)?;
+        w.flush()?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::command::parse_path;
+
+    #[test]
+    fn dates() {
+        assert_eq!(iso_date(0), "1970-01-01");
+        assert_eq!(iso_date(11_016), "2000-02-29");
+        assert_eq!(iso_date(18_321), "2020-02-29");
+        assert_eq!(iso_date(19_722), "2023-12-31");
+        assert_eq!(today().len(), 10);
+    }
+
+    #[test]
+    fn release_materialized() {
+        let (ont, _, _) = parse_path(Path::new("src/ont/owl-xml/subclass.owx"))
+            .unwrap()
+            .decompose();
+        let mut m = OntologyManager::new();
+        let iri = m.insert(ont).unwrap();
+        let told = m.get(&iri).unwrap().i().iter().count();
+
+        let options = ReleaseOptions {
+            version_iri: Some("http://www.example.com/{date}".to_string()),
+            materialize: Some(MaterializeOptions::default()),
+            ..Default::default()
+        };
+        let released = release(&mut m, &iri, "2020-01-01", &options).unwrap();
+        assert_eq!(released.id().iri, Some(iri.clone()));
+        assert!(released.i().iter().count() >= told);
+        assert_eq!(
+            released.id().viri,
+            Some(m.build().iri("http://www.example.com/2020-01-01"))
+        );
+        assert_ne!(m.get(&iri).unwrap().id().viri, released.id().viri);
+
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let paths = write_release(
+            &released,
+            None,
+            &dir.as_ref().join("o"),
+            &[ResourceType::OWX, ResourceType::OFN],
+        )
+        .unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[1].ends_with("o.ofn"));
+        assert!(paths.iter().all(|p| p.exists()));
+    }
+}
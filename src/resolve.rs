@@ -1,5 +1,28 @@
-use curl::easy::Easy;
-use crate::model::{Build, IRI};
+//! Resolution of IRIs to the documents that they identify
+//!
+//! An `IRIResolver` returns the bytes of the document for an IRI. The
+//! HTTP implementation is available with the `remote` feature.
+#[cfg(feature = "remote")]
+use curl::easy::{Easy, List};
+
+use crate::error::HornedError;
+
+use crate::io::{is_gzip, read_with_build};
+use crate::error::ResolveError;
+use crate::io::ResourceType;
+use crate::model::{AnnotatedAxiom, Axiom, Build, Ontology, IRI};
+use crate::ontology::set::SetOntology;
+use crate::vocab::{Namespace, WithIRI};
+
+use flate2::read::GzDecoder;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
 
 
 // fn from_dir_bufread<R: BufRead>(dir: PathBuf, iri:&String) -> R {
@@ -9,43 +32,391 @@ use crate::model::{Build, IRI};
 // }
 
 // Given an `iri`, return the IRI local to `doc_iri` that this would
-// have.
-pub fn localize_iri(iri:&IRI, doc_iri:&IRI) -> IRI {
+// have; this fails if `iri` has no path.
+pub fn localize_iri(iri:&IRI, doc_iri:&IRI) -> Result<IRI, HornedError> {
     let b = Build::new();
-    let (_,term_iri) = iri.split_at(iri.rfind('/').unwrap() + 1);
+    let index = iri.rfind('/')
+        .ok_or_else(|| ResolveError::Unresolvable(iri.to_string()))?;
+    let (_,term_iri) = iri.split_at(index + 1);
 
-    b.iri(
+    Ok(b.iri(
         if let Some(index) = doc_iri.rfind("/") {
             format!("{}/{}", doc_iri.split_at(index).0, term_iri)
         }
         else{
             format!("./{}", term_iri)
         }
-    )
+    ))
 }
 
 // Return the ontology as Vec<u8> from `iri` unless we think that it
 // is local to doc_iri
-pub fn resolve_iri(iri:&IRI, doc_iri: &IRI) -> Vec<u8> {
-    strict_resolve_iri(&localize_iri(iri, doc_iri))
+#[cfg(feature = "remote")]
+pub fn resolve_iri(iri:&IRI, doc_iri: &IRI) -> Result<Vec<u8>, HornedError> {
+    strict_resolve_iri(&localize_iri(iri, doc_iri)?)
 }
 
 // Return the ontology as Vec<u8> from `iri`.
-pub fn strict_resolve_iri(iri: &IRI) -> Vec<u8> {
-    let mut data = Vec::new();
-    let mut handle = Easy::new();
-    {
-        let s:String = iri.into();
-        handle.url(&s).unwrap();
-        handle.follow_location(true).unwrap();
-        let mut transfer = handle.transfer();
-        transfer.write_function(|new_data| {
-            data.extend_from_slice(new_data);
-            Ok(new_data.len())
-        }).unwrap();
-        transfer.perform().unwrap();
-    }
-    data
+#[cfg(feature = "remote")]
+pub fn strict_resolve_iri(iri: &IRI) -> Result<Vec<u8>, HornedError> {
+    HTTPResolver::default().resolve(iri)
+}
+
+/// Return the document for an IRI.
+pub trait IRIResolver {
+    fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError>;
+}
+
+/// Resolve `file://` IRIs from the local file system.
+#[derive(Debug, Default)]
+pub struct FileResolver;
+
+impl IRIResolver for FileResolver {
+    fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+        match iri.strip_prefix("file://") {
+            Some(path) => Ok(std::fs::read(path)?),
+            None => Err(ResolveError::Unresolvable(iri.to_string()).into()),
+        }
+    }
+}
+
+/// Resolve IRIs over HTTP, following redirects.
+///
+/// `file://` IRIs are also supported.
+#[cfg(feature = "remote")]
+#[derive(Debug)]
+pub struct HTTPResolver {
+    pub max_redirections: u32,
+}
+
+#[cfg(feature = "remote")]
+impl Default for HTTPResolver {
+    fn default() -> HTTPResolver {
+        HTTPResolver {
+            max_redirections: 10,
+        }
+    }
+}
+
+#[cfg(feature = "remote")]
+impl IRIResolver for HTTPResolver {
+    fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+        let fetch = || -> Result<Vec<u8>, curl::Error> {
+            let mut data = Vec::new();
+            let mut handle = Easy::new();
+            handle.url(iri)?;
+            handle.follow_location(true)?;
+            handle.max_redirections(self.max_redirections)?;
+            handle.fail_on_error(true)?;
+
+            let mut headers = List::new();
+            headers.append(
+                "Accept: application/rdf+xml, application/owl+xml;q=0.9, */*;q=0.1",
+            )?;
+            handle.http_headers(headers)?;
+            {
+                let mut transfer = handle.transfer();
+                transfer.write_function(|new_data| {
+                    data.extend_from_slice(new_data);
+                    Ok(new_data.len())
+                })?;
+                transfer.perform()?;
+            }
+            Ok(data)
+        };
+
+        fetch().map_err(|e| ResolveError::Unresolvable(format!("{}: {}", iri, e)).into())
+    }
+}
+
+/// Cache the documents returned by another resolver, so that each IRI
+/// is only resolved once.
+#[derive(Debug, Default)]
+pub struct CachingResolver<R> {
+    resolver: R,
+    cache: HashMap<IRI, Vec<u8>>,
+}
+
+impl<R: IRIResolver> CachingResolver<R> {
+    pub fn new(resolver: R) -> CachingResolver<R> {
+        CachingResolver {
+            resolver,
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<R: IRIResolver> IRIResolver for CachingResolver<R> {
+    fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+        if let Some(data) = self.cache.get(iri) {
+            return Ok(data.clone());
+        }
+
+        let data = self.resolver.resolve(iri)?;
+        self.cache.insert(iri.clone(), data.clone());
+        Ok(data)
+    }
+}
+
+/// Resolve IRIs to local files using an OASIS XML catalog, such as
+/// the `catalog-v001.xml` files written by Protégé.
+///
+/// `uri` and `rewriteURI` entries are supported. IRIs which are not
+/// in the catalog are passed to `resolver`.
+#[derive(Debug)]
+pub struct CatalogResolver<R> {
+    uri: HashMap<String, PathBuf>,
+    rewrite: Vec<(String, PathBuf)>,
+    resolver: R,
+}
+
+impl<R: IRIResolver> CatalogResolver<R> {
+    /// Read the catalog at `path`; relative locations are resolved
+    /// against the directory containing it.
+    pub fn new(path: &Path, resolver: R) -> Result<CatalogResolver<R>, HornedError> {
+        let base = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        let mut bufread = BufReader::new(File::open(path)?);
+        CatalogResolver::from_bufread(&mut bufread, base, resolver)
+    }
+
+    pub fn from_bufread<B: BufRead>(
+        bufread: &mut B,
+        base: PathBuf,
+        resolver: R,
+    ) -> Result<CatalogResolver<R>, HornedError> {
+        let mut reader = Reader::from_reader(bufread);
+        let mut buf = Vec::new();
+        let mut bases = vec![base];
+        let mut uri = HashMap::new();
+        let mut rewrite = vec![];
+
+        loop {
+            let base = bases.last().cloned().unwrap_or_default();
+            match reader.read_event(&mut buf)? {
+                Event::Start(ref e) if e.local_name() == b"group" => {
+                    let b = match catalog_attrib(&reader, e, b"xml:base")? {
+                        Some(b) => base.join(b),
+                        None => base,
+                    };
+                    bases.push(b);
+                }
+                Event::End(ref e) if e.local_name() == b"group" => {
+                    bases.pop();
+                }
+                Event::Start(ref e) | Event::Empty(ref e) => match e.local_name() {
+                    b"uri" => {
+                        if let (Some(name), Some(loc)) = (
+                            catalog_attrib(&reader, e, b"name")?,
+                            catalog_attrib(&reader, e, b"uri")?,
+                        ) {
+                            uri.insert(name, base.join(loc));
+                        }
+                    }
+                    b"rewriteURI" => {
+                        if let (Some(start), Some(prefix)) = (
+                            catalog_attrib(&reader, e, b"uriStartString")?,
+                            catalog_attrib(&reader, e, b"rewritePrefix")?,
+                        ) {
+                            rewrite.push((start, base.join(prefix)));
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        // The longest matching start string wins
+        rewrite.sort_by_key(|r: &(String, PathBuf)| std::cmp::Reverse(r.0.len()));
+
+        Ok(CatalogResolver {
+            uri,
+            rewrite,
+            resolver,
+        })
+    }
+
+    /// Return the local file for `iri`, if the catalog has one.
+    pub fn local_path(&self, iri: &IRI) -> Option<PathBuf> {
+        self.uri.get(iri.as_str()).cloned().or_else(|| {
+            self.rewrite.iter().find_map(|(start, prefix)| {
+                iri.strip_prefix(start.as_str()).map(|rest| prefix.join(rest))
+            })
+        })
+    }
+}
+
+impl<R: IRIResolver> IRIResolver for CatalogResolver<R> {
+    fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+        match self.local_path(iri) {
+            Some(path) => Ok(std::fs::read(path)?),
+            None => self.resolver.resolve(iri),
+        }
+    }
+}
+
+fn catalog_attrib<B: BufRead>(
+    reader: &Reader<B>,
+    e: &BytesStart,
+    key: &[u8],
+) -> Result<Option<String>, HornedError> {
+    for attrib in e.attributes() {
+        let attrib = attrib?;
+        if attrib.key == key {
+            return Ok(Some(attrib.unescape_and_decode_value(reader)?));
+        }
+    }
+    Ok(None)
+}
+
+/// Return the Protégé catalog in `dir`, if there is one.
+pub fn find_catalog(dir: &Path) -> Option<PathBuf> {
+    let path = dir.join("catalog-v001.xml");
+    if path.exists() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Guess the syntax of a document from its opening bytes, if it is
+/// one that can be read.
+fn sniff_type(data: &[u8]) -> Option<ResourceType> {
+    sniff_any_type(data).filter(|rt| crate::io::parser(*rt).is_ok())
+}
+
+fn sniff_any_type(data: &[u8]) -> Option<ResourceType> {
+    let head = &data[..data.len().min(4096)];
+    let text = String::from_utf8_lossy(head);
+    if data.starts_with(crate::io::hob::MAGIC) {
+        Some(ResourceType::HOB)
+    } else if is_ntriples(&text) {
+        Some(ResourceType::NT)
+    } else if text.trim_start().starts_with('<') {
+        sniff_xml(head)
+    } else if text.contains("Ontology(") {
+        Some(ResourceType::OFN)
+    } else {
+        None
+    }
+}
+
+/// Return true if the first line of `text`, other than blank lines
+/// and comments, starts with an IRI or blank node and an IRI, as an
+/// N-Triples triple does.
+fn is_ntriples(text: &str) -> bool {
+    let iri = |t: &str| t.starts_with('<') && t.ends_with('>');
+    let mut terms = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .unwrap_or_default()
+        .split_whitespace();
+
+    match (terms.next(), terms.next()) {
+        (Some(s), Some(p)) => (iri(s) || s.starts_with("_:")) && iri(p),
+        _ => false,
+    }
+}
+
+/// Guess the syntax of an XML document from the namespace and local
+/// name of its root element, whatever prefix that uses.
+fn sniff_xml(head: &[u8]) -> Option<ResourceType> {
+    let mut reader = Reader::from_reader(head);
+    let mut buf = Vec::new();
+    let mut ns_buf = Vec::new();
+    loop {
+        match reader.read_namespaced_event(&mut buf, &mut ns_buf).ok()? {
+            (ns, Event::Start(ref e)) | (ns, Event::Empty(ref e)) => {
+                let ns = ns?;
+                return if ns == Namespace::RDF.iri_b() && e.local_name() == b"RDF" {
+                    Some(ResourceType::RDF)
+                } else if ns == Namespace::OWL.iri_b() && e.local_name() == b"Ontology" {
+                    Some(ResourceType::OWX)
+                } else {
+                    None
+                };
+            }
+            (_, Event::Eof) => return None,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Fetch and parse the document for `iri`.
+pub fn resolve_ontology(
+    iri: &IRI,
+    resolver: &mut dyn IRIResolver,
+) -> Result<SetOntology, HornedError> {
+    resolve_ontology_with_build(iri, resolver, &Build::new())
+}
+
+/// Fetch and parse the document for `iri`, creating IRIs with `b`.
+pub fn resolve_ontology_with_build(
+    iri: &IRI,
+    resolver: &mut dyn IRIResolver,
+    b: &Build,
+) -> Result<SetOntology, HornedError> {
+    let mut data = resolver.resolve(iri)?;
+    if is_gzip(&mut &data[..])? {
+        let mut plain = Vec::new();
+        GzDecoder::new(&data[..]).read_to_end(&mut plain)?;
+        data = plain;
+    }
+    let rt = sniff_type(&data).ok_or_else(|| ResolveError::UnknownFormat(iri.to_string()))?;
+    Ok(read_with_build(&mut Cursor::new(data), rt, b)?.into())
+}
+
+/// Return the imports closure of `ont`, not including `ont` itself.
+///
+/// Each import is resolved and parsed once, even if it is imported
+/// more than once.
+pub fn imports_closure<'a, O>(
+    ont: O,
+    resolver: &mut dyn IRIResolver,
+) -> Result<Vec<SetOntology>, HornedError>
+where
+    O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut done = HashSet::new();
+    let mut closure = vec![];
+    let mut todo = imports(ont);
+
+    while let Some(iri) = todo.pop() {
+        if !done.insert(iri.clone()) {
+            continue;
+        }
+
+        let imported = resolve_ontology(&iri, resolver)?;
+        if let Some(id) = &imported.id().iri {
+            done.insert(id.clone());
+        }
+
+        todo.extend(imports(&imported));
+        closure.push(imported);
+    }
+
+    Ok(closure)
+}
+
+fn imports<'a, O>(ont: O) -> Vec<IRI>
+where
+    O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut v: Vec<IRI> = ont
+        .into_iter()
+        .filter_map(|aa| match &aa.axiom {
+            Axiom::Import(i) => Some(i.0.clone()),
+            _ => None,
+        })
+        .collect();
+
+    // Pop from the end, so reverse to resolve in document order
+    v.reverse();
+    v
 }
 
 #[cfg(test)]
@@ -71,10 +442,15 @@ mod test{
         );
 
 
-        assert_eq!(localize_iri(&iri, &doc_iri), local);
+        assert_eq!(localize_iri(&iri, &doc_iri).unwrap(), local);
+        assert!(matches!(
+            localize_iri(&b.iri("urn:isbn:0451450523"), &doc_iri),
+            Err(HornedError::ResolveError(ResolveError::Unresolvable(_)))
+        ));
     }
 
     #[test]
+    #[cfg(feature = "remote")]
     fn simple_iri() {
         let dir_path_buf = PathBuf::from(file!());
         let dir = dir_path_buf.parent().unwrap();
@@ -84,10 +460,230 @@ mod test{
             format!("file://{}/ont/owl-rdf/and.owl", cdir.to_string_lossy())
         );
 
-        let s:String = String::from_utf8(strict_resolve_iri(&i)).unwrap();
+        let s:String = String::from_utf8(strict_resolve_iri(&i).unwrap()).unwrap();
 
         let ont_s = include_str!("./ont/owl-rdf/and.owl");
 
         assert_eq!(s, ont_s);
     }
+
+    #[derive(Default)]
+    struct MapResolver {
+        docs: HashMap<String, &'static str>,
+        count: usize,
+    }
+
+    impl IRIResolver for MapResolver {
+        fn resolve(&mut self, iri: &IRI) -> Result<Vec<u8>, HornedError> {
+            self.count += 1;
+            self.docs
+                .get(iri.as_str())
+                .map(|s| s.as_bytes().to_vec())
+                .ok_or_else(|| ResolveError::Unresolvable(iri.to_string()).into())
+        }
+    }
+
+    fn and_iri() -> IRI {
+        let dir_path_buf = PathBuf::from(file!());
+        let cdir = dir_path_buf.parent().unwrap().canonicalize().unwrap();
+        Build::new().iri(format!("file://{}/ont/owl-rdf/and.owl", cdir.to_string_lossy()))
+    }
+
+    #[test]
+    fn file_resolver() {
+        let data = FileResolver.resolve(&and_iri()).unwrap();
+        assert_eq!(data, include_bytes!("./ont/owl-rdf/and.owl").to_vec());
+
+        let b = Build::new();
+        assert!(FileResolver.resolve(&b.iri("http://www.example.com")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn http_resolver_file() {
+        let data = HTTPResolver::default().resolve(&and_iri()).unwrap();
+        assert_eq!(data, include_bytes!("./ont/owl-rdf/and.owl").to_vec());
+    }
+
+    #[test]
+    fn caching() {
+        let b = Build::new();
+        let mut m = MapResolver::default();
+        m.docs.insert("http://www.example.com/a".into(), "a");
+
+        let mut c = CachingResolver::new(m);
+        let a = b.iri("http://www.example.com/a");
+        assert_eq!(c.resolve(&a).unwrap(), b"a".to_vec());
+        assert_eq!(c.resolve(&a).unwrap(), b"a".to_vec());
+        assert_eq!(c.resolver.count, 1);
+    }
+
+    #[test]
+    fn closure() {
+        let mut m = MapResolver::default();
+        m.docs.insert(
+            "http://www.example.com/other-property".into(),
+            include_str!("./ont/owl-xml/other-property.owx"),
+        );
+
+        let ont: SetOntology = crate::io::owx::reader::read(
+            &mut include_str!("./ont/owl-xml/import-property.owx").as_bytes(),
+        )
+        .unwrap()
+        .0;
+
+        let closure = imports_closure(&ont, &mut m).unwrap();
+        assert_eq!(closure.len(), 1);
+        assert_eq!(
+            closure[0].id().iri,
+            Some(Build::new().iri("http://www.example.com/other-property"))
+        );
+    }
+
+    #[test]
+    fn closure_unresolvable() {
+        let ont: SetOntology = crate::io::owx::reader::read(
+            &mut include_str!("./ont/owl-xml/import.owx").as_bytes(),
+        )
+        .unwrap()
+        .0;
+
+        assert!(imports_closure(&ont, &mut MapResolver::default()).is_err());
+    }
+
+    struct BytesResolver(Vec<u8>);
+
+    impl IRIResolver for BytesResolver {
+        fn resolve(&mut self, _iri: &IRI) -> Result<Vec<u8>, HornedError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn sniff_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(include_bytes!("./ont/owl-xml/other-property.owx"))
+            .unwrap();
+        let mut r = BytesResolver(gz.finish().unwrap());
+
+        let iri = Build::new().iri("http://www.example.com/other-property");
+        let ont = resolve_ontology(&iri, &mut r).unwrap();
+        assert_eq!(ont.id().iri, Some(iri));
+    }
+
+    #[test]
+    fn sniff_xml_root() {
+        let rdf = r#"<?xml version="1.0"?>
+<!DOCTYPE rdf:RDF [<!ENTITY owl "http://www.w3.org/2002/07/owl#">]>
+<!-- a comment -->
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"/>"#;
+        assert_eq!(sniff_any_type(rdf.as_bytes()), Some(ResourceType::RDF));
+
+        let rdf = r#"<?xml version="1.0"?><RDF xmlns="http://www.w3.org/1999/02/22-rdf-syntax-ns#"></RDF>"#;
+        assert_eq!(sniff_any_type(rdf.as_bytes()), Some(ResourceType::RDF));
+
+        let rdf = r#"<r:RDF xmlns:r="http://www.w3.org/1999/02/22-rdf-syntax-ns#"></r:RDF>"#;
+        assert_eq!(sniff_any_type(rdf.as_bytes()), Some(ResourceType::RDF));
+
+        let owx = r#"<?xml version="1.0"?>
+<owl:Ontology xmlns:owl="http://www.w3.org/2002/07/owl#"></owl:Ontology>"#;
+        assert_eq!(sniff_any_type(owx.as_bytes()), Some(ResourceType::OWX));
+
+        assert_eq!(
+            sniff_any_type(include_bytes!("./ont/owl-xml/class.owx")),
+            Some(ResourceType::OWX)
+        );
+        assert_eq!(
+            sniff_any_type(include_bytes!("./ont/owl-rdf/class.owl")),
+            Some(ResourceType::RDF)
+        );
+
+        // Not an ontology, and not N-Triples either
+        let xml = r#"<?xml version="1.0"?><html xmlns="http://www.w3.org/1999/xhtml"/>"#;
+        assert_eq!(sniff_any_type(xml.as_bytes()), None);
+        assert_eq!(sniff_any_type(b"<Ontology/>"), None);
+    }
+
+    #[test]
+    fn sniff_ntriples() {
+        let nt = "# a comment\n\n<http://www.example.com/a> <http://www.example.com/p> _:b .\n";
+        assert_eq!(sniff_any_type(nt.as_bytes()), Some(ResourceType::NT));
+
+        let nt = "_:a <http://www.example.com/p> <http://www.example.com/b> .\n";
+        assert_eq!(sniff_any_type(nt.as_bytes()), Some(ResourceType::NT));
+    }
+
+    #[test]
+    fn sniff_unreadable() {
+        let mut r = BytesResolver(b"Ontology(<http://www.example.com/o>)".to_vec());
+        let iri = Build::new().iri("http://www.example.com/o");
+        assert!(matches!(
+            resolve_ontology(&iri, &mut r),
+            Err(HornedError::ResolveError(ResolveError::UnknownFormat(_)))
+        ));
+    }
+
+    fn catalog() -> CatalogResolver<MapResolver> {
+        let dir_path_buf = PathBuf::from(file!());
+        let dir = dir_path_buf.parent().unwrap().join("ont/catalog");
+        CatalogResolver::new(&find_catalog(&dir).unwrap(), MapResolver::default()).unwrap()
+    }
+
+    #[test]
+    fn catalog_uri() {
+        let b = Build::new();
+        let mut c = catalog();
+        let data = c.resolve(&b.iri("http://www.example.com/other-property")).unwrap();
+        assert_eq!(data, include_bytes!("./ont/owl-xml/other-property.owx").to_vec());
+    }
+
+    #[test]
+    fn catalog_rewrite() {
+        let b = Build::new();
+        let mut c = catalog();
+        let data = c.resolve(&b.iri("http://www.example.com/rdf/and.owl")).unwrap();
+        assert_eq!(data, include_bytes!("./ont/owl-rdf/and.owl").to_vec());
+    }
+
+    #[test]
+    fn catalog_escaped() {
+        let xml = r#"<catalog xmlns="urn:oasis:names:tc:entity:xmlns:xml:catalog">
+  <uri name="http://www.example.com/o?a=1&amp;b=2" uri="o&amp;p.owx"/>
+</catalog>"#;
+        let c = CatalogResolver::from_bufread(
+            &mut xml.as_bytes(),
+            PathBuf::from("dir"),
+            MapResolver::default(),
+        )
+        .unwrap();
+
+        let b = Build::new();
+        assert_eq!(
+            c.local_path(&b.iri("http://www.example.com/o?a=1&b=2")),
+            Some(PathBuf::from("dir/o&p.owx"))
+        );
+    }
+
+    #[test]
+    fn catalog_fallback() {
+        let b = Build::new();
+        let mut c = catalog();
+        assert!(c.resolve(&b.iri("http://www.example.com/missing")).is_err());
+        assert_eq!(c.resolver.count, 1);
+    }
+
+    #[test]
+    fn catalog_closure() {
+        let ont: SetOntology = crate::io::owx::reader::read(
+            &mut include_str!("./ont/owl-xml/import-property.owx").as_bytes(),
+        )
+        .unwrap()
+        .0;
+
+        let closure = imports_closure(&ont, &mut catalog()).unwrap();
+        assert_eq!(closure.len(), 1);
+    }
 }
@@ -0,0 +1,282 @@
+//! Search for entities by IRI or label
+//!
+//! An `EntitySearch` holds the IRIs of the entities of an ontology,
+//! together with their labels, and finds those matching a query by
+//! prefix, substring or edit distance, as is needed for autocomplete.
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::label_mapped::LabelMappedIndex;
+use crate::signature::Signature;
+use crate::vocab::{AnnotationBuiltIn, WithIRI};
+
+use std::collections::BTreeMap;
+
+/// How a query must match the text of an entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MatchMode {
+    /// The text starts with the query.
+    Prefix,
+    /// The text contains the query.
+    Substring,
+    /// The text is within this edit distance of the query, or
+    /// contains it.
+    Fuzzy(usize),
+}
+
+/// Options which control a search.
+#[derive(Clone, Debug)]
+pub struct SearchOptions {
+    pub mode: MatchMode,
+    pub case_insensitive: bool,
+    /// Order results by edit distance from the query, rather than
+    /// by IRI.
+    pub ranked: bool,
+    pub limit: Option<usize>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> SearchOptions {
+        SearchOptions {
+            mode: MatchMode::Substring,
+            case_insensitive: true,
+            ranked: true,
+            limit: None,
+        }
+    }
+}
+
+/// An entity which matches a query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResult<'a> {
+    pub iri: &'a IRI,
+    /// The IRI or label which matched.
+    pub text: &'a str,
+    /// The edit distance between the query and `text`.
+    pub distance: usize,
+}
+
+/// The IRIs and labels of a set of entities.
+#[derive(Debug, Default)]
+pub struct EntitySearch {
+    entries: Vec<(String, IRI)>,
+}
+
+impl EntitySearch {
+    pub fn new() -> EntitySearch {
+        EntitySearch::default()
+    }
+
+    /// Add `text`, which is an IRI or label of `iri`.
+    pub fn add(&mut self, text: String, iri: IRI) {
+        self.entries.push((text, iri));
+    }
+
+    /// Create a search over the entities of `ont` and their
+    /// `rdfs:label`s.
+    pub fn from_ontology(ont: &AxiomMappedOntology) -> EntitySearch {
+        let mut s = EntitySearch::new();
+        for e in ont.signature() {
+            s.add(e.iri().to_string(), e.iri().clone());
+        }
+
+        let label = AnnotationBuiltIn::LABEL.iri_str();
+        for aa in ont.i().annotation_assertion() {
+            if let AnnotationValue::Literal(l) = &aa.ann.av {
+                if aa.ann.ap.0.as_str() == label {
+                    s.add(l.literal().clone(), aa.subject.clone());
+                }
+            }
+        }
+        s
+    }
+
+    /// Add the labels in `index`.
+    pub fn add_labels(&mut self, index: &LabelMappedIndex) {
+        for (label, iris) in index.labels() {
+            for iri in iris {
+                self.add(label.clone(), iri.clone());
+            }
+        }
+    }
+
+    /// Return the entities which have an IRI or label matching
+    /// `query`, with the best matching text for each.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::search::{EntitySearch, SearchOptions};
+    /// let b = Build::new();
+    /// let mut s = EntitySearch::new();
+    /// s.add("Apoptosis".to_string(), b.iri("http://www.example.com/a"));
+    ///
+    /// let r = s.search("apop", &SearchOptions::default());
+    /// assert_eq!(r[0].text, "Apoptosis");
+    /// ```
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Vec<SearchResult<'_>> {
+        let fold = |s: &str| {
+            if options.case_insensitive {
+                s.to_lowercase()
+            } else {
+                s.to_string()
+            }
+        };
+        let query = fold(query);
+
+        let mut best: BTreeMap<&IRI, SearchResult<'_>> = BTreeMap::new();
+        for (text, iri) in &self.entries {
+            let folded = fold(text);
+            let matched = match options.mode {
+                MatchMode::Prefix => folded.starts_with(&query),
+                MatchMode::Substring => folded.contains(&query),
+                MatchMode::Fuzzy(max) => {
+                    folded.contains(&query) || levenshtein(&query, &folded) <= max
+                }
+            };
+            if !matched {
+                continue;
+            }
+
+            let result = SearchResult {
+                iri,
+                text,
+                distance: levenshtein(&query, &folded),
+            };
+            match best.get(iri) {
+                Some(r) if r.distance <= result.distance => {}
+                _ => {
+                    best.insert(iri, result);
+                }
+            }
+        }
+
+        let mut results: Vec<_> = best.into_values().collect();
+        if options.ranked {
+            // Stable, so ties stay in IRI order
+            results.sort_by_key(|r| r.distance);
+        }
+        if let Some(limit) = options.limit {
+            results.truncate(limit);
+        }
+        results
+    }
+}
+
+/// The number of single character insertions, deletions and
+/// substitutions needed to change `a` into `b`.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::test::read_ok;
+
+    fn search() -> (Build, EntitySearch) {
+        let b = Build::new();
+        let mut s = EntitySearch::new();
+        s.add(
+            "http://www.example.com/apoptosis".to_string(),
+            b.iri("http://www.example.com/apoptosis"),
+        );
+        s.add(
+            "Apoptosis".to_string(),
+            b.iri("http://www.example.com/apoptosis"),
+        );
+        s.add(
+            "cell death".to_string(),
+            b.iri("http://www.example.com/death"),
+        );
+        s.add(
+            "Cell Cycle".to_string(),
+            b.iri("http://www.example.com/cycle"),
+        );
+        (b, s)
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn prefix() {
+        let (b, s) = search();
+        let options = SearchOptions {
+            mode: MatchMode::Prefix,
+            ..Default::default()
+        };
+
+        let r = s.search("cell", &options);
+        assert_eq!(r.len(), 2);
+        assert_eq!(s.search("death", &options).len(), 0);
+
+        let r = s.search("http://www.example.com/apop", &options);
+        assert_eq!(r[0].iri, &b.iri("http://www.example.com/apoptosis"));
+    }
+
+    #[test]
+    fn substring() {
+        let (_, s) = search();
+        assert_eq!(s.search("death", &SearchOptions::default()).len(), 1);
+
+        // One result for each entity
+        let r = s.search("apoptosis", &SearchOptions::default());
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].text, "Apoptosis");
+
+        let options = SearchOptions {
+            case_insensitive: false,
+            ..Default::default()
+        };
+        assert_eq!(s.search("Cell", &options).len(), 1);
+    }
+
+    #[test]
+    fn fuzzy() {
+        let (b, s) = search();
+        let options = SearchOptions {
+            mode: MatchMode::Fuzzy(2),
+            ..Default::default()
+        };
+
+        let r = s.search("apoptsis", &options);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].distance, 1);
+
+        let r = s.search("cell dath", &options);
+        assert_eq!(r[0].iri, &b.iri("http://www.example.com/death"));
+
+        let options = SearchOptions {
+            limit: Some(1),
+            ..options
+        };
+        assert_eq!(s.search("cell", &options).len(), 1);
+    }
+
+    #[test]
+    fn ontology() {
+        let (ont, _) = read_ok(&mut include_str!("ont/owl-xml/label.owx").as_bytes());
+        let s = EntitySearch::from_ontology(&ont);
+        assert!(!s.search("example", &SearchOptions::default()).is_empty());
+    }
+}
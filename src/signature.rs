@@ -0,0 +1,643 @@
+//! The signature of an ontology or its parts.
+//!
+//! The signature is the set of all `NamedEntity` values which are
+//! referenced, including those which are only declared. IRIs which
+//! are not entities, such as the subject of an annotation assertion,
+//! are not part of the signature, and nor are anonymous individuals,
+//! whose IRIs are of the form `_:id`.
+//!
+//! An IRI may appear in the signature as more than one kind of
+//! entity, which is known as punning.
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::set::{SetIndex, SetOntology};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Access to the named entities used by an entity.
+pub trait Signature {
+    /// Add the named entities used by `self` to `sig`.
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>);
+
+    /// Return the named entities used by `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::signature::Signature;
+    /// let b = Build::new();
+    /// let ax: Axiom = SubClassOf {
+    ///     sup: b.class("http://www.example.com/B").into(),
+    ///     sub: b.class("http://www.example.com/A").into(),
+    /// }.into();
+    ///
+    /// assert_eq!(ax.signature().len(), 2);
+    /// ```
+    fn signature(&self) -> BTreeSet<NamedEntity> {
+        let mut sig = BTreeSet::new();
+        self.signature_into(&mut sig);
+        sig
+    }
+}
+
+macro_rules! signature_named {
+    ($($name:ident),*) => {
+        $(
+            impl Signature for $name {
+                fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+                    sig.insert(self.clone().into());
+                }
+            }
+        )*
+    }
+}
+
+signature_named! {
+    Class, Datatype, ObjectProperty, DataProperty, AnnotationProperty
+}
+
+impl Signature for NamedIndividual {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        if self.0.node_id().is_none() {
+            sig.insert(self.clone().into());
+        }
+    }
+}
+
+impl<T: Signature> Signature for Vec<T> {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        for t in self {
+            t.signature_into(sig);
+        }
+    }
+}
+
+impl<T: Signature> Signature for BTreeSet<T> {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        for t in self {
+            t.signature_into(sig);
+        }
+    }
+}
+
+impl<T: Signature> Signature for Box<T> {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        (**self).signature_into(sig);
+    }
+}
+
+impl Signature for Literal {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        if let Literal::Datatype { datatype_iri, .. } = self {
+            sig.insert(Datatype(datatype_iri.clone()).into());
+        }
+    }
+}
+
+impl Signature for AnnotationValue {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        if let AnnotationValue::Literal(l) = self {
+            l.signature_into(sig);
+        }
+    }
+}
+
+impl Signature for Annotation {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        self.ap.signature_into(sig);
+        self.av.signature_into(sig);
+    }
+}
+
+impl Signature for ObjectPropertyExpression {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op)
+            | ObjectPropertyExpression::InverseObjectProperty(op) => op.signature_into(sig),
+        }
+    }
+}
+
+impl Signature for SubObjectPropertyExpression {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            SubObjectPropertyExpression::ObjectPropertyChain(v) => v.signature_into(sig),
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => ope.signature_into(sig),
+        }
+    }
+}
+
+impl Signature for PropertyExpression {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            PropertyExpression::ObjectPropertyExpression(ope) => ope.signature_into(sig),
+            PropertyExpression::DataProperty(dp) => dp.signature_into(sig),
+            PropertyExpression::AnnotationProperty(ap) => ap.signature_into(sig),
+        }
+    }
+}
+
+impl Signature for FacetRestriction {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        self.l.signature_into(sig);
+    }
+}
+
+impl Signature for DataRange {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            DataRange::Datatype(dt) => dt.signature_into(sig),
+            DataRange::DataIntersectionOf(v) | DataRange::DataUnionOf(v) => v.signature_into(sig),
+            DataRange::DataComplementOf(dr) => dr.signature_into(sig),
+            DataRange::DataOneOf(v) => v.signature_into(sig),
+            DataRange::DatatypeRestriction(dt, v) => {
+                dt.signature_into(sig);
+                v.signature_into(sig);
+            }
+        }
+    }
+}
+
+impl Signature for ClassExpression {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            ClassExpression::Class(c) => c.signature_into(sig),
+            ClassExpression::ObjectIntersectionOf(v) | ClassExpression::ObjectUnionOf(v) => {
+                v.signature_into(sig)
+            }
+            ClassExpression::ObjectComplementOf(bce) => bce.signature_into(sig),
+            ClassExpression::ObjectOneOf(v) => v.signature_into(sig),
+            ClassExpression::ObjectSomeValuesFrom { ope, bce }
+            | ClassExpression::ObjectAllValuesFrom { ope, bce }
+            | ClassExpression::ObjectMinCardinality { ope, bce, .. }
+            | ClassExpression::ObjectMaxCardinality { ope, bce, .. }
+            | ClassExpression::ObjectExactCardinality { ope, bce, .. } => {
+                ope.signature_into(sig);
+                bce.signature_into(sig);
+            }
+            ClassExpression::ObjectHasValue { ope, i } => {
+                ope.signature_into(sig);
+                i.signature_into(sig);
+            }
+            ClassExpression::ObjectHasSelf(ope) => ope.signature_into(sig),
+            ClassExpression::DataSomeValuesFrom { dp, dr }
+            | ClassExpression::DataAllValuesFrom { dp, dr }
+            | ClassExpression::DataMinCardinality { dp, dr, .. }
+            | ClassExpression::DataMaxCardinality { dp, dr, .. }
+            | ClassExpression::DataExactCardinality { dp, dr, .. } => {
+                dp.signature_into(sig);
+                dr.signature_into(sig);
+            }
+            ClassExpression::DataHasValue { dp, l } => {
+                dp.signature_into(sig);
+                l.signature_into(sig);
+            }
+        }
+    }
+}
+
+/// Implement `Signature` for an axiom struct from the signature of
+/// some of its fields.
+macro_rules! signature {
+    ($type:ty, $self:ident, [$($part:expr),*]) => {
+        impl Signature for $type {
+            fn signature_into(&$self, sig: &mut BTreeSet<NamedEntity>) {
+                $($part.signature_into(sig);)*
+            }
+        }
+    }
+}
+
+signature!(OntologyAnnotation, self, [self.0]);
+signature!(DeclareClass, self, [self.0]);
+signature!(DeclareObjectProperty, self, [self.0]);
+signature!(DeclareAnnotationProperty, self, [self.0]);
+signature!(DeclareDataProperty, self, [self.0]);
+signature!(DeclareNamedIndividual, self, [self.0]);
+signature!(DeclareDatatype, self, [self.0]);
+signature!(SubClassOf, self, [self.sub, self.sup]);
+signature!(EquivalentClasses, self, [self.0]);
+signature!(DisjointClasses, self, [self.0]);
+signature!(DisjointUnion, self, [self.0, self.1]);
+signature!(SubObjectPropertyOf, self, [self.sub, self.sup]);
+signature!(EquivalentObjectProperties, self, [self.0]);
+signature!(DisjointObjectProperties, self, [self.0]);
+signature!(InverseObjectProperties, self, [self.0, self.1]);
+signature!(ObjectPropertyDomain, self, [self.ope, self.ce]);
+signature!(ObjectPropertyRange, self, [self.ope, self.ce]);
+signature!(FunctionalObjectProperty, self, [self.0]);
+signature!(InverseFunctionalObjectProperty, self, [self.0]);
+signature!(ReflexiveObjectProperty, self, [self.0]);
+signature!(IrreflexiveObjectProperty, self, [self.0]);
+signature!(SymmetricObjectProperty, self, [self.0]);
+signature!(AsymmetricObjectProperty, self, [self.0]);
+signature!(TransitiveObjectProperty, self, [self.0]);
+signature!(SubDataPropertyOf, self, [self.sub, self.sup]);
+signature!(EquivalentDataProperties, self, [self.0]);
+signature!(DisjointDataProperties, self, [self.0]);
+signature!(DataPropertyDomain, self, [self.dp, self.ce]);
+signature!(DataPropertyRange, self, [self.dp, self.dr]);
+signature!(FunctionalDataProperty, self, [self.0]);
+signature!(DatatypeDefinition, self, [self.kind, self.range]);
+signature!(HasKey, self, [self.ce, self.vpe]);
+signature!(SameIndividual, self, [self.0]);
+signature!(DifferentIndividuals, self, [self.0]);
+signature!(ClassAssertion, self, [self.ce, self.i]);
+signature!(
+    ObjectPropertyAssertion,
+    self,
+    [self.ope, self.from, self.to]
+);
+signature!(
+    NegativeObjectPropertyAssertion,
+    self,
+    [self.ope, self.from, self.to]
+);
+signature!(DataPropertyAssertion, self, [self.dp, self.from, self.to]);
+signature!(
+    NegativeDataPropertyAssertion,
+    self,
+    [self.dp, self.from, self.to]
+);
+signature!(AnnotationAssertion, self, [self.ann]);
+signature!(SubAnnotationPropertyOf, self, [self.sub, self.sup]);
+signature!(AnnotationPropertyDomain, self, [self.ap]);
+signature!(AnnotationPropertyRange, self, [self.ap]);
+
+impl Signature for Import {
+    fn signature_into(&self, _: &mut BTreeSet<NamedEntity>) {}
+}
+
+impl Signature for Axiom {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        match self {
+            Axiom::OntologyAnnotation(ax) => ax.signature_into(sig),
+            Axiom::Import(ax) => ax.signature_into(sig),
+            Axiom::DeclareClass(ax) => ax.signature_into(sig),
+            Axiom::DeclareObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::DeclareAnnotationProperty(ax) => ax.signature_into(sig),
+            Axiom::DeclareDataProperty(ax) => ax.signature_into(sig),
+            Axiom::DeclareNamedIndividual(ax) => ax.signature_into(sig),
+            Axiom::DeclareDatatype(ax) => ax.signature_into(sig),
+            Axiom::SubClassOf(ax) => ax.signature_into(sig),
+            Axiom::EquivalentClasses(ax) => ax.signature_into(sig),
+            Axiom::DisjointClasses(ax) => ax.signature_into(sig),
+            Axiom::DisjointUnion(ax) => ax.signature_into(sig),
+            Axiom::SubObjectPropertyOf(ax) => ax.signature_into(sig),
+            Axiom::EquivalentObjectProperties(ax) => ax.signature_into(sig),
+            Axiom::DisjointObjectProperties(ax) => ax.signature_into(sig),
+            Axiom::InverseObjectProperties(ax) => ax.signature_into(sig),
+            Axiom::ObjectPropertyDomain(ax) => ax.signature_into(sig),
+            Axiom::ObjectPropertyRange(ax) => ax.signature_into(sig),
+            Axiom::FunctionalObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::InverseFunctionalObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::ReflexiveObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::IrreflexiveObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::SymmetricObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::AsymmetricObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::TransitiveObjectProperty(ax) => ax.signature_into(sig),
+            Axiom::SubDataPropertyOf(ax) => ax.signature_into(sig),
+            Axiom::EquivalentDataProperties(ax) => ax.signature_into(sig),
+            Axiom::DisjointDataProperties(ax) => ax.signature_into(sig),
+            Axiom::DataPropertyDomain(ax) => ax.signature_into(sig),
+            Axiom::DataPropertyRange(ax) => ax.signature_into(sig),
+            Axiom::FunctionalDataProperty(ax) => ax.signature_into(sig),
+            Axiom::DatatypeDefinition(ax) => ax.signature_into(sig),
+            Axiom::HasKey(ax) => ax.signature_into(sig),
+            Axiom::SameIndividual(ax) => ax.signature_into(sig),
+            Axiom::DifferentIndividuals(ax) => ax.signature_into(sig),
+            Axiom::ClassAssertion(ax) => ax.signature_into(sig),
+            Axiom::ObjectPropertyAssertion(ax) => ax.signature_into(sig),
+            Axiom::NegativeObjectPropertyAssertion(ax) => ax.signature_into(sig),
+            Axiom::DataPropertyAssertion(ax) => ax.signature_into(sig),
+            Axiom::NegativeDataPropertyAssertion(ax) => ax.signature_into(sig),
+            Axiom::AnnotationAssertion(ax) => ax.signature_into(sig),
+            Axiom::SubAnnotationPropertyOf(ax) => ax.signature_into(sig),
+            Axiom::AnnotationPropertyDomain(ax) => ax.signature_into(sig),
+            Axiom::AnnotationPropertyRange(ax) => ax.signature_into(sig),
+        }
+    }
+}
+
+impl Signature for AnnotatedAxiom {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        self.axiom.signature_into(sig);
+        self.ann.signature_into(sig);
+    }
+}
+
+/// Add the signatures of `axioms` to `sig`, in parallel.
+#[cfg(feature = "rayon")]
+fn par_signature_into<'a, I>(axioms: I, sig: &mut BTreeSet<NamedEntity>)
+where
+    I: rayon::iter::ParallelIterator<Item = &'a AnnotatedAxiom>,
+{
+    use rayon::prelude::*;
+    let mut par = axioms
+        .fold(BTreeSet::new, |mut s, ax| {
+            ax.signature_into(&mut s);
+            s
+        })
+        .reduce(BTreeSet::new, |mut a, mut b| {
+            if a.len() < b.len() {
+                std::mem::swap(&mut a, &mut b);
+            }
+            a.append(&mut b);
+            a
+        });
+    sig.append(&mut par);
+}
+
+impl Signature for SetOntology {
+    #[cfg(feature = "rayon")]
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        par_signature_into(self.par_iter(), sig)
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        for ax in self.iter() {
+            ax.signature_into(sig);
+        }
+    }
+}
+
+impl Signature for AxiomMappedOntology {
+    #[cfg(feature = "rayon")]
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        par_signature_into(self.i().par_iter(), sig)
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        for ax in self.i().iter() {
+            ax.signature_into(sig);
+        }
+    }
+}
+
+impl Signature for SetIndex {
+    fn signature_into(&self, sig: &mut BTreeSet<NamedEntity>) {
+        for ax in self {
+            ax.signature_into(sig);
+        }
+    }
+}
+
+/// How `subset_for_signature` selects axioms.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SubsetMode {
+    /// Select axioms which use only the given entities.
+    #[default]
+    Contained,
+    /// Select axioms which use any of the given entities.
+    Intersects,
+}
+
+fn in_subset(ax: &AnnotatedAxiom, entities: &BTreeSet<&NamedEntity>, mode: SubsetMode) -> bool {
+    // An annotation assertion is selected by its subject, so that
+    // the annotations of the selected entities are kept
+    if let Axiom::AnnotationAssertion(aa) = &ax.axiom {
+        return entities.iter().any(|e| e.iri() == &aa.subject);
+    }
+
+    let sig = ax.axiom.signature();
+    match mode {
+        SubsetMode::Contained => sig.iter().all(|e| entities.contains(e)),
+        SubsetMode::Intersects => sig.iter().any(|e| entities.contains(e)),
+    }
+}
+
+fn subset<'a, I>(
+    id: &OntologyID,
+    axioms: I,
+    entities: &[NamedEntity],
+    mode: SubsetMode,
+) -> SetOntology
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let entities: BTreeSet<&NamedEntity> = entities.iter().collect();
+    let selected = axioms
+        .into_iter()
+        .filter(|ax| in_subset(ax, &entities, mode))
+        .cloned();
+    (id.clone(), selected).into()
+}
+
+/// Select the axioms of an ontology by their signature.
+///
+/// This is a purely syntactic slice, which is much cheaper than
+/// extracting a module, but need not preserve any entailments. Only
+/// the signature of the axiom itself is used, not that of its
+/// annotations. An axiom with an empty signature, such as an import,
+/// is only contained in any set of entities.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::set::SetOntology;
+/// use horned_owl::signature::{SignatureSubset, SubsetMode};
+///
+/// let b = Build::new();
+/// let mut o = SetOntology::new();
+/// o.declare(b.class("http://www.example.com/A"));
+/// o.insert(SubClassOf::new(
+///     b.class("http://www.example.com/B"),
+///     b.class("http://www.example.com/A"),
+/// ));
+///
+/// let a: NamedEntity = b.class("http://www.example.com/A").into();
+/// let contained = o.subset_for_signature(&[a.clone()], SubsetMode::Contained);
+/// assert_eq!(contained.iter().count(), 1);
+/// let intersects = o.subset_for_signature(&[a], SubsetMode::Intersects);
+/// assert_eq!(intersects.iter().count(), 2);
+/// ```
+pub trait SignatureSubset {
+    /// Return the axioms whose signature is contained in, or
+    /// intersects, `entities`, as given by `mode`.
+    fn subset_for_signature(&self, entities: &[NamedEntity], mode: SubsetMode) -> SetOntology;
+}
+
+impl SignatureSubset for SetOntology {
+    fn subset_for_signature(&self, entities: &[NamedEntity], mode: SubsetMode) -> SetOntology {
+        subset(self.id(), self.iter(), entities, mode)
+    }
+}
+
+impl SignatureSubset for AxiomMappedOntology {
+    fn subset_for_signature(&self, entities: &[NamedEntity], mode: SubsetMode) -> SetOntology {
+        subset(self.id(), self.i().iter(), entities, mode)
+    }
+}
+
+/// Return the entities declared by `axioms`.
+pub fn declared<'a, I>(axioms: I) -> BTreeSet<NamedEntity>
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    axioms
+        .into_iter()
+        .filter_map(|ax| match &ax.axiom {
+            Axiom::DeclareClass(DeclareClass(c)) => Some(c.clone().into()),
+            Axiom::DeclareObjectProperty(DeclareObjectProperty(op)) => Some(op.clone().into()),
+            Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => {
+                Some(ap.clone().into())
+            }
+            Axiom::DeclareDataProperty(DeclareDataProperty(dp)) => Some(dp.clone().into()),
+            Axiom::DeclareNamedIndividual(DeclareNamedIndividual(i)) => Some(i.clone().into()),
+            Axiom::DeclareDatatype(DeclareDatatype(dt)) => Some(dt.clone().into()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Return the IRIs which are used as more than one kind of entity in
+/// `sig`, with the kinds that each is used as.
+pub fn punned(sig: &BTreeSet<NamedEntity>) -> BTreeMap<IRI, BTreeSet<NamedEntityKind>> {
+    let mut kinds: BTreeMap<IRI, BTreeSet<NamedEntityKind>> = BTreeMap::new();
+    for e in sig {
+        kinds.entry(e.iri().clone()).or_default().insert(e.kind());
+    }
+
+    kinds.retain(|_, k| k.len() > 1);
+    kinds
+}
+
+/// Returns true if an IRI may be used as all of `kinds` in OWL 2 DL.
+///
+/// OWL 2 allows an IRI to be, for example, both a class and an
+/// individual, but not both a class and a datatype, nor more than one
+/// kind of property.
+pub fn is_legal_punning(kinds: &BTreeSet<NamedEntityKind>) -> bool {
+    use NamedEntityKind::*;
+    let properties = [ObjectProperty, DataProperty, AnnotationProperty]
+        .iter()
+        .filter(|k| kinds.contains(k))
+        .count();
+
+    properties <= 1 && !(kinds.contains(&Class) && kinds.contains(&Datatype))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::io::owx::reader::test::read_ok;
+
+    #[test]
+    fn declaration() {
+        let (ont, _) = read_ok(&mut include_str!("ont/owl-xml/class.owx").as_bytes());
+        let b = Build::new();
+
+        let sig = ont.signature();
+        assert_eq!(sig.len(), 1);
+        assert!(sig.contains(&b.class("http://www.example.com/iri#C").into()));
+    }
+
+    #[test]
+    fn nested() {
+        let b = Build::new();
+        let ax: AnnotatedAxiom = SubClassOf {
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::InverseObjectProperty(
+                    b.object_property("http://www.example.com/r"),
+                ),
+                bce: ClassExpression::DataHasValue {
+                    dp: b.data_property("http://www.example.com/d"),
+                    l: Literal::Datatype {
+                        literal: "1".into(),
+                        datatype_iri: b.iri("http://www.example.com/dt"),
+                    },
+                }
+                .into(),
+            },
+            sub: b.class("http://www.example.com/A").into(),
+        }
+        .into();
+
+        let sig = ax.signature();
+        assert_eq!(sig.len(), 4);
+        assert!(sig.contains(&b.object_property("http://www.example.com/r").into()));
+        assert!(sig.contains(&b.data_property("http://www.example.com/d").into()));
+        assert!(sig.contains(&b.datatype("http://www.example.com/dt").into()));
+    }
+
+    #[test]
+    fn anonymous_individual() {
+        let b = Build::new();
+        let ax: Axiom = ObjectPropertyAssertion {
+            ope: b.object_property("http://www.example.com/r").into(),
+            from: b.named_individual("http://www.example.com/i"),
+            to: b.anonymous_individual("x"),
+        }
+        .into();
+
+        let sig = ax.signature();
+        assert_eq!(sig.len(), 2);
+        assert!(!sig.contains(&b.anonymous_individual("x").into()));
+    }
+
+    #[test]
+    fn annotation_subject() {
+        let b = Build::new();
+        let ax: Axiom = AnnotationAssertion {
+            subject: b.iri("http://www.example.com/A"),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.example.com/p"),
+                av: b.iri("http://www.example.com/B").into(),
+            },
+        }
+        .into();
+
+        let sig = ax.signature();
+        assert_eq!(sig.len(), 1);
+        assert!(sig.contains(&b.annotation_property("http://www.example.com/p").into()));
+    }
+
+    #[test]
+    fn subset_for_signature() {
+        let b = Build::new();
+        let (ont, _) = read_ok(&mut include_str!("ont/owl-xml/family.owx").as_bytes());
+        let person: NamedEntity = b.class("http://example.com/owl/families/Person").into();
+
+        let sig = [person.clone()];
+        let contained = ont.subset_for_signature(&sig, SubsetMode::Contained);
+        let intersects = ont.subset_for_signature(&sig, SubsetMode::Intersects);
+        assert_eq!(contained.id(), ont.id());
+        assert!(contained.iter().count() < intersects.iter().count());
+
+        for ax in contained.iter() {
+            if ax.kind() != AxiomKind::AnnotationAssertion {
+                assert!(ax.axiom.signature().iter().all(|e| e == &person));
+            }
+        }
+        for ax in intersects.iter() {
+            if ax.kind() != AxiomKind::AnnotationAssertion {
+                assert!(ax.axiom.signature().contains(&person));
+            }
+        }
+    }
+
+    #[test]
+    fn punning() {
+        let b = Build::new();
+        let mut o = SetOntology::new();
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.named_individual("http://www.example.com/a"));
+        o.declare(b.class("http://www.example.com/b"));
+
+        let p = punned(&o.signature());
+        assert_eq!(p.len(), 1);
+
+        let kinds = &p[&b.iri("http://www.example.com/a")];
+        assert!(kinds.contains(&NamedEntityKind::NamedIndividual));
+        assert!(is_legal_punning(kinds));
+
+        o.declare(b.object_property("http://www.example.com/b"));
+        o.declare(b.data_property("http://www.example.com/b"));
+        assert!(!is_legal_punning(
+            &punned(&o.signature())[&b.iri("http://www.example.com/b")]
+        ));
+    }
+}
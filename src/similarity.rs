@@ -0,0 +1,197 @@
+//! Semantic similarity between classes
+//!
+//! The similarity of two classes can be measured by the classes they
+//! have in common in a `ClassHierarchy`, either by their shared
+//! ancestors alone, as with `jaccard`, or by the information content
+//! of their most informative common ancestor, as with the measures
+//! of Resnik and Lin.
+//!
+//! The information content of a class is `-ln p`, where `p` is the
+//! probability that a term, drawn according to the frequencies, is
+//! the class or one of its sub classes. Without frequencies, such as
+//! the number of genes annotated to each term, every class counts
+//! once, which gives an intrinsic measure from the hierarchy alone.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! use horned_owl::hierarchy::ClassHierarchy;
+//! use horned_owl::similarity::{jaccard, Similarity};
+//!
+//! let b = Build::new();
+//! let animal = b.class("http://www.example.com/animal");
+//! let cat = b.class("http://www.example.com/cat");
+//! let dog = b.class("http://www.example.com/dog");
+//! let mut h = ClassHierarchy::new();
+//! h.add_edge(cat.clone(), animal.clone());
+//! h.add_edge(dog.clone(), animal.clone());
+//!
+//! let s = Similarity::new(&h);
+//! assert_eq!(s.resnik(&cat, &dog), 0.0);
+//! assert_eq!(s.lin(&cat, &cat), 1.0);
+//! assert_eq!(jaccard(&h, &cat, &dog), 1.0 / 3.0);
+//! ```
+use crate::hierarchy::ClassHierarchy;
+use crate::model::*;
+
+use std::collections::BTreeMap;
+
+/// The information content of the classes of a hierarchy, from
+/// which the Resnik and Lin similarities are computed.
+#[derive(Clone, Debug)]
+pub struct Similarity<'a> {
+    h: &'a ClassHierarchy,
+    ic: BTreeMap<&'a Class, f64>,
+}
+
+impl<'a> Similarity<'a> {
+    /// Compute the intrinsic information content of the classes of
+    /// `h`, counting each class once.
+    pub fn new(h: &'a ClassHierarchy) -> Similarity<'a> {
+        let frequencies = h.classes().map(|c| (c.clone(), 1)).collect();
+        Similarity::with_frequencies(h, &frequencies)
+    }
+
+    /// Compute the information content of the classes of `h` from
+    /// the number of times that each is used, such as the number of
+    /// annotations to it. Classes which are not in `frequencies` are
+    /// used no times.
+    pub fn with_frequencies(
+        h: &'a ClassHierarchy,
+        frequencies: &BTreeMap<Class, usize>,
+    ) -> Similarity<'a> {
+        let frequency = |c: &Class| frequencies.get(c).copied().unwrap_or(0);
+        let total: usize = h.classes().map(frequency).sum();
+
+        let ic = h
+            .classes()
+            .filter_map(|c| {
+                let count: usize = h.descendants(c).into_iter().map(frequency).sum();
+                if count == 0 {
+                    None
+                } else {
+                    Some((c, -(count as f64 / total as f64).ln()))
+                }
+            })
+            .collect();
+
+        Similarity { h, ic }
+    }
+
+    /// Return the information content of `c`, or `None` if neither it
+    /// nor any of its sub classes is used.
+    pub fn information_content(&self, c: &Class) -> Option<f64> {
+        self.ic.get(c).copied()
+    }
+
+    /// Return the Resnik similarity of `a` and `b`, the greatest
+    /// information content of any of their common ancestors.
+    pub fn resnik(&self, a: &Class, b: &Class) -> f64 {
+        self.h
+            .common_ancestors(a, b)
+            .into_iter()
+            .filter_map(|c| self.information_content(c))
+            .fold(0.0, f64::max)
+    }
+
+    /// Return the Lin similarity of `a` and `b`, their Resnik
+    /// similarity relative to their own information content, between
+    /// 0 and 1.
+    ///
+    /// A class is always 1 to itself, even if it has no information
+    /// content. Otherwise, classes which are not used are 0 to every
+    /// other class.
+    pub fn lin(&self, a: &Class, b: &Class) -> f64 {
+        if a == b && self.h.contains(a) {
+            return 1.0;
+        }
+
+        match (self.information_content(a), self.information_content(b)) {
+            (Some(ic_a), Some(ic_b)) if ic_a + ic_b > 0.0 => {
+                2.0 * self.resnik(a, b) / (ic_a + ic_b)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Return the Jaccard similarity of the ancestors of `a` and `b`,
+/// the number of their common ancestors divided by the number of
+/// classes which are an ancestor of either. Each class counts as an
+/// ancestor of itself.
+pub fn jaccard(h: &ClassHierarchy, a: &Class, b: &Class) -> f64 {
+    let a = h.ancestors(a);
+    let b = h.ancestors(b);
+    let union = a.union(&b).count();
+    if union == 0 {
+        0.0
+    } else {
+        a.intersection(&b).count() as f64 / union as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hierarchy(b: &Build) -> ClassHierarchy {
+        let mut h = ClassHierarchy::new();
+        for (sub, sup) in &[("b", "a"), ("c", "a"), ("d", "b"), ("e", "b")] {
+            h.add_edge(
+                b.class(format!("http://ex.com/{}", sub)),
+                b.class(format!("http://ex.com/{}", sup)),
+            );
+        }
+        h
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn intrinsic() {
+        let b = Build::new();
+        let h = hierarchy(&b);
+        let c = |s: &str| b.class(format!("http://ex.com/{}", s));
+        let s = Similarity::new(&h);
+
+        assert_eq!(s.information_content(&c("a")), Some(0.0));
+        assert!(close(
+            s.information_content(&c("b")).unwrap(),
+            (5.0_f64 / 3.0).ln()
+        ));
+        assert!(close(s.resnik(&c("d"), &c("e")), (5.0_f64 / 3.0).ln()));
+        assert_eq!(s.resnik(&c("d"), &c("c")), 0.0);
+        assert!(close(
+            s.lin(&c("d"), &c("e")),
+            2.0 * (5.0_f64 / 3.0).ln() / (2.0 * 5.0_f64.ln())
+        ));
+        assert_eq!(s.lin(&c("x"), &c("x")), 0.0);
+
+        assert!(close(jaccard(&h, &c("d"), &c("e")), 0.5));
+        assert_eq!(jaccard(&h, &c("x"), &c("y")), 0.0);
+    }
+
+    #[test]
+    fn frequencies() {
+        let b = Build::new();
+        let h = hierarchy(&b);
+        let c = |s: &str| b.class(format!("http://ex.com/{}", s));
+
+        let mut frequencies = BTreeMap::new();
+        frequencies.insert(c("d"), 3);
+        frequencies.insert(c("c"), 1);
+        let s = Similarity::with_frequencies(&h, &frequencies);
+
+        assert_eq!(s.information_content(&c("e")), None);
+        assert!(close(
+            s.information_content(&c("b")).unwrap(),
+            (4.0_f64 / 3.0).ln()
+        ));
+        assert!(close(s.resnik(&c("d"), &c("e")), (4.0_f64 / 3.0).ln()));
+        assert_eq!(s.lin(&c("d"), &c("e")), 0.0);
+        assert!(close(s.lin(&c("d"), &c("b")), 1.0));
+        assert_eq!(s.lin(&c("d"), &c("c")), 0.0);
+    }
+}
@@ -0,0 +1,170 @@
+//! SKOS annotations
+//!
+//! Many vocabularies are published as OWL ontologies which use the
+//! [SKOS](https://www.w3.org/TR/skos-reference/) annotation
+//! properties, such as `skos:prefLabel` and `skos:broader`, alongside
+//! or instead of their RDFS and OWL equivalents. The functions here
+//! return the values of these properties for an entity.
+//!
+//! `LabelMappedIndex::with_skos` indexes preferred labels together
+//! with `rdfs:label`.
+use crate::model::*;
+use crate::ontology::axiom_mapped::{literal_with_lang, AxiomMappedOntology};
+use crate::vocab::{WithIRI, SKOS};
+
+/// Iterate over the values of the annotation assertions on `subject`
+/// with the SKOS property `ap`.
+pub fn values_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    subject: &'a IRI,
+    ap: SKOS,
+) -> impl Iterator<Item = &'a AnnotationValue> {
+    ont.i()
+        .annotation_assertion()
+        .filter(move |aa| &aa.subject == subject && aa.ann.ap.0.as_str() == ap.iri_str())
+        .map(|aa| &aa.ann.av)
+}
+
+fn literals_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI, ap: SKOS) -> Vec<&'a Literal> {
+    values_of(ont, subject, ap)
+        .filter_map(AnnotationValue::as_literal)
+        .collect()
+}
+
+fn iris_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI, ap: SKOS) -> Vec<&'a IRI> {
+    values_of(ont, subject, ap)
+        .filter_map(AnnotationValue::as_iri)
+        .collect()
+}
+
+/// Return the `skos:prefLabel` of `subject` in the first language of
+/// `langs` for which it has one.
+///
+/// Languages are matched as for `AxiomMappedIndex::label_with_lang`.
+pub fn pref_label_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    subject: &'a IRI,
+    langs: &[&str],
+) -> Option<&'a str> {
+    literal_with_lang(&literals_of(ont, subject, SKOS::PrefLabel), langs)
+}
+
+/// Return the `skos:prefLabel` of `subject`, or failing that its
+/// `rdfs:label`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// use horned_owl::skos::label_of;
+///
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// let c = b.iri("http://www.example.com/c");
+/// o.insert(AnnotationAssertion {
+///     subject: c.clone(),
+///     ann: Annotation {
+///         ap: b.annotation_property("http://www.w3.org/2004/02/skos/core#prefLabel"),
+///         av: Literal::Language {
+///             literal: "cat".to_string(),
+///             lang: "en".to_string(),
+///         }
+///         .into(),
+///     },
+/// });
+///
+/// assert_eq!(label_of(&o, &c, &["en"]), Some("cat"));
+/// ```
+pub fn label_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    subject: &'a IRI,
+    langs: &[&str],
+) -> Option<&'a str> {
+    pref_label_of(ont, subject, langs).or_else(|| ont.i().label_with_lang(subject, langs))
+}
+
+/// Return the `skos:altLabel` values of `subject`.
+pub fn alt_labels_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a Literal> {
+    literals_of(ont, subject, SKOS::AltLabel)
+}
+
+/// Return the `skos:definition` values of `subject`.
+pub fn definitions_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a Literal> {
+    literals_of(ont, subject, SKOS::Definition)
+}
+
+/// Return the `skos:notation` values of `subject`.
+pub fn notations_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a Literal> {
+    literals_of(ont, subject, SKOS::Notation)
+}
+
+/// Return the concepts which `subject` is `skos:broader` than, as
+/// given by annotation assertions.
+pub fn broader_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a IRI> {
+    iris_of(ont, subject, SKOS::Broader)
+}
+
+/// Return the concepts which are `skos:narrower` than `subject`, as
+/// given by annotation assertions.
+pub fn narrower_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a IRI> {
+    iris_of(ont, subject, SKOS::Narrower)
+}
+
+/// Return the concepts which are `skos:related` to `subject`.
+pub fn related_of<'a>(ont: &'a AxiomMappedOntology, subject: &'a IRI) -> Vec<&'a IRI> {
+    iris_of(ont, subject, SKOS::Related)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vocab::AnnotationBuiltIn;
+
+    #[test]
+    fn concepts() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let cat = b.iri("http://www.example.com/cat");
+        let animal = b.iri("http://www.example.com/animal");
+
+        let annotate = |o: &mut AxiomMappedOntology, ap: &str, av: AnnotationValue| {
+            o.insert(AnnotationAssertion {
+                subject: cat.clone(),
+                ann: Annotation {
+                    ap: b.annotation_property(ap),
+                    av,
+                },
+            });
+        };
+        let lang = |literal: &str, lang: &str| -> AnnotationValue {
+            Literal::Language {
+                literal: literal.to_string(),
+                lang: lang.to_string(),
+            }
+            .into()
+        };
+        annotate(&mut o, SKOS::PrefLabel.iri_str(), lang("cat", "en"));
+        annotate(&mut o, SKOS::PrefLabel.iri_str(), lang("chat", "fr"));
+        annotate(&mut o, SKOS::AltLabel.iri_str(), lang("moggy", "en-GB"));
+        annotate(
+            &mut o,
+            SKOS::Definition.iri_str(),
+            lang("A small felid", "en"),
+        );
+        annotate(&mut o, SKOS::Broader.iri_str(), animal.clone().into());
+        annotate(
+            &mut o,
+            AnnotationBuiltIn::LABEL.iri_str(),
+            lang("Cat", "en"),
+        );
+
+        assert_eq!(pref_label_of(&o, &cat, &["fr"]), Some("chat"));
+        assert_eq!(label_of(&o, &cat, &["en"]), Some("cat"));
+        assert_eq!(label_of(&o, &cat, &["de"]), None);
+        assert_eq!(alt_labels_of(&o, &cat).len(), 1);
+        assert_eq!(definitions_of(&o, &cat)[0].literal(), "A small felid");
+        assert_eq!(broader_of(&o, &cat), vec![&animal]);
+        assert!(narrower_of(&o, &cat).is_empty());
+        assert_eq!(label_of(&o, &animal, &["en"]), None);
+    }
+}
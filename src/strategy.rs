@@ -0,0 +1,301 @@
+//! Proptest strategies for the model
+//!
+//! These strategies generate random but valid IRIs, class
+//! expressions, axioms and ontologies, so that properties such as
+//! the round tripping of parsers and writers, or invariants of
+//! reasoners, can be tested. They are available with the `proptest`
+//! feature.
+//!
+//! Entities are drawn from a small vocabulary for each kind, so
+//! that generated axioms often share entities, and no IRI is used
+//! for more than one kind of entity.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::strategy::class_expression;
+//! # use horned_owl::signature::Signature;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(ce in class_expression())| {
+//!     prop_assert!(!ce.signature().is_empty());
+//! });
+//! ```
+use crate::model::*;
+use crate::ontology::set::SetOntology;
+use crate::signature::Signature;
+
+use proptest::collection::{btree_set, vec};
+use proptest::prelude::*;
+
+const NS: &str = "http://www.example.com/generated#";
+
+/// The number of entities of each kind in the vocabulary.
+pub const VOCABULARY: usize = 5;
+
+/// Generate an IRI in the generated namespace.
+pub fn iri() -> BoxedStrategy<IRI> {
+    "[a-zA-Z][a-zA-Z0-9_]{0,7}"
+        .prop_map(|s| Build::new().iri(format!("{}{}", NS, s)))
+        .boxed()
+}
+
+fn entity_iri(kind: &'static str) -> impl Strategy<Value = IRI> {
+    (0..VOCABULARY).prop_map(move |n| Build::new().iri(format!("{}{}{}", NS, kind, n)))
+}
+
+pub fn class() -> BoxedStrategy<Class> {
+    entity_iri("C").prop_map(Class).boxed()
+}
+
+pub fn object_property() -> BoxedStrategy<ObjectProperty> {
+    entity_iri("op").prop_map(ObjectProperty).boxed()
+}
+
+pub fn data_property() -> BoxedStrategy<DataProperty> {
+    entity_iri("dp").prop_map(DataProperty).boxed()
+}
+
+pub fn annotation_property() -> BoxedStrategy<AnnotationProperty> {
+    entity_iri("ap").prop_map(AnnotationProperty).boxed()
+}
+
+pub fn named_individual() -> BoxedStrategy<NamedIndividual> {
+    entity_iri("i").prop_map(NamedIndividual).boxed()
+}
+
+pub fn datatype() -> BoxedStrategy<Datatype> {
+    entity_iri("DT").prop_map(Datatype).boxed()
+}
+
+pub fn object_property_expression() -> BoxedStrategy<ObjectPropertyExpression> {
+    prop_oneof![
+        3 => object_property().prop_map(ObjectPropertyExpression::ObjectProperty),
+        1 => object_property().prop_map(ObjectPropertyExpression::InverseObjectProperty),
+    ]
+    .boxed()
+}
+
+pub fn literal() -> BoxedStrategy<Literal> {
+    prop_oneof![
+        "[a-zA-Z0-9]{0,8}".prop_map(|literal| Literal::Simple { literal }),
+        ("[a-zA-Z0-9]{0,8}", "en|fr|de").prop_map(|(literal, lang)| Literal::Language {
+            literal,
+            lang: lang.to_string()
+        }),
+        any::<i32>().prop_map(|n| Literal::Datatype {
+            literal: n.to_string(),
+            datatype_iri: Build::new().iri("http://www.w3.org/2001/XMLSchema#integer"),
+        }),
+    ]
+    .boxed()
+}
+
+pub fn facet() -> BoxedStrategy<Facet> {
+    use Facet::*;
+    prop_oneof![
+        Just(Length),
+        Just(MinLength),
+        Just(MaxLength),
+        Just(Pattern),
+        Just(MinInclusive),
+        Just(MinExclusive),
+        Just(MaxInclusive),
+        Just(MaxExclusive),
+        Just(TotalDigits),
+        Just(FractionDigits),
+        Just(LangRange),
+    ]
+    .boxed()
+}
+
+pub fn data_range() -> BoxedStrategy<DataRange> {
+    let leaf = prop_oneof![
+        3 => datatype().prop_map(DataRange::Datatype),
+        1 => vec(literal(), 1..3).prop_map(DataRange::DataOneOf),
+        1 => (datatype(), vec((facet(), literal()), 1..3)).prop_map(|(dt, v)| {
+            DataRange::DatatypeRestriction(
+                dt,
+                v.into_iter().map(|(f, l)| FacetRestriction { f, l }).collect(),
+            )
+        }),
+    ];
+
+    leaf.prop_recursive(2, 8, 3, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 2..4).prop_map(DataRange::DataIntersectionOf),
+            vec(inner.clone(), 2..4).prop_map(DataRange::DataUnionOf),
+            inner.prop_map(|dr| DataRange::DataComplementOf(Box::new(dr))),
+        ]
+    })
+    .boxed()
+}
+
+/// Generate a class expression, nested at most three deep.
+pub fn class_expression() -> BoxedStrategy<ClassExpression> {
+    use ClassExpression::*;
+
+    let leaf = prop_oneof![
+        6 => class().prop_map(Class),
+        1 => vec(named_individual(), 1..3).prop_map(ObjectOneOf),
+        1 => (object_property_expression(), named_individual())
+            .prop_map(|(ope, i)| ObjectHasValue { ope, i }),
+        1 => object_property_expression().prop_map(ObjectHasSelf),
+        1 => (data_property(), data_range()).prop_map(|(dp, dr)| DataSomeValuesFrom { dp, dr }),
+        1 => (data_property(), data_range()).prop_map(|(dp, dr)| DataAllValuesFrom { dp, dr }),
+        1 => (data_property(), literal()).prop_map(|(dp, l)| DataHasValue { dp, l }),
+        1 => (0..4u32, data_property(), data_range())
+            .prop_map(|(n, dp, dr)| DataMinCardinality { n, dp, dr }),
+        1 => (0..4u32, data_property(), data_range())
+            .prop_map(|(n, dp, dr)| DataMaxCardinality { n, dp, dr }),
+        1 => (0..4u32, data_property(), data_range())
+            .prop_map(|(n, dp, dr)| DataExactCardinality { n, dp, dr }),
+    ];
+
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        let restriction = || {
+            (
+                object_property_expression(),
+                inner.clone().prop_map(Box::new),
+            )
+        };
+        let cardinality = || {
+            (
+                0..4u32,
+                object_property_expression(),
+                inner.clone().prop_map(Box::new),
+            )
+        };
+
+        prop_oneof![
+            vec(inner.clone(), 2..4).prop_map(ObjectIntersectionOf),
+            vec(inner.clone(), 2..4).prop_map(ObjectUnionOf),
+            inner
+                .clone()
+                .prop_map(|ce| ObjectComplementOf(Box::new(ce))),
+            restriction().prop_map(|(ope, bce)| ObjectSomeValuesFrom { ope, bce }),
+            restriction().prop_map(|(ope, bce)| ObjectAllValuesFrom { ope, bce }),
+            cardinality().prop_map(|(n, ope, bce)| ObjectMinCardinality { n, ope, bce }),
+            cardinality().prop_map(|(n, ope, bce)| ObjectMaxCardinality { n, ope, bce }),
+            cardinality().prop_map(|(n, ope, bce)| ObjectExactCardinality { n, ope, bce }),
+        ]
+    })
+    .boxed()
+}
+
+pub fn annotation() -> BoxedStrategy<Annotation> {
+    (
+        annotation_property(),
+        prop_oneof![
+            literal().prop_map(AnnotationValue::Literal),
+            iri().prop_map(AnnotationValue::IRI),
+            "[a-z]{1,4}".prop_map(|id| Build::new().anonymous_individual(id).into()),
+        ],
+    )
+        .prop_map(|(ap, av)| Annotation { ap, av })
+        .boxed()
+}
+
+/// Generate a logical axiom, or an annotation assertion.
+pub fn axiom() -> BoxedStrategy<Axiom> {
+    let ce = class_expression;
+    let ope = object_property_expression;
+
+    prop_oneof![
+        4 => (ce(), ce()).prop_map(|(sub, sup)| SubClassOf { sub, sup }.into()),
+        1 => vec(ce(), 2..4).prop_map(|v| EquivalentClasses(v).into()),
+        1 => vec(ce(), 2..4).prop_map(|v| DisjointClasses(v).into()),
+        1 => (ope(), ope()).prop_map(|(sub, sup)| SubObjectPropertyOf {
+            sub: sub.into(),
+            sup
+        }
+        .into()),
+        1 => ope().prop_map(|ope| TransitiveObjectProperty(ope).into()),
+        1 => (ope(), ce()).prop_map(|(ope, ce)| ObjectPropertyDomain { ope, ce }.into()),
+        1 => (ope(), ce()).prop_map(|(ope, ce)| ObjectPropertyRange { ope, ce }.into()),
+        1 => (data_property(), data_property())
+            .prop_map(|(sub, sup)| SubDataPropertyOf { sub, sup }.into()),
+        1 => (data_property(), ce()).prop_map(|(dp, ce)| DataPropertyDomain { dp, ce }.into()),
+        1 => (data_property(), data_range())
+            .prop_map(|(dp, dr)| DataPropertyRange { dp, dr }.into()),
+        2 => (ce(), named_individual()).prop_map(|(ce, i)| ClassAssertion { ce, i }.into()),
+        1 => (ope(), named_individual(), named_individual())
+            .prop_map(|(ope, from, to)| ObjectPropertyAssertion { ope, from, to }.into()),
+        1 => (data_property(), named_individual(), literal())
+            .prop_map(|(dp, from, to)| DataPropertyAssertion { dp, from, to }.into()),
+        1 => vec(named_individual(), 2..4).prop_map(|v| SameIndividual(v).into()),
+        1 => (class(), annotation()).prop_map(|(c, ann)| AnnotationAssertion {
+            subject: c.0,
+            ann
+        }
+        .into()),
+    ]
+    .boxed()
+}
+
+pub fn annotated_axiom() -> BoxedStrategy<AnnotatedAxiom> {
+    (axiom(), btree_set(annotation(), 0..2))
+        .prop_map(|(ax, ann)| AnnotatedAxiom::new(ax, ann))
+        .boxed()
+}
+
+/// Generate an ontology of up to `size` axioms, with an IRI and a
+/// declaration for every entity in its signature.
+pub fn ontology(size: usize) -> BoxedStrategy<SetOntology> {
+    vec(annotated_axiom(), 0..=size)
+        .prop_map(|axioms| {
+            let mut o = SetOntology::default();
+            o.mut_id().iri = Some(Build::new().iri("http://www.example.com/generated"));
+            for ax in axioms {
+                o.insert(ax);
+            }
+            for ne in o.signature() {
+                o.insert(ne);
+            }
+            o
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::read;
+    use crate::io::owx::writer::write;
+    use crate::ontology::axiom_mapped::AxiomMappedOntology;
+    use crate::reasoner::structural::StructuralReasoner;
+    use crate::reasoner::Reasoner;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn owx_roundtrip(o in ontology(8)) {
+            let amo: AxiomMappedOntology = o.into();
+            let mut buf = vec![];
+            write(&mut buf, &amo, None).unwrap();
+
+            let (round, _) = read(&mut buf.as_slice()).unwrap();
+            prop_assert_eq!(SetOntology::from(amo), round);
+        }
+
+        #[test]
+        fn hob_roundtrip(o in ontology(8)) {
+            let amo: AxiomMappedOntology = o.into();
+            let mut buf = vec![];
+            crate::io::hob::write(&mut buf, &amo, None).unwrap();
+
+            let (round, _) = crate::io::hob::read(&mut buf.as_slice()).unwrap();
+            prop_assert_eq!(SetOntology::from(amo), round);
+        }
+
+        #[test]
+        fn reflexive(o in ontology(8), ce in class_expression()) {
+            let r = StructuralReasoner::new(o.into());
+            prop_assert!(r.is_subclass(&ce, &ce));
+            prop_assert!(r.is_subclass(
+                &ce,
+                &Build::new().class("http://www.w3.org/2002/07/owl#Thing").into()
+            ));
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Structural comparison of ontologies
+//!
+//! Two ontologies are structurally equal if they have the same
+//! `OntologyID` and the same axioms, where the arguments of
+//! set-like constructs, such as `EquivalentClasses` or
+//! `ObjectIntersectionOf`, are compared without regard to order or
+//! repetition. This is the notion of structural equivalence from the
+//! [OWL2 specification](https://www.w3.org/TR/owl2-syntax/#Structural_Specification).
+use crate::io::ofn::writer::Functional;
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::set::SetOntology;
+
+use curie::PrefixMapping;
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// Rewrite a value to a canonical form, such that structurally
+/// equivalent values are equal.
+pub trait Canonical {
+    fn canonicalize(&mut self);
+
+    fn canonical(&self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut c = self.clone();
+        c.canonicalize();
+        c
+    }
+}
+
+fn set<T: Ord>(v: &mut Vec<T>) {
+    v.sort();
+    v.dedup();
+}
+
+fn canonical_set<T: Canonical + Ord>(v: &mut Vec<T>) {
+    for t in v.iter_mut() {
+        t.canonicalize();
+    }
+    set(v);
+}
+
+impl<T: Canonical> Canonical for Box<T> {
+    fn canonicalize(&mut self) {
+        (**self).canonicalize()
+    }
+}
+
+impl Canonical for DataRange {
+    fn canonicalize(&mut self) {
+        match self {
+            DataRange::DataIntersectionOf(v) | DataRange::DataUnionOf(v) => canonical_set(v),
+            DataRange::DataComplementOf(dr) => dr.canonicalize(),
+            DataRange::DataOneOf(v) => set(v),
+            DataRange::DatatypeRestriction(_, v) => set(v),
+            DataRange::Datatype(_) => {}
+        }
+    }
+}
+
+impl Canonical for ClassExpression {
+    fn canonicalize(&mut self) {
+        use ClassExpression::*;
+        match self {
+            ObjectIntersectionOf(v) | ObjectUnionOf(v) => canonical_set(v),
+            ObjectComplementOf(bce) => bce.canonicalize(),
+            ObjectOneOf(v) => set(v),
+            ObjectSomeValuesFrom { bce, .. }
+            | ObjectAllValuesFrom { bce, .. }
+            | ObjectMinCardinality { bce, .. }
+            | ObjectMaxCardinality { bce, .. }
+            | ObjectExactCardinality { bce, .. } => bce.canonicalize(),
+            DataSomeValuesFrom { dr, .. }
+            | DataAllValuesFrom { dr, .. }
+            | DataMinCardinality { dr, .. }
+            | DataMaxCardinality { dr, .. }
+            | DataExactCardinality { dr, .. } => dr.canonicalize(),
+            Class(_) | ObjectHasValue { .. } | ObjectHasSelf(_) | DataHasValue { .. } => {}
+        }
+    }
+}
+
+impl Canonical for Axiom {
+    fn canonicalize(&mut self) {
+        match self {
+            Axiom::SubClassOf(ax) => {
+                ax.sub.canonicalize();
+                ax.sup.canonicalize();
+            }
+            Axiom::EquivalentClasses(ax) => canonical_set(&mut ax.0),
+            Axiom::DisjointClasses(ax) => canonical_set(&mut ax.0),
+            Axiom::DisjointUnion(ax) => canonical_set(&mut ax.1),
+            Axiom::EquivalentObjectProperties(ax) => set(&mut ax.0),
+            Axiom::DisjointObjectProperties(ax) => set(&mut ax.0),
+            Axiom::InverseObjectProperties(ax) if ax.0 > ax.1 => {
+                std::mem::swap(&mut ax.0, &mut ax.1);
+            }
+            Axiom::ObjectPropertyDomain(ax) => ax.ce.canonicalize(),
+            Axiom::ObjectPropertyRange(ax) => ax.ce.canonicalize(),
+            Axiom::EquivalentDataProperties(ax) => set(&mut ax.0),
+            Axiom::DisjointDataProperties(ax) => set(&mut ax.0),
+            Axiom::DataPropertyDomain(ax) => ax.ce.canonicalize(),
+            Axiom::DataPropertyRange(ax) => ax.dr.canonicalize(),
+            Axiom::DatatypeDefinition(ax) => ax.range.canonicalize(),
+            Axiom::HasKey(ax) => {
+                ax.ce.canonicalize();
+                set(&mut ax.vpe);
+            }
+            Axiom::SameIndividual(ax) => set(&mut ax.0),
+            Axiom::DifferentIndividuals(ax) => set(&mut ax.0),
+            Axiom::ClassAssertion(ax) => ax.ce.canonicalize(),
+            _ => {}
+        }
+    }
+}
+
+impl Canonical for AnnotatedAxiom {
+    fn canonicalize(&mut self) {
+        self.axiom.canonicalize()
+    }
+}
+
+/// The canonical form of an ontology.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct CanonicalForm {
+    pub id: OntologyID,
+    pub axioms: BTreeSet<AnnotatedAxiom>,
+}
+
+impl CanonicalForm {
+    pub fn new<'a, I>(id: &OntologyID, axioms: I) -> CanonicalForm
+    where
+        I: IntoIterator<Item = &'a AnnotatedAxiom>,
+    {
+        CanonicalForm {
+            id: id.clone(),
+            axioms: axioms.into_iter().map(Canonical::canonical).collect(),
+        }
+    }
+}
+
+/// A hash of the content of an ontology.
+///
+/// The hash is calculated using 64 bit FNV-1a over the functional
+/// syntax of the canonical form, so it is independent of the order
+/// of axioms and stable between runs and versions of Rust.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ContentHash(pub u64);
+
+impl Display for ContentHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+struct Fnv(u64);
+
+impl Write for Fnv {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for b in buf {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CanonicalForm {
+    pub fn content_hash(&self) -> ContentHash {
+        let m = PrefixMapping::default();
+        let mut h = Fnv(0xcbf2_9ce4_8422_2325);
+
+        // Writing to Fnv cannot fail
+        for iri in [&self.id.iri, &self.id.viri].iter() {
+            match iri {
+                Some(iri) => writeln!(h, "<{}>", iri),
+                None => writeln!(h),
+            }
+            .unwrap();
+        }
+        for ax in &self.axioms {
+            writeln!(h, "{}", Functional(ax, &m)).unwrap();
+        }
+
+        ContentHash(h.0)
+    }
+}
+
+/// Ontologies which can be compared structurally.
+pub trait Structural: Ontology {
+    fn canonical_form(&self) -> CanonicalForm;
+
+    /// Return true if `self` and `other` are structurally equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// # use horned_owl::structural::Structural;
+    /// let b = Build::new();
+    /// let a: ClassExpression = b.class("http://www.example.com/A").into();
+    /// let c: ClassExpression = b.class("http://www.example.com/C").into();
+    ///
+    /// let mut o1 = SetOntology::new();
+    /// o1.insert(EquivalentClasses(vec![a.clone(), c.clone()]));
+    ///
+    /// let mut o2 = SetOntology::new();
+    /// o2.insert(EquivalentClasses(vec![c, a]));
+    ///
+    /// assert!(o1.structurally_equal(&o2));
+    /// ```
+    fn structurally_equal<O: Structural>(&self, other: &O) -> bool {
+        self.canonical_form() == other.canonical_form()
+    }
+
+    /// Return a hash of the header and axioms of the ontology, which
+    /// is the same for structurally equal ontologies.
+    fn content_hash(&self) -> ContentHash {
+        self.canonical_form().content_hash()
+    }
+}
+
+impl Structural for SetOntology {
+    fn canonical_form(&self) -> CanonicalForm {
+        CanonicalForm::new(self.id(), self)
+    }
+}
+
+impl Structural for AxiomMappedOntology {
+    fn canonical_form(&self) -> CanonicalForm {
+        CanonicalForm::new(self.id(), self.i())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::test::read_ok;
+    use crate::io::rdf::reader::read;
+
+    #[test]
+    fn nested_order() {
+        let b = Build::new();
+        let a: ClassExpression = b.class("http://www.example.com/A").into();
+        let c: ClassExpression = b.class("http://www.example.com/C").into();
+
+        let mut ce =
+            ClassExpression::ObjectComplementOf(Box::new(ClassExpression::ObjectUnionOf(vec![
+                c.clone(),
+                a.clone(),
+                c.clone(),
+            ])));
+        ce.canonicalize();
+
+        assert_eq!(
+            ce,
+            ClassExpression::ObjectComplementOf(Box::new(ClassExpression::ObjectUnionOf(vec![
+                a, c
+            ])))
+        );
+    }
+
+    #[test]
+    fn different() {
+        let b = Build::new();
+        let mut o1 = SetOntology::new();
+        o1.declare(b.class("http://www.example.com/A"));
+        let mut o2 = SetOntology::new();
+        o2.declare(b.class("http://www.example.com/B"));
+
+        assert!(!o1.structurally_equal(&o2));
+    }
+
+    #[test]
+    fn owx_rdf() {
+        let (owx, _) = read_ok(&mut include_str!("ont/owl-xml/and.owx").as_bytes());
+        let (rdf, _) = read(&mut include_str!("ont/owl-rdf/and.owl").as_bytes()).unwrap();
+        let rdf: AxiomMappedOntology = rdf.into();
+
+        assert!(owx.structurally_equal(&rdf));
+    }
+
+    #[test]
+    fn hash() {
+        let (owx, _) = read_ok(&mut include_str!("ont/owl-xml/and.owx").as_bytes());
+        let (rdf, _) = read(&mut include_str!("ont/owl-rdf/and.owl").as_bytes()).unwrap();
+        let rdf: AxiomMappedOntology = rdf.into();
+        assert_eq!(owx.content_hash(), rdf.content_hash());
+
+        let (or, _) = read_ok(&mut include_str!("ont/owl-xml/or.owx").as_bytes());
+        assert_ne!(owx.content_hash(), or.content_hash());
+        assert_eq!(format!("{}", or.content_hash()).len(), 16);
+    }
+
+    #[test]
+    fn hash_header() {
+        let b = Build::new();
+        let mut o1 = SetOntology::new();
+        o1.declare(b.class("http://www.example.com/A"));
+        let before = o1.content_hash();
+
+        o1.mut_id().viri = Some(b.iri("http://www.example.com/v2"));
+        assert_ne!(before, o1.content_hash());
+    }
+
+    #[test]
+    fn hash_empty() {
+        // FNV-1a of the two empty header lines
+        assert_eq!(
+            SetOntology::new().content_hash(),
+            ContentHash(0x0854_7e07_b508_4555)
+        );
+    }
+}
@@ -0,0 +1,1294 @@
+//! Structural validation of an ontology.
+//!
+//! Validation checks for problems which do not prevent an ontology
+//! from being parsed, but which make it invalid or outside of OWL 2
+//! DL. Currently this checks for
+//!
+//!  - IRIs which are not valid absolute IRIs.
+//!  - Entities which are used but not declared.
+//!  - Entities in the reserved vocabulary which are not built in.
+//!  - IRIs which are punned in a way that OWL 2 DL does not allow.
+//!  - Non-simple object properties used where a simple property is
+//!    required by the [global
+//!    restrictions](https://www.w3.org/TR/owl2-syntax/#Global_Restrictions_on_Axioms_in_OWL_2_DL).
+//!  - Property chains which make the object property hierarchy
+//!    irregular, as defined by the same restrictions.
+//!
+//! `validate_profile` also checks that each axiom is within one of
+//! the [OWL 2 profiles](https://www.w3.org/TR/owl2-profiles/), EL, QL
+//! or RL: that it is of a kind which the profile allows, built from
+//! the class expressions, property expressions and data ranges which
+//! the profile allows where they are used, and that it uses only the
+//! datatypes of the profile. The further restrictions of OWL 2 EL on
+//! property ranges and chains are not checked.
+use crate::error::{HornedError, ValidityError};
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::signature::{declared, is_legal_punning, punned, Signature};
+use crate::vocab::{is_annotation_builtin, is_reserved, Namespace, WithIRI};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// One of the OWL 2 profiles.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Profile {
+    EL,
+    QL,
+    RL,
+}
+
+impl Profile {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Profile::EL => "EL",
+            Profile::QL => "QL",
+            Profile::RL => "RL",
+        }
+    }
+}
+
+/// A problem found during validation.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Problem {
+    /// The IRI is not a valid absolute IRI.
+    MalformedIRI(IRI),
+    /// The entity is used but not declared.
+    UndeclaredEntity(NamedEntity),
+    /// The entity uses the reserved vocabulary, but is not built in.
+    ReservedVocabulary(NamedEntity),
+    /// A non-simple property is used in an axiom of this kind, where
+    /// a simple property is required.
+    NonSimpleProperty(ObjectProperty, AxiomKind),
+    /// The IRI is used as kinds of entity which cannot be punned,
+    /// such as both an object and a data property.
+    IllegalPunning(IRI),
+    /// A chain implying the first property contains the second, so
+    /// the second must come before the first in a regular order; but
+    /// other chains or the hierarchy require the opposite.
+    IrregularPropertyChain(ObjectProperty, ObjectProperty),
+    /// An axiom of this kind, using the IRI, is outside of the
+    /// profile. There is no IRI if the axiom uses only anonymous
+    /// individuals.
+    NotInProfile(Profile, AxiomKind, Option<IRI>),
+    /// The ontology could not be parsed, with this error.
+    ParseError(String),
+}
+
+impl Problem {
+    /// A short name for the kind of this problem.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Problem::MalformedIRI(_) => "MalformedIRI",
+            Problem::UndeclaredEntity(_) => "UndeclaredEntity",
+            Problem::ReservedVocabulary(_) => "ReservedVocabulary",
+            Problem::NonSimpleProperty(_, _) => "NonSimpleProperty",
+            Problem::IllegalPunning(_) => "IllegalPunning",
+            Problem::IrregularPropertyChain(_, _) => "IrregularPropertyChain",
+            Problem::NotInProfile(_, _, _) => "NotInProfile",
+            Problem::ParseError(_) => "ParseError",
+        }
+    }
+
+    /// The IRI with the problem, if there is one.
+    pub fn iri(&self) -> Option<&IRI> {
+        match self {
+            Problem::MalformedIRI(iri) | Problem::IllegalPunning(iri) => Some(iri),
+            Problem::UndeclaredEntity(e) | Problem::ReservedVocabulary(e) => Some(e.iri()),
+            Problem::NonSimpleProperty(op, _) | Problem::IrregularPropertyChain(op, _) => {
+                Some(&op.0)
+            }
+            Problem::NotInProfile(_, _, iri) => iri.as_ref(),
+            Problem::ParseError(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MalformedIRI(iri) => write!(f, "Malformed IRI: {}", iri),
+            Problem::IllegalPunning(iri) => write!(f, "Illegal punning: {}", iri),
+            Problem::UndeclaredEntity(e) => {
+                write!(f, "Undeclared {:?}: {}", e.kind(), e.iri())
+            }
+            Problem::ReservedVocabulary(e) => {
+                write!(f, "Reserved vocabulary used as {:?}: {}", e.kind(), e.iri())
+            }
+            Problem::NonSimpleProperty(op, kind) => write!(
+                f,
+                "Non-simple property used in {}: {}",
+                kind_name(*kind),
+                op.0
+            ),
+            Problem::IrregularPropertyChain(sup, op) => write!(
+                f,
+                "Irregular property chain: {} is in a chain implying {}, but is not below it",
+                op.0, sup.0
+            ),
+            Problem::NotInProfile(profile, kind, Some(iri)) => write!(
+                f,
+                "Not in OWL 2 {}: {} using {}",
+                profile.name(),
+                kind_name(*kind),
+                iri
+            ),
+            Problem::NotInProfile(profile, kind, None) => {
+                write!(f, "Not in OWL 2 {}: {}", profile.name(), kind_name(*kind))
+            }
+            Problem::ParseError(e) => write!(f, "Could not parse: {}", e),
+        }
+    }
+}
+
+/// The result of validating an ontology.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    /// Returns true if no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Return an error holding this report, unless it is valid.
+    pub fn into_result(self) -> Result<(), HornedError> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(ValidityError::Problems(self).into())
+        }
+    }
+
+    /// Render the report as JSON.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::validate::Report;
+    /// let r = Report::default();
+    /// assert_eq!(r.to_json(), r#"{"valid":true,"problems":[]}"#);
+    /// ```
+    pub fn to_json(&self) -> String {
+        let problems: Vec<String> = self
+            .problems
+            .iter()
+            .map(|p| {
+                let mut fields = vec![
+                    format!("\"problem\":{}", json_string(p.name())),
+                    format!(
+                        "\"iri\":{}",
+                        p.iri().map_or("null".to_string(), |iri| json_string(iri))
+                    ),
+                ];
+                match p {
+                    Problem::UndeclaredEntity(e) | Problem::ReservedVocabulary(e) => {
+                        fields.push(format!(
+                            "\"entity\":{}",
+                            json_string(&format!("{:?}", e.kind()))
+                        ));
+                    }
+                    Problem::NonSimpleProperty(_, kind) => {
+                        fields.push(format!("\"axiom\":{}", json_string(&kind_name(*kind))));
+                    }
+                    Problem::IrregularPropertyChain(_, op) => {
+                        fields.push(format!("\"property\":{}", json_string(&op.0)));
+                    }
+                    Problem::NotInProfile(profile, kind, _) => {
+                        fields.push(format!("\"profile\":{}", json_string(profile.name())));
+                        fields.push(format!("\"axiom\":{}", json_string(&kind_name(*kind))));
+                    }
+                    Problem::ParseError(e) => {
+                        fields.push(format!("\"message\":{}", json_string(e)));
+                    }
+                    Problem::MalformedIRI(_) | Problem::IllegalPunning(_) => {}
+                }
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect();
+
+        format!(
+            "{{\"valid\":{},\"problems\":[{}]}}",
+            self.is_valid(),
+            problems.join(",")
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn kind_name(kind: AxiomKind) -> String {
+    format!("{:?}", kind)
+        .trim_start_matches("AxiomKind::")
+        .to_string()
+}
+
+/// Validate `ont`, returning a report of any problems found.
+pub fn validate(ont: &AxiomMappedOntology) -> Report {
+    Report {
+        problems: problems(ont).into_iter().collect(),
+    }
+}
+
+/// Validate `ont`, and check that it is within `profile`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// use horned_owl::validate::{validate_profile, Profile};
+///
+/// let b = Build::new();
+/// let mut o = AxiomMappedOntology::default();
+/// o.declare(b.class("http://www.example.com/A"));
+/// o.declare(b.class("http://www.example.com/B"));
+/// o.insert(SubClassOf {
+///     sub: b.class("http://www.example.com/A").into(),
+///     sup: ClassExpression::ObjectUnionOf(vec![
+///         b.class("http://www.example.com/A").into(),
+///         b.class("http://www.example.com/B").into(),
+///     ]),
+/// });
+///
+/// assert!(!validate_profile(&o, Profile::EL).is_valid());
+/// assert!(!validate_profile(&o, Profile::QL).is_valid());
+/// assert!(!validate_profile(&o, Profile::RL).is_valid());
+/// ```
+pub fn validate_profile(ont: &AxiomMappedOntology, profile: Profile) -> Report {
+    let mut problems = problems(ont);
+    for ax in ont.i().iter() {
+        problems.extend(not_in_profile(&ax.axiom, profile));
+    }
+    Report {
+        problems: problems.into_iter().collect(),
+    }
+}
+
+/// Validate `axioms` on their own, without the rest of an ontology,
+/// checking for IRIs which are malformed, reserved vocabulary which
+/// is not built in and illegal punning between the axioms.
+pub fn validate_axioms<'a, I>(axioms: I) -> Report
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut problems = BTreeSet::new();
+    let mut sig = BTreeSet::new();
+    let mut iris = vec![];
+    for ax in axioms {
+        ax.signature_into(&mut sig);
+        match &ax.axiom {
+            Axiom::Import(i) => iris.push(&i.0),
+            Axiom::AnnotationAssertion(aa) => iris.push(&aa.subject),
+            _ => {}
+        }
+    }
+
+    malformed(sig.iter().map(|e| e.iri()).chain(iris), &mut problems);
+    reserved(&sig, &mut problems);
+    illegal_punning(&sig, &mut problems);
+    Report {
+        problems: problems.into_iter().collect(),
+    }
+}
+
+fn problems(ont: &AxiomMappedOntology) -> BTreeSet<Problem> {
+    let mut problems = BTreeSet::new();
+    let sig = ont.signature();
+
+    malformed_iri(ont, &sig, &mut problems);
+    undeclared(ont, &sig, &mut problems);
+    reserved(&sig, &mut problems);
+    non_simple(ont, &mut problems);
+    illegal_punning(&sig, &mut problems);
+    irregular(ont, &mut problems);
+    problems
+}
+
+fn malformed_iri(
+    ont: &AxiomMappedOntology,
+    sig: &BTreeSet<NamedEntity>,
+    problems: &mut BTreeSet<Problem>,
+) {
+    let id = ont.id();
+    let iris = sig
+        .iter()
+        .map(|e| e.iri())
+        .chain(id.iri.iter())
+        .chain(id.viri.iter())
+        .chain(ont.i().import().map(|i| &i.0))
+        .chain(ont.i().annotation_assertion().map(|aa| &aa.subject));
+    malformed(iris, problems);
+}
+
+fn malformed<'a, I>(iris: I, problems: &mut BTreeSet<Problem>)
+where
+    I: IntoIterator<Item = &'a IRI>,
+{
+    for iri in iris {
+        if iri.node_id().is_none() && !sophia_iri::is_absolute_iri_ref(iri) {
+            problems.insert(Problem::MalformedIRI(iri.clone()));
+        }
+    }
+}
+
+fn undeclared(
+    ont: &AxiomMappedOntology,
+    sig: &BTreeSet<NamedEntity>,
+    problems: &mut BTreeSet<Problem>,
+) {
+    let declared = declared(ont.i().iter());
+
+    for e in sig.difference(&declared) {
+        if !is_reserved(e.iri()) {
+            problems.insert(Problem::UndeclaredEntity(e.clone()));
+        }
+    }
+}
+
+/// Is `e` one of the entities built in to OWL.
+fn is_builtin(e: &NamedEntity) -> bool {
+    let iri: &str = e.iri();
+    let owl = |local: &str| iri == format!("{}{}", Namespace::OWL.iri_s(), local);
+    let rdf = |local: &str| iri == format!("{}{}", Namespace::RDF.iri_s(), local);
+    let rdfs = |local: &str| iri == format!("{}{}", Namespace::RDFS.iri_s(), local);
+
+    match e {
+        NamedEntity::Class(_) => owl("Thing") || owl("Nothing"),
+        NamedEntity::ObjectProperty(_) => owl("topObjectProperty") || owl("bottomObjectProperty"),
+        NamedEntity::DataProperty(_) => owl("topDataProperty") || owl("bottomDataProperty"),
+        NamedEntity::Datatype(_) => {
+            iri.starts_with(Namespace::XSD.iri_str())
+                || rdfs("Literal")
+                || rdf("PlainLiteral")
+                || rdf("XMLLiteral")
+                || rdf("langString")
+                || owl("real")
+                || owl("rational")
+        }
+        NamedEntity::AnnotationProperty(_) => is_annotation_builtin(e.iri()),
+        NamedEntity::NamedIndividual(_) => false,
+    }
+}
+
+fn reserved(sig: &BTreeSet<NamedEntity>, problems: &mut BTreeSet<Problem>) {
+    for e in sig {
+        if is_reserved(e.iri()) && !is_builtin(e) {
+            problems.insert(Problem::ReservedVocabulary(e.clone()));
+        }
+    }
+}
+
+fn illegal_punning(sig: &BTreeSet<NamedEntity>, problems: &mut BTreeSet<Problem>) {
+    for (iri, kinds) in punned(sig) {
+        if !is_legal_punning(&kinds) {
+            problems.insert(Problem::IllegalPunning(iri));
+        }
+    }
+}
+
+fn named(ope: &ObjectPropertyExpression) -> &ObjectProperty {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op)
+        | ObjectPropertyExpression::InverseObjectProperty(op) => op,
+    }
+}
+
+/// Return the non-simple object properties of `ont`.
+///
+/// A property is non-simple if it is transitive, is the super
+/// property of a chain or is the super property of another
+/// non-simple property.
+fn non_simple_properties(ont: &AxiomMappedOntology) -> BTreeSet<ObjectProperty> {
+    let mut ns = BTreeSet::new();
+    let mut sub = vec![];
+
+    for ax in ont.i().iter() {
+        match &ax.axiom {
+            Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                ns.insert(named(ope).clone());
+            }
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub: s }) => match s {
+                SubObjectPropertyExpression::ObjectPropertyChain(_) => {
+                    ns.insert(named(sup).clone());
+                }
+                SubObjectPropertyExpression::ObjectPropertyExpression(s) => {
+                    sub.push((named(s), named(sup)));
+                }
+            },
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                for a in v {
+                    for b in v {
+                        sub.push((named(a), named(b)));
+                    }
+                }
+            }
+            Axiom::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+                sub.push((a, b));
+                sub.push((b, a));
+            }
+            _ => {}
+        }
+    }
+
+    // Propagate to super properties
+    loop {
+        let before = ns.len();
+        for (s, sup) in &sub {
+            if ns.contains(*s) {
+                ns.insert((*sup).clone());
+            }
+        }
+        if ns.len() == before {
+            return ns;
+        }
+    }
+}
+
+/// Add the class expressions nested within `ce` to `v`.
+fn nested<'a>(ce: &'a ClassExpression, v: &mut Vec<&'a ClassExpression>) {
+    v.push(ce);
+    match ce {
+        ClassExpression::ObjectIntersectionOf(ces) | ClassExpression::ObjectUnionOf(ces) => {
+            for ce in ces {
+                nested(ce, v);
+            }
+        }
+        ClassExpression::ObjectComplementOf(bce)
+        | ClassExpression::ObjectSomeValuesFrom { bce, .. }
+        | ClassExpression::ObjectAllValuesFrom { bce, .. }
+        | ClassExpression::ObjectMinCardinality { bce, .. }
+        | ClassExpression::ObjectMaxCardinality { bce, .. }
+        | ClassExpression::ObjectExactCardinality { bce, .. } => nested(bce, v),
+        _ => {}
+    }
+}
+
+/// Return all the class expressions in `ax`, including nested ones.
+fn class_expressions(ax: &Axiom) -> Vec<&ClassExpression> {
+    let top: Vec<&ClassExpression> = match ax {
+        Axiom::SubClassOf(SubClassOf { sup, sub }) => vec![sub, sup],
+        Axiom::EquivalentClasses(EquivalentClasses(v))
+        | Axiom::DisjointClasses(DisjointClasses(v))
+        | Axiom::DisjointUnion(DisjointUnion(_, v)) => v.iter().collect(),
+        Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ce, .. })
+        | Axiom::ObjectPropertyRange(ObjectPropertyRange { ce, .. })
+        | Axiom::DataPropertyDomain(DataPropertyDomain { ce, .. })
+        | Axiom::HasKey(HasKey { ce, .. })
+        | Axiom::ClassAssertion(ClassAssertion { ce, .. }) => vec![ce],
+        _ => vec![],
+    };
+
+    let mut v = vec![];
+    for ce in top {
+        nested(ce, &mut v);
+    }
+    v
+}
+
+/// Return the problems from uses of the non-simple properties `ns`
+/// in `ax`.
+fn non_simple_uses(ax: &Axiom, ns: &BTreeSet<ObjectProperty>) -> Vec<Problem> {
+    let mut used: Vec<&ObjectPropertyExpression> = match ax {
+        Axiom::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+        | Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+        | Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+        | Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => vec![ope],
+        Axiom::DisjointObjectProperties(DisjointObjectProperties(v)) => v.iter().collect(),
+        _ => vec![],
+    };
+
+    for ce in class_expressions(ax) {
+        match ce {
+            ClassExpression::ObjectMinCardinality { ope, .. }
+            | ClassExpression::ObjectMaxCardinality { ope, .. }
+            | ClassExpression::ObjectExactCardinality { ope, .. }
+            | ClassExpression::ObjectHasSelf(ope) => used.push(ope),
+            _ => {}
+        }
+    }
+
+    used.into_iter()
+        .map(named)
+        .filter(|op| ns.contains(*op))
+        .map(|op| Problem::NonSimpleProperty(op.clone(), ax.kind()))
+        .collect()
+}
+
+fn non_simple(ont: &AxiomMappedOntology, problems: &mut BTreeSet<Problem>) {
+    let ns = non_simple_properties(ont);
+    if ns.is_empty() {
+        return;
+    }
+
+    // Each axiom is checked independently, so this can be parallel
+    #[cfg(feature = "rayon")]
+    let axioms = ont.i().par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let axioms = ont.i().iter();
+
+    let found: Vec<Vec<Problem>> = axioms.map(|ax| non_simple_uses(&ax.axiom, &ns)).collect();
+    problems.extend(found.into_iter().flatten());
+}
+
+fn inverse(ope: &ObjectPropertyExpression) -> ObjectPropertyExpression {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => {
+            ObjectPropertyExpression::InverseObjectProperty(op.clone())
+        }
+        ObjectPropertyExpression::InverseObjectProperty(op) => op.clone().into(),
+    }
+}
+
+/// Return every expression reachable from each key of `edges`,
+/// including itself.
+fn reachable<'a>(
+    edges: &BTreeMap<&'a ObjectPropertyExpression, BTreeSet<&'a ObjectPropertyExpression>>,
+    from: &'a ObjectPropertyExpression,
+) -> BTreeSet<&'a ObjectPropertyExpression> {
+    let mut seen = BTreeSet::new();
+    let mut todo = vec![from];
+    while let Some(next) = todo.pop() {
+        if seen.insert(next) {
+            todo.extend(edges.get(next).into_iter().flatten());
+        }
+    }
+    seen
+}
+
+/// Check the restriction on the property hierarchy.
+///
+/// The hierarchy is regular if there is a strict order `<` on the
+/// object property expressions, with `P < R` exactly when `INV(P) <
+/// INV(R)`, such that `S < R` whenever `S` is a strict sub-property
+/// of `R`, and each chain axiom `SubObjectPropertyOf(ObjectPropertyChain(P1 ... Pn) R)`
+/// is transitivity, `P1 P1 -> P1`, or has `Pi < R` for each `Pi`,
+/// except for `P1` or `Pn` if either is `R`.
+///
+/// Each requirement of a chain which is on a cycle of the order is a
+/// problem.
+fn irregular(ont: &AxiomMappedOntology, problems: &mut BTreeSet<Problem>) {
+    let mut hierarchy: Vec<(ObjectPropertyExpression, ObjectPropertyExpression)> = vec![];
+    let mut chains: Vec<(&ObjectPropertyExpression, &[ObjectPropertyExpression])> = vec![];
+
+    for ax in ont.i().iter() {
+        match &ax.axiom {
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub }) => match sub {
+                SubObjectPropertyExpression::ObjectPropertyExpression(s) => {
+                    hierarchy.push((s.clone(), sup.clone()));
+                }
+                SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                    let top = format!("{}topObjectProperty", Namespace::OWL.iri_s());
+                    if v.len() > 1 && *named(sup).0 != top {
+                        chains.push((sup, v));
+                    }
+                }
+            },
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+                for a in v {
+                    for b in v {
+                        hierarchy.push((a.clone(), b.clone()));
+                    }
+                }
+            }
+            Axiom::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+                let a: ObjectPropertyExpression = a.clone().into();
+                let b = ObjectPropertyExpression::InverseObjectProperty(b.clone());
+                hierarchy.push((a.clone(), b.clone()));
+                hierarchy.push((b, a));
+            }
+            Axiom::SymmetricObjectProperty(SymmetricObjectProperty(ope)) => {
+                hierarchy.push((ope.clone(), inverse(ope)));
+            }
+            _ => {}
+        }
+    }
+    if chains.is_empty() {
+        return;
+    }
+
+    // The hierarchy holds for the inverses too
+    let inverses: Vec<_> = hierarchy
+        .iter()
+        .map(|(s, sup)| (inverse(s), inverse(sup)))
+        .collect();
+    hierarchy.extend(inverses);
+
+    let mut sub_of: BTreeMap<&ObjectPropertyExpression, BTreeSet<&ObjectPropertyExpression>> =
+        BTreeMap::new();
+    for (s, sup) in &hierarchy {
+        sub_of.entry(s).or_default().insert(sup);
+    }
+
+    // The requirements of each chain, as (Pi, R), with their inverses
+    let mut required: Vec<(ObjectPropertyExpression, ObjectPropertyExpression)> = vec![];
+    for (sup, v) in &chains {
+        let transitive = v.len() == 2 && v[0] == **sup && v[1] == **sup;
+        if transitive {
+            continue;
+        }
+
+        let skip_first = v[0] == **sup;
+        let skip_last = !skip_first && v[v.len() - 1] == **sup;
+        for (i, p) in v.iter().enumerate() {
+            if (i == 0 && skip_first) || (i == v.len() - 1 && skip_last) {
+                continue;
+            }
+            required.push((p.clone(), (*sup).clone()));
+            required.push((inverse(p), inverse(sup)));
+        }
+    }
+
+    // Strict sub-properties must come first, as must the
+    // requirements of the chains
+    let mut less: BTreeMap<&ObjectPropertyExpression, BTreeSet<&ObjectPropertyExpression>> =
+        BTreeMap::new();
+    for s in sub_of.keys() {
+        for sup in reachable(&sub_of, s) {
+            if !reachable(&sub_of, sup).contains(s) {
+                less.entry(s).or_default().insert(sup);
+            }
+        }
+    }
+    for (p, sup) in &required {
+        less.entry(p).or_default().insert(sup);
+    }
+
+    // The order is strict, so a requirement `P < R` fails if `R`
+    // comes before `P`, or is `P`
+    for (p, r) in required.iter().step_by(2) {
+        if reachable(&less, r).contains(p) {
+            problems.insert(Problem::IrregularPropertyChain(
+                named(r).clone(),
+                named(p).clone(),
+            ));
+        }
+    }
+}
+
+fn is_owl(ce: &ClassExpression, local: &str) -> bool {
+    matches!(ce, ClassExpression::Class(c)
+             if *c.0 == format!("{}{}", Namespace::OWL.iri_s(), local))
+}
+
+fn is_named(ope: &ObjectPropertyExpression) -> bool {
+    matches!(ope, ObjectPropertyExpression::ObjectProperty(_))
+}
+
+/// Is `dt` in the datatype map of `profile`.
+fn in_datatype_map(dt: &Datatype, profile: Profile) -> bool {
+    let iri: &str = &dt.0;
+    let owl = |local: &str| iri == format!("{}{}", Namespace::OWL.iri_s(), local);
+    let e: NamedEntity = dt.clone().into();
+
+    // Datatypes which are not built in will be reported as reserved
+    // vocabulary, or are defined by the ontology
+    if !is_builtin(&e) {
+        return true;
+    }
+    match profile {
+        Profile::RL => !owl("real") && !owl("rational"),
+        Profile::EL | Profile::QL => {
+            let xsd = iri.strip_prefix(Namespace::XSD.iri_str());
+            owl("real")
+                || owl("rational")
+                || matches!(
+                    xsd,
+                    Some(
+                        "decimal"
+                            | "integer"
+                            | "nonNegativeInteger"
+                            | "string"
+                            | "normalizedString"
+                            | "token"
+                            | "Name"
+                            | "NCName"
+                            | "NMTOKEN"
+                            | "hexBinary"
+                            | "base64Binary"
+                            | "anyURI"
+                            | "dateTime"
+                            | "dateTimeStamp"
+                    )
+                )
+                || iri == format!("{}PlainLiteral", Namespace::RDF.iri_s())
+                || iri == format!("{}XMLLiteral", Namespace::RDF.iri_s())
+                || iri == format!("{}Literal", Namespace::RDFS.iri_s())
+        }
+    }
+}
+
+/// Is `dr` a data range of `profile`.
+fn data_range(dr: &DataRange, profile: Profile) -> bool {
+    match dr {
+        DataRange::Datatype(_) => true,
+        DataRange::DataIntersectionOf(v) => v.iter().all(|dr| data_range(dr, profile)),
+        DataRange::DataOneOf(v) => profile == Profile::EL && v.len() == 1,
+        _ => false,
+    }
+}
+
+fn el_class(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) | DataHasValue { .. } => true,
+        ObjectIntersectionOf(v) => v.iter().all(el_class),
+        ObjectOneOf(v) => v.len() == 1,
+        ObjectSomeValuesFrom { ope, bce } => is_named(ope) && el_class(bce),
+        ObjectHasValue { ope, .. } | ObjectHasSelf(ope) => is_named(ope),
+        DataSomeValuesFrom { dr, .. } => data_range(dr, Profile::EL),
+        _ => false,
+    }
+}
+
+fn ql_sub(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => true,
+        ObjectSomeValuesFrom { bce, .. } => is_owl(bce, "Thing"),
+        DataSomeValuesFrom { dr, .. } => data_range(dr, Profile::QL),
+        _ => false,
+    }
+}
+
+fn ql_super(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => true,
+        ObjectIntersectionOf(v) => v.iter().all(ql_super),
+        ObjectComplementOf(bce) => ql_sub(bce),
+        ObjectSomeValuesFrom { bce, .. } => matches!(**bce, Class(_)),
+        DataSomeValuesFrom { dr, .. } => data_range(dr, Profile::QL),
+        _ => false,
+    }
+}
+
+fn rl_sub(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => !is_owl(ce, "Thing"),
+        ObjectIntersectionOf(v) | ObjectUnionOf(v) => v.iter().all(rl_sub),
+        ObjectOneOf(_) | ObjectHasValue { .. } | DataHasValue { .. } => true,
+        ObjectSomeValuesFrom { bce, .. } => is_owl(bce, "Thing") || rl_sub(bce),
+        DataSomeValuesFrom { dr, .. } => data_range(dr, Profile::RL),
+        _ => false,
+    }
+}
+
+fn rl_super(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => !is_owl(ce, "Thing"),
+        ObjectIntersectionOf(v) => v.iter().all(rl_super),
+        ObjectComplementOf(bce) => rl_sub(bce),
+        ObjectAllValuesFrom { bce, .. } => rl_super(bce),
+        ObjectHasValue { .. } | DataHasValue { .. } => true,
+        ObjectMaxCardinality { n, bce, .. } => *n <= 1 && (is_owl(bce, "Thing") || rl_sub(bce)),
+        DataAllValuesFrom { dr, .. } => data_range(dr, Profile::RL),
+        DataMaxCardinality { n, dr, .. } => *n <= 1 && data_range(dr, Profile::RL),
+        _ => false,
+    }
+}
+
+fn rl_equivalent(ce: &ClassExpression) -> bool {
+    use ClassExpression::*;
+    match ce {
+        Class(_) => !is_owl(ce, "Thing"),
+        ObjectIntersectionOf(v) => v.iter().all(rl_equivalent),
+        ObjectHasValue { .. } | DataHasValue { .. } => true,
+        _ => false,
+    }
+}
+
+/// A check that a class expression is allowed, in some position.
+type Check = fn(&ClassExpression) -> bool;
+
+/// Is the kind and the structure of `ax` allowed by `profile`.
+fn in_profile(ax: &Axiom, profile: Profile) -> bool {
+    use Profile::*;
+
+    let (sub, sup, equivalent): (Check, Check, Check) = match profile {
+        EL => (el_class, el_class, el_class),
+        QL => (ql_sub, ql_super, ql_sub),
+        RL => (rl_sub, rl_super, rl_equivalent),
+    };
+    let range = |dr| data_range(dr, profile);
+
+    match ax {
+        Axiom::SubClassOf(SubClassOf { sub: a, sup: b }) => sub(a) && sup(b),
+        Axiom::EquivalentClasses(EquivalentClasses(v)) => v.iter().all(equivalent),
+        Axiom::DisjointClasses(DisjointClasses(v)) => v.iter().all(sub),
+        Axiom::DisjointUnion(_) => false,
+        Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub }) => match profile {
+            EL => {
+                is_named(sup)
+                    && match sub {
+                        SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                            v.iter().all(is_named)
+                        }
+                        SubObjectPropertyExpression::ObjectPropertyExpression(ope) => is_named(ope),
+                    }
+            }
+            QL => matches!(
+                sub,
+                SubObjectPropertyExpression::ObjectPropertyExpression(_)
+            ),
+            RL => true,
+        },
+        Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => {
+            profile != EL || v.iter().all(is_named)
+        }
+        Axiom::DisjointObjectProperties(_) | Axiom::InverseObjectProperties(_) => profile != EL,
+        Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce })
+        | Axiom::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+            (profile != EL || is_named(ope)) && sup(ce)
+        }
+        Axiom::FunctionalObjectProperty(_) | Axiom::InverseFunctionalObjectProperty(_) => {
+            profile == RL
+        }
+        Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(ope)) => match profile {
+            EL => is_named(ope),
+            QL => true,
+            RL => false,
+        },
+        Axiom::IrreflexiveObjectProperty(_)
+        | Axiom::SymmetricObjectProperty(_)
+        | Axiom::AsymmetricObjectProperty(_) => profile != EL,
+        Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => match profile {
+            EL => is_named(ope),
+            QL => false,
+            RL => true,
+        },
+        Axiom::SubDataPropertyOf(_) | Axiom::EquivalentDataProperties(_) => true,
+        Axiom::DisjointDataProperties(_) => profile != EL,
+        Axiom::DataPropertyDomain(DataPropertyDomain { ce, .. }) => sup(ce),
+        Axiom::DataPropertyRange(DataPropertyRange { dr, .. }) => range(dr),
+        Axiom::FunctionalDataProperty(_) => profile != QL,
+        Axiom::DatatypeDefinition(DatatypeDefinition { range: dr, .. }) => range(dr),
+        Axiom::HasKey(HasKey { ce, vpe }) => match profile {
+            EL => {
+                el_class(ce)
+                    && vpe.iter().all(|pe| match pe {
+                        PropertyExpression::ObjectPropertyExpression(ope) => is_named(ope),
+                        _ => true,
+                    })
+            }
+            QL => false,
+            RL => rl_sub(ce),
+        },
+        Axiom::SameIndividual(_) => profile != QL,
+        Axiom::DifferentIndividuals(_) => true,
+        Axiom::ClassAssertion(ClassAssertion { ce, .. }) => match profile {
+            EL => el_class(ce),
+            QL => matches!(ce, ClassExpression::Class(_)),
+            RL => rl_super(ce),
+        },
+        Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, .. }) => {
+            profile != EL || is_named(ope)
+        }
+        Axiom::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion { ope, .. }) => {
+            match profile {
+                EL => is_named(ope),
+                QL => false,
+                RL => true,
+            }
+        }
+        Axiom::DataPropertyAssertion(_) => true,
+        Axiom::NegativeDataPropertyAssertion(_) => profile != QL,
+        // Declarations, annotations and imports are in every profile
+        _ => true,
+    }
+}
+
+/// Return the problem with `ax`, if it is outside of `profile`.
+fn not_in_profile(ax: &Axiom, profile: Profile) -> Option<Problem> {
+    let sig = ax.signature();
+    let outside = sig.iter().find(|e| match e {
+        NamedEntity::Datatype(dt) => !in_datatype_map(dt, profile),
+        _ => false,
+    });
+    let iri = match outside {
+        Some(dt) => Some(dt.iri().clone()),
+        None if in_profile(ax, profile) => return None,
+        // Anonymous individuals are not in the signature, so there
+        // may be no IRI to report
+        None => sig.iter().next().map(|e| e.iri().clone()),
+    };
+    Some(Problem::NotInProfile(profile, ax.kind(), iri))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::io::owx::reader::test::read_ok;
+
+    fn validate_owx(owx: &str) -> Report {
+        let (ont, _) = read_ok(&mut owx.as_bytes());
+        validate(&ont)
+    }
+
+    #[test]
+    fn valid() {
+        let r = validate_owx(include_str!("ont/owl-xml/class.owx"));
+        assert!(r.is_valid(), "{:?}", r);
+
+        let r = validate_owx(include_str!("ont/owl-xml/some.owx"));
+        assert!(r.is_valid(), "{:?}", r);
+    }
+
+    #[test]
+    fn undeclared() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.example.com/A"));
+        o.insert(SubClassOf {
+            sup: b.class("http://www.example.com/B").into(),
+            sub: b.class("http://www.example.com/A").into(),
+        });
+        o.insert(SubClassOf {
+            sup: b.class("http://www.w3.org/2002/07/owl#Thing").into(),
+            sub: b.class("http://www.example.com/A").into(),
+        });
+        o.insert(ClassAssertion {
+            ce: b.class("http://www.example.com/A").into(),
+            i: b.anonymous_individual("x"),
+        });
+
+        let r = validate(&o);
+        assert_eq!(
+            r.problems,
+            vec![Problem::UndeclaredEntity(
+                b.class("http://www.example.com/B").into()
+            )]
+        );
+        assert_eq!(
+            r.into_result().unwrap_err().to_string(),
+            "Validity Error: 1 problems found, the first: \
+             Undeclared Class: http://www.example.com/B"
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("not an iri"));
+
+        let r = validate(&o);
+        assert_eq!(r.problems, vec![Problem::MalformedIRI(b.iri("not an iri"))]);
+    }
+
+    #[test]
+    fn reserved() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.w3.org/2002/07/owl#Fred"));
+        o.declare(b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"));
+
+        let r = validate(&o);
+        assert_eq!(
+            r.problems,
+            vec![Problem::ReservedVocabulary(
+                b.class("http://www.w3.org/2002/07/owl#Fred").into()
+            )]
+        );
+    }
+
+    #[test]
+    fn non_simple() {
+        let b = Build::new();
+        let r = b.object_property("http://www.example.com/r");
+        let s = b.object_property("http://www.example.com/s");
+
+        let mut o = AxiomMappedOntology::default();
+        o.declare(r.clone());
+        o.declare(s.clone());
+        o.declare(b.class("http://www.example.com/A"));
+        o.insert(TransitiveObjectProperty(r.clone().into()));
+        o.insert(SubObjectPropertyOf {
+            sup: s.clone().into(),
+            sub: SubObjectPropertyExpression::ObjectPropertyExpression(r.into()),
+        });
+        o.insert(SubClassOf {
+            sup: ClassExpression::ObjectMaxCardinality {
+                n: 1,
+                ope: s.clone().into(),
+                bce: b.class("http://www.example.com/A").into(),
+            },
+            sub: b.class("http://www.example.com/A").into(),
+        });
+
+        let r = validate(&o);
+        assert_eq!(
+            r.problems,
+            vec![Problem::NonSimpleProperty(s, AxiomKind::SubClassOf)]
+        );
+    }
+
+    #[test]
+    fn punning() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.named_individual("http://www.example.com/a"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        o.declare(b.annotation_property("http://www.example.com/r"));
+
+        let r = validate(&o);
+        assert_eq!(
+            r.problems,
+            vec![Problem::IllegalPunning(b.iri("http://www.example.com/r"))]
+        );
+    }
+
+    #[test]
+    fn irregular() {
+        let b = Build::new();
+        let op = |s: &str| -> ObjectPropertyExpression {
+            b.object_property(format!("http://www.example.com/{}", s)).into()
+        };
+        let chain = |v: &[&str], sup: &str| SubObjectPropertyOf {
+            sup: op(sup),
+            sub: v.iter().map(|s| op(s)).collect::<Vec<_>>().into(),
+        };
+        let problems = |axioms: Vec<SubObjectPropertyOf>| {
+            let mut o = AxiomMappedOntology::default();
+            for ax in axioms {
+                o.insert(ax);
+            }
+            validate(&o)
+                .problems
+                .into_iter()
+                .filter_map(|p| match p {
+                    Problem::IrregularPropertyChain(sup, p) => Some((sup, p)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let pair = |sup: &str, p: &str| {
+            (
+                b.object_property(format!("http://www.example.com/{}", sup)),
+                b.object_property(format!("http://www.example.com/{}", p)),
+            )
+        };
+
+        // Transitivity, and chains ending or starting with the super
+        // property
+        assert!(problems(vec![
+            chain(&["r", "r"], "r"),
+            chain(&["parent", "brother"], "uncle"),
+            chain(&["uncle", "r"], "r"),
+            chain(&["r", "s"], "s"),
+        ])
+        .is_empty());
+
+        assert_eq!(
+            problems(vec![chain(&["r", "s"], "s"), chain(&["s", "r"], "r")]),
+            vec![pair("r", "s"), pair("s", "r")]
+        );
+        assert_eq!(
+            problems(vec![
+                chain(&["p", "q"], "r"),
+                SubObjectPropertyOf {
+                    sup: op("p"),
+                    sub: op("r").into()
+                }
+            ]),
+            vec![pair("r", "p")]
+        );
+        assert_eq!(problems(vec![chain(&["r", "r", "r"], "r")]), vec![pair("r", "r")]);
+    }
+
+    #[test]
+    fn axioms() {
+        let b = Build::new();
+        let axioms: Vec<AnnotatedAxiom> = vec![
+            SubClassOf {
+                sub: b.class("http://www.example.com/A").into(),
+                sup: b.class("http://www.example.com/B").into(),
+            }
+            .into(),
+            DeclareClass(b.class("not an iri")).into(),
+            DeclareObjectProperty(b.object_property("http://www.example.com/A")).into(),
+            DeclareDataProperty(b.data_property("http://www.example.com/A")).into(),
+        ];
+
+        // The classes are not declared, but that needs the ontology
+        assert_eq!(
+            validate_axioms(&axioms).problems,
+            vec![
+                Problem::MalformedIRI(b.iri("not an iri")),
+                Problem::IllegalPunning(b.iri("http://www.example.com/A")),
+            ]
+        );
+        assert!(validate_axioms(&axioms[..1]).is_valid());
+    }
+
+    #[test]
+    fn profiles() {
+        use ClassExpression::*;
+        let b = Build::new();
+        let c = |s: &str| -> ClassExpression {
+            b.class(format!("http://www.example.com/{}", s)).into()
+        };
+        let r: ObjectPropertyExpression = b.object_property("http://www.example.com/r").into();
+        let thing: ClassExpression = b.class("http://www.w3.org/2002/07/owl#Thing").into();
+        let in_profiles = |ax: Axiom| -> Vec<Profile> {
+            [Profile::EL, Profile::QL, Profile::RL]
+                .iter()
+                .filter(|p| not_in_profile(&ax, **p).is_none())
+                .copied()
+                .collect()
+        };
+        use Profile::*;
+
+        let some = |bce: ClassExpression| ObjectSomeValuesFrom {
+            ope: r.clone(),
+            bce: Box::new(bce),
+        };
+        let sc = |sub, sup| -> Axiom { SubClassOf { sub, sup }.into() };
+
+        assert_eq!(in_profiles(sc(c("A"), c("B"))), vec![EL, QL, RL]);
+        assert_eq!(in_profiles(sc(some(c("A")), c("B"))), vec![EL, RL]);
+        assert_eq!(
+            in_profiles(sc(some(thing.clone()), c("B"))),
+            vec![EL, QL, RL]
+        );
+        assert_eq!(in_profiles(sc(c("A"), some(c("B")))), vec![EL, QL]);
+        assert_eq!(
+            in_profiles(sc(
+                c("A"),
+                ObjectAllValuesFrom {
+                    ope: r.clone(),
+                    bce: Box::new(c("B"))
+                }
+            )),
+            vec![RL]
+        );
+        assert_eq!(
+            in_profiles(sc(c("A"), ObjectComplementOf(Box::new(c("B"))))),
+            vec![QL, RL]
+        );
+        assert_eq!(
+            in_profiles(sc(c("A"), ObjectUnionOf(vec![c("B"), c("C")]))),
+            vec![]
+        );
+        assert_eq!(in_profiles(sc(c("A"), thing)), vec![EL, QL]);
+
+        assert_eq!(
+            in_profiles(TransitiveObjectProperty(r.clone()).into()),
+            vec![EL, RL]
+        );
+        assert_eq!(
+            in_profiles(FunctionalObjectProperty(r.clone()).into()),
+            vec![RL]
+        );
+        assert_eq!(
+            in_profiles(
+                InverseObjectProperties(
+                    b.object_property("http://www.example.com/r"),
+                    b.object_property("http://www.example.com/s")
+                )
+                .into()
+            ),
+            vec![QL, RL]
+        );
+
+        // Datatypes outside of the datatype map
+        let double: Axiom = DataPropertyRange {
+            dp: b.data_property("http://www.example.com/p"),
+            dr: b.datatype("http://www.w3.org/2001/XMLSchema#double").into(),
+        }
+        .into();
+        assert_eq!(in_profiles(double.clone()), vec![RL]);
+        assert_eq!(
+            not_in_profile(&double, EL),
+            Some(Problem::NotInProfile(
+                EL,
+                AxiomKind::DataPropertyRange,
+                Some(b.iri("http://www.w3.org/2001/XMLSchema#double"))
+            ))
+        );
+
+        let mut o = AxiomMappedOntology::default();
+        o.declare(b.class("http://www.example.com/A"));
+        o.declare(b.class("http://www.example.com/B"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        o.insert(SubClassOf {
+            sub: c("A"),
+            sup: some(c("B")),
+        });
+        assert!(validate(&o).is_valid());
+        assert!(validate_profile(&o, EL).is_valid());
+        let report = validate_profile(&o, RL);
+        assert_eq!(
+            report.problems,
+            vec![Problem::NotInProfile(
+                RL,
+                AxiomKind::SubClassOf,
+                Some(b.iri("http://www.example.com/A"))
+            )]
+        );
+        assert_eq!(
+            report.problems[0].to_string(),
+            "Not in OWL 2 RL: SubClassOf using http://www.example.com/A"
+        );
+        assert!(report
+            .to_json()
+            .contains(r#""profile":"RL","axiom":"SubClassOf""#));
+
+        // Only anonymous individuals, so no IRI to report
+        let anonymous: Axiom = ClassAssertion {
+            ce: ClassExpression::ObjectOneOf(vec![
+                b.anonymous_individual("x"),
+                b.anonymous_individual("y"),
+            ]),
+            i: b.anonymous_individual("x"),
+        }
+        .into();
+        assert!(anonymous.signature().is_empty());
+        let problem = not_in_profile(&anonymous, QL).unwrap();
+        assert_eq!(
+            problem,
+            Problem::NotInProfile(QL, AxiomKind::ClassAssertion, None)
+        );
+        assert_eq!(problem.to_string(), "Not in OWL 2 QL: ClassAssertion");
+        assert!(Report {
+            problems: vec![problem]
+        }
+        .to_json()
+        .contains(r#""iri":null"#));
+    }
+
+    #[test]
+    fn json() {
+        let b = Build::new();
+        let r = Report {
+            problems: vec![Problem::UndeclaredEntity(
+                b.class("http://www.example.com/\"A\"").into(),
+            )],
+        };
+
+        assert_eq!(
+            r.to_json(),
+            r#"{"valid":false,"problems":[{"problem":"UndeclaredEntity","iri":"http://www.example.com/\"A\"","entity":"Class"}]}"#
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Version metadata of an ontology
+//!
+//! As well as a version IRI, the header of an ontology can carry
+//! annotations describing its release: `owl:versionInfo`, a free text
+//! description of the version, `owl:priorVersion`, the IRI of the
+//! previous version, and `owl:backwardCompatibleWith` and
+//! `owl:incompatibleWith`, the IRIs of earlier versions which are
+//! or are not compatible with this one.
+//!
+//! These functions read and replace these ontology annotations.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+//! use horned_owl::version::*;
+//!
+//! let b = Build::new();
+//! let mut o = AxiomMappedOntology::default();
+//! set_version_info(&mut o, Some("1.1".to_string()));
+//! set_prior_version(&mut o, Some(b.iri("http://www.example.com/o/1.0")));
+//!
+//! assert_eq!(version_info(&o), Some(&"1.1".to_string()));
+//! assert_eq!(prior_version(&o), Some(&b.iri("http://www.example.com/o/1.0")));
+//! ```
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::vocab::{AnnotationBuiltIn, WithIRI};
+
+/// Iterate over the values of the ontology annotations with the
+/// property `ap`.
+pub(crate) fn values_of<'a>(
+    ont: &'a AxiomMappedOntology,
+    ap: &'a str,
+) -> impl Iterator<Item = &'a AnnotationValue> {
+    ont.i()
+        .ontology_annotation()
+        .filter(move |oa| oa.0.ap.0.as_str() == ap)
+        .map(|oa| &oa.0.av)
+}
+
+fn iris_of<'a>(ont: &'a AxiomMappedOntology, ap: &'a str) -> impl Iterator<Item = &'a IRI> {
+    values_of(ont, ap).filter_map(AnnotationValue::as_iri)
+}
+
+/// Replace all ontology annotations with property `ap` by ones with
+/// the values `avs`.
+pub(crate) fn replace<I>(ont: &mut AxiomMappedOntology, ap: &str, avs: I)
+where
+    I: IntoIterator<Item = AnnotationValue>,
+{
+    let old: Vec<AnnotatedAxiom> = ont
+        .i()
+        .annotated_axiom(AxiomKind::OntologyAnnotation)
+        .filter(|ax| match &ax.axiom {
+            Axiom::OntologyAnnotation(oa) => oa.0.ap.0.as_str() == ap,
+            _ => false,
+        })
+        .cloned()
+        .collect();
+
+    for ax in old {
+        ont.remove(&ax);
+    }
+
+    let ap = Build::new().annotation_property(ap);
+    for av in avs {
+        ont.insert(OntologyAnnotation(Annotation { ap: ap.clone(), av }));
+    }
+}
+
+/// Return the `owl:versionInfo` of the ontology.
+pub fn version_info(ont: &AxiomMappedOntology) -> Option<&String> {
+    values_of(ont, AnnotationBuiltIn::VERSIOINFO.iri_str())
+        .find_map(|av| av.as_literal().map(Literal::literal))
+}
+
+/// Set or, with `None`, remove the `owl:versionInfo` of the ontology.
+pub fn set_version_info(ont: &mut AxiomMappedOntology, info: Option<String>) {
+    replace(
+        ont,
+        AnnotationBuiltIn::VERSIOINFO.iri_str(),
+        info.map(|literal| Literal::Simple { literal }.into()),
+    );
+}
+
+/// Return the `owl:priorVersion` of the ontology.
+pub fn prior_version(ont: &AxiomMappedOntology) -> Option<&IRI> {
+    iris_of(ont, AnnotationBuiltIn::PRIORVERSION.iri_str()).next()
+}
+
+/// Set or, with `None`, remove the `owl:priorVersion` of the
+/// ontology.
+pub fn set_prior_version(ont: &mut AxiomMappedOntology, iri: Option<IRI>) {
+    replace(
+        ont,
+        AnnotationBuiltIn::PRIORVERSION.iri_str(),
+        iri.map(Into::into),
+    );
+}
+
+/// Return the versions with which the ontology is
+/// `owl:backwardCompatibleWith`.
+pub fn backward_compatible_with(ont: &AxiomMappedOntology) -> Vec<&IRI> {
+    iris_of(ont, AnnotationBuiltIn::BACKWARDCOMPATIBLEWITH.iri_str()).collect()
+}
+
+/// Replace the versions with which the ontology is
+/// `owl:backwardCompatibleWith`.
+pub fn set_backward_compatible_with<I>(ont: &mut AxiomMappedOntology, iris: I)
+where
+    I: IntoIterator<Item = IRI>,
+{
+    replace(
+        ont,
+        AnnotationBuiltIn::BACKWARDCOMPATIBLEWITH.iri_str(),
+        iris.into_iter().map(Into::into),
+    );
+}
+
+/// Return the versions with which the ontology is
+/// `owl:incompatibleWith`.
+pub fn incompatible_with(ont: &AxiomMappedOntology) -> Vec<&IRI> {
+    iris_of(ont, AnnotationBuiltIn::INCOMPATIBLEWITH.iri_str()).collect()
+}
+
+/// Replace the versions with which the ontology is
+/// `owl:incompatibleWith`.
+pub fn set_incompatible_with<I>(ont: &mut AxiomMappedOntology, iris: I)
+where
+    I: IntoIterator<Item = IRI>,
+{
+    replace(
+        ont,
+        AnnotationBuiltIn::INCOMPATIBLEWITH.iri_str(),
+        iris.into_iter().map(Into::into),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::reader::read;
+
+    #[test]
+    fn replace_values() {
+        let b = Build::new();
+        let mut o = AxiomMappedOntology::default();
+        let v1 = b.iri("http://www.example.com/o/1.0");
+        let v2 = b.iri("http://www.example.com/o/2.0");
+
+        set_version_info(&mut o, Some("2.0".to_string()));
+        set_version_info(&mut o, Some("2.1".to_string()));
+        assert_eq!(version_info(&o), Some(&"2.1".to_string()));
+
+        set_backward_compatible_with(&mut o, vec![v1.clone()]);
+        set_incompatible_with(&mut o, vec![v1.clone(), v2.clone()]);
+        set_incompatible_with(&mut o, vec![v2.clone()]);
+        assert_eq!(backward_compatible_with(&o), vec![&v1]);
+        assert_eq!(incompatible_with(&o), vec![&v2]);
+
+        assert_eq!(o.i().ontology_annotation().count(), 3);
+        set_version_info(&mut o, None);
+        set_backward_compatible_with(&mut o, vec![]);
+        assert_eq!(version_info(&o), None);
+        assert_eq!(o.i().ontology_annotation().count(), 1);
+    }
+
+    #[test]
+    fn from_header() {
+        let ont = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+          ontologyIRI="http://www.example.com/o">
+  <Annotation>
+    <AnnotationProperty IRI="http://www.w3.org/2002/07/owl#versionInfo"/>
+    <Literal>1.1</Literal>
+  </Annotation>
+  <Annotation>
+    <AnnotationProperty IRI="http://www.w3.org/2002/07/owl#priorVersion"/>
+    <IRI>http://www.example.com/o/1.0</IRI>
+  </Annotation>
+</Ontology>"#;
+        let (so, _) = read(&mut ont.as_bytes()).unwrap();
+        let o: AxiomMappedOntology = so.into();
+
+        assert_eq!(version_info(&o), Some(&"1.1".to_string()));
+        assert_eq!(
+            prior_version(&o),
+            Some(&Build::new().iri("http://www.example.com/o/1.0"))
+        );
+    }
+}
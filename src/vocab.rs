@@ -8,7 +8,7 @@ use crate::model::NamedEntity;
 use crate::model::NamedEntityKind;
 use crate::model::IRI;
 
-use failure::Error;
+use crate::error::{HornedError, ValidityError};
 
 pub trait WithIRI<'a>: Meta<&'a IRIString> {
     /// Return a string representation of the IRI associated with this
@@ -18,7 +18,7 @@ pub trait WithIRI<'a>: Meta<&'a IRIString> {
     }
 
     fn iri_b(&self) -> &'a [u8] {
-        &self.meta().0.as_bytes()
+        self.meta().0.as_bytes()
     }
 
     fn iri_str(&self) -> &'a str {
@@ -30,12 +30,7 @@ pub trait WithIRI<'a>: Meta<&'a IRIString> {
     }
 
     fn var_b(tag: &'a [u8]) -> Option<Self> {
-        for v in Self::all() {
-            if tag == v.iri_b() {
-                return Some(v);
-            }
-        }
-        None
+        Self::all().into_iter().find(|v| tag == v.iri_b())
     }
 }
 
@@ -103,10 +98,7 @@ pub enum RDFS {
 
 impl RDFS {
     pub fn is_builtin(&self) -> bool {
-        match self {
-            RDFS::Label | RDFS::Comment | RDFS::SeeAlso | RDFS::IsDefinedBy => true,
-            _ => false,
-        }
+        matches!(self, RDFS::Label | RDFS::Comment | RDFS::SeeAlso | RDFS::IsDefinedBy)
     }
 }
 
@@ -126,6 +118,8 @@ lazy_meta! {
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum OWL {
     AllDifferent,
+    AllDisjointClasses,
+    AllDisjointProperties,
     AllValuesFrom,
     AnnotatedProperty,
     AnnotatedSource,
@@ -134,10 +128,12 @@ pub enum OWL {
     AssertionProperty,
     AsymmetricProperty,
     Axiom,
+    Cardinality,
     Class,
     ComplementOf,
     DatatypeComplementOf,
     DatatypeProperty,
+    DifferentFrom,
     DisjointUnionOf,
     DisjointWith,
     DistinctMembers,
@@ -145,6 +141,7 @@ pub enum OWL {
     EquivalentProperty,
     FunctionalProperty,
     HasKey,
+    HasSelf,
     HasValue,
     Imports,
     IntersectionOf,
@@ -153,6 +150,8 @@ pub enum OWL {
     IrreflexiveProperty,
     MaxCardinality,
     MaxQualifiedCardinality,
+    Members,
+    MinCardinality,
     MinQualifiedCardinality,
     NamedIndividual,
     NegativePropertyAssertion,
@@ -188,6 +187,8 @@ lazy_meta! {
     OWL, IRIString, METAOWL;
 
     AllDifferent, extend(OWL, "AllDifferent");
+    AllDisjointClasses, extend(OWL, "AllDisjointClasses");
+    AllDisjointProperties, extend(OWL, "AllDisjointProperties");
     AllValuesFrom, extend(OWL, "allValuesFrom");
     AnnotatedProperty, extend(OWL, "annotatedProperty");
     AnnotatedSource, extend(OWL, "annotatedSource");
@@ -196,10 +197,12 @@ lazy_meta! {
     AssertionProperty, extend(OWL, "assertionProperty");
     AsymmetricProperty, extend(OWL, "AsymmetricProperty");
     Axiom, extend(OWL, "Axiom");
+    Cardinality, extend(OWL, "cardinality");
     Class, extend(OWL, "Class");
     ComplementOf, extend(OWL, "complementOf");
     DatatypeComplementOf, extend(OWL, "datatypeComplementOf");
     DatatypeProperty, extend(OWL, "DatatypeProperty");
+    DifferentFrom, extend(OWL, "differentFrom");
     DisjointUnionOf, extend(OWL, "disjointUnionOf");
     DisjointWith, extend(OWL, "disjointWith");
     DistinctMembers, extend(OWL, "distinctMembers");
@@ -212,9 +215,12 @@ lazy_meta! {
     InverseOf, extend(OWL, "inverseOf");
     IrreflexiveProperty, extend(OWL, "IrreflexiveProperty");
     HasKey, extend(OWL, "hasKey");
+    HasSelf, extend(OWL, "hasSelf");
     HasValue, extend(OWL, "hasValue");
     MaxCardinality, extend(OWL, "maxCardinality");
     MaxQualifiedCardinality, extend(OWL, "maxQualifiedCardinality");
+    Members, extend(OWL, "members");
+    MinCardinality, extend(OWL, "minCardinality");
     MinQualifiedCardinality, extend(OWL, "minQualifiedCardinality");
     NamedIndividual, extend(OWL, "NamedIndividual");
     NegativePropertyAssertion, extend(OWL, "NegativePropertyAssertion");
@@ -246,6 +252,14 @@ lazy_meta! {
     WithRestrictions, extend(OWL, "withRestrictions");
 }
 
+/// Is `iri` in one of the namespaces whose vocabulary is reserved by
+/// OWL.
+pub fn is_reserved(iri: &str) -> bool {
+    Namespace::all()
+        .iter()
+        .any(|ns| iri.starts_with(ns.iri_str()))
+}
+
 pub fn to_built_in_entity(iri: &IRI) -> Option<NamedEntityKind> {
     let ir = iri.as_ref();
     match ir {
@@ -270,7 +284,11 @@ fn meta_testing() {
     );
 }
 
-pub fn entity_for_iri(type_iri: &str, entity_iri: &str, b: &Build) -> Result<NamedEntity, Error> {
+pub fn entity_for_iri(
+    type_iri: &str,
+    entity_iri: &str,
+    b: &Build,
+) -> Result<NamedEntity, HornedError> {
     // Datatypes are handled here because they are not a
     // "type" but an "RDF schema" element.
     if type_iri == "http://www.w3.org/2000/01/rdf-schema#Datatype" {
@@ -278,7 +296,7 @@ pub fn entity_for_iri(type_iri: &str, entity_iri: &str, b: &Build) -> Result<Nam
     }
 
     if type_iri.len() < 30 {
-        bail!("IRI is not for a type of entity:{}", type_iri);
+        return Err(ValidityError::NotAnEntityType(type_iri.to_string()).into());
     }
 
     Ok(match &type_iri[30..] {
@@ -287,7 +305,7 @@ pub fn entity_for_iri(type_iri: &str, entity_iri: &str, b: &Build) -> Result<Nam
         "DatatypeProperty" => b.data_property(entity_iri).into(),
         "AnnotationProperty" => b.annotation_property(entity_iri).into(),
         "NamedIndividual" => b.named_individual(entity_iri).into(),
-        _ => bail!("IRI is not a type of entity:{}", type_iri),
+        _ => return Err(ValidityError::NotAnEntityType(type_iri.to_string()).into()),
     })
 }
 
@@ -309,13 +327,84 @@ pub fn test_entity_for_iri() {
     .is_err());
 }
 
+/// The datatypes of the [OWL 2 datatype
+/// map](https://www.w3.org/TR/owl2-syntax/#Datatype_Maps).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum OWL2Datatype {
     RDFSLiteral,
+    OWLReal,
+    OWLRational,
+    RDFPlainLiteral,
+    RDFXMLLiteral,
+    RDFLangString,
+    XSDDecimal,
+    XSDInteger,
+    XSDNonNegativeInteger,
+    XSDNonPositiveInteger,
+    XSDPositiveInteger,
+    XSDNegativeInteger,
+    XSDLong,
+    XSDInt,
+    XSDShort,
+    XSDByte,
+    XSDUnsignedLong,
+    XSDUnsignedInt,
+    XSDUnsignedShort,
+    XSDUnsignedByte,
+    XSDDouble,
+    XSDFloat,
+    XSDString,
+    XSDNormalizedString,
+    XSDToken,
+    XSDLanguage,
+    XSDName,
+    XSDNCName,
+    XSDNMTOKEN,
+    XSDBoolean,
+    XSDHexBinary,
+    XSDBase64Binary,
+    XSDAnyURI,
+    XSDDateTime,
+    XSDDateTimeStamp,
 }
 
 lazy_meta! {
     OWL2Datatype, IRIString, METAOWL2DATATYPE;
-    RDFSLiteral, extend(RDFS, "Literal")
+    RDFSLiteral, extend(RDFS, "Literal");
+    OWLReal, extend(OWL, "real");
+    OWLRational, extend(OWL, "rational");
+    RDFPlainLiteral, extend(RDF, "PlainLiteral");
+    RDFXMLLiteral, extend(RDF, "XMLLiteral");
+    RDFLangString, extend(RDF, "langString");
+    XSDDecimal, extend(XSD, "decimal");
+    XSDInteger, extend(XSD, "integer");
+    XSDNonNegativeInteger, extend(XSD, "nonNegativeInteger");
+    XSDNonPositiveInteger, extend(XSD, "nonPositiveInteger");
+    XSDPositiveInteger, extend(XSD, "positiveInteger");
+    XSDNegativeInteger, extend(XSD, "negativeInteger");
+    XSDLong, extend(XSD, "long");
+    XSDInt, extend(XSD, "int");
+    XSDShort, extend(XSD, "short");
+    XSDByte, extend(XSD, "byte");
+    XSDUnsignedLong, extend(XSD, "unsignedLong");
+    XSDUnsignedInt, extend(XSD, "unsignedInt");
+    XSDUnsignedShort, extend(XSD, "unsignedShort");
+    XSDUnsignedByte, extend(XSD, "unsignedByte");
+    XSDDouble, extend(XSD, "double");
+    XSDFloat, extend(XSD, "float");
+    XSDString, extend(XSD, "string");
+    XSDNormalizedString, extend(XSD, "normalizedString");
+    XSDToken, extend(XSD, "token");
+    XSDLanguage, extend(XSD, "language");
+    XSDName, extend(XSD, "Name");
+    XSDNCName, extend(XSD, "NCName");
+    XSDNMTOKEN, extend(XSD, "NMTOKEN");
+    XSDBoolean, extend(XSD, "boolean");
+    XSDHexBinary, extend(XSD, "hexBinary");
+    XSDBase64Binary, extend(XSD, "base64Binary");
+    XSDAnyURI, extend(XSD, "anyURI");
+    XSDDateTime, extend(XSD, "dateTime");
+    XSDDateTimeStamp, extend(XSD, "dateTimeStamp");
 }
 
 pub enum AnnotationBuiltIn {
@@ -343,13 +432,109 @@ lazy_meta! {
     INCOMPATIBLEWITH, extend(OWL, "incompatibleWith");
 }
 
+/// The oboInOwl vocabulary, used for the metadata of OBO ontologies.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum OBOInOwl {
+    HasBroadSynonym,
+    HasDbXref,
+    HasExactSynonym,
+    HasNarrowSynonym,
+    HasOBONamespace,
+    HasRelatedSynonym,
+    Id,
+}
+
+lazy_meta! {
+    OBOInOwl, IRIString, METAOBOINOWL;
+    HasBroadSynonym, to_meta("http://www.geneontology.org/formats/oboInOwl#hasBroadSynonym");
+    HasDbXref, to_meta("http://www.geneontology.org/formats/oboInOwl#hasDbXref");
+    HasExactSynonym, to_meta("http://www.geneontology.org/formats/oboInOwl#hasExactSynonym");
+    HasNarrowSynonym, to_meta("http://www.geneontology.org/formats/oboInOwl#hasNarrowSynonym");
+    HasOBONamespace, to_meta("http://www.geneontology.org/formats/oboInOwl#hasOBONamespace");
+    HasRelatedSynonym, to_meta("http://www.geneontology.org/formats/oboInOwl#hasRelatedSynonym");
+    Id, to_meta("http://www.geneontology.org/formats/oboInOwl#id");
+}
+
+/// The SKOS annotation properties which are commonly used in OWL
+/// ontologies.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SKOS {
+    AltLabel,
+    Broader,
+    Definition,
+    HiddenLabel,
+    Narrower,
+    Notation,
+    PrefLabel,
+    Related,
+}
+
+lazy_meta! {
+    SKOS, IRIString, METASKOS;
+    AltLabel, to_meta("http://www.w3.org/2004/02/skos/core#altLabel");
+    Broader, to_meta("http://www.w3.org/2004/02/skos/core#broader");
+    Definition, to_meta("http://www.w3.org/2004/02/skos/core#definition");
+    HiddenLabel, to_meta("http://www.w3.org/2004/02/skos/core#hiddenLabel");
+    Narrower, to_meta("http://www.w3.org/2004/02/skos/core#narrower");
+    Notation, to_meta("http://www.w3.org/2004/02/skos/core#notation");
+    PrefLabel, to_meta("http://www.w3.org/2004/02/skos/core#prefLabel");
+    Related, to_meta("http://www.w3.org/2004/02/skos/core#related");
+}
+
+/// The Dublin Core elements which are commonly used in ontology
+/// headers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DC {
+    Contributor,
+    Creator,
+    Date,
+    Description,
+    Publisher,
+    Rights,
+    Title,
+}
+
+lazy_meta! {
+    DC, IRIString, METADC;
+    Contributor, to_meta("http://purl.org/dc/elements/1.1/contributor");
+    Creator, to_meta("http://purl.org/dc/elements/1.1/creator");
+    Date, to_meta("http://purl.org/dc/elements/1.1/date");
+    Description, to_meta("http://purl.org/dc/elements/1.1/description");
+    Publisher, to_meta("http://purl.org/dc/elements/1.1/publisher");
+    Rights, to_meta("http://purl.org/dc/elements/1.1/rights");
+    Title, to_meta("http://purl.org/dc/elements/1.1/title");
+}
+
+/// The Dublin Core terms which are commonly used in ontology headers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DCTerms {
+    Contributor,
+    Created,
+    Creator,
+    Description,
+    License,
+    Modified,
+    Title,
+}
+
+lazy_meta! {
+    DCTerms, IRIString, METADCTERMS;
+    Contributor, to_meta("http://purl.org/dc/terms/contributor");
+    Created, to_meta("http://purl.org/dc/terms/created");
+    Creator, to_meta("http://purl.org/dc/terms/creator");
+    Description, to_meta("http://purl.org/dc/terms/description");
+    License, to_meta("http://purl.org/dc/terms/license");
+    Modified, to_meta("http://purl.org/dc/terms/modified");
+    Title, to_meta("http://purl.org/dc/terms/title");
+}
+
 pub fn is_annotation_builtin(iri: &String) -> bool {
     for meta in AnnotationBuiltIn::all() {
         if meta.iri_s() == iri {
             return true;
         }
     }
-    return false;
+    false
 }
 
 #[test]
@@ -393,7 +578,7 @@ fn facet_meta() {
     );
 
     assert_eq!(
-        Facet::var_s(&"http://www.w3.org/2001/XMLSchema#pattern".to_string()).unwrap(),
+        Facet::var_s("http://www.w3.org/2001/XMLSchema#pattern").unwrap(),
         Facet::Pattern
     );
 
@@ -402,3 +587,18 @@ fn facet_meta() {
         Facet::MinExclusive
     );
 }
+
+#[test]
+fn datatype_meta() {
+    assert_eq!(
+        OWL2Datatype::XSDDateTime.iri_s(),
+        "http://www.w3.org/2001/XMLSchema#dateTime"
+    );
+
+    assert_eq!(
+        OWL2Datatype::var_s("http://www.w3.org/2002/07/owl#rational").unwrap(),
+        OWL2Datatype::OWLRational
+    );
+
+    assert!(OWL2Datatype::var_s("http://www.w3.org/2001/XMLSchema#gYear").is_none());
+}